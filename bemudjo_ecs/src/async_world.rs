@@ -0,0 +1,179 @@
+use crate::{SequentialSystemScheduler, World};
+use std::sync::{Arc, PoisonError, RwLock};
+
+// Sharing is only sound because thread-safe-components makes every part of a
+// World Send + Sync
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<World>();
+};
+
+/// A [`World`] shared between threads or async tasks behind a read-write lock.
+///
+/// Only available with the default `thread-safe-components` feature, which
+/// makes every component, and with them the world, `Send + Sync`.
+///
+/// Clones are cheap handles to the same world. Any number of readers can run
+/// [`with_read`](AsyncWorld::with_read) at once, while
+/// [`with_write`](AsyncWorld::with_write) and [`tick`](AsyncWorld::tick)
+/// get exclusive access, so readers always see the world between ticks, never
+/// in the middle of one.
+///
+/// The world is only reachable inside the closures, and the lock is released
+/// when they return. A closure's result can't borrow from the world, and it
+/// must be `Send`, so results can be handed to any thread or task; copy out
+/// what you need instead of holding component references.
+///
+/// The lock is a blocking `std::sync::RwLock`. From async code keep the
+/// closures short, as they block the executor thread while waiting for and
+/// holding the lock. Calling `with_read` or `with_write` from inside another
+/// closure on the same handle deadlocks.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{AsyncWorld, Component, SequentialSystemScheduler, System, World};
+/// use std::thread;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Turn(u64);
+/// impl Component for Turn {}
+///
+/// struct TurnSystem;
+/// impl System for TurnSystem {
+///     fn run(&self, world: &mut World) {
+///         world.update_resource::<Turn, _>(|turn| Turn(turn.0 + 1)).unwrap();
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Turn(0));
+/// let world = AsyncWorld::new(world);
+///
+/// let mut scheduler = SequentialSystemScheduler::new();
+/// scheduler.add_system(TurnSystem).unwrap();
+/// scheduler.build().unwrap();
+///
+/// let turn = |world: &World| world.get_resource::<Turn>().unwrap().0;
+///
+/// let reader = world.clone();
+/// let observer = thread::spawn(move || reader.with_read(turn));
+///
+/// world.tick(&mut scheduler);
+/// assert!(observer.join().unwrap() <= 1);
+/// assert_eq!(world.with_read(turn), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct AsyncWorld {
+    world: Arc<RwLock<World>>,
+}
+
+impl AsyncWorld {
+    /// Wraps `world` for sharing.
+    pub fn new(world: World) -> Self {
+        Self {
+            world: Arc::new(RwLock::new(world)),
+        }
+    }
+
+    /// Runs `f` with shared access to the world and returns its result.
+    ///
+    /// Blocks while a writer holds the lock. A panic in another closure
+    /// doesn't lock readers out; they see the world as the panicking closure
+    /// left it.
+    pub fn with_read<R, F>(&self, f: F) -> R
+    where
+        R: Send,
+        F: FnOnce(&World) -> R,
+    {
+        let world = self.world.read().unwrap_or_else(PoisonError::into_inner);
+        f(&world)
+    }
+
+    /// Runs `f` with exclusive access to the world and returns its result.
+    ///
+    /// Blocks until all readers and writers have released the lock.
+    pub fn with_write<R, F>(&self, f: F) -> R
+    where
+        R: Send,
+        F: FnOnce(&mut World) -> R,
+    {
+        let mut world = self.world.write().unwrap_or_else(PoisonError::into_inner);
+        f(&mut world)
+    }
+
+    /// Runs one `scheduler.run_tick()`, holding the write lock for exactly
+    /// that tick.
+    pub fn tick(&self, scheduler: &mut SequentialSystemScheduler) {
+        self.with_write(|world| scheduler.run_tick(world));
+    }
+
+    /// Returns the world if this is the last handle to it, or the handle back
+    /// otherwise.
+    pub fn into_inner(self) -> Result<World, Self> {
+        match Arc::try_unwrap(self.world) {
+            Ok(lock) => Ok(lock.into_inner().unwrap_or_else(PoisonError::into_inner)),
+            Err(world) => Err(Self { world }),
+        }
+    }
+}
+
+impl std::fmt::Debug for AsyncWorld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncWorld")
+            .field("handles", &Arc::strong_count(&self.world))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Component;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[test]
+    fn test_reads_see_writes() {
+        let world = AsyncWorld::new(World::new());
+        let hero = world.with_write(|world| {
+            let hero = world.spawn_entity();
+            world.add_component(hero, Health { value: 10 }).unwrap();
+            hero
+        });
+
+        let handle = world.clone();
+        let value = handle.with_read(|world| world.get_component::<Health>(hero).unwrap().value);
+        assert_eq!(value, 10);
+
+        // The world comes back only once the other handle is gone
+        let Err(world) = world.into_inner() else {
+            panic!("another handle is still alive");
+        };
+        drop(handle);
+        let world = world.into_inner().unwrap();
+        assert!(world.has_component::<Health>(hero));
+    }
+
+    #[test]
+    fn test_panicking_writer_does_not_lock_out_others() {
+        let world = AsyncWorld::new(World::new());
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            world.with_write(|world| {
+                world.insert_resource(Health { value: 1 });
+                panic!("command failed");
+            })
+        }));
+        assert!(result.is_err());
+
+        assert_eq!(
+            world.with_read(|world| world.get_resource::<Health>().cloned()),
+            Some(Health { value: 1 })
+        );
+        world.with_write(|world| world.insert_resource(Health { value: 2 }));
+    }
+}
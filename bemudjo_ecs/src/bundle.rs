@@ -0,0 +1,95 @@
+use crate::{Component, ComponentError, Entity, World};
+
+/// A group of components that can be inserted into an entity in one call.
+///
+/// Bundles make reusable archetypes (a "goblin", a "player", a "room") easy to
+/// spawn without repeating a long list of `add_component` calls. Tuples of up to
+/// twelve components implement `Bundle` out of the box, and custom structs can
+/// implement it by delegating to a tuple.
+///
+/// Inserting a bundle is all-or-nothing. Tuples check every component before
+/// adding any, so a missing entity or a component the entity already has fails
+/// the call without touching the world, and no observer runs. Should a later
+/// insert still fail, for example because a tuple repeats a type, the
+/// components added so far by the same call are removed again, so the entity
+/// is never left half-built.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Bundle, Component, ComponentError, Entity, World};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// struct Goblin { x: f32, y: f32 }
+///
+/// impl Bundle for Goblin {
+///     fn insert(self, world: &mut World, entity: Entity) -> Result<(), ComponentError> {
+///         (Position { x: self.x, y: self.y }, Health { value: 30 }).insert(world, entity)
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let goblin = world.spawn_bundle(Goblin { x: 1.0, y: 2.0 }).unwrap();
+///
+/// assert!(world.has_component::<Position>(goblin));
+/// assert_eq!(world.get_component::<Health>(goblin).unwrap().value, 30);
+/// ```
+pub trait Bundle {
+    /// Adds every component of this bundle to an entity.
+    ///
+    /// # Returns
+    /// * `Ok(())` if all components were added
+    /// * `Err(ComponentError)` if any component could not be added; components
+    ///   added earlier in the same call have been removed again
+    fn insert(self, world: &mut World, entity: Entity) -> Result<(), ComponentError>;
+}
+
+/// Removes a single component type; used to undo partially inserted bundles.
+fn rollback<C: Component>(world: &mut World, entity: Entity) {
+    world.remove_component::<C>(entity);
+}
+
+macro_rules! impl_bundle_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Component),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert(self, world: &mut World, entity: Entity) -> Result<(), ComponentError> {
+                $(world.check_add_component::<$name>(entity)?;)+
+
+                let ($($name,)+) = self;
+                let mut inserted: Vec<fn(&mut World, Entity)> = Vec::new();
+
+                $(
+                    if let Err(error) = world.add_component(entity, $name) {
+                        for undo in inserted.into_iter().rev() {
+                            undo(world, entity);
+                        }
+                        return Err(error);
+                    }
+                    inserted.push(rollback::<$name>);
+                )+
+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_bundle_for_tuple!(A);
+impl_bundle_for_tuple!(A, B);
+impl_bundle_for_tuple!(A, B, C);
+impl_bundle_for_tuple!(A, B, C, D);
+impl_bundle_for_tuple!(A, B, C, D, E);
+impl_bundle_for_tuple!(A, B, C, D, E, F);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
@@ -0,0 +1,247 @@
+use crate::{Component, Entity, Query, World};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// A [`Query`] that remembers which entities matched it.
+///
+/// Computing a query's matches takes several set operations over the reverse
+/// component index. Systems that run the same query every tick against a world
+/// whose structure rarely changes can wrap it in a `CachedQuery` instead: the
+/// matched entities are stored together with the [`World::generation`] they were
+/// computed at, and reused for as long as the world's generation stays the same.
+///
+/// Caching is transparent. Any add, remove or delete bumps the generation, so
+/// the next [`CachedQuery::iter`] recomputes the matches before yielding
+/// anything. Component values are always read fresh from the world, so in-place
+/// updates are visible without a recompute.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{CachedQuery, Component, Query, World};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// let mut world = World::new();
+/// let hero = world.spawn_entity();
+/// world.add_component(hero, Health { value: 100 }).unwrap();
+///
+/// let query = CachedQuery::new(Query::<Health>::new());
+/// assert_eq!(query.iter(&world).count(), 1);
+/// assert_eq!(query.iter(&world).count(), 1);
+/// assert_eq!(query.recompute_count(), 1); // second call reused the cache
+///
+/// let goblin = world.spawn_entity();
+/// world.add_component(goblin, Health { value: 20 }).unwrap();
+/// assert_eq!(query.iter(&world).count(), 2);
+/// assert_eq!(query.recompute_count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct CachedQuery<T> {
+    query: Query<T>,
+    /// World generation and the entities that matched at that generation
+    cache: RefCell<Option<(u64, Rc<[Entity]>)>>,
+    recomputes: Cell<u64>,
+}
+
+impl<T: Component> CachedQuery<T> {
+    /// Wraps `query` with an initially empty cache.
+    pub fn new(query: Query<T>) -> Self {
+        Self {
+            query,
+            cache: RefCell::new(None),
+            recomputes: Cell::new(0),
+        }
+    }
+
+    /// Returns the wrapped query.
+    pub fn query(&self) -> &Query<T> {
+        &self.query
+    }
+
+    /// Creates an iterator over the matching entities and their `T` component.
+    ///
+    /// Yields the same items as [`Query::iter`]. The matched entities are
+    /// recomputed only if the world's generation changed since the last call.
+    pub fn iter<'w>(&'w self, world: &'w World) -> impl Iterator<Item = (Entity, &'w T)> + 'w {
+        let entities = self.matched_entities(world);
+
+        (0..entities.len()).filter_map(move |index| {
+            let entity = entities[index];
+            world
+                .get_component::<T>(entity)
+                .map(|component| (entity, component))
+        })
+    }
+
+    /// Drops the cached matches so the next `iter()` recomputes them.
+    pub fn invalidate(&self) {
+        self.cache.replace(None);
+    }
+
+    /// Returns how many times the matches have been computed.
+    ///
+    /// Mostly useful for tests and profiling cache effectiveness.
+    pub fn recompute_count(&self) -> u64 {
+        self.recomputes.get()
+    }
+
+    /// Returns the cached matches, recomputing them if they are stale.
+    fn matched_entities(&self, world: &World) -> Rc<[Entity]> {
+        let generation = world.generation();
+
+        if let Some((cached_generation, entities)) = &*self.cache.borrow() {
+            if *cached_generation == generation {
+                return entities.clone();
+            }
+        }
+
+        let entities: Rc<[Entity]> = self.query.matching_entities(world).into_iter().collect();
+        self.recomputes.set(self.recomputes.get() + 1);
+        self.cache.replace(Some((generation, entities.clone())));
+        entities
+    }
+}
+
+impl<T: Component> From<Query<T>> for CachedQuery<T> {
+    fn from(query: Query<T>) -> Self {
+        Self::new(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Stunned;
+    impl Component for Stunned {}
+
+    fn matched(query: &CachedQuery<Position>, world: &World) -> HashSet<Entity> {
+        query.iter(world).map(|(entity, _)| entity).collect()
+    }
+
+    #[test]
+    fn test_repeated_iter_reuses_cache() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Position { x: 1.0 }).unwrap();
+
+        let query = CachedQuery::new(Query::<Position>::new());
+        for _ in 0..5 {
+            assert_eq!(matched(&query, &world), HashSet::from([entity]));
+        }
+
+        assert_eq!(query.recompute_count(), 1);
+    }
+
+    #[test]
+    fn test_value_updates_visible_without_recompute() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Position { x: 1.0 }).unwrap();
+
+        let query = CachedQuery::new(Query::<Position>::new());
+        assert_eq!(query.iter(&world).next().unwrap().1.x, 1.0);
+
+        world
+            .update_component::<Position, _>(entity, |_| Position { x: 2.0 })
+            .unwrap();
+        world.replace_component(entity, Position { x: 3.0 });
+        world.spawn_entity();
+
+        assert_eq!(query.iter(&world).next().unwrap().1.x, 3.0);
+        assert_eq!(query.recompute_count(), 1);
+    }
+
+    #[test]
+    fn test_structural_changes_force_recompute() {
+        let mut world = World::new();
+        let a = world.spawn_entity();
+        let b = world.spawn_entity();
+        world.add_component(a, Position { x: 0.0 }).unwrap();
+
+        let query = CachedQuery::new(Query::<Position>::new().without::<Dead>());
+        assert_eq!(matched(&query, &world), HashSet::from([a]));
+
+        world.add_component(b, Position { x: 0.0 }).unwrap();
+        assert_eq!(matched(&query, &world), HashSet::from([a, b]));
+        assert_eq!(query.recompute_count(), 2);
+
+        world.add_component(b, Dead).unwrap();
+        assert_eq!(matched(&query, &world), HashSet::from([a]));
+        assert_eq!(query.recompute_count(), 3);
+
+        world.remove_component::<Dead>(b);
+        assert_eq!(matched(&query, &world), HashSet::from([a, b]));
+        assert_eq!(query.recompute_count(), 4);
+
+        world.delete_entity(a);
+        assert_eq!(matched(&query, &world), HashSet::from([b]));
+        assert_eq!(query.recompute_count(), 5);
+
+        world.replace_component(a, Position { x: 1.0 }); // deleted, no-op
+        world.cleanup_deleted_entities();
+        assert_eq!(matched(&query, &world), HashSet::from([b]));
+        assert_eq!(query.recompute_count(), 5);
+    }
+
+    #[test]
+    fn test_ephemeral_changes_force_recompute() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Position { x: 0.0 }).unwrap();
+
+        let query = CachedQuery::new(Query::<Position>::new().without_ephemeral::<Stunned>());
+        assert_eq!(matched(&query, &world).len(), 1);
+
+        world.add_ephemeral_component(entity, Stunned).unwrap();
+        assert!(matched(&query, &world).is_empty());
+
+        world.clean_ephemeral_storage();
+        assert_eq!(matched(&query, &world).len(), 1);
+        assert_eq!(query.recompute_count(), 3);
+
+        // Cleaning already-empty ephemeral storage isn't a structural change
+        world.clean_ephemeral_storage();
+        assert_eq!(matched(&query, &world).len(), 1);
+        assert_eq!(query.recompute_count(), 3);
+    }
+
+    #[test]
+    fn test_cache_not_shared_between_worlds() {
+        let mut first = World::new();
+        let entity = first.spawn_entity();
+        first.add_component(entity, Position { x: 0.0 }).unwrap();
+        let second = World::new();
+
+        let query = CachedQuery::new(Query::<Position>::new());
+        assert_eq!(matched(&query, &first).len(), 1);
+        assert!(matched(&query, &second).is_empty());
+        assert_eq!(query.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_invalidate_forces_recompute() {
+        let world = World::new();
+        let query: CachedQuery<Position> = Query::new().into();
+
+        query.iter(&world).count();
+        query.invalidate();
+        query.iter(&world).count();
+
+        assert_eq!(query.recompute_count(), 2);
+    }
+}
@@ -1,14 +1,93 @@
-use crate::Entity;
+use crate::{Entity, World};
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// `Send + Sync` with the default `thread-safe-components` feature, no bound
+/// without it. Implemented for every type; only used as a supertrait.
+#[doc(hidden)]
+#[cfg(feature = "thread-safe-components")]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(feature = "thread-safe-components")]
+impl<T: Send + Sync + ?Sized> MaybeSendSync for T {}
+
+#[doc(hidden)]
+#[cfg(not(feature = "thread-safe-components"))]
+pub trait MaybeSendSync {}
+
+#[cfg(not(feature = "thread-safe-components"))]
+impl<T: ?Sized> MaybeSendSync for T {}
 
 /// Marker trait for components.
 /// All component types must implement this trait.
-pub trait Component: 'static {}
+///
+/// With the default `thread-safe-components` feature, components must be
+/// `Send + Sync`, so that a [`World`] can be shared between threads, see
+/// `AsyncWorld`. Components holding `Rc` or `RefCell` are rejected at compile
+/// time; shared state belongs in an owned value (`Vec<String>`), an
+/// `Arc<Mutex<..>>`, or a resource instead. Systems are not components and may
+/// still hold `Rc` and `RefCell`.
+///
+/// Single-threaded games that need such components can turn the feature off
+/// with `default-features = false`, which also removes `AsyncWorld`.
+///
+/// With the `derive` feature, `#[derive(Component)]` writes the impl and checks
+/// that the type is `Clone`.
+pub trait Component: MaybeSendSync + 'static {
+    /// Returns `true` for event types that may only be attached with
+    /// [`World::add_ephemeral_component`]. [`World::add_component`] rejects
+    /// them with [`ComponentError::EphemeralOnly`].
+    ///
+    /// `#[component(ephemeral_only)]` overrides this when deriving.
+    fn ephemeral_only() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Returns the name the world reports for this component type, e.g. in
+    /// [`World::component_type_name`] and error messages of type-erased
+    /// operations.
+    ///
+    /// Defaults to [`std::any::type_name`]. Override it to get a name that
+    /// doesn't change when the type moves between modules, e.g. for logging or
+    /// serialization keys.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, World};
+    /// use std::any::TypeId;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {
+    ///     fn type_name() -> &'static str {
+    ///         "health"
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    ///
+    /// assert_eq!(world.component_type_name(TypeId::of::<Health>()), Some("health"));
+    /// ```
+    fn type_name() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
+}
 
 /// Trait for component storage operations on a specific component type.
-#[doc(hidden)]
-pub trait ComponentStorage<T: Component> {
+///
+/// Implement it to store a component type your own way, and hand the storage
+/// to the world with [`World::set_storage`].
+pub trait ComponentStorage<T: Component>: AnyStorage {
     /// Adds a component to an entity.
     fn insert(&mut self, entity: Entity, component: T) -> Result<(), ComponentError>;
 
@@ -32,12 +111,35 @@ pub trait ComponentStorage<T: Component> {
     /// Returns an iterator over all entities that have this component.
     /// This enables efficient component-first iteration for queries.
     fn entities(&self) -> Box<dyn Iterator<Item = Entity> + '_>;
+
+    /// Iterates over every stored `(Entity, &T)` pair.
+    ///
+    /// The order is unspecified. Components of soft-deleted entities that
+    /// haven't been cleaned up yet are included.
+    fn iter(&self) -> Box<dyn Iterator<Item = (Entity, &T)> + '_>;
+
+    /// Iterates over every stored `(Entity, &mut T)` pair.
+    ///
+    /// Same order and contents as [`ComponentStorage::iter`].
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (Entity, &mut T)> + '_>;
+
+    /// Reserves capacity for at least `additional` more components.
+    ///
+    /// This is a performance hint only; storages that cannot pre-allocate
+    /// may ignore it.
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// Returns the number of components the storage can hold without reallocating.
+    fn capacity(&self) -> usize;
 }
 
 /// Type-erased storage trait for storing different component types in the same collection.
 /// This is the key trait that enables storing different component storages in a HashMap.
-#[doc(hidden)]
-pub trait AnyStorage {
+///
+/// Hidden from the docs unless the `introspection` feature is enabled, for
+/// tooling that walks storages without knowing the component types.
+#[cfg_attr(not(feature = "introspection"), doc(hidden))]
+pub trait AnyStorage: MaybeSendSync {
     /// Returns a reference to the storage as `&dyn Any` for downcasting.
     fn as_any(&self) -> &dyn Any;
 
@@ -50,17 +152,100 @@ pub trait AnyStorage {
     /// Removes all components from this storage.
     fn clear(&mut self);
 
-    /// Returns the type name of the component this storage handles.
+    /// Returns the type name of the component this storage handles, see
+    /// [`Component::type_name`].
     fn component_type_name(&self) -> &'static str;
 
     /// Checks if an entity has a component in this storage.
     /// Used internally by the query system for TypeId-based filtering.
     fn contains_entity(&self, entity: Entity) -> bool;
+
+    /// Returns every entity with a component in this storage, in no particular order.
+    /// Used internally to cross-check storages against the world's indexes.
+    fn stored_entities(&self) -> Vec<Entity>;
+
+    /// Returns the entity's component as `&dyn Any` for downcasting.
+    /// Used internally to notify observers about type-erased removals.
+    fn get_any(&self, entity: Entity) -> Option<&dyn Any>;
+
+    /// Removes the entity's component and returns it type-erased.
+    /// Used internally to move entities between worlds.
+    fn take_boxed(&mut self, entity: Entity) -> Option<Box<dyn ErasedComponent>>;
+
+    /// Returns the number of components stored, including those of
+    /// soft-deleted entities that haven't been cleaned up yet.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the storage holds no components.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the approximate memory used by the stored components in bytes.
+    ///
+    /// Computed as `len() * size_of::<T>()`; heap data owned by the components
+    /// (strings, vectors) and the storage's own overhead are not included.
+    fn approx_memory_bytes(&self) -> usize;
+
+    /// Returns the number of components the storage can hold without reallocating.
+    fn allocated_capacity(&self) -> usize;
+
+    /// Shrinks the storage's capacity as much as possible.
+    fn shrink(&mut self);
+}
+
+/// A component value whose concrete type has been erased.
+///
+/// Produced by [`AnyStorage::take_boxed`]; the value remembers its own type, so it
+/// can be stored into any world without a registry of component types.
+#[doc(hidden)]
+pub trait ErasedComponent: MaybeSendSync {
+    /// Returns the type name of the wrapped component.
+    fn component_type_name(&self) -> &'static str;
+
+    /// Returns the wrapped component as `&dyn Any` for downcasting.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Adds the wrapped component to `entity` in `world`.
+    fn insert_into(
+        self: Box<Self>,
+        world: &mut World,
+        entity: Entity,
+    ) -> Result<(), ComponentError>;
+
+    /// Inserts the wrapped value as a global resource of `world`, replacing
+    /// any resource of the same type.
+    fn insert_resource_into(self: Box<Self>, world: &mut World);
+}
+
+/// The [`ErasedComponent`] implementation for every component type.
+struct BoxedComponent<T: Component>(T);
+
+impl<T: Component> ErasedComponent for BoxedComponent<T> {
+    fn component_type_name(&self) -> &'static str {
+        T::type_name()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        &self.0
+    }
+
+    fn insert_into(
+        self: Box<Self>,
+        world: &mut World,
+        entity: Entity,
+    ) -> Result<(), ComponentError> {
+        world.add_component(entity, self.0)
+    }
+
+    fn insert_resource_into(self: Box<Self>, world: &mut World) {
+        world.insert_resource(self.0);
+    }
 }
 
 /// A HashMap-based implementation of ComponentStorage.
 #[doc(hidden)]
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct HashMapComponentStorage<T: Component> {
     data: HashMap<Entity, T>,
 }
@@ -74,6 +259,21 @@ impl<T: Component> HashMapComponentStorage<T> {
     }
 }
 
+impl<T: Component> Default for HashMapComponentStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Component + Clone> HashMapComponentStorage<T> {
+    /// Deep-copies the storage into a new type-erased box.
+    pub fn clone_box(&self) -> Box<dyn AnyStorage> {
+        Box::new(Self {
+            data: self.data.clone(),
+        })
+    }
+}
+
 impl<T: Component> ComponentStorage<T> for HashMapComponentStorage<T> {
     fn insert(&mut self, entity: Entity, component: T) -> Result<(), ComponentError> {
         match self.data.entry(entity) {
@@ -81,9 +281,10 @@ impl<T: Component> ComponentStorage<T> for HashMapComponentStorage<T> {
                 e.insert(component);
                 Ok(())
             }
-            std::collections::hash_map::Entry::Occupied(_) => {
-                Err(ComponentError::ComponentAlreadyExists)
-            }
+            std::collections::hash_map::Entry::Occupied(_) => Err(ComponentError::AlreadyExists {
+                entity,
+                type_name: T::type_name(),
+            }),
         }
     }
 
@@ -110,6 +311,30 @@ impl<T: Component> ComponentStorage<T> for HashMapComponentStorage<T> {
     fn entities(&self) -> Box<dyn Iterator<Item = Entity> + '_> {
         Box::new(self.data.keys().copied())
     }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Entity, &T)> + '_> {
+        Box::new(
+            self.data
+                .iter()
+                .map(|(entity, component)| (*entity, component)),
+        )
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (Entity, &mut T)> + '_> {
+        Box::new(
+            self.data
+                .iter_mut()
+                .map(|(entity, component)| (*entity, component)),
+        )
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
 }
 
 impl<T: Component> AnyStorage for HashMapComponentStorage<T> {
@@ -130,21 +355,613 @@ impl<T: Component> AnyStorage for HashMapComponentStorage<T> {
     }
 
     fn component_type_name(&self) -> &'static str {
-        std::any::type_name::<T>()
+        T::type_name()
     }
 
     fn contains_entity(&self, entity: Entity) -> bool {
         self.data.contains_key(&entity)
     }
+
+    fn stored_entities(&self) -> Vec<Entity> {
+        self.data.keys().copied().collect()
+    }
+
+    fn get_any(&self, entity: Entity) -> Option<&dyn Any> {
+        self.data
+            .get(&entity)
+            .map(|component| component as &dyn Any)
+    }
+
+    fn take_boxed(&mut self, entity: Entity) -> Option<Box<dyn ErasedComponent>> {
+        self.data
+            .remove(&entity)
+            .map(|component| Box::new(BoxedComponent(component)) as Box<dyn ErasedComponent>)
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn approx_memory_bytes(&self) -> usize {
+        self.data.len() * std::mem::size_of::<T>()
+    }
+
+    fn allocated_capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    fn shrink(&mut self) {
+        self.data.shrink_to_fit();
+    }
+}
+
+/// A storage for tag components: zero-sized types without drop logic.
+///
+/// Tags like `Dead` or `Player` carry no data, so only the set of entities that
+/// have them is stored. Values are recreated on demand; this is sound because a
+/// zero-sized type has exactly one possible value and, lacking drop logic,
+/// nothing observable happens when one is created or discarded.
+///
+/// The world picks this storage automatically for every type where
+/// [`TagStorage::is_supported`] holds.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct TagStorage<T: Component> {
+    entities: HashSet<Entity>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Component> TagStorage<T> {
+    /// Returns `true` if `T` can be stored as a tag.
+    pub fn is_supported() -> bool {
+        std::mem::size_of::<T>() == 0 && !std::mem::needs_drop::<T>()
+    }
+
+    /// Creates a new empty storage.
+    ///
+    /// # Panics
+    /// Panics if `T` is not a tag type, see [`TagStorage::is_supported`].
+    pub fn new() -> Self {
+        assert!(
+            Self::is_supported(),
+            "TagStorage requires a zero-sized type without drop logic, got {}",
+            std::any::type_name::<T>()
+        );
+
+        Self {
+            entities: HashSet::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the tag value.
+    fn tag<'a>() -> &'a T {
+        // SAFETY: T is zero-sized (checked in new), so a dangling, well-aligned
+        // pointer is valid for reads, and no memory is ever accessed through it.
+        // Only called for entities holding a tag, so T is inhabited.
+        unsafe { NonNull::<T>::dangling().as_ref() }
+    }
+
+    /// Returns a mutable reference to the tag value.
+    fn tag_mut<'a>() -> &'a mut T {
+        // SAFETY: see `tag`; zero-sized references never alias any memory.
+        unsafe { NonNull::<T>::dangling().as_mut() }
+    }
+
+    /// Recreates an owned tag value.
+    fn recreate() -> T {
+        // SAFETY: see `tag`; reading a zero-sized value copies no bytes, and T
+        // has no drop logic, so duplicating the value is unobservable.
+        unsafe { std::ptr::read(NonNull::<T>::dangling().as_ptr()) }
+    }
+}
+
+impl<T: Component> Default for TagStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Component + Clone> TagStorage<T> {
+    /// Deep-copies the storage into a new type-erased box.
+    pub fn clone_box(&self) -> Box<dyn AnyStorage> {
+        Box::new(Self {
+            entities: self.entities.clone(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Component> ComponentStorage<T> for TagStorage<T> {
+    fn insert(&mut self, entity: Entity, _component: T) -> Result<(), ComponentError> {
+        if self.entities.insert(entity) {
+            Ok(())
+        } else {
+            Err(ComponentError::AlreadyExists {
+                entity,
+                type_name: T::type_name(),
+            })
+        }
+    }
+
+    fn insert_or_update(&mut self, entity: Entity, _component: T) -> Option<T> {
+        (!self.entities.insert(entity)).then(Self::recreate)
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.entities.remove(&entity).then(Self::recreate)
+    }
+
+    fn get(&self, entity: Entity) -> Option<&T> {
+        self.entities.contains(&entity).then(Self::tag)
+    }
+
+    fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.entities.contains(&entity).then(Self::tag_mut)
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    fn entities(&self) -> Box<dyn Iterator<Item = Entity> + '_> {
+        Box::new(self.entities.iter().copied())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Entity, &T)> + '_> {
+        Box::new(self.entities.iter().map(|entity| (*entity, Self::tag())))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (Entity, &mut T)> + '_> {
+        Box::new(
+            self.entities
+                .iter()
+                .map(|entity| (*entity, Self::tag_mut())),
+        )
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
+    fn capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+}
+
+impl<T: Component> AnyStorage for TagStorage<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.entities.remove(&entity);
+    }
+
+    fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    fn component_type_name(&self) -> &'static str {
+        T::type_name()
+    }
+
+    fn contains_entity(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    fn stored_entities(&self) -> Vec<Entity> {
+        self.entities.iter().copied().collect()
+    }
+
+    fn get_any(&self, entity: Entity) -> Option<&dyn Any> {
+        self.get(entity).map(|component| component as &dyn Any)
+    }
+
+    fn take_boxed(&mut self, entity: Entity) -> Option<Box<dyn ErasedComponent>> {
+        self.remove(entity)
+            .map(|component| Box::new(BoxedComponent(component)) as Box<dyn ErasedComponent>)
+    }
+
+    fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    fn approx_memory_bytes(&self) -> usize {
+        // Tags have no payload; only the entity set is kept
+        0
+    }
+
+    fn allocated_capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+
+    fn shrink(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+}
+
+/// A storage chosen with [`World::set_storage`].
+///
+/// Type-erased storages are only downcast to concrete storage types, so custom
+/// storages are wrapped in this one, which forwards every call to the box.
+pub(crate) struct CustomStorage<T: Component>(pub(crate) Box<dyn ComponentStorage<T>>);
+
+impl<T: Component> ComponentStorage<T> for CustomStorage<T> {
+    fn insert(&mut self, entity: Entity, component: T) -> Result<(), ComponentError> {
+        self.0.insert(entity, component)
+    }
+
+    fn insert_or_update(&mut self, entity: Entity, component: T) -> Option<T> {
+        self.0.insert_or_update(entity, component)
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.0.remove(entity)
+    }
+
+    fn get(&self, entity: Entity) -> Option<&T> {
+        self.0.get(entity)
+    }
+
+    fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.0.get_mut(entity)
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(entity)
+    }
+
+    fn entities(&self) -> Box<dyn Iterator<Item = Entity> + '_> {
+        self.0.entities()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Entity, &T)> + '_> {
+        self.0.iter()
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (Entity, &mut T)> + '_> {
+        self.0.iter_mut()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+}
+
+impl<T: Component> AnyStorage for CustomStorage<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.0.remove_entity(entity);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn component_type_name(&self) -> &'static str {
+        self.0.component_type_name()
+    }
+
+    fn contains_entity(&self, entity: Entity) -> bool {
+        self.0.contains_entity(entity)
+    }
+
+    fn stored_entities(&self) -> Vec<Entity> {
+        self.0.stored_entities()
+    }
+
+    fn get_any(&self, entity: Entity) -> Option<&dyn Any> {
+        self.0.get_any(entity)
+    }
+
+    fn take_boxed(&mut self, entity: Entity) -> Option<Box<dyn ErasedComponent>> {
+        self.0.take_boxed(entity)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn approx_memory_bytes(&self) -> usize {
+        self.0.approx_memory_bytes()
+    }
+
+    fn allocated_capacity(&self) -> usize {
+        self.0.allocated_capacity()
+    }
+
+    fn shrink(&mut self) {
+        self.0.shrink();
+    }
+}
+
+/// Views a type-erased storage as the component storage for `T`.
+///
+/// Returns `None` if the storage holds a different component type.
+pub(crate) fn downcast_storage<T: Component>(
+    storage: &dyn AnyStorage,
+) -> Option<&dyn ComponentStorage<T>> {
+    let any = storage.as_any();
+    if let Some(storage) = any.downcast_ref::<HashMapComponentStorage<T>>() {
+        return Some(storage);
+    }
+    if let Some(storage) = any.downcast_ref::<TagStorage<T>>() {
+        return Some(storage);
+    }
+    any.downcast_ref::<CustomStorage<T>>()
+        .map(|storage| storage.0.as_ref())
+}
+
+/// Mutable counterpart of [`downcast_storage`].
+pub(crate) fn downcast_storage_mut<T: Component>(
+    storage: &mut dyn AnyStorage,
+) -> Option<&mut dyn ComponentStorage<T>> {
+    let any = storage.as_any_mut();
+    if any.is::<HashMapComponentStorage<T>>() {
+        return any
+            .downcast_mut::<HashMapComponentStorage<T>>()
+            .map(|storage| storage as &mut dyn ComponentStorage<T>);
+    }
+    if any.is::<TagStorage<T>>() {
+        return any
+            .downcast_mut::<TagStorage<T>>()
+            .map(|storage| storage as &mut dyn ComponentStorage<T>);
+    }
+    let storage = any.downcast_mut::<CustomStorage<T>>()?;
+    Some(storage.0.as_mut())
 }
 
 /// Errors that can occur when working with components.
+///
+/// Every variant carries the component type name (from [`std::any::type_name`])
+/// and, where one is involved, the entity, so errors can be logged without
+/// extra bookkeeping at the call site.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{World, Component, ComponentError};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// let mut world = World::new();
+/// let entity = world.spawn_entity();
+/// world.add_component(entity, Position { x: 0.0, y: 0.0 }).unwrap();
+///
+/// let error = world.add_component(entity, Position { x: 1.0, y: 1.0 }).unwrap_err();
+/// assert!(matches!(error, ComponentError::AlreadyExists { entity: e, .. } if e == entity));
+/// assert_eq!(
+///     error.to_string(),
+///     format!("failed to add Position to entity {entity}: already exists")
+/// );
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ComponentError {
-    /// The component already exists for this entity.
-    ComponentAlreadyExists,
+    /// The entity already has a component of this type.
+    AlreadyExists {
+        entity: Entity,
+        type_name: &'static str,
+    },
     /// The component storage for this type is not registered.
-    StorageNotRegistered,
-    /// The component does not exist for this entity.
-    ComponentNotFound,
+    StorageNotRegistered { type_name: &'static str },
+    /// The entity does not have a component of this type.
+    NotFound {
+        entity: Entity,
+        type_name: &'static str,
+    },
+    /// The entity does not exist or has been deleted.
+    EntityNotFound {
+        entity: Entity,
+        type_name: &'static str,
+    },
+    /// The component type may only be added as an ephemeral component.
+    EphemeralOnly {
+        entity: Entity,
+        type_name: &'static str,
+    },
+    /// The resource has not been inserted.
+    ResourceNotFound { type_name: &'static str },
+    /// The resource has already been inserted.
+    ResourceAlreadyExists { type_name: &'static str },
+    /// The storage for this type already holds components, see [`World::set_storage`].
+    StorageNotEmpty { type_name: &'static str },
+}
+
+impl ComponentError {
+    /// Returns the entity involved in the error, if any.
+    pub fn entity(&self) -> Option<Entity> {
+        match self {
+            Self::AlreadyExists { entity, .. }
+            | Self::NotFound { entity, .. }
+            | Self::EntityNotFound { entity, .. }
+            | Self::EphemeralOnly { entity, .. } => Some(*entity),
+            Self::StorageNotRegistered { .. }
+            | Self::ResourceNotFound { .. }
+            | Self::ResourceAlreadyExists { .. }
+            | Self::StorageNotEmpty { .. } => None,
+        }
+    }
+
+    /// Returns the full type name of the component or resource involved.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::AlreadyExists { type_name, .. }
+            | Self::StorageNotRegistered { type_name }
+            | Self::NotFound { type_name, .. }
+            | Self::EntityNotFound { type_name, .. }
+            | Self::EphemeralOnly { type_name, .. }
+            | Self::ResourceNotFound { type_name }
+            | Self::ResourceAlreadyExists { type_name }
+            | Self::StorageNotEmpty { type_name } => type_name,
+        }
+    }
+}
+
+/// Strips the module path from a type name, e.g. `game::Position` -> `Position`.
+///
+/// Generic parameters are kept as they are.
+pub(crate) fn short_type_name(type_name: &str) -> &str {
+    let base_end = type_name.find('<').unwrap_or(type_name.len());
+    let start = type_name[..base_end]
+        .rfind("::")
+        .map_or(0, |index| index + 2);
+    &type_name[start..]
+}
+
+impl std::fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = short_type_name(self.type_name());
+        match self {
+            Self::AlreadyExists { entity, .. } => {
+                write!(f, "failed to add {name} to entity {entity}: already exists")
+            }
+            Self::StorageNotRegistered { .. } => {
+                write!(f, "no storage registered for component {name}")
+            }
+            Self::NotFound { entity, .. } => {
+                write!(f, "failed to access {name} on entity {entity}: not found")
+            }
+            Self::EntityNotFound { entity, .. } => {
+                write!(
+                    f,
+                    "failed to access {name} on entity {entity}: entity does not exist"
+                )
+            }
+            Self::EphemeralOnly { entity, .. } => {
+                write!(
+                    f,
+                    "failed to add {name} to entity {entity}: only allowed as an ephemeral component"
+                )
+            }
+            Self::ResourceNotFound { .. } => write!(f, "resource {name} not found"),
+            Self::ResourceAlreadyExists { .. } => write!(f, "resource {name} already exists"),
+            Self::StorageNotEmpty { .. } => {
+                write!(f, "cannot set the storage for {name}: storage is not empty")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComponentError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity() -> Entity {
+        Entity::new()
+    }
+
+    #[test]
+    fn test_component_error_display() {
+        let e = entity();
+        let name = "game::components::Position";
+
+        assert_eq!(
+            ComponentError::AlreadyExists {
+                entity: e,
+                type_name: name
+            }
+            .to_string(),
+            format!("failed to add Position to entity {e}: already exists")
+        );
+        assert_eq!(
+            ComponentError::StorageNotRegistered { type_name: name }.to_string(),
+            "no storage registered for component Position"
+        );
+        assert_eq!(
+            ComponentError::NotFound {
+                entity: e,
+                type_name: name
+            }
+            .to_string(),
+            format!("failed to access Position on entity {e}: not found")
+        );
+        assert_eq!(
+            ComponentError::EntityNotFound {
+                entity: e,
+                type_name: name
+            }
+            .to_string(),
+            format!("failed to access Position on entity {e}: entity does not exist")
+        );
+        assert_eq!(
+            ComponentError::EphemeralOnly {
+                entity: e,
+                type_name: name
+            }
+            .to_string(),
+            format!("failed to add Position to entity {e}: only allowed as an ephemeral component")
+        );
+        assert_eq!(
+            ComponentError::ResourceNotFound { type_name: name }.to_string(),
+            "resource Position not found"
+        );
+        assert_eq!(
+            ComponentError::ResourceAlreadyExists { type_name: name }.to_string(),
+            "resource Position already exists"
+        );
+    }
+
+    #[test]
+    fn test_component_error_is_std_error() {
+        let e = entity();
+        let error: Box<dyn std::error::Error> = Box::new(ComponentError::EntityNotFound {
+            entity: e,
+            type_name: "Health",
+        });
+        assert!(error.source().is_none());
+        assert_eq!(
+            error.to_string(),
+            format!("failed to access Health on entity {e}: entity does not exist")
+        );
+    }
+
+    #[test]
+    fn test_component_error_accessors() {
+        let e = entity();
+        let error = ComponentError::NotFound {
+            entity: e,
+            type_name: "game::Health",
+        };
+        assert_eq!(error.entity(), Some(e));
+        assert_eq!(error.type_name(), "game::Health");
+
+        let error = ComponentError::ResourceNotFound {
+            type_name: "game::Time",
+        };
+        assert_eq!(error.entity(), None);
+        assert_eq!(error.type_name(), "game::Time");
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("Position"), "Position");
+        assert_eq!(short_type_name("game::world::Position"), "Position");
+        assert_eq!(
+            short_type_name("game::Wrapper<core::Inner>"),
+            "Wrapper<core::Inner>"
+        );
+    }
 }
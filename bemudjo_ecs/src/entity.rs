@@ -6,6 +6,10 @@ use std::sync::atomic::{AtomicU64, Ordering};
 /// Each entity is guaranteed to be unique and should only be created through
 /// the [`World::spawn_entity()`](crate::World::spawn_entity) method.
 ///
+/// Ids of cleaned up entities are reused by later spawns with a bumped
+/// generation, so a handle kept after its entity was deleted never refers to
+/// the entity that reuses the id.
+///
 /// # Examples
 ///
 /// ```
@@ -20,11 +24,59 @@ use std::sync::atomic::{AtomicU64, Ordering};
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Entity {
     id: u64,
+    generation: u32, // bumped each time the id is reused
+}
+
+impl std::fmt::Display for Entity {
+    /// Formats the entity as its numeric id, e.g. for log messages. Reused ids
+    /// carry their generation, as in `42v3`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.generation == 0 {
+            write!(f, "{}", self.id)
+        } else {
+            write!(f, "{}v{}", self.id, self.generation)
+        }
+    }
 }
 
 static CURRENT_ID: AtomicU64 = AtomicU64::new(0);
 
 impl Entity {
+    /// A sentinel entity that never refers to a spawned entity.
+    ///
+    /// Handy for initializing fields like `target: Entity` in hot structs without
+    /// wrapping them in `Option`. [`World::spawn_entity()`] never returns it, and
+    /// every `World` accessor treats it as a missing entity, returning `None`,
+    /// `false` or an `EntityNotFound` error.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Entity, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let target = Entity::PLACEHOLDER;
+    ///
+    /// assert!(target.is_placeholder());
+    /// assert_eq!(world.get_component::<Health>(target), None);
+    /// assert!(world.add_component(target, Health { value: 10 }).is_err());
+    /// assert_ne!(world.spawn_entity(), target);
+    /// ```
+    ///
+    /// [`World::spawn_entity()`]: crate::World::spawn_entity
+    pub const PLACEHOLDER: Entity = Entity {
+        id: u64::MAX,
+        generation: 0,
+    };
+
+    /// Returns `true` if this is [`Entity::PLACEHOLDER`].
+    pub const fn is_placeholder(self) -> bool {
+        self.id == Self::PLACEHOLDER.id
+    }
+
     /// Creates a new unique entity.
     ///
     /// This method is internal to the crate and should only be called by [`World::spawn_entity()`].
@@ -33,10 +85,60 @@ impl Entity {
     /// [`World::spawn_entity()`]: crate::World::spawn_entity
     #[allow(clippy::new_without_default)]
     pub(crate) fn new() -> Entity {
-        Entity {
-            id: CURRENT_ID.fetch_add(1, Ordering::Relaxed),
+        let mut id = CURRENT_ID.fetch_add(1, Ordering::Relaxed);
+        if id == Self::PLACEHOLDER.id {
+            // The counter wrapped around; the placeholder id is reserved
+            id = CURRENT_ID.fetch_add(1, Ordering::Relaxed);
         }
+        Entity::from_id(id)
     }
+
+    /// Creates the entity with the given id, for per-world allocation.
+    pub(crate) const fn from_id(id: u64) -> Entity {
+        Entity { id, generation: 0 }
+    }
+
+    /// Returns the id, shared by every generation of the entity.
+    pub(crate) const fn id(self) -> u64 {
+        self.id
+    }
+
+    /// Returns a handle for the same id with the next generation, or `None`
+    /// once the generations are exhausted and the id must be retired.
+    pub(crate) fn next_generation(self) -> Option<Entity> {
+        Some(Entity {
+            id: self.id,
+            generation: self.generation.checked_add(1)?,
+        })
+    }
+}
+
+/// How a [`World`](crate::World) assigns ids to the entities it spawns.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{EntityAllocator, World};
+///
+/// let mut first = World::new_with_allocator(EntityAllocator::Deterministic);
+/// let mut second = World::new_with_allocator(EntityAllocator::Deterministic);
+///
+/// // The same operations produce the same ids, whatever else ran before
+/// assert_eq!(first.spawn_entity(), second.spawn_entity());
+/// assert_eq!(first.spawn_entity(), second.spawn_entity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntityAllocator {
+    /// Ids come from a process-wide counter, so entities are unique across
+    /// every world. Used by [`World::new`](crate::World::new).
+    #[default]
+    Global,
+    /// Ids come from a counter owned by the world, so they only depend on the
+    /// world's own history. Useful for replays and golden tests.
+    ///
+    /// Id 0 is reserved for the world's resources and the first spawned entity
+    /// gets id 1. Entities of different deterministic worlds can have equal
+    /// ids, so an entity must only be used with the world that spawned it.
+    Deterministic,
 }
 
 #[cfg(test)]
@@ -57,4 +159,29 @@ mod tests {
 
         assert_eq!(entity, entity);
     }
+
+    #[test]
+    fn test_placeholder_is_never_spawned() {
+        let entities: Vec<Entity> = (0..1000).map(|_| Entity::new()).collect();
+
+        assert!(entities.iter().all(|entity| !entity.is_placeholder()));
+        assert!(!entities.contains(&Entity::PLACEHOLDER));
+        assert!(Entity::PLACEHOLDER.is_placeholder());
+    }
+
+    #[test]
+    fn test_next_generation_keeps_id() {
+        let entity = Entity::new();
+        let reused = entity.next_generation().unwrap();
+
+        assert_ne!(entity, reused);
+        assert_eq!(entity.id, reused.id);
+        assert_eq!(reused.to_string(), format!("{}v1", entity.id));
+
+        let exhausted = Entity {
+            id: entity.id,
+            generation: u32::MAX,
+        };
+        assert_eq!(exhausted.next_generation(), None);
+    }
 }
@@ -0,0 +1,196 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{Entity, World};
+
+/// Assigns small, stable public ids to entities, e.g. for network protocols.
+///
+/// [`Entity`] values come from a process-wide counter, so they grow without
+/// bound and reveal how long the server has been running. An `EntityMapper`
+/// hands out compact `u32` ids instead, on demand, and recycles an id only
+/// after it was told the entity is gone. Released ids are reused in the order
+/// they were freed, so a stale id held by a client takes as long as possible
+/// to point at a different entity.
+///
+/// Keep the mapper in sync with a world by enabling
+/// [`World::track_cleaned_entities`] and calling [`EntityMapper::sync`] after
+/// each tick.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{EntityMapper, World};
+///
+/// let mut world = World::new();
+/// world.track_cleaned_entities();
+/// let mut mapper = EntityMapper::new();
+///
+/// let player = world.spawn_entity();
+/// let id = mapper.public_id(player);
+/// assert_eq!(id, 0);
+/// assert_eq!(mapper.resolve(id), Some(player));
+///
+/// world.delete_entity(player);
+/// world.cleanup_deleted_entities();
+/// mapper.sync(&mut world);
+/// assert_eq!(mapper.resolve(id), None);
+/// ```
+#[derive(Debug, Default)]
+pub struct EntityMapper {
+    public_ids: HashMap<Entity, u32>,
+    entities: Vec<Option<Entity>>, // indexed by public id
+    free_ids: VecDeque<u32>,
+}
+
+impl EntityMapper {
+    /// Creates an empty mapper.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the public id of an entity, assigning one if it has none yet.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` entities are mapped at once.
+    pub fn public_id(&mut self, entity: Entity) -> u32 {
+        if let Some(&id) = self.public_ids.get(&entity) {
+            return id;
+        }
+
+        let id = match self.free_ids.pop_front() {
+            Some(id) => {
+                self.entities[id as usize] = Some(entity);
+                id
+            }
+            None => {
+                let id =
+                    u32::try_from(self.entities.len()).expect("EntityMapper ran out of public ids");
+                self.entities.push(Some(entity));
+                id
+            }
+        };
+        self.public_ids.insert(entity, id);
+        id
+    }
+
+    /// Returns the public id of an entity without assigning one.
+    pub fn get(&self, entity: Entity) -> Option<u32> {
+        self.public_ids.get(&entity).copied()
+    }
+
+    /// Returns the entity currently mapped to `public_id`.
+    pub fn resolve(&self, public_id: u32) -> Option<Entity> {
+        self.entities.get(public_id as usize).copied().flatten()
+    }
+
+    /// Forgets an entity and makes its public id available for reuse.
+    ///
+    /// # Returns
+    /// The id the entity had, or `None` if it wasn't mapped
+    pub fn release(&mut self, entity: Entity) -> Option<u32> {
+        let id = self.public_ids.remove(&entity)?;
+        self.entities[id as usize] = None;
+        self.free_ids.push_back(id);
+        Some(id)
+    }
+
+    /// Releases every entity the world cleaned up since the last sync.
+    ///
+    /// Drains [`World::drain_recently_cleaned`], so only one mapper should sync
+    /// with a given world.
+    pub fn sync(&mut self, world: &mut World) {
+        for entity in world.drain_recently_cleaned() {
+            self.release(entity);
+        }
+    }
+
+    /// Returns the number of entities currently mapped.
+    pub fn len(&self) -> usize {
+        self.public_ids.len()
+    }
+
+    /// Returns `true` if no entity is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.public_ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_public_id_is_stable_and_compact() {
+        let mut world = World::new();
+        let mut mapper = EntityMapper::new();
+        let a = world.spawn_entity();
+        let b = world.spawn_entity();
+
+        assert_eq!(mapper.public_id(a), 0);
+        assert_eq!(mapper.public_id(b), 1);
+        assert_eq!(mapper.public_id(a), 0);
+        assert_eq!(mapper.get(b), Some(1));
+        assert_eq!(mapper.resolve(2), None);
+        assert_eq!(mapper.len(), 2);
+    }
+
+    #[test]
+    fn test_ids_recycled_only_after_cleanup() {
+        let mut world = World::new();
+        world.track_cleaned_entities();
+        let mut mapper = EntityMapper::new();
+        let old = world.spawn_entity();
+        let old_id = mapper.public_id(old);
+
+        // Deleted but not cleaned up: the id stays reserved
+        world.delete_entity(old);
+        mapper.sync(&mut world);
+        let fresh = world.spawn_entity();
+        assert_ne!(mapper.public_id(fresh), old_id);
+        assert_eq!(mapper.resolve(old_id), Some(old));
+
+        world.cleanup_deleted_entities();
+        mapper.sync(&mut world);
+        assert_eq!(mapper.resolve(old_id), None);
+        let newest = world.spawn_entity();
+        assert_eq!(mapper.public_id(newest), old_id);
+        assert_eq!(mapper.release(old), None);
+    }
+
+    #[test]
+    fn test_consistent_across_spawn_delete_cycles() {
+        let mut world = World::new();
+        world.track_cleaned_entities();
+        let mut mapper = EntityMapper::new();
+        let mut live: Vec<Entity> = Vec::new();
+        let mut max_live = 0;
+
+        for cycle in 0..200 {
+            for _ in 0..(cycle % 7) + 1 {
+                let entity = world.spawn_entity();
+                mapper.public_id(entity);
+                live.push(entity);
+            }
+            // Delete every third live entity
+            let mut index = cycle % 3;
+            while index < live.len() {
+                world.delete_entity(live.swap_remove(index));
+                index += 3;
+            }
+            world.cleanup_deleted_entities();
+            mapper.sync(&mut world);
+            max_live = max_live.max(live.len());
+
+            // Every live entity round-trips and no two share an id
+            let ids: HashSet<u32> = live.iter().map(|&e| mapper.get(e).unwrap()).collect();
+            assert_eq!(ids.len(), live.len());
+            for &entity in &live {
+                assert_eq!(mapper.resolve(mapper.get(entity).unwrap()), Some(entity));
+            }
+            assert_eq!(mapper.len(), live.len());
+        }
+
+        // Ids stay compact: never more than the peak number of mapped entities
+        let highest = live.iter().map(|&e| mapper.get(e).unwrap()).max().unwrap();
+        assert!((highest as usize) < max_live + 7, "highest id {highest}");
+    }
+}
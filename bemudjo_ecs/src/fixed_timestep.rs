@@ -0,0 +1,282 @@
+use crate::{SequentialSystemScheduler, World};
+use std::time::Duration;
+
+/// Drives a scheduler at a fixed simulation rate from real elapsed time.
+///
+/// Real frame or loop times vary, but game logic is easiest to reason about
+/// when every tick represents the same amount of time. `FixedTimestep`
+/// accumulates the real time passed to [`FixedTimestep::advance`] and runs as
+/// many whole ticks as fit into it, carrying the remainder over to the next call.
+///
+/// To avoid a "spiral of death" after a long stall (every advance having to run
+/// more ticks than the last), at most `max_steps` ticks run per advance. Any time
+/// that is still left once the cap is hit is discarded.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{FixedTimestep, SequentialSystemScheduler, World};
+/// use std::time::Duration;
+///
+/// let mut world = World::new();
+/// let mut scheduler = SequentialSystemScheduler::new();
+/// scheduler.build().unwrap();
+///
+/// let mut driver = FixedTimestep::new(Duration::from_millis(16));
+///
+/// // 50ms of real time is three 16ms ticks, 2ms carry over
+/// let ticks = driver.advance(&scheduler, &mut world, Duration::from_millis(50));
+/// assert_eq!(ticks, 3);
+/// assert_eq!(driver.accumulated(), Duration::from_millis(2));
+///
+/// // The carried time counts towards the next tick
+/// let ticks = driver.advance(&scheduler, &mut world, Duration::from_millis(14));
+/// assert_eq!(ticks, 1);
+/// assert_eq!(driver.accumulated(), Duration::ZERO);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulated: Duration,
+    max_steps: u32,
+}
+
+impl FixedTimestep {
+    /// Default cap on the number of ticks run by a single `advance()`.
+    pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+    /// Creates a driver running one tick per `step` of real time.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero.
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "FixedTimestep step must be non-zero");
+
+        Self {
+            step,
+            accumulated: Duration::ZERO,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+        }
+    }
+
+    /// Sets the maximum number of ticks a single `advance()` may run.
+    ///
+    /// # Panics
+    /// Panics if `max_steps` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{FixedTimestep, SequentialSystemScheduler, World};
+    /// use std::time::Duration;
+    ///
+    /// let mut world = World::new();
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut driver = FixedTimestep::new(Duration::from_millis(10)).with_max_steps(2);
+    ///
+    /// // A one second stall only runs two ticks and drops the backlog
+    /// assert_eq!(driver.advance(&scheduler, &mut world, Duration::from_secs(1)), 2);
+    /// assert_eq!(driver.accumulated(), Duration::ZERO);
+    /// ```
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        assert!(max_steps > 0, "FixedTimestep max_steps must be at least 1");
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Returns the fixed simulation step.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Returns the maximum number of ticks run per `advance()`.
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    /// Returns the real time accumulated but not yet consumed by a tick.
+    ///
+    /// Always less than `step()` after an `advance()`.
+    pub fn accumulated(&self) -> Duration {
+        self.accumulated
+    }
+
+    /// Returns how far the accumulated time is into the next step, from 0.0 to 1.0.
+    ///
+    /// Useful for interpolating output between two simulation states.
+    pub fn overstep_fraction(&self) -> f32 {
+        self.accumulated.as_secs_f32() / self.step.as_secs_f32()
+    }
+
+    /// Adds `elapsed` real time and runs every whole step that fits into it.
+    ///
+    /// # Parameters
+    /// * `scheduler` - A built scheduler to tick
+    /// * `world` - The world to run the ticks against
+    /// * `elapsed` - Real time passed since the previous call
+    ///
+    /// # Returns
+    /// The number of ticks that were run
+    ///
+    /// # Panics
+    /// Panics if the scheduler has not been built and at least one tick is due.
+    pub fn advance(
+        &mut self,
+        scheduler: &SequentialSystemScheduler,
+        world: &mut World,
+        elapsed: Duration,
+    ) -> u32 {
+        self.advance_with(elapsed, || scheduler.run_tick(world))
+    }
+
+    /// Adds `elapsed` real time and calls `tick` once for every whole step.
+    ///
+    /// This is the scheduler-independent core of [`FixedTimestep::advance`], for
+    /// callers that need to do extra work around each tick.
+    ///
+    /// # Returns
+    /// The number of times `tick` was called
+    pub fn advance_with<F: FnMut()>(&mut self, elapsed: Duration, mut tick: F) -> u32 {
+        self.accumulated += elapsed;
+
+        let mut steps = 0;
+        while self.accumulated >= self.step {
+            if steps == self.max_steps {
+                // Spiral of death protection: drop the backlog
+                self.accumulated = Duration::ZERO;
+                break;
+            }
+
+            self.accumulated -= self.step;
+            tick();
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// Discards any accumulated time, e.g. after the server was paused.
+    pub fn reset(&mut self) {
+        self.accumulated = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, System};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TickCount(u32);
+    impl Component for TickCount {}
+
+    struct CountingSystem;
+    impl System for CountingSystem {
+        fn run(&self, world: &mut World) {
+            let count = world.get_resource::<TickCount>().map_or(0, |c| c.0);
+            world.insert_resource(TickCount(count + 1));
+        }
+    }
+
+    fn counting_scheduler() -> SequentialSystemScheduler {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(CountingSystem).unwrap();
+        scheduler.build().unwrap();
+        scheduler
+    }
+
+    fn ticks(world: &World) -> u32 {
+        world.get_resource::<TickCount>().map_or(0, |c| c.0)
+    }
+
+    #[test]
+    fn test_advance_runs_whole_steps_and_keeps_remainder() {
+        let scheduler = counting_scheduler();
+        let mut world = World::new();
+        let mut driver = FixedTimestep::new(Duration::from_secs_f32(0.016));
+
+        let ran = driver.advance(&scheduler, &mut world, Duration::from_secs_f32(0.05));
+
+        assert_eq!(ran, 3);
+        assert_eq!(ticks(&world), 3);
+        let remainder = driver.accumulated().as_secs_f32();
+        assert!(
+            (remainder - 0.002).abs() < 1e-5,
+            "remainder was {remainder}"
+        );
+    }
+
+    #[test]
+    fn test_remainder_carries_over() {
+        let scheduler = counting_scheduler();
+        let mut world = World::new();
+        let mut driver = FixedTimestep::new(Duration::from_millis(10));
+
+        assert_eq!(
+            driver.advance(&scheduler, &mut world, Duration::from_millis(4)),
+            0
+        );
+        assert_eq!(
+            driver.advance(&scheduler, &mut world, Duration::from_millis(4)),
+            0
+        );
+        assert_eq!(
+            driver.advance(&scheduler, &mut world, Duration::from_millis(4)),
+            1
+        );
+
+        assert_eq!(ticks(&world), 1);
+        assert_eq!(driver.accumulated(), Duration::from_millis(2));
+        assert!((driver.overstep_fraction() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_max_steps_caps_and_drops_backlog() {
+        let scheduler = counting_scheduler();
+        let mut world = World::new();
+        let mut driver = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(driver.max_steps(), FixedTimestep::DEFAULT_MAX_STEPS);
+
+        let ran = driver.advance(&scheduler, &mut world, Duration::from_secs(10));
+
+        assert_eq!(ran, FixedTimestep::DEFAULT_MAX_STEPS);
+        assert_eq!(driver.accumulated(), Duration::ZERO);
+
+        // Back to normal on the next advance
+        assert_eq!(
+            driver.advance(&scheduler, &mut world, Duration::from_millis(25)),
+            2
+        );
+        assert_eq!(ticks(&world), FixedTimestep::DEFAULT_MAX_STEPS + 2);
+    }
+
+    #[test]
+    fn test_exact_multiple_leaves_no_remainder() {
+        let mut driver = FixedTimestep::new(Duration::from_millis(20)).with_max_steps(3);
+        let mut calls = 0;
+
+        assert_eq!(
+            driver.advance_with(Duration::from_millis(60), || calls += 1),
+            3
+        );
+        assert_eq!(calls, 3);
+        assert_eq!(driver.accumulated(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_reset_discards_accumulated_time() {
+        let mut driver = FixedTimestep::new(Duration::from_millis(20));
+        driver.advance_with(Duration::from_millis(15), || {});
+
+        driver.reset();
+
+        assert_eq!(driver.accumulated(), Duration::ZERO);
+        assert_eq!(driver.advance_with(Duration::from_millis(15), || {}), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn test_zero_step_panics() {
+        FixedTimestep::new(Duration::ZERO);
+    }
+}
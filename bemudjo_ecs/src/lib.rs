@@ -1,18 +1,90 @@
+#[cfg(feature = "thread-safe-components")]
+pub mod async_world;
+pub mod bundle;
+pub mod cached_query;
 pub mod component;
 pub mod entity;
+pub mod entity_mapper;
+pub mod fixed_timestep;
+#[cfg(feature = "prefab")]
+pub mod prefab;
 pub mod query;
+pub mod registry;
+pub mod rng;
 pub mod sequential_system_scheduler;
+pub mod spatial;
 pub mod system;
+pub mod time;
 pub mod world;
+pub mod world_registry;
+
+/// Builds a list of component `TypeId`s for [`Query::with_any_of`].
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{any_of, Component};
+/// use std::any::TypeId;
+///
+/// struct Player;
+/// impl Component for Player {}
+///
+/// struct Npc;
+/// impl Component for Npc {}
+///
+/// let types = any_of!(Player, Npc);
+/// assert_eq!(types, [TypeId::of::<Player>(), TypeId::of::<Npc>()]);
+/// ```
+#[macro_export]
+macro_rules! any_of {
+    ($($component:ty),* $(,)?) => {
+        [$(::std::any::TypeId::of::<$component>()),*]
+    };
+}
 
 // Re-export commonly used types
+#[cfg(feature = "thread-safe-components")]
+pub use async_world::AsyncWorld;
+#[cfg(feature = "derive")]
+pub use bemudjo_ecs_derive::Component;
+pub use bundle::Bundle;
+pub use cached_query::CachedQuery;
 pub use component::{Component, ComponentError};
-pub use entity::Entity;
-pub use query::Query;
-pub use sequential_system_scheduler::SequentialSystemScheduler;
-pub use system::System;
-pub use world::World;
+pub use entity::{Entity, EntityAllocator};
+pub use entity_mapper::EntityMapper;
+pub use fixed_timestep::FixedTimestep;
+pub use query::{ComponentSet, Query, QueryIter};
+pub use registry::ComponentRegistry;
+pub use rng::{Rng, SampleUniform};
+pub use sequential_system_scheduler::{BuildReport, SequentialSystemScheduler, Stage};
+pub use system::{ResourceAccess, System};
+pub use time::{TickRunner, Time};
+pub use world::{
+    BatchResult, ComponentDump, ComponentRefTuple, ComponentStats, ConsistencyReport,
+    ConsistencyViolation, DebugComponent, EntityBundle, EntityDump, EventBatch, EventFilter,
+    EventReceiver, Events, HierarchyError, HistoryRecorder, LabelError, MergeError, MergeReport,
+    NamedQueryError, QueryDef, ResourceError, ResourceMergePolicy, RollbackError, SnapshotError,
+    TagError, TagId, TransferError, UpsertOutcome, ViolationKind, World, WorldDump, WorldEvent,
+    WorldSnapshot, WorldStats, WorldView,
+};
+pub use world_registry::{GlobalEntityRef, WorldId, WorldRegistry, WorldRegistryError};
+
+pub use component::ComponentStorage;
 
 // Re-export internal types that advanced users might need
 #[doc(hidden)]
-pub use component::{AnyStorage, ComponentStorage, HashMapComponentStorage};
+pub use component::{ErasedComponent, HashMapComponentStorage, TagStorage};
+
+// Documented for custom tooling with the `introspection` feature
+#[cfg_attr(not(feature = "introspection"), doc(hidden))]
+pub use component::AnyStorage;
+
+// Support code for `#[derive(Component)]`, not part of the public API
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    /// Implemented for every type `#[derive(Component)]` accepts. Components
+    /// have to be `Clone`, e.g. for `update_component` and snapshots.
+    pub trait ComponentMustBeClone: Clone + 'static {}
+
+    impl<T: Clone + 'static> ComponentMustBeClone for T {}
+}
@@ -0,0 +1,406 @@
+//! Data-driven entity definitions.
+//!
+//! A [`Prefab`] is a named set of component values loaded from JSON, which can
+//! be instantiated any number of times with [`World::instantiate`]. Components
+//! are looked up by name in a [`PrefabRegistry`], so content like goblins, rooms
+//! and items can live in data files instead of spawn code.
+//!
+//! Only available with the `prefab` feature.
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Component, ComponentError, ComponentRegistry, Entity, World};
+
+/// Errors that can occur when loading or instantiating a prefab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefabError {
+    /// The input is not valid JSON or doesn't have the prefab layout.
+    Parse { message: String },
+    /// A component name is not registered in the prefab registry.
+    UnknownComponent { component: String },
+    /// A component's data doesn't match its type.
+    InvalidComponent { component: String, message: String },
+    /// Adding a component to the new entity failed.
+    Component(ComponentError),
+}
+
+impl std::fmt::Display for PrefabError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefabError::Parse { message } => write!(f, "invalid prefab: {message}"),
+            PrefabError::UnknownComponent { component } => {
+                write!(f, "unknown prefab component {component}")
+            }
+            PrefabError::InvalidComponent { component, message } => {
+                write!(f, "invalid prefab component {component}: {message}")
+            }
+            PrefabError::Component(error) => write!(f, "cannot instantiate prefab: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PrefabError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PrefabError::Component(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ComponentError> for PrefabError {
+    fn from(error: ComponentError) -> Self {
+        PrefabError::Component(error)
+    }
+}
+
+/// A deserialized component value that can be added to many entities.
+trait PrefabComponent {
+    /// Adds a copy of the component to `entity`.
+    fn add_to(&self, world: &mut World, entity: Entity) -> Result<(), ComponentError>;
+}
+
+struct Template<T>(T);
+
+impl<T: Component + Clone> PrefabComponent for Template<T> {
+    fn add_to(&self, world: &mut World, entity: Entity) -> Result<(), ComponentError> {
+        world.add_component(entity, self.0.clone())
+    }
+}
+
+/// Turns the JSON data of one component into a template.
+type DeserializeFn = fn(Value) -> Result<Box<dyn PrefabComponent>, serde_json::Error>;
+
+fn deserialize_template<T: Component + Clone + DeserializeOwned>(
+    value: Value,
+) -> Result<Box<dyn PrefabComponent>, serde_json::Error> {
+    let component: T = serde_json::from_value(value)?;
+    Ok(Box::new(Template(component)))
+}
+
+/// A loaded entity definition, see [`PrefabRegistry::load_str`].
+pub struct Prefab {
+    name: Option<String>,
+    components: Vec<(String, Box<dyn PrefabComponent>)>,
+}
+
+impl Prefab {
+    /// Returns the prefab's name, if the data declared one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the names of the prefab's components.
+    pub fn component_names(&self) -> Vec<&str> {
+        self.components
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the number of components in the prefab.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns `true` if the prefab has no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Prefab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Prefab")
+            .field("name", &self.name)
+            .field("components", &self.component_names())
+            .finish()
+    }
+}
+
+/// Maps component names used in prefab data to component types.
+///
+/// Prefabs are JSON objects with an optional `name` and a `components` object
+/// keyed by the registered component names. Unit structs are written as `null`.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::prefab::PrefabRegistry;
+/// use bemudjo_ecs::{Component, World};
+/// use serde::Deserialize;
+///
+/// #[derive(Clone, Debug, PartialEq, Deserialize)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// #[derive(Clone, Debug, PartialEq, Deserialize)]
+/// struct Hostile;
+/// impl Component for Hostile {}
+///
+/// let mut registry = PrefabRegistry::new();
+/// registry.register::<Health>("Health");
+/// registry.register::<Hostile>("Hostile");
+///
+/// let goblin = registry.load_str(r#"{
+///     "name": "goblin",
+///     "components": {
+///         "Health": { "value": 15 },
+///         "Hostile": null
+///     }
+/// }"#).unwrap();
+///
+/// let mut world = World::new();
+/// let entity = world.instantiate(&goblin).unwrap();
+/// assert_eq!(world.get_component::<Health>(entity), Some(&Health { value: 15 }));
+/// assert!(world.has_component::<Hostile>(entity));
+/// ```
+#[derive(Default)]
+pub struct PrefabRegistry {
+    deserializers: HashMap<String, DeserializeFn>,
+    components: ComponentRegistry,
+}
+
+impl PrefabRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers component type `T` under `name`.
+    ///
+    /// Registering another type under the same name replaces the previous one.
+    pub fn register<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: Component + Clone + DeserializeOwned,
+    {
+        self.deserializers
+            .insert(name.to_string(), deserialize_template::<T>);
+        self.components.register::<T>();
+        self
+    }
+
+    /// Returns the component types registered so far.
+    ///
+    /// Entities built from prefabs only have registered components, so this
+    /// registry can also be passed to [`World::transfer_entity`].
+    pub fn components(&self) -> &ComponentRegistry {
+        &self.components
+    }
+
+    /// Parses a prefab from JSON.
+    ///
+    /// Every component is deserialized right away, so errors surface at load
+    /// time rather than when the prefab is instantiated.
+    ///
+    /// # Returns
+    /// * `Ok(Prefab)` if all components are registered and valid
+    /// * `Err(PrefabError::Parse)` if the input isn't a JSON prefab object
+    /// * `Err(PrefabError::UnknownComponent)` naming the first unregistered component
+    /// * `Err(PrefabError::InvalidComponent)` naming the component with malformed data
+    pub fn load_str(&self, source: &str) -> Result<Prefab, PrefabError> {
+        let value: Value = serde_json::from_str(source).map_err(|error| PrefabError::Parse {
+            message: error.to_string(),
+        })?;
+        let Value::Object(mut object) = value else {
+            return Err(PrefabError::Parse {
+                message: "expected a JSON object".to_string(),
+            });
+        };
+
+        let name = match object.remove("name") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(name)) => Some(name),
+            Some(_) => {
+                return Err(PrefabError::Parse {
+                    message: "`name` must be a string".to_string(),
+                })
+            }
+        };
+        let Some(Value::Object(component_values)) = object.remove("components") else {
+            return Err(PrefabError::Parse {
+                message: "expected a `components` object".to_string(),
+            });
+        };
+
+        let mut components = Vec::with_capacity(component_values.len());
+        for (component, data) in component_values {
+            let Some(deserialize) = self.deserializers.get(&component) else {
+                return Err(PrefabError::UnknownComponent { component });
+            };
+            match deserialize(data) {
+                Ok(template) => components.push((component, template)),
+                Err(error) => {
+                    return Err(PrefabError::InvalidComponent {
+                        component,
+                        message: error.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(Prefab { name, components })
+    }
+}
+
+impl World {
+    /// Spawns a new entity with a copy of every component of `prefab`.
+    ///
+    /// Each call creates an independent entity; changing the components of one
+    /// instance doesn't affect the prefab or other instances.
+    ///
+    /// # Returns
+    /// * `Ok(Entity)` - The new entity
+    /// * `Err(PrefabError::Component)` if a component couldn't be added; the
+    ///   partially built entity is deleted
+    pub fn instantiate(&mut self, prefab: &Prefab) -> Result<Entity, PrefabError> {
+        let entity = self.spawn_entity();
+
+        for (_, component) in &prefab.components {
+            if let Err(error) = component.add_to(self, entity) {
+                self.delete_entity(entity);
+                return Err(error.into());
+            }
+        }
+
+        Ok(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Inventory {
+        items: Vec<String>,
+    }
+    impl Component for Inventory {}
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Hostile;
+    impl Component for Hostile {}
+
+    const GOBLIN: &str = r#"{
+        "name": "goblin",
+        "components": {
+            "Position": { "x": 1.0, "y": 2.0 },
+            "Inventory": { "items": ["dagger"] },
+            "Hostile": null
+        }
+    }"#;
+
+    fn registry() -> PrefabRegistry {
+        let mut registry = PrefabRegistry::new();
+        registry
+            .register::<Position>("Position")
+            .register::<Inventory>("Inventory")
+            .register::<Hostile>("Hostile");
+        registry
+    }
+
+    #[test]
+    fn test_instances_are_independent() {
+        let prefab = registry().load_str(GOBLIN).unwrap();
+        assert_eq!(prefab.name(), Some("goblin"));
+        assert_eq!(prefab.len(), 3);
+
+        let mut world = World::new();
+        let first = world.instantiate(&prefab).unwrap();
+        let second = world.instantiate(&prefab).unwrap();
+        assert_ne!(first, second);
+
+        world
+            .update_component::<Inventory, _>(first, |mut inventory| {
+                inventory.items.push("gold".to_string());
+                inventory
+            })
+            .unwrap();
+        world.replace_component(first, Position { x: 9.0, y: 9.0 });
+
+        assert_eq!(
+            world.get_component::<Inventory>(second).unwrap().items,
+            vec!["dagger".to_string()]
+        );
+        assert_eq!(
+            world.get_component::<Position>(second),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert!(world.has_component::<Hostile>(first));
+        assert!(world.has_component::<Hostile>(second));
+
+        // The prefab itself is unchanged
+        let third = world.instantiate(&prefab).unwrap();
+        assert_eq!(
+            world.get_component::<Inventory>(third).unwrap().items,
+            vec!["dagger".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_unknown_component_is_named() {
+        let error = registry()
+            .load_str(r#"{ "components": { "Position": { "x": 0, "y": 0 }, "Mana": 5 } }"#)
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            PrefabError::UnknownComponent {
+                component: "Mana".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "unknown prefab component Mana");
+    }
+
+    #[test]
+    fn test_malformed_fields_point_at_component() {
+        let error = registry()
+            .load_str(r#"{ "components": { "Position": { "x": "left" } } }"#)
+            .unwrap_err();
+
+        match error {
+            PrefabError::InvalidComponent { component, message } => {
+                assert_eq!(component, "Position");
+                assert!(message.contains("invalid type"), "{message}");
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_malformed_prefab_layout() {
+        let registry = registry();
+
+        for source in ["not json", "[]", r#"{ "name": 3, "components": {} }"#, "{}"] {
+            assert!(
+                matches!(registry.load_str(source), Err(PrefabError::Parse { .. })),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefab_components_can_be_transferred() {
+        let registry = registry();
+        let prefab = registry.load_str(GOBLIN).unwrap();
+        let mut town = World::new();
+        let mut dungeon = World::new();
+
+        let goblin = town.instantiate(&prefab).unwrap();
+        let moved = town
+            .transfer_entity(goblin, &mut dungeon, registry.components())
+            .unwrap();
+
+        assert!(dungeon.has_component::<Hostile>(moved));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Component, Entity, World};
+use crate::{Component, Entity, TagId, World};
 use std::any::TypeId;
 use std::collections::HashSet;
 use std::marker::PhantomData;
@@ -7,8 +7,9 @@ use std::marker::PhantomData;
 ///
 /// Queries provide an efficient, iterator-based API for accessing entities
 /// that have specific components. They support filtering with `.with()` and `.without()`
-/// methods for regular components, and `.with_ephemeral()` and `.without_ephemeral()`
-/// methods for ephemeral components.
+/// methods for regular components, `.with_ephemeral()` and `.without_ephemeral()`
+/// methods for ephemeral components, `.with_tag()` and `.without_tag()` for
+/// tags, and `.with_parent()` and `.with_children()` for parent links.
 ///
 /// # Basic Usage
 /// ```
@@ -67,7 +68,7 @@ use std::marker::PhantomData;
 /// Queries maintain the decoupled architecture by being independent structs
 /// that operate on World references, rather than methods on World itself.
 /// The unified design ensures all queries return the same iterator type
-/// regardless of filtering complexity.
+/// regardless of filtering complexity: [`QueryIter`].
 #[derive(Debug)]
 pub struct Query<T> {
     /// Component types that entities must have (in addition to T)
@@ -78,6 +79,16 @@ pub struct Query<T> {
     with_ephemeral_components: HashSet<TypeId>,
     /// Ephemeral component types that entities must NOT have
     without_ephemeral_components: HashSet<TypeId>,
+    /// Groups of component types where entities must have at least one type per group
+    any_of_groups: Vec<Vec<TypeId>>,
+    /// Tags that entities must have, as a bitset of `TagId`s
+    with_tags: u64,
+    /// Tags that entities must NOT have, as a bitset of `TagId`s
+    without_tags: u64,
+    /// Entity whose children entities must be, see `World::set_parent`
+    with_parent: Option<Entity>,
+    /// Whether entities must have at least one child
+    with_children: bool,
     /// Zero-sized type marker for the primary component type
     _marker: PhantomData<T>,
 }
@@ -101,6 +112,11 @@ impl<T: Component> Query<T> {
             without_components: HashSet::new(),
             with_ephemeral_components: HashSet::new(),
             without_ephemeral_components: HashSet::new(),
+            any_of_groups: Vec::new(),
+            with_tags: 0,
+            without_tags: 0,
+            with_parent: None,
+            with_children: false,
             _marker: PhantomData,
         }
     }
@@ -159,6 +175,128 @@ impl<T: Component> Query<T> {
         self
     }
 
+    /// Adds a condition that entities must have a tag, see [`World::register_tag`].
+    ///
+    /// Tags are tested with one bit operation per candidate, after the
+    /// component filters have narrowed the candidates down.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.register_tag("player").unwrap();
+    ///
+    /// let hero = world.spawn_entity();
+    /// world.add_component(hero, Health { value: 100 }).unwrap();
+    /// world.set_tag(hero, player).unwrap();
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Health { value: 10 }).unwrap();
+    ///
+    /// let players = Query::<Health>::new().with_tag(player);
+    /// assert_eq!(players.collect_entities(&world), vec![hero]);
+    /// ```
+    pub fn with_tag(mut self, tag: TagId) -> Self {
+        self.with_tags |= tag.bit();
+        self
+    }
+
+    /// Adds a condition that entities must NOT have a tag, see [`World::register_tag`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let dead = world.register_tag("dead").unwrap();
+    ///
+    /// let corpse = world.spawn_entity();
+    /// world.add_component(corpse, Health { value: 0 }).unwrap();
+    /// world.set_tag(corpse, dead).unwrap();
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Health { value: 10 }).unwrap();
+    ///
+    /// let living = Query::<Health>::new().without_tag(dead);
+    /// assert_eq!(living.collect_entities(&world), vec![goblin]);
+    /// ```
+    pub fn without_tag(mut self, tag: TagId) -> Self {
+        self.without_tags |= tag.bit();
+        self
+    }
+
+    /// Adds a condition that entities must be direct children of `parent`,
+    /// see [`World::set_parent`].
+    ///
+    /// Grandchildren don't match. If `parent` doesn't exist or has been
+    /// deleted, nothing matches, even before its links are dropped by
+    /// cleanup. Calling it again replaces the previous parent.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Item { weight: u32 }
+    /// impl Component for Item {}
+    ///
+    /// let mut world = World::new();
+    /// let tavern = world.spawn_entity();
+    /// let street = world.spawn_entity();
+    ///
+    /// let mug = world.spawn_entity();
+    /// world.add_component(mug, Item { weight: 1 }).unwrap();
+    /// world.set_parent(mug, tavern).unwrap();
+    /// let cart = world.spawn_entity();
+    /// world.add_component(cart, Item { weight: 200 }).unwrap();
+    /// world.set_parent(cart, street).unwrap();
+    ///
+    /// let in_tavern = Query::<Item>::new().with_parent(tavern);
+    /// assert_eq!(in_tavern.collect_entities(&world), vec![mug]);
+    ///
+    /// world.delete_entity(tavern);
+    /// assert!(in_tavern.collect_entities(&world).is_empty());
+    /// ```
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.with_parent = Some(parent);
+        self
+    }
+
+    /// Adds a condition that entities must have at least one live child,
+    /// see [`World::set_parent`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Container;
+    /// impl Component for Container {}
+    ///
+    /// let mut world = World::new();
+    /// let chest = world.spawn_entity();
+    /// world.add_component(chest, Container).unwrap();
+    /// let barrel = world.spawn_entity();
+    /// world.add_component(barrel, Container).unwrap();
+    ///
+    /// let coin = world.spawn_entity();
+    /// world.set_parent(coin, chest).unwrap();
+    ///
+    /// let filled = Query::<Container>::new().with_children();
+    /// assert_eq!(filled.collect_entities(&world), vec![chest]);
+    /// ```
+    pub fn with_children(mut self) -> Self {
+        self.with_children = true;
+        self
+    }
+
     /// Adds a condition that entities must also have another ephemeral component type.
     ///
     /// Returns the same `Query<T>` type for seamless chaining and composability.
@@ -213,15 +351,135 @@ impl<T: Component> Query<T> {
         self
     }
 
-    /// Creates an iterator over all entities that have the specified component.
+    /// Adds a condition that entities must have at least one of the given component types.
     ///
-    /// Returns an iterator that yields `(Entity, &T)` pairs for each entity
-    /// that matches all the query criteria using efficient set operations.
+    /// Each call adds an independent OR-group; multiple groups are combined with AND,
+    /// as are the regular `with`/`without` filters. An empty group matches no entities.
+    /// Use the [`any_of!`](crate::any_of) macro to build the list of `TypeId`s.
     ///
-    /// # Performance
-    /// This method uses set intersection and difference operations for filtering,
-    /// providing O(size_of_smallest_set) complexity for multi-component queries
-    /// instead of O(entities_with_T) * number_of_filters per-entity checking.
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{any_of, Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player;
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Npc;
+    /// impl Component for Npc {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Health { value: 100 }).unwrap();
+    /// world.add_component(player, Player).unwrap();
+    ///
+    /// let npc = world.spawn_entity();
+    /// world.add_component(npc, Health { value: 50 }).unwrap();
+    /// world.add_component(npc, Npc).unwrap();
+    ///
+    /// let rock = world.spawn_entity();
+    /// world.add_component(rock, Health { value: 999 }).unwrap();
+    ///
+    /// // Find players or NPCs with health
+    /// let combatants = Query::<Health>::new().with_any_of(&any_of!(Player, Npc));
+    /// assert_eq!(combatants.iter(&world).count(), 2);
+    /// ```
+    pub fn with_any_of(mut self, type_ids: &[TypeId]) -> Self {
+        self.any_of_groups.push(type_ids.to_vec());
+        self
+    }
+
+    /// Adds a condition that entities must have every listed component type.
+    ///
+    /// The runtime counterpart of chaining [`Query::with`], for filters
+    /// assembled from a list of `TypeId`s. An empty list adds no condition.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{any_of, Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player;
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Hostile;
+    /// impl Component for Hostile {}
+    ///
+    /// let mut world = World::new();
+    /// let traitor = world.spawn_entity();
+    /// world.add_component(traitor, Health { value: 100 }).unwrap();
+    /// world.add_component(traitor, Player).unwrap();
+    /// world.add_component(traitor, Hostile).unwrap();
+    ///
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Health { value: 100 }).unwrap();
+    /// world.add_component(player, Player).unwrap();
+    ///
+    /// let query = Query::<Health>::new().with_all_of(&any_of!(Player, Hostile));
+    /// assert_eq!(query.entities(&world).collect::<Vec<_>>(), vec![traitor]);
+    /// ```
+    pub fn with_all_of(mut self, type_ids: &[TypeId]) -> Self {
+        self.with_components.extend(type_ids.iter().copied());
+        self
+    }
+
+    /// Adds a condition that entities must have none of the listed component types.
+    ///
+    /// The runtime counterpart of chaining [`Query::without`]: an entity
+    /// having any one of the types is excluded. An empty list adds no
+    /// condition.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{any_of, Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player;
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Health { value: 100 }).unwrap();
+    /// world.add_component(player, Player).unwrap();
+    ///
+    /// let wolf = world.spawn_entity();
+    /// world.add_component(wolf, Health { value: 30 }).unwrap();
+    ///
+    /// let corpse = world.spawn_entity();
+    /// world.add_component(corpse, Health { value: 0 }).unwrap();
+    /// world.add_component(corpse, Dead).unwrap();
+    ///
+    /// // Valid targets for an area spell cast by a player
+    /// let targets = Query::<Health>::new().without_any_of(&any_of!(Player, Dead));
+    /// assert_eq!(targets.entities(&world).collect::<Vec<_>>(), vec![wolf]);
+    /// ```
+    pub fn without_any_of(mut self, type_ids: &[TypeId]) -> Self {
+        self.without_components.extend(type_ids.iter().copied());
+        self
+    }
+
+    /// Adds a condition that entities must have at least one of the component types in `S`.
+    ///
+    /// A typed alternative to [`Query::with_any_of`] that takes a tuple of up to
+    /// twelve component types instead of a list of `TypeId`s.
     ///
     /// # Example
     /// ```
@@ -231,28 +489,83 @@ impl<T: Component> Query<T> {
     /// struct Position { x: f32, y: f32 }
     /// impl Component for Position {}
     ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player;
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Npc;
+    /// impl Component for Npc {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Position { x: 0.0, y: 0.0 }).unwrap();
+    /// world.add_component(player, Player).unwrap();
+    ///
+    /// let corpse = world.spawn_entity();
+    /// world.add_component(corpse, Position { x: 1.0, y: 0.0 }).unwrap();
+    /// world.add_component(corpse, Npc).unwrap();
+    /// world.add_component(corpse, Dead).unwrap();
+    ///
+    /// // Players or NPCs, but not dead ones
+    /// let living = Query::<Position>::new().any_of::<(Player, Npc)>().without::<Dead>();
+    /// assert_eq!(living.iter(&world).count(), 1);
+    /// ```
+    pub fn any_of<S: ComponentSet>(self) -> Self {
+        self.with_any_of(&S::type_ids())
+    }
+
+    /// Wraps the query in a [`CachedQuery`](crate::CachedQuery).
+    ///
+    /// The cached query reuses its matched entities until the world's structure
+    /// changes, which pays off for queries run every tick over large, mostly
+    /// static worlds.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
     /// let mut world = World::new();
     /// let entity = world.spawn_entity();
-    /// world.add_component(entity, Position { x: 5.0, y: 10.0 }).unwrap();
+    /// world.add_component(entity, Position { x: 0.0, y: 0.0 }).unwrap();
     ///
-    /// let query = Query::<Position>::new();
-    /// let positions: Vec<_> = query.iter(&world)
-    ///     .map(|(entity, pos)| (entity, pos.x, pos.y))
-    ///     .collect();
+    /// let alive = Query::<Position>::new().without::<Dead>().cached();
+    /// assert_eq!(alive.iter(&world).count(), 1);
     ///
-    /// assert_eq!(positions.len(), 1);
-    /// assert_eq!(positions[0].1, 5.0);
-    /// assert_eq!(positions[0].2, 10.0);
+    /// world.add_component(entity, Dead).unwrap();
+    /// assert_eq!(alive.iter(&world).count(), 0);
     /// ```
-    pub fn iter<'w>(&'w self, world: &'w World) -> impl Iterator<Item = (Entity, &'w T)> + 'w {
-        // Start with entities that have the primary component T
-        let mut result_entities = world.entities_with_component_by_type_id(TypeId::of::<T>());
+    pub fn cached(self) -> crate::CachedQuery<T> {
+        crate::CachedQuery::new(self)
+    }
+
+    /// Intersects the candidate entities with every OR-group of the query.
+    fn apply_any_of_groups(
+        &self,
+        world: &World,
+        mut result_entities: HashSet<Entity>,
+    ) -> HashSet<Entity> {
+        for group in &self.any_of_groups {
+            // Union of all entities having at least one of the group's types
+            let mut entities_in_group = HashSet::new();
+            for &type_id in group {
+                entities_in_group.extend(world.entities_with_component_by_type_id(type_id));
+            }
 
-        // Intersect with entities that have all required components
-        for &type_id in &self.with_components {
-            let entities_with_component = world.entities_with_component_by_type_id(type_id);
             result_entities = result_entities
-                .intersection(&entities_with_component)
+                .intersection(&entities_in_group)
                 .copied()
                 .collect();
 
@@ -262,6 +575,85 @@ impl<T: Component> Query<T> {
             }
         }
 
+        result_entities
+    }
+
+    /// Returns the entities with the primary component `T` that match every filter.
+    ///
+    /// The intersection starts from the smallest set among `T` and the `with`
+    /// types, so a selective filter like a rare marker component keeps the work
+    /// proportional to its few entities rather than to every `T`.
+    pub(crate) fn matching_entities(&self, world: &World) -> HashSet<Entity> {
+        let primary = TypeId::of::<T>();
+        let smallest = std::iter::once(primary)
+            .chain(self.with_components.iter().copied())
+            .min_by_key(|&type_id| {
+                world
+                    .component_index_by_type_id(type_id)
+                    .map_or(0, HashSet::len)
+            })
+            .unwrap_or(primary);
+
+        let mut result_entities = world.entities_with_component_by_type_id(smallest);
+        if smallest != primary {
+            Self::retain_with_component(world, &mut result_entities, primary);
+        }
+        self.apply_filters(world, result_entities)
+    }
+
+    /// Keeps only the candidates that have the component `type_id`.
+    ///
+    /// Candidates are already free of soft-deleted entities, so the reverse
+    /// index can be probed directly instead of being copied.
+    fn retain_with_component(world: &World, entities: &mut HashSet<Entity>, type_id: TypeId) {
+        match world.component_index_by_type_id(type_id) {
+            Some(entities_with_component) => {
+                entities.retain(|entity| entities_with_component.contains(entity))
+            }
+            None => entities.clear(),
+        }
+    }
+
+    /// Applies the `with`, `without`, OR-group, tag, parent link and ephemeral
+    /// filters to the candidates.
+    fn apply_filters(
+        &self,
+        world: &World,
+        mut result_entities: HashSet<Entity>,
+    ) -> HashSet<Entity> {
+        // Intersect with entities that have all required components
+        for &type_id in &self.with_components {
+            Self::retain_with_component(world, &mut result_entities, type_id);
+
+            // Early exit if intersection becomes empty
+            if result_entities.is_empty() {
+                break;
+            }
+        }
+
+        // Intersect with entities that have at least one type of every OR-group
+        result_entities = self.apply_any_of_groups(world, result_entities);
+
+        // Check tags with one bit test per remaining candidate
+        if self.with_tags | self.without_tags != 0 {
+            result_entities.retain(|&entity| {
+                let tags = world.tag_bits(entity);
+                tags & self.with_tags == self.with_tags && tags & self.without_tags == 0
+            });
+        }
+
+        // Intersect with the children of the parent, if it's still alive
+        if let Some(parent) = self.with_parent {
+            match world.children_index(parent) {
+                Some(children) => result_entities.retain(|entity| children.contains(entity)),
+                None => result_entities.clear(),
+            }
+        }
+
+        if self.with_children {
+            result_entities.retain(|&entity| world.has_children(entity));
+        }
+
         // Remove entities that have any forbidden components using set difference
         for &type_id in &self.without_components {
             let entities_with_component = world.entities_with_component_by_type_id(type_id);
@@ -296,14 +688,218 @@ impl<T: Component> Query<T> {
                 .collect();
         }
 
-        // Return iterator that maps entities to (Entity, &T) tuples
-        result_entities.into_iter().filter_map(move |entity| {
-            world
-                .get_component::<T>(entity)
-                .map(|component| (entity, component))
+        result_entities
+    }
+
+    /// Creates an iterator over all entities that have the specified component.
+    ///
+    /// Returns an iterator that yields `(Entity, &T)` pairs for each entity
+    /// that matches all the query criteria using efficient set operations.
+    ///
+    /// # Performance
+    /// This method uses set intersection and difference operations for filtering,
+    /// providing O(size_of_smallest_set) complexity for multi-component queries
+    /// instead of O(entities_with_T) * number_of_filters per-entity checking.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Position { x: 5.0, y: 10.0 }).unwrap();
+    ///
+    /// let query = Query::<Position>::new();
+    /// let positions: Vec<_> = query.iter(&world)
+    ///     .map(|(entity, pos)| (entity, pos.x, pos.y))
+    ///     .collect();
+    ///
+    /// assert_eq!(positions.len(), 1);
+    /// assert_eq!(positions[0].1, 5.0);
+    /// assert_eq!(positions[0].2, 10.0);
+    /// ```
+    pub fn iter<'w>(&self, world: &'w World) -> QueryIter<'w, T> {
+        let result_entities = self.matching_entities(world);
+        QueryIter::new(world, result_entities, |world, entity| {
+            world.get_component::<T>(entity)
         })
     }
 
+    /// Creates an iterator over the matching entities whose `T` satisfies `predicate`.
+    ///
+    /// The predicate only sees the component, which covers the common case of
+    /// filtering on `T`'s fields without looking anything up in the world.
+    /// To filter on several components, filter [`Query::iter`] and fetch the
+    /// others with [`World::get_components2`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, Query, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { current: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// for current in [20, 60, 90] {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Health { current }).unwrap();
+    /// }
+    ///
+    /// let healthy = Query::<Health>::new().filter_by(&world, |health| health.current > 50);
+    /// assert_eq!(healthy.count(), 2);
+    /// ```
+    pub fn filter_by<'w, P>(
+        &self,
+        world: &'w World,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (Entity, &'w T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter(world)
+            .filter(move |(_, component)| predicate(component))
+    }
+
+    /// Creates a parallel iterator over the same `(Entity, &T)` pairs as
+    /// [`Query::iter`].
+    ///
+    /// The matching entities are resolved up front on the calling thread, then
+    /// handed to rayon, so the closures run in parallel only ever see shared
+    /// references. This suits read-only passes over many entities, like
+    /// finding targets; changing the world still goes through the sequential
+    /// path, e.g. by collecting the results and applying them afterwards.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    /// use rayon::iter::ParallelIterator;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// for value in 0..100 {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Health { value }).unwrap();
+    /// }
+    ///
+    /// let wounded = Query::<Health>::new()
+    ///     .par_iter(&world)
+    ///     .filter(|(_, health)| health.value < 25)
+    ///     .count();
+    /// assert_eq!(wounded, 25);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter<'w>(
+        &self,
+        world: &'w World,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = (Entity, &'w T)> {
+        use rayon::iter::IntoParallelIterator;
+
+        self.iter(world).collect::<Vec<_>>().into_par_iter()
+    }
+
+    /// Creates an iterator over the entities [`Query::iter`] would yield, without
+    /// their components.
+    ///
+    /// The matching entities are resolved from the world's component indexes
+    /// alone and no component storage is read, which makes this the cheaper
+    /// choice when only membership matters, e.g. for marker components.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player;
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// let alive = world.spawn_entity();
+    /// world.add_component(alive, Player).unwrap();
+    /// let ghost = world.spawn_entity();
+    /// world.add_component(ghost, Player).unwrap();
+    /// world.add_component(ghost, Dead).unwrap();
+    ///
+    /// let living_players: Vec<_> = Query::<Player>::new()
+    ///     .without::<Dead>()
+    ///     .entities(&world)
+    ///     .collect();
+    /// assert_eq!(living_players, vec![alive]);
+    /// ```
+    pub fn entities(&self, world: &World) -> impl ExactSizeIterator<Item = Entity> {
+        self.matching_entities(world).into_iter()
+    }
+
+    /// Collects the entities [`Query::iter`] would yield into a `Vec`.
+    ///
+    /// Shorthand for `query.entities(&world).collect()`, the usual first step
+    /// of a system that changes the matched entities afterwards. The order is
+    /// unspecified.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Poisoned;
+    /// impl Component for Poisoned {}
+    ///
+    /// let mut world = World::new();
+    /// let victim = world.spawn_entity();
+    /// world.add_component(victim, Poisoned).unwrap();
+    ///
+    /// for entity in Query::<Poisoned>::new().collect_entities(&world) {
+    ///     world.remove_component::<Poisoned>(entity);
+    /// }
+    /// assert!(!world.has_component::<Poisoned>(victim));
+    /// ```
+    pub fn collect_entities(&self, world: &World) -> Vec<Entity> {
+        self.entities(world).collect()
+    }
+
+    /// Collects the components [`Query::iter`] would yield into a `Vec`,
+    /// without their entities.
+    ///
+    /// The references borrow from `world`. The order is unspecified.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Gold { amount: u32 }
+    /// impl Component for Gold {}
+    ///
+    /// let mut world = World::new();
+    /// for amount in [10, 25] {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Gold { amount }).unwrap();
+    /// }
+    ///
+    /// let total: u32 = Query::<Gold>::new()
+    ///     .collect_components(&world)
+    ///     .iter()
+    ///     .map(|gold| gold.amount)
+    ///     .sum();
+    /// assert_eq!(total, 35);
+    /// ```
+    pub fn collect_components<'w>(&self, world: &'w World) -> Vec<&'w T> {
+        self.iter(world).map(|(_, component)| component).collect()
+    }
+
     /// Creates an iterator over all entities that have the specified ephemeral component.
     ///
     /// Returns an iterator that yields `(Entity, &T)` pairs for each entity
@@ -335,71 +931,269 @@ impl<T: Component> Query<T> {
     /// assert_eq!(damage_events.len(), 1);
     /// assert_eq!(damage_events[0].1, 50);
     /// ```
-    pub fn iter_ephemeral<'w>(
+    pub fn iter_ephemeral<'w>(&self, world: &'w World) -> QueryIter<'w, T> {
+        // Start with entities that have the primary ephemeral component T
+        let result_entities = world.entities_with_ephemeral_component_by_type_id(TypeId::of::<T>());
+        let result_entities = self.apply_filters(world, result_entities);
+
+        QueryIter::new(world, result_entities, |world, entity| {
+            world.get_ephemeral_component::<T>(entity)
+        })
+    }
+
+    /// Creates an iterator over the entities [`Query::iter_ephemeral`] would
+    /// yield, without their components.
+    ///
+    /// Like [`Query::entities`], this never reads a component storage.
+    pub fn ephemeral_entities(&self, world: &World) -> impl ExactSizeIterator<Item = Entity> {
+        let result_entities = world.entities_with_ephemeral_component_by_type_id(TypeId::of::<T>());
+        self.apply_filters(world, result_entities).into_iter()
+    }
+
+    /// Collects the entities [`Query::iter_ephemeral`] would yield into a `Vec`.
+    ///
+    /// The ephemeral counterpart of [`Query::collect_entities`].
+    pub fn collect_ephemeral_entities(&self, world: &World) -> Vec<Entity> {
+        self.ephemeral_entities(world).collect()
+    }
+
+    /// Collects the components [`Query::iter_ephemeral`] would yield into a
+    /// `Vec`, without their entities.
+    ///
+    /// The ephemeral counterpart of [`Query::collect_components`].
+    pub fn collect_ephemeral_components<'w>(&self, world: &'w World) -> Vec<&'w T> {
+        self.iter_ephemeral(world)
+            .map(|(_, component)| component)
+            .collect()
+    }
+
+    /// Creates an iterator over the ephemeral events of type `T` pushed this tick.
+    ///
+    /// Yields every entity with at least one event together with all of its
+    /// events in push order, see [`World::push_ephemeral_event`]. The query's
+    /// `with`, `without`, OR-group and ephemeral filters apply as usual.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct DamageEvent { amount: u32 }
+    /// impl Component for DamageEvent {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.push_ephemeral_event(player, DamageEvent { amount: 5 }).unwrap();
+    /// world.push_ephemeral_event(player, DamageEvent { amount: 8 }).unwrap();
+    ///
+    /// let query = Query::<DamageEvent>::new();
+    /// for (entity, events) in query.iter_ephemeral_events(&world) {
+    ///     let total: u32 = events.iter().map(|event| event.amount).sum();
+    ///     assert_eq!((entity, total), (player, 13));
+    /// }
+    /// ```
+    pub fn iter_ephemeral_events<'w>(
         &'w self,
         world: &'w World,
-    ) -> impl Iterator<Item = (Entity, &'w T)> + 'w {
-        // Start with entities that have the primary ephemeral component T
-        let mut result_entities =
-            world.entities_with_ephemeral_component_by_type_id(TypeId::of::<T>());
+    ) -> impl Iterator<Item = (Entity, &'w [T])> + 'w {
+        let result_entities = world.entities_with_ephemeral_events::<T>();
+        let result_entities = self.apply_filters(world, result_entities);
+
+        result_entities
+            .into_iter()
+            .map(move |entity| (entity, world.ephemeral_events::<T>(entity)))
+    }
+
+    /// Creates an iterator over the matching entities whose `T` was added this tick.
+    ///
+    /// Only entities that gained the component count: overwriting an existing
+    /// component with `replace_component()` or `update_component()` does not.
+    /// Entities that got the component added and removed within the same tick are
+    /// not yielded. See [`World::was_added`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// let old_corpse = world.spawn_entity();
+    /// world.add_component(old_corpse, Dead).unwrap();
+    /// world.clear_change_tracking(); // a tick went by
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Dead).unwrap();
+    ///
+    /// // Grant loot only for enemies that died this tick
+    /// let newly_dead: Vec<_> = Query::<Dead>::new()
+    ///     .iter_added(&world)
+    ///     .map(|(entity, _)| entity)
+    ///     .collect();
+    /// assert_eq!(newly_dead, vec![goblin]);
+    /// ```
+    pub fn iter_added<'w>(&self, world: &'w World) -> QueryIter<'w, T> {
+        let result_entities = self.apply_filters(world, world.entities_with_added_component::<T>());
+        QueryIter::new(world, result_entities, |world, entity| {
+            world.get_component::<T>(entity)
+        })
+    }
+
+    /// Creates an iterator over the matching entities whose `T` was added or changed this tick.
+    ///
+    /// Covers everything [`Query::iter_added`] yields plus writes to existing
+    /// components through `update_component()` and `replace_component()`.
+    /// See [`World::was_changed`].
+    pub fn iter_changed<'w>(&self, world: &'w World) -> QueryIter<'w, T> {
+        let result_entities =
+            self.apply_filters(world, world.entities_with_changed_component::<T>());
+        QueryIter::new(world, result_entities, |world, entity| {
+            world.get_component::<T>(entity)
+        })
+    }
+
+    /// Returns how many matching entities had `T` added or changed this tick.
+    ///
+    /// Equivalent to `iter_changed(world).count()` without fetching the components.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Query, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// for value in [10, 20, 30] {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Health { value }).unwrap();
+    /// }
+    /// assert_eq!(Query::<Health>::new().count_changed(&world), 3);
+    ///
+    /// world.clear_change_tracking();
+    /// assert_eq!(Query::<Health>::new().count_changed(&world), 0);
+    /// ```
+    pub fn count_changed(&self, world: &World) -> usize {
+        self.apply_filters(world, world.entities_with_changed_component::<T>())
+            .len()
+    }
+}
+
+/// The iterator returned by [`Query::iter`] and its variants.
+///
+/// Yields `(Entity, &T)` pairs. The matching entities and their components are
+/// resolved up front, so the iterator knows exactly how many items are left and
+/// implements [`ExactSizeIterator`] and [`DoubleEndedIterator`].
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, Query, QueryIter, World};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// fn with_health<'w>(world: &'w World) -> QueryIter<'w, Health> {
+///     Query::new().iter(world)
+/// }
+///
+/// let mut world = World::new();
+/// for value in [3, 5] {
+///     let entity = world.spawn_entity();
+///     world.add_component(entity, Health { value }).unwrap();
+/// }
+///
+/// let iter = with_health(&world);
+/// assert_eq!(iter.len(), 2);
+/// ```
+pub struct QueryIter<'w, T> {
+    items: std::vec::IntoIter<(Entity, &'w T)>,
+}
+
+impl<'w, T> QueryIter<'w, T> {
+    /// Resolves the component of every matched entity.
+    ///
+    /// Entities whose component can't be fetched are pruned here rather than
+    /// skipped while iterating, so the reported length is always exact.
+    fn new(
+        world: &'w World,
+        entities: HashSet<Entity>,
+        fetch: fn(&'w World, Entity) -> Option<&'w T>,
+    ) -> Self {
+        let items: Vec<_> = entities
+            .into_iter()
+            .filter_map(|entity| Some((entity, fetch(world, entity)?)))
+            .collect();
+        Self {
+            items: items.into_iter(),
+        }
+    }
+}
 
-        // Intersect with entities that have all required components (regular components for filters)
-        for &type_id in &self.with_components {
-            let entities_with_component = world.entities_with_component_by_type_id(type_id);
-            result_entities = result_entities
-                .intersection(&entities_with_component)
-                .copied()
-                .collect();
+impl<'w, T> Iterator for QueryIter<'w, T> {
+    type Item = (Entity, &'w T);
 
-            // Early exit if intersection becomes empty
-            if result_entities.is_empty() {
-                break;
-            }
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next()
+    }
 
-        // Remove entities that have any forbidden components using set difference
-        for &type_id in &self.without_components {
-            let entities_with_component = world.entities_with_component_by_type_id(type_id);
-            result_entities = result_entities
-                .difference(&entities_with_component)
-                .copied()
-                .collect();
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
 
-        // Intersect with entities that have all required ephemeral components
-        for &type_id in &self.with_ephemeral_components {
-            let entities_with_component =
-                world.entities_with_ephemeral_component_by_type_id(type_id);
-            result_entities = result_entities
-                .intersection(&entities_with_component)
-                .copied()
-                .collect();
+impl<T> DoubleEndedIterator for QueryIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back()
+    }
+}
 
-            // Early exit if intersection becomes empty
-            if result_entities.is_empty() {
-                break;
-            }
-        }
+impl<T> ExactSizeIterator for QueryIter<'_, T> {}
 
-        // Remove entities that have any forbidden ephemeral components using set difference
-        for &type_id in &self.without_ephemeral_components {
-            let entities_with_component =
-                world.entities_with_ephemeral_component_by_type_id(type_id);
-            result_entities = result_entities
-                .difference(&entities_with_component)
-                .copied()
-                .collect();
-        }
+impl<T> std::iter::FusedIterator for QueryIter<'_, T> {}
 
-        // Return iterator that maps entities to (Entity, &T) tuples
-        result_entities.into_iter().filter_map(move |entity| {
-            world
-                .get_ephemeral_component::<T>(entity)
-                .map(|component| (entity, component))
-        })
+impl<T> std::fmt::Debug for QueryIter<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryIter")
+            .field("remaining", &self.items.len())
+            .finish()
     }
 }
 
+/// A tuple of component types, used by [`Query::any_of`].
+///
+/// Implemented for tuples of one to twelve components.
+pub trait ComponentSet {
+    /// Returns the `TypeId` of every component type in the set.
+    fn type_ids() -> Vec<TypeId>;
+}
+
+macro_rules! impl_component_set_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Component),+> ComponentSet for ($($name,)+) {
+            fn type_ids() -> Vec<TypeId> {
+                vec![$(TypeId::of::<$name>()),+]
+            }
+        }
+    };
+}
+
+impl_component_set_for_tuple!(A);
+impl_component_set_for_tuple!(A, B);
+impl_component_set_for_tuple!(A, B, C);
+impl_component_set_for_tuple!(A, B, C, D);
+impl_component_set_for_tuple!(A, B, C, D, E);
+impl_component_set_for_tuple!(A, B, C, D, E, F);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G, H);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_component_set_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 impl<T: Component> Default for Query<T> {
     /// Creates a new query using the default constructor.
     ///
@@ -898,4 +1692,636 @@ mod tests {
         assert_eq!(results_without_dead.len(), 1);
         assert_eq!(results_without_dead[0].0, entity1);
     }
+
+    #[test]
+    fn test_with_any_of_two_types() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Player;
+        impl Component for Player {}
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Npc;
+        impl Component for Npc {}
+
+        let mut world = World::new();
+        let player = world.spawn_entity();
+        let npc = world.spawn_entity();
+        let both = world.spawn_entity();
+        let neither = world.spawn_entity();
+
+        for entity in [player, npc, both, neither] {
+            world.add_component(entity, Health { value: 10 }).unwrap();
+        }
+        world.add_component(player, Player).unwrap();
+        world.add_component(npc, Npc).unwrap();
+        world.add_component(both, Player).unwrap();
+        world.add_component(both, Npc).unwrap();
+
+        let query = Query::<Health>::new().with_any_of(&crate::any_of!(Player, Npc));
+        let entities: HashSet<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+
+        assert_eq!(entities.len(), 3);
+        assert!(entities.contains(&player));
+        assert!(entities.contains(&npc));
+        assert!(entities.contains(&both));
+        assert!(!entities.contains(&neither));
+    }
+
+    #[test]
+    fn test_with_any_of_three_types_composes_with_with_and_without() {
+        let mut world = World::new();
+        let moving = world.spawn_entity();
+        let healthy = world.spawn_entity();
+        let dead = world.spawn_entity();
+        let unrelated = world.spawn_entity();
+
+        for entity in [moving, healthy, dead, unrelated] {
+            world
+                .add_component(entity, Position { x: 0.0, y: 0.0 })
+                .unwrap();
+        }
+        world
+            .add_component(moving, Velocity { x: 1.0, y: 0.0 })
+            .unwrap();
+        world.add_component(healthy, Health { value: 5 }).unwrap();
+        world.add_component(dead, Dead).unwrap();
+        world.add_component(dead, Health { value: 0 }).unwrap();
+
+        let query = Query::<Position>::new()
+            .with_any_of(&crate::any_of!(Velocity, Health, Dead))
+            .without::<Dead>();
+        let entities: HashSet<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+        assert_eq!(entities, HashSet::from([moving, healthy]));
+
+        let query = Query::<Position>::new()
+            .with::<Health>()
+            .with_any_of(&crate::any_of!(Velocity, Health, Dead));
+        let entities: HashSet<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+        assert_eq!(entities, HashSet::from([healthy, dead]));
+    }
+
+    #[test]
+    fn test_with_any_of_multiple_groups_are_anded() {
+        let mut world = World::new();
+        let a = world.spawn_entity();
+        let b = world.spawn_entity();
+
+        world.add_component(a, Position { x: 0.0, y: 0.0 }).unwrap();
+        world.add_component(a, Velocity { x: 0.0, y: 0.0 }).unwrap();
+        world.add_component(a, Health { value: 1 }).unwrap();
+        world.add_component(b, Position { x: 0.0, y: 0.0 }).unwrap();
+        world.add_component(b, Velocity { x: 0.0, y: 0.0 }).unwrap();
+
+        let query = Query::<Position>::new()
+            .with_any_of(&crate::any_of!(Velocity))
+            .with_any_of(&crate::any_of!(Health, Dead));
+        let entities: Vec<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+
+        assert_eq!(entities, vec![a]);
+    }
+
+    #[test]
+    fn test_with_any_of_empty_group_matches_nothing() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+
+        let query = Query::<Position>::new().with_any_of(&[]);
+        assert_eq!(query.iter(&world).count(), 0);
+
+        // Same for the ephemeral iterator
+        world
+            .add_ephemeral_component(entity, Position { x: 1.0, y: 1.0 })
+            .unwrap();
+        assert_eq!(query.iter_ephemeral(&world).count(), 0);
+    }
+
+    #[test]
+    fn test_with_any_of_excludes_deleted_entities() {
+        let mut world = World::new();
+        let alive = world.spawn_entity();
+        let deleted = world.spawn_entity();
+        for entity in [alive, deleted] {
+            world
+                .add_component(entity, Position { x: 0.0, y: 0.0 })
+                .unwrap();
+            world.add_component(entity, Health { value: 1 }).unwrap();
+        }
+        world.delete_entity(deleted);
+
+        let query = Query::<Position>::new().with_any_of(&crate::any_of!(Health, Velocity));
+        let entities: Vec<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+
+        assert_eq!(entities, vec![alive]);
+    }
+
+    #[test]
+    fn test_with_any_of_with_ephemeral_primary() {
+        let mut world = World::new();
+        let entity1 = world.spawn_entity();
+        let entity2 = world.spawn_entity();
+        world.add_component(entity1, Health { value: 1 }).unwrap();
+        for entity in [entity1, entity2] {
+            world
+                .add_ephemeral_component(entity, Position { x: 0.0, y: 0.0 })
+                .unwrap();
+        }
+
+        let query = Query::<Position>::new().with_any_of(&crate::any_of!(Health, Dead));
+        let entities: Vec<Entity> = query.iter_ephemeral(&world).map(|(e, _)| e).collect();
+
+        assert_eq!(entities, vec![entity1]);
+    }
+
+    #[test]
+    fn test_any_of_tuple_matches_type_id_groups() {
+        let mut world = World::new();
+        let both = world.spawn_entity();
+        world
+            .add_component(both, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world
+            .add_component(both, Velocity { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_component(both, Health { value: 1 }).unwrap();
+        let velocity_only = world.spawn_entity();
+        world
+            .add_component(velocity_only, Position { x: 1.0, y: 0.0 })
+            .unwrap();
+        world
+            .add_component(velocity_only, Velocity { x: 0.0, y: 0.0 })
+            .unwrap();
+        let neither = world.spawn_entity();
+        world
+            .add_component(neither, Position { x: 2.0, y: 0.0 })
+            .unwrap();
+
+        let typed: HashSet<_> = Query::<Position>::new()
+            .any_of::<(Velocity, Health)>()
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .collect();
+        let by_id: HashSet<_> = Query::<Position>::new()
+            .with_any_of(&crate::any_of!(Velocity, Health))
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .collect();
+
+        // An entity having several types of the group is yielded once
+        assert_eq!(typed, HashSet::from([both, velocity_only]));
+        assert_eq!(typed, by_id);
+        assert_eq!(
+            <(Velocity, Health)>::type_ids(),
+            vec![TypeId::of::<Velocity>(), TypeId::of::<Health>()]
+        );
+    }
+
+    #[test]
+    fn test_iter_added_distinguishes_add_replace_update() {
+        let mut world = World::new();
+        let added = world.spawn_entity();
+        let replaced = world.spawn_entity();
+        let updated = world.spawn_entity();
+        let untouched = world.spawn_entity();
+        for entity in [replaced, updated, untouched] {
+            world.add_component(entity, Health { value: 10 }).unwrap();
+        }
+        world.clear_change_tracking();
+
+        world.add_component(added, Health { value: 10 }).unwrap();
+        world.replace_component(replaced, Health { value: 20 });
+        world
+            .update_component::<Health, _>(updated, |h| Health { value: h.value - 1 })
+            .unwrap();
+
+        let query = Query::<Health>::new();
+        let added_entities: HashSet<_> = query.iter_added(&world).map(|(e, _)| e).collect();
+        let changed_entities: HashSet<_> = query.iter_changed(&world).map(|(e, _)| e).collect();
+
+        assert_eq!(added_entities, HashSet::from([added]));
+        assert_eq!(changed_entities, HashSet::from([added, replaced, updated]));
+        assert_eq!(query.count_changed(&world), 3);
+        assert_eq!(query.iter(&world).count(), 4);
+    }
+
+    #[test]
+    fn test_iter_added_skips_added_then_removed() {
+        let mut world = World::new();
+        let flicker = world.spawn_entity();
+        let stays = world.spawn_entity();
+
+        world.add_component(flicker, Dead).unwrap();
+        world.add_component(stays, Dead).unwrap();
+        world.remove_component::<Dead>(flicker);
+
+        let query = Query::<Dead>::new();
+        let added: Vec<_> = query.iter_added(&world).map(|(e, _)| e).collect();
+        assert_eq!(added, vec![stays]);
+        assert_eq!(query.count_changed(&world), 1);
+
+        // Re-adding within the same tick counts as an addition again
+        world.add_component(flicker, Dead).unwrap();
+        assert_eq!(query.iter_added(&world).count(), 2);
+    }
+
+    #[test]
+    fn test_iter_added_applies_filters() {
+        let mut world = World::new();
+        let moving = world.spawn_entity();
+        let still = world.spawn_entity();
+        world
+            .add_component(moving, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world
+            .add_component(moving, Velocity { x: 1.0, y: 0.0 })
+            .unwrap();
+        world
+            .add_component(still, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+
+        let query = Query::<Position>::new().with::<Velocity>();
+        let added: Vec<_> = query.iter_added(&world).map(|(e, _)| e).collect();
+        assert_eq!(added, vec![moving]);
+
+        world.delete_entity(moving);
+        assert_eq!(query.iter_added(&world).count(), 0);
+        assert_eq!(query.count_changed(&world), 0);
+    }
+
+    #[test]
+    fn test_change_tracking_cleared_by_scheduler() {
+        struct MarkDead;
+        impl crate::System for MarkDead {
+            fn run(&self, world: &mut World) {
+                let alive: Vec<_> = Query::<Health>::new()
+                    .without::<Dead>()
+                    .iter(world)
+                    .filter(|(_, health)| health.value == 0)
+                    .map(|(entity, _)| entity)
+                    .collect();
+                for entity in alive {
+                    world.add_component(entity, Dead).unwrap();
+                }
+            }
+        }
+
+        let mut world = World::new();
+        let goblin = world.spawn_entity();
+        world.add_component(goblin, Health { value: 0 }).unwrap();
+
+        let mut scheduler = crate::SequentialSystemScheduler::new();
+        scheduler.add_system(MarkDead).unwrap();
+        scheduler.build().unwrap();
+
+        scheduler.run_tick(&mut world);
+        assert!(world.has_component::<Dead>(goblin));
+        assert_eq!(Query::<Dead>::new().iter_added(&world).count(), 0);
+        assert_eq!(Query::<Health>::new().count_changed(&world), 0);
+    }
+
+    #[test]
+    fn test_query_iter_exact_size() {
+        let mut world = World::new();
+        for i in 0..10 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value: i }).unwrap();
+            if i % 2 == 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+            if i == 4 {
+                world.delete_entity(entity);
+            }
+        }
+
+        let query = Query::<Health>::new().with::<Dead>();
+        let iter = query.iter(&world);
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        assert_eq!(iter.len(), query.iter(&world).count());
+
+        let mut iter = query.iter(&world);
+        iter.next();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_query_iter_ephemeral_exact_size() {
+        let mut world = World::new();
+        for _ in 0..3 {
+            let entity = world.spawn_entity();
+            world.add_ephemeral_component(entity, Dead).unwrap();
+        }
+
+        // The iterator doesn't borrow the query, so temporaries are fine
+        let iter = Query::<Dead>::new().iter_ephemeral(&world);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.count(), 3);
+        assert_eq!(Query::<Dead>::new().iter_added(&world).len(), 0);
+    }
+
+    #[test]
+    fn test_filter_by_applies_predicate_after_query_filters() {
+        let mut world = World::new();
+        let mut expected = HashSet::new();
+        for value in [10, 40, 51, 75, 100] {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value }).unwrap();
+            if value == 100 {
+                world.add_component(entity, Dead).unwrap();
+            } else if value > 50 {
+                expected.insert(entity);
+            }
+        }
+
+        let healthy: HashSet<Entity> = Query::<Health>::new()
+            .without::<Dead>()
+            .filter_by(&world, |health| health.value > 50)
+            .map(|(entity, health)| {
+                assert!(health.value > 50);
+                entity
+            })
+            .collect();
+        assert_eq!(healthy, expected);
+
+        // The world stays borrowable while iterating
+        for (entity, _) in Query::<Health>::new().filter_by(&world, |health| health.value > 50) {
+            assert!(world.has_component::<Health>(entity));
+        }
+    }
+
+    #[test]
+    fn test_query_iter_len_matches_collected_after_mid_tick_changes() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..8).map(|_| world.spawn_entity()).collect();
+        for (i, &entity) in entities.iter().enumerate() {
+            world
+                .add_component(entity, Health { value: i as u32 })
+                .unwrap();
+            world.add_component(entity, Dead).unwrap();
+            world
+                .add_ephemeral_component(entity, Velocity { x: 0.0, y: 0.0 })
+                .unwrap();
+        }
+
+        // Soft-deleted entities and mid-tick removals, cleanup hasn't run yet
+        world.delete_entity(entities[0]);
+        world.delete_entity(entities[1]);
+        world.remove_component::<Health>(entities[2]);
+        world.remove_component::<Dead>(entities[3]);
+
+        let regular = Query::<Health>::new().with::<Dead>();
+        let iter = regular.iter(&world);
+        let len = iter.len();
+        let collected: Vec<_> = iter.collect();
+        assert_eq!(len, collected.len());
+        assert_eq!(len, 4);
+
+        // Ephemeral primary filtered by regular components
+        let ephemeral = Query::<Velocity>::new().with::<Health>().with::<Dead>();
+        let iter = ephemeral.iter_ephemeral(&world);
+        let len = iter.len();
+        let collected: Vec<_> = iter.collect();
+        assert_eq!(len, collected.len());
+        assert_eq!(len, 4);
+        assert!(collected
+            .iter()
+            .all(|(entity, _)| entities[4..].contains(entity)));
+    }
+
+    #[test]
+    fn test_query_iter_double_ended() {
+        let mut world = World::new();
+        for value in 0..5 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value }).unwrap();
+        }
+
+        let query = Query::<Health>::new();
+        let mut iter = query.iter(&world);
+        let first = iter.next().unwrap();
+        let last = iter.next_back().unwrap();
+        assert_eq!(iter.len(), 3);
+
+        let mut values: Vec<_> = iter.rev().map(|(_, health)| health.value).collect();
+        values.extend([first.1.value, last.1.value]);
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_entities_matches_iter_membership() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Player;
+        impl Component for Player {}
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Stunned;
+        impl Component for Stunned {}
+
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..60).map(|_| world.spawn_entity()).collect();
+        for (i, &entity) in entities.iter().enumerate() {
+            if i % 2 == 0 {
+                world.add_component(entity, Player).unwrap();
+            }
+            if i % 3 == 0 {
+                world.add_component(entity, Health { value: 1 }).unwrap();
+            }
+            if i % 5 == 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+            if i % 4 == 0 {
+                world.add_ephemeral_component(entity, Stunned).unwrap();
+            }
+            if i % 7 == 0 {
+                world.add_ephemeral_component(entity, Player).unwrap();
+            }
+        }
+        world.delete_entity(entities[6]);
+        world.remove_component::<Player>(entities[12]);
+
+        let queries = [
+            Query::<Player>::new(),
+            Query::<Player>::new().with::<Health>().without::<Dead>(),
+            Query::<Player>::new()
+                .with_any_of(&crate::any_of!(Health, Dead))
+                .without_ephemeral::<Stunned>(),
+            Query::<Player>::new()
+                .with_ephemeral::<Stunned>()
+                .without::<Health>(),
+        ];
+        for query in &queries {
+            let expected: HashSet<Entity> = query.iter(&world).map(|(entity, _)| entity).collect();
+            let entities = query.entities(&world);
+            assert_eq!(entities.len(), expected.len());
+            assert_eq!(entities.collect::<HashSet<_>>(), expected);
+
+            let expected: HashSet<Entity> = query
+                .iter_ephemeral(&world)
+                .map(|(entity, _)| entity)
+                .collect();
+            let entities = query.ephemeral_entities(&world);
+            assert_eq!(entities.len(), expected.len());
+            assert_eq!(entities.collect::<HashSet<_>>(), expected);
+        }
+
+        // Sanity check that the filters above are not trivially empty
+        assert_eq!(queries[1].entities(&world).count(), 6);
+        assert!(queries[3].ephemeral_entities(&world).len() > 0);
+    }
+
+    #[test]
+    fn test_slice_filters_match_generic_filters() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Player;
+        impl Component for Player {}
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Npc;
+        impl Component for Npc {}
+
+        let mut world = World::new();
+        for i in 0..64u32 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value: i }).unwrap();
+            if i & 1 != 0 {
+                world.add_component(entity, Player).unwrap();
+            }
+            if i & 2 != 0 {
+                world.add_component(entity, Npc).unwrap();
+            }
+            if i & 4 != 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+            if i & 8 != 0 {
+                world
+                    .add_component(entity, Position { x: 0.0, y: 0.0 })
+                    .unwrap();
+            }
+            if i & 16 != 0 {
+                world
+                    .add_component(entity, Velocity { x: 0.0, y: 0.0 })
+                    .unwrap();
+            }
+        }
+
+        let matches = |query: Query<Health>| -> HashSet<u32> {
+            query.iter(&world).map(|(_, health)| health.value).collect()
+        };
+
+        let generic = matches(
+            Query::new()
+                .without::<Player>()
+                .without::<Npc>()
+                .without::<Dead>(),
+        );
+        let slice = matches(Query::new().without_any_of(&crate::any_of!(Player, Npc, Dead)));
+        assert_eq!(slice, generic);
+        assert_eq!(generic.len(), 8);
+
+        let generic = matches(Query::new().with::<Position>().with::<Velocity>());
+        let slice = matches(Query::new().with_all_of(&crate::any_of!(Position, Velocity)));
+        assert_eq!(slice, generic);
+        assert_eq!(generic.len(), 16);
+
+        // Both styles compose with each other
+        let generic = matches(
+            Query::new()
+                .with::<Position>()
+                .with::<Velocity>()
+                .without::<Player>()
+                .without::<Dead>(),
+        );
+        let mixed = matches(
+            Query::new()
+                .with::<Position>()
+                .with_all_of(&crate::any_of!(Velocity))
+                .without_any_of(&crate::any_of!(Player))
+                .without::<Dead>(),
+        );
+        assert_eq!(mixed, generic);
+        assert_eq!(generic, HashSet::from([24, 26, 56, 58]));
+
+        // Empty lists add no condition
+        assert_eq!(
+            matches(Query::new().with_all_of(&[]).without_any_of(&[])).len(),
+            64
+        );
+    }
+
+    #[test]
+    fn test_collect_matches_manual_collection() {
+        let mut world = World::new();
+        for i in 0..20u32 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value: i }).unwrap();
+            if i % 4 == 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+            if i % 2 == 0 {
+                world
+                    .add_ephemeral_component(entity, Health { value: i * 10 })
+                    .unwrap();
+            }
+        }
+        let query = Query::<Health>::new().without::<Dead>();
+
+        let entities: HashSet<Entity> = query.collect_entities(&world).into_iter().collect();
+        let manual: HashSet<Entity> = query.iter(&world).map(|(e, _)| e).collect();
+        assert_eq!(entities, manual);
+        assert_eq!(entities.len(), 15);
+
+        let mut values: Vec<u32> = query
+            .collect_components(&world)
+            .iter()
+            .map(|h| h.value)
+            .collect();
+        let mut manual: Vec<u32> = query.iter(&world).map(|(_, h)| h.value).collect();
+        values.sort_unstable();
+        manual.sort_unstable();
+        assert_eq!(values, manual);
+
+        let entities: HashSet<Entity> = query
+            .collect_ephemeral_entities(&world)
+            .into_iter()
+            .collect();
+        let manual: HashSet<Entity> = query.iter_ephemeral(&world).map(|(e, _)| e).collect();
+        assert_eq!(entities, manual);
+        assert_eq!(entities.len(), 5);
+
+        let mut values: Vec<u32> = query
+            .collect_ephemeral_components(&world)
+            .iter()
+            .map(|h| h.value)
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![20, 60, 100, 140, 180]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_matches_iter() {
+        use rayon::iter::ParallelIterator;
+
+        let mut world = World::new();
+        for i in 0..1000u32 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value: i }).unwrap();
+            if i % 3 == 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+        }
+
+        let query = Query::<Health>::new().without::<Dead>();
+        assert_eq!(query.par_iter(&world).count(), query.iter(&world).count());
+
+        let sequential: HashSet<u32> = query.iter(&world).map(|(_, h)| h.value).collect();
+        let parallel: HashSet<u32> = query.par_iter(&world).map(|(_, h)| h.value).collect();
+        assert_eq!(parallel, sequential);
+    }
 }
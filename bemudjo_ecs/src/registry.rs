@@ -0,0 +1,120 @@
+use crate::Component;
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// A set of component types that the application has explicitly opted in.
+///
+/// Operations that act on all of an entity's components without knowing their
+/// types, like [`World::transfer_entity`](crate::World::transfer_entity), use a
+/// registry to tell intended components from ones that were forgotten. Build it
+/// once at startup, next to the code that defines the component types.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, ComponentRegistry};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// let registry = ComponentRegistry::new()
+///     .with::<Position>()
+///     .with::<Health>();
+///
+/// assert!(registry.is_registered::<Position>());
+/// assert_eq!(registry.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComponentRegistry {
+    /// Registered component types and their type names
+    types: HashMap<TypeId, &'static str>,
+}
+
+impl ComponentRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers component type `T`. Registering a type twice has no effect.
+    pub fn register<T: Component>(&mut self) -> &mut Self {
+        self.types
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+        self
+    }
+
+    /// Registers component type `T`, builder style.
+    pub fn with<T: Component>(mut self) -> Self {
+        self.register::<T>();
+        self
+    }
+
+    /// Returns `true` if `T` has been registered.
+    pub fn is_registered<T: Component>(&self) -> bool {
+        self.contains_type_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the component type with the given TypeId has been registered.
+    pub fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.types.contains_key(&type_id)
+    }
+
+    /// Returns the number of registered types.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns `true` if no type has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns the type names of all registered types, sorted.
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.types.values().copied().collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position;
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health;
+    impl Component for Health {}
+
+    #[test]
+    fn test_register_is_idempotent() {
+        let mut registry = ComponentRegistry::new();
+        registry.register::<Position>().register::<Position>();
+
+        assert_eq!(registry.len(), 1);
+        assert!(registry.is_registered::<Position>());
+        assert!(!registry.is_registered::<Health>());
+        assert!(registry.contains_type_id(TypeId::of::<Position>()));
+    }
+
+    #[test]
+    fn test_type_names_sorted() {
+        let registry = ComponentRegistry::new().with::<Position>().with::<Health>();
+
+        assert_eq!(
+            registry.type_names(),
+            vec![
+                std::any::type_name::<Health>(),
+                std::any::type_name::<Position>()
+            ]
+        );
+        assert!(ComponentRegistry::new().is_empty());
+    }
+}
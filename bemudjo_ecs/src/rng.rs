@@ -0,0 +1,280 @@
+use crate::Component;
+use std::any::TypeId;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+const DEFAULT_STREAM: u64 = 0xda3e39cb94b95bdb;
+
+/// Seedable random number generator, meant to be inserted as a world resource.
+///
+/// A small PCG32 generator: fast, good enough for gameplay, and fully
+/// determined by its seed, so tests and replays can reproduce every roll.
+/// It is not suitable for cryptography.
+///
+/// Systems should not share one sequence, since then adding a system shifts
+/// the rolls of every system after it. Instead each system takes its own
+/// stream with [`fork`](Rng::fork), keyed by its type. A fork depends only on
+/// the seed, the key and the current tick, which the scheduler advances after
+/// every `run_tick()`, so a system gets fresh rolls each tick that no other
+/// system can perturb.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, Rng, SequentialSystemScheduler, System, World};
+/// use std::any::TypeId;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Gold(u32);
+/// impl Component for Gold {}
+///
+/// struct LootDropSystem;
+/// impl System for LootDropSystem {
+///     fn run(&self, world: &mut World) {
+///         let mut rng = world.get_resource::<Rng>().unwrap().fork(TypeId::of::<Self>());
+///         let drop = world.spawn_entity();
+///         world.add_component(drop, Gold(rng.range(1..100))).unwrap();
+///     }
+/// }
+///
+/// let play = |seed| {
+///     let mut world = World::new();
+///     world.insert_resource(Rng::seeded(seed));
+///     let mut scheduler = SequentialSystemScheduler::new();
+///     scheduler.add_system(LootDropSystem).unwrap();
+///     scheduler.build().unwrap();
+///     for _ in 0..3 {
+///         scheduler.run_tick(&mut world);
+///     }
+///     let mut gold: Vec<u32> = world.entities().map(|&e| world.get_component::<Gold>(e).unwrap().0).collect();
+///     gold.sort_unstable();
+///     gold
+/// };
+///
+/// assert_eq!(play(42), play(42));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    seed: u64,
+    stream: u64,
+    state: u64,
+    tick: u64,
+}
+
+impl Component for Rng {}
+
+impl Rng {
+    /// Creates a generator whose sequence is fully determined by `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_stream(seed, DEFAULT_STREAM, 0)
+    }
+
+    fn with_stream(seed: u64, stream: u64, tick: u64) -> Self {
+        let mut rng = Self {
+            seed,
+            stream: (stream << 1) | 1,
+            state: 0,
+            tick,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Returns the number of ticks run since the generator was inserted.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns an independent generator for `key`, usually a system's type.
+    ///
+    /// The fork depends on the seed, `key` and the current tick only, not on
+    /// how many numbers were drawn so far, so forking again within the same
+    /// tick repeats the same sequence. Fork once per `run()` and draw from the
+    /// fork. Keys are hashed, which is stable within one build of a program.
+    pub fn fork(&self, key: TypeId) -> Rng {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let seed = mix(self.seed ^ mix(self.tick.wrapping_add(self.stream)));
+        Self::with_stream(seed, hasher.finish(), self.tick)
+    }
+
+    /// Returns a uniformly distributed `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    /// Returns a uniformly distributed `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        (u64::from(self.next_u32()) << 32) | u64::from(self.next_u32())
+    }
+
+    /// Returns a uniformly distributed `f32` in `0.0..1.0`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a uniformly distributed value in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::Rng;
+    ///
+    /// let mut rng = Rng::seeded(7);
+    /// let damage = rng.range(5..10);
+    /// assert!((5..10).contains(&damage));
+    ///
+    /// let offset = rng.range(-1.0..1.0);
+    /// assert!((-1.0..1.0).contains(&offset));
+    /// ```
+    pub fn range<T: SampleUniform>(&mut self, range: Range<T>) -> T {
+        T::sample(range, self)
+    }
+
+    /// Returns a value uniformly distributed in `0..span`, without modulo bias.
+    fn below(&mut self, span: u64) -> u64 {
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let value = self.next_u64();
+            if value >= threshold {
+                return value % span;
+            }
+        }
+    }
+
+    /// Advances the state, returning the previous one.
+    fn step(&mut self) -> u64 {
+        let old = self.state;
+        self.state = old.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.stream);
+        old
+    }
+
+    /// Moves the generator to the next tick, see [`Rng::fork`].
+    pub(crate) fn advance_tick(&mut self) {
+        self.tick += 1;
+    }
+}
+
+/// Scrambles the bits of `value` (the SplitMix64 finalizer).
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// A type [`Rng::range`] can draw values of.
+pub trait SampleUniform: Sized {
+    /// Draws a uniformly distributed value from `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    fn sample(range: Range<Self>, rng: &mut Rng) -> Self;
+}
+
+macro_rules! impl_integer_sample_uniform {
+    ($($int:ty),*) => {$(
+        impl SampleUniform for $int {
+            fn sample(range: Range<$int>, rng: &mut Rng) -> $int {
+                assert!(range.start < range.end, "cannot sample empty range {range:?}");
+                let span = (range.end as i128 - range.start as i128) as u64;
+                (range.start as i128 + rng.below(span) as i128) as $int
+            }
+        }
+    )*};
+}
+
+impl_integer_sample_uniform!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl SampleUniform for f32 {
+    fn sample(range: Range<f32>, rng: &mut Rng) -> f32 {
+        assert!(
+            range.start < range.end,
+            "cannot sample empty range {range:?}"
+        );
+        let value = range.start + rng.next_f32() * (range.end - range.start);
+        // Rounding can land exactly on the excluded end
+        if value < range.end {
+            value
+        } else {
+            range.start
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CombatSystem;
+    struct SpawnSystem;
+
+    fn draws(rng: &mut Rng, count: usize) -> Vec<u32> {
+        (0..count).map(|_| rng.next_u32()).collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::seeded(42);
+        let mut b = Rng::seeded(42);
+        let mut c = Rng::seeded(43);
+
+        let sequence = draws(&mut a, 100);
+        assert_eq!(sequence, draws(&mut b, 100));
+        assert_ne!(sequence, draws(&mut c, 100));
+    }
+
+    #[test]
+    fn test_ranges_stay_in_bounds_and_cover_them() {
+        let mut rng = Rng::seeded(1);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let roll = rng.range(1..7u32);
+            assert!((1..7).contains(&roll));
+            seen[roll as usize - 1] = true;
+
+            assert!((-3..3).contains(&rng.range(-3..3i32)));
+            assert!((0.0..1.0).contains(&rng.next_f32()));
+            assert!((-0.5..0.5).contains(&rng.range(-0.5..0.5f32)));
+        }
+
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    fn test_empty_range_panics() {
+        Rng::seeded(1).range(5..5u32);
+    }
+
+    #[test]
+    fn test_forks_are_independent_of_draws_and_each_other() {
+        let mut rng = Rng::seeded(42);
+        let combat = draws(&mut rng.fork(TypeId::of::<CombatSystem>()), 20);
+
+        // Drawing from the parent or another fork doesn't shift the stream
+        draws(&mut rng, 5);
+        let spawn = draws(&mut rng.fork(TypeId::of::<SpawnSystem>()), 20);
+        assert_eq!(
+            combat,
+            draws(&mut rng.fork(TypeId::of::<CombatSystem>()), 20)
+        );
+        assert_ne!(combat, spawn);
+
+        // A new tick gives new rolls
+        rng.advance_tick();
+        assert_ne!(
+            combat,
+            draws(&mut rng.fork(TypeId::of::<CombatSystem>()), 20)
+        );
+    }
+}
@@ -1,32 +1,136 @@
-use crate::{System, World};
-use std::any::TypeId;
-use std::collections::{HashMap, VecDeque};
+use crate::{ResourceAccess, Rng, System, World};
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A predicate deciding whether a system runs in the current tick.
+type RunCondition = Box<dyn Fn(&World) -> bool>;
+
+/// Panics caught by `run_tick_catch_unwind()`, with the type of the system that panicked.
+type SystemPanics = Vec<(TypeId, Box<dyn Any + Send>)>;
+
+/// A coarse execution phase such as `Input`, `Update` or `Render`.
+///
+/// Any ordered type can be a stage, typically a user-defined enum deriving
+/// `Ord`, whose variants then run in declaration order. See
+/// [`SequentialSystemScheduler::add_system_to_stage`].
+pub trait Stage: Ord + Copy + Debug + 'static {}
+
+impl<T: Ord + Copy + Debug + 'static> Stage for T {}
+
+/// A [`Stage`] with its concrete type erased.
+trait AnyStage {
+    fn as_any(&self) -> &dyn Any;
+    /// Compares two stages, or returns `None` if they have different types.
+    fn compare(&self, other: &dyn AnyStage) -> Option<Ordering>;
+    fn describe(&self) -> String;
+}
+
+impl<T: Stage> AnyStage for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn compare(&self, other: &dyn AnyStage) -> Option<Ordering> {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .map(|other| self.cmp(other))
+    }
+
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
 
-/// Information about a registered system
 struct SystemInfo {
     system: Box<dyn System>,
     type_id: TypeId,
+    name: String, // System::name(), captured by add_system()
     dependencies: Vec<TypeId>,
+    enabled: bool,
+    condition: Option<RunCondition>, // Evaluated once per tick, see add_system_if()
+    initialized: Cell<bool>,         // Whether on_build has run for this system
+    stage: Option<Box<dyn AnyStage>>, // See add_system_to_stage()
+    resources: ResourceAccess,       // System::resources(), captured by add_system()
+    rate: Option<RunRate>,           // See add_system_with_rate()
+}
+
+/// Runs a system only on ticks `offset`, `offset + every_n_ticks`, and so on.
+#[derive(Debug, Clone, Copy)]
+struct RunRate {
+    every_n_ticks: u32,
+    offset: u32,
+}
+
+impl RunRate {
+    fn is_due(&self, tick: u64) -> bool {
+        tick % u64::from(self.every_n_ticks) == u64::from(self.offset)
+    }
+}
+
+/// Outcome of [`SequentialSystemScheduler::build_with_report`].
+///
+/// Building with a report succeeds in the same cases as `build()`, but instead
+/// of silently ignoring dependencies on systems that were never added it lists
+/// them in `missing_dependencies`, along with the resource conflicts found in
+/// the systems' [`System::resources`] declarations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// System names in the resolved execution order.
+    pub execution_order: Vec<String>,
+    /// One entry per declared dependency that doesn't match any registered
+    /// system: the name of the dependent system and the missing `TypeId`.
+    pub missing_dependencies: Vec<(String, TypeId)>,
+    /// One entry per pair of systems that both write a resource while neither
+    /// is ordered before the other: the two system names, in execution order,
+    /// and the resource's type name. Their effects depend on the order they
+    /// happened to be added in.
+    pub unordered_resource_writes: Vec<(String, String, &'static str)>,
+}
+
+impl BuildReport {
+    /// Returns `true` if every declared dependency was found.
+    pub fn is_complete(&self) -> bool {
+        self.missing_dependencies.is_empty()
+    }
 }
 
 /// A sequential system scheduler that executes systems in dependency order.
 ///
-/// This scheduler runs all systems through three distinct phases sequentially,
-/// followed by automatic cleanup operations:
+/// This scheduler runs all systems through four system phases: three
+/// sequential ones, followed by automatic cleanup operations and a final
+/// read-only phase that sees the cleaned-up world. Bookkeeping for the next
+/// tick comes last. On the first tick after `build()`, every system's `on_build` hook runs once
+/// before these phases.
+/// 0. Staged ephemeral promotion (see `World::add_ephemeral_component_next_tick`)
 /// 1. All systems' `before_run` methods (preparation)
 /// 2. All systems' `run` methods (main logic)
 /// 3. All systems' `after_run` methods (cleanup/output)
 /// 4. Entity cleanup (remove deleted entities)
 /// 5. Ephemeral component cleanup (clear all ephemeral components)
+/// 6. All systems' `post_cleanup` methods (metrics, persistence)
+/// 7. Change tracking reset (forget which components were added or changed)
+/// 8. Event flush (hand the tick's changes to `World::subscribe` receivers)
+/// 9. Rng forks advance (forked streams draw fresh numbers next tick)
+/// 10. History (worlds with `World::enable_history` record the finished tick)
+///
+/// The four system phases each run every active system in execution order
+/// before the next phase starts. Whether a system is active is decided once
+/// per tick, so a system runs either all of its phases or none.
 ///
 /// # Execution Order
-/// Systems execute in the order they were added with `add_system()`.
+/// Systems execute in the order they were added with `add_system()`, except
+/// where dependencies, stages or [`System::priority`] demand otherwise.
 /// This makes the execution predictable and deterministic, which is
 /// crucial for applications that require consistent behavior.
 ///
 /// # Example Usage
 /// ```
-/// use bemudjo_ecs::{SequentialSystemScheduler, System, World, Component};
+/// use bemudjo_ecs::{SequentialSystemScheduler, System, World, WorldView, Component};
 ///
 /// #[derive(Clone, Debug, PartialEq)]
 /// struct Health { value: u32 }
@@ -42,7 +146,7 @@ struct SystemInfo {
 ///
 /// struct RenderSystem;
 /// impl System for RenderSystem {
-///     fn after_run(&self, world: &World) {
+///     fn after_run(&self, world: &WorldView) {
 ///         // Render entities
 ///         println!("Rendering frame...");
 ///     }
@@ -74,6 +178,9 @@ pub struct SequentialSystemScheduler {
     systems: Vec<SystemInfo>,
     execution_order: Vec<usize>, // Indices into systems vec in dependency order
     is_built: bool,              // Whether build() has been called
+    is_strict: bool,             // Whether the last build was build_strict()
+    on_build_pending: Cell<bool>, // Whether on_build hooks still have to run
+    tick: Cell<u64>,             // Number of ticks run so far, see add_system_with_rate()
 }
 
 impl SequentialSystemScheduler {
@@ -91,20 +198,28 @@ impl SequentialSystemScheduler {
             systems: Vec::new(),
             execution_order: Vec::new(),
             is_built: false,
+            is_strict: false,
+            on_build_pending: Cell::new(false),
+            tick: Cell::new(0),
         }
     }
 
     /// Adds a system to the scheduler.
     ///
     /// Systems can only be added before calling `build()`. After building,
-    /// the scheduler is immutable and ready for execution.
+    /// call `unbuild()` to add more systems, then build again.
     ///
     /// # Parameters
     /// * `system` - Any type implementing the `System` trait
     ///
     /// # Returns
     /// * `Ok(())` if the system was added successfully
-    /// * `Err(String)` if the scheduler has already been built
+    /// * `Err(String)` if the scheduler has already been built, or if a
+    ///   system of the same type is already registered
+    ///
+    /// Adding a system type twice is usually a mistake that silently runs it
+    /// twice per tick, so it is rejected. Use `add_system_allow_duplicates()`
+    /// for systems meant to run as several instances.
     ///
     /// # Example
     /// ```
@@ -123,17 +238,255 @@ impl SequentialSystemScheduler {
     /// assert_eq!(scheduler.system_count(), 1);
     /// ```
     pub fn add_system<S: System + 'static>(&mut self, system: S) -> Result<(), String> {
+        self.push_system(system, None, None, None, false)
+    }
+
+    /// Adds a system even if a system of the same type is already registered.
+    ///
+    /// Meant for stateful systems that run as several instances with
+    /// different configurations, like one spawner per zone. Every instance
+    /// runs each tick, in the order they were added unless dependencies say
+    /// otherwise. A dependency on the type is a dependency on all of its
+    /// instances, and methods taking the type as a parameter, like
+    /// `remove_system()`, act on all of them.
+    ///
+    /// Like `add_system()`, this only works before `build()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    ///
+    /// struct WaveSystem {
+    ///     size: usize,
+    /// }
+    /// impl System for WaveSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         for _ in 0..self.size {
+    ///             world.spawn_entity();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(WaveSystem { size: 2 }).unwrap();
+    /// assert!(scheduler.add_system(WaveSystem { size: 3 }).is_err());
+    ///
+    /// scheduler
+    ///     .add_system_allow_duplicates(WaveSystem { size: 3 })
+    ///     .unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.entities().count(), 5);
+    /// ```
+    pub fn add_system_allow_duplicates<S: System + 'static>(
+        &mut self,
+        system: S,
+    ) -> Result<(), String> {
+        self.push_system(system, None, None, None, true)
+    }
+
+    /// Adds a system to a stage.
+    ///
+    /// Every system of an earlier stage runs before any system of a later one,
+    /// whatever order they were added in. Within a stage, systems are ordered
+    /// by their dependencies as usual. Systems added without a stage are only
+    /// ordered by their dependencies.
+    ///
+    /// All stages of a scheduler must have the same type. Building fails if
+    /// they don't, or if a system depends on a system of a later stage.
+    ///
+    /// Like `add_system()`, this only works before `build()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    ///
+    /// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    /// enum GameStage { Input, Update, Render }
+    ///
+    /// struct RenderSystem;
+    /// impl System for RenderSystem {}
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {}
+    ///
+    /// struct TimeSystem;
+    /// impl System for TimeSystem {}
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system_to_stage(GameStage::Render, RenderSystem).unwrap();
+    /// scheduler.add_system_to_stage(GameStage::Update, MovementSystem).unwrap();
+    /// scheduler.add_system_to_stage(GameStage::Input, TimeSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     scheduler.system_names(),
+    ///     vec!["TimeSystem", "MovementSystem", "RenderSystem"]
+    /// );
+    /// ```
+    pub fn add_system_to_stage<S: System + 'static>(
+        &mut self,
+        stage: impl Stage,
+        system: S,
+    ) -> Result<(), String> {
+        self.push_system(system, None, Some(Box::new(stage)), None, false)
+    }
+
+    /// Adds a system that only runs on ticks where `condition` holds.
+    ///
+    /// The condition is evaluated once per tick, right before the `before_run`
+    /// phase, with read access to the world. If it returns `false`, the
+    /// system's `before_run`, `run` and `after_run` are all skipped for that
+    /// tick. Conditions don't affect the execution order: systems depending on a
+    /// skipped system still run after its place in the order.
+    ///
+    /// Like `add_system()`, this only works before `build()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Paused;
+    /// impl Component for Paused {}
+    ///
+    /// struct EnemySpawnSystem;
+    /// impl System for EnemySpawnSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         world.spawn_entity();
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler
+    ///     .add_system_if(EnemySpawnSystem, |world: &World| {
+    ///         world.get_resource::<Paused>().is_none()
+    ///     })
+    ///     .unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.entities().count(), 1);
+    ///
+    /// world.insert_resource(Paused);
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.entities().count(), 1);
+    /// ```
+    pub fn add_system_if<S, F>(&mut self, system: S, condition: F) -> Result<(), String>
+    where
+        S: System + 'static,
+        F: Fn(&World) -> bool + 'static,
+    {
+        self.push_system(system, Some(Box::new(condition)), None, None, false)
+    }
+
+    /// Adds a system that only runs every `every_n_ticks` ticks.
+    ///
+    /// Ticks are counted by the scheduler from 0, starting with its first
+    /// `run_tick()`; the system runs on ticks `offset`, `offset + every_n_ticks`,
+    /// `offset + 2 * every_n_ticks` and so on. Giving systems with the same rate
+    /// different offsets spreads their load over several ticks. On other ticks
+    /// the system's `before_run`, `run` and `after_run` are all skipped, without
+    /// affecting the execution order: systems depending on a skipped system
+    /// still run after its place in the order.
+    ///
+    /// The tick count lives in the scheduler and is kept across `unbuild()`
+    /// and `build()`.
+    ///
+    /// Like `add_system()`, this only works before `build()`.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the system was added successfully
+    /// * `Err(String)` if the scheduler has already been built, if
+    ///   `every_n_ticks` is 0 or if `offset` is not smaller than `every_n_ticks`
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    ///
+    /// struct AutosaveSystem;
+    /// impl System for AutosaveSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         world.spawn_entity();
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system_with_rate(AutosaveSystem, 10, 5).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// for _ in 0..20 {
+    ///     scheduler.run_tick(&mut world);
+    /// }
+    ///
+    /// // Ran on ticks 5 and 15
+    /// assert_eq!(world.entities().count(), 2);
+    /// ```
+    pub fn add_system_with_rate<S: System + 'static>(
+        &mut self,
+        system: S,
+        every_n_ticks: u32,
+        offset: u32,
+    ) -> Result<(), String> {
+        if every_n_ticks == 0 {
+            return Err(format!("System {} cannot run every 0 ticks", system.name()));
+        }
+        if offset >= every_n_ticks {
+            return Err(format!(
+                "Offset {offset} of system {} must be smaller than its rate of {every_n_ticks} ticks",
+                system.name()
+            ));
+        }
+
+        let rate = RunRate {
+            every_n_ticks,
+            offset,
+        };
+        self.push_system(system, None, None, Some(rate), false)
+    }
+
+    /// Registers a system with an optional run condition, stage and rate.
+    ///
+    /// Unless `allow_duplicates` is set, fails if a system of the same type
+    /// is already registered.
+    fn push_system<S: System + 'static>(
+        &mut self,
+        system: S,
+        condition: Option<RunCondition>,
+        stage: Option<Box<dyn AnyStage>>,
+        rate: Option<RunRate>,
+        allow_duplicates: bool,
+    ) -> Result<(), String> {
         if self.is_built {
-            return Err("Cannot add systems after scheduler has been built. Create a new scheduler if you need to add more systems.".to_string());
+            return Err("Cannot add systems after scheduler has been built. Call unbuild() first to add more systems.".to_string());
         }
 
         let type_id = TypeId::of::<S>();
+        if !allow_duplicates && self.is_registered(type_id) {
+            return Err(format!(
+                "System {} is already registered. Use add_system_allow_duplicates() to run several instances.",
+                system.name()
+            ));
+        }
         let dependencies = system.dependencies().to_vec();
+        let name = system.name().to_string();
+        let resources = system.resources();
 
         let system_info = SystemInfo {
             system: Box::new(system),
             type_id,
+            name,
             dependencies,
+            enabled: true,
+            condition,
+            initialized: Cell::new(false),
+            stage,
+            resources,
+            rate,
         };
 
         self.systems.push(system_info);
@@ -148,9 +501,14 @@ impl SequentialSystemScheduler {
     ///
     /// Once built, no more systems can be added to the scheduler.
     ///
+    /// Building also schedules every system's [`System::on_build`] hook. The hooks
+    /// run exactly once, in dependency order, at the start of the first
+    /// `run_tick()`; calling `build()` again does not run them a second time.
+    ///
     /// # Returns
     /// * `Ok(())` if dependencies were resolved successfully
-    /// * `Err(String)` if circular dependencies were detected
+    /// * `Err(String)` if circular dependencies were detected, or if two systems
+    ///   initialize the same resource, see [`System::resources`]
     ///
     /// # Example
     /// ```
@@ -200,113 +558,919 @@ impl SequentialSystemScheduler {
 
         // Mark as built
         self.is_built = true;
+        self.is_strict = false;
+        self.on_build_pending.set(true);
 
         Ok(())
     }
 
-    /// Returns the number of systems currently registered.
+    /// Returns the scheduler to its unbuilt state so systems can be added again.
+    ///
+    /// The execution order is discarded and `run_tick()` panics until the
+    /// scheduler is built again. Systems keep their enabled state, and `on_build`
+    /// hooks that already ran are not repeated after the next build; only newly
+    /// added systems get theirs.
     ///
     /// # Example
     /// ```
-    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
     ///
-    /// struct System1;
-    /// impl System for System1 {}
+    /// struct CombatSystem;
+    /// impl System for CombatSystem {}
     ///
-    /// struct System2;
-    /// impl System for System2 {}
+    /// struct PvpSystem;
+    /// impl System for PvpSystem {}
     ///
     /// let mut scheduler = SequentialSystemScheduler::new();
-    /// assert_eq!(scheduler.system_count(), 0);
-    ///
-    /// scheduler.add_system(System1);
-    /// assert_eq!(scheduler.system_count(), 1);
+    /// scheduler.add_system(CombatSystem).unwrap();
+    /// scheduler.build().unwrap();
+    /// assert!(scheduler.add_system(PvpSystem).is_err());
     ///
-    /// scheduler.add_system(System2);
+    /// // Enable a feature on a live server
+    /// scheduler.unbuild();
+    /// scheduler.add_system(PvpSystem).unwrap();
+    /// scheduler.build().unwrap();
     /// assert_eq!(scheduler.system_count(), 2);
     /// ```
-    pub fn system_count(&self) -> usize {
-        self.systems.len()
+    pub fn unbuild(&mut self) {
+        self.is_built = false;
+        self.execution_order.clear();
+        self.on_build_pending.set(false);
     }
 
-    /// Executes one complete tick of all registered systems.
+    /// Recomputes the execution order from scratch.
     ///
-    /// This method runs all systems through the five execution phases described
-    /// in the [`SequentialSystemScheduler`] documentation, followed by automatic
-    /// cleanup of deleted entities and ephemeral components.
+    /// Equivalent to `unbuild()` followed by `build()`, or by `build_strict()`
+    /// if that is how the scheduler was last built. On error the scheduler is
+    /// left unbuilt.
     ///
-    /// # Panics
-    /// Panics if `build()` has not been called yet. The scheduler must be built
-    /// before it can execute systems.
+    /// # Returns
+    /// * `Ok(())` if dependencies were resolved successfully
+    /// * `Err(String)` if circular dependencies were detected, or in strict mode
+    ///   if a dependency is not registered
+    pub fn rebuild(&mut self) -> Result<(), String> {
+        let strict = self.is_strict;
+        self.unbuild();
+
+        if strict {
+            self.build_strict()
+        } else {
+            self.build()
+        }
+    }
+
+    /// Builds the scheduler, failing if any declared dependency is not registered.
     ///
-    /// # Parameters
-    /// * `world` - Mutable reference to the ECS world
+    /// `build()` silently ignores dependencies on systems that were never added,
+    /// which can hide misconfiguration: the dependent system may end up running
+    /// before work it expects to be done. `build_strict()` reports every such
+    /// dependency instead and leaves the scheduler unbuilt.
+    ///
+    /// # Returns
+    /// * `Ok(())` if all dependencies are registered and resolved successfully
+    /// * `Err(String)` listing each unresolved dependency, or if circular
+    ///   dependencies were detected
     ///
     /// # Example
     /// ```
-    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World, Component};
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
     ///
-    /// #[derive(Clone, Debug, PartialEq)]
-    /// struct Counter { value: u32 }
-    /// impl Component for Counter {}
+    /// static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<InputSystem>()]
+    /// });
     ///
-    /// struct IncrementSystem;
-    /// impl System for IncrementSystem {
-    ///     fn run(&self, world: &mut World) {
-    ///         for entity in world.entities().cloned().collect::<Vec<_>>() {
-    ///             if let Some(counter) = world.get_component::<Counter>(entity) {
-    ///                 let new_counter = Counter { value: counter.value + 1 };
-    ///                 world.replace_component(entity, new_counter);
-    ///             }
-    ///         }
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &MOVEMENT_DEPS
     ///     }
     /// }
     ///
-    /// let mut world = World::new();
-    /// let entity = world.spawn_entity();
-    /// world.add_component(entity, Counter { value: 0 }).unwrap();
-    ///
     /// let mut scheduler = SequentialSystemScheduler::new();
-    /// scheduler.add_system(IncrementSystem).unwrap();
-    /// scheduler.build().unwrap(); // Must build before running
+    /// scheduler.add_system(MovementSystem).unwrap();
     ///
-    /// // Run one tick
-    /// scheduler.run_tick(&mut world);
+    /// // InputSystem was never added
+    /// assert!(scheduler.build_strict().is_err());
     ///
-    /// // Counter should be incremented
-    /// let counter = world.get_component::<Counter>(entity).unwrap();
-    /// assert_eq!(counter.value, 1);
+    /// scheduler.add_system(InputSystem).unwrap();
+    /// scheduler.build_strict().unwrap();
     /// ```
-    pub fn run_tick(&self, world: &mut World) {
-        if !self.is_built {
-            panic!("SequentialSystemScheduler must be built before running. Call build() first.");
+    pub fn build_strict(&mut self) -> Result<(), String> {
+        let missing: Vec<String> = self
+            .missing_dependencies()
+            .into_iter()
+            .map(|(name, dep)| format!("{name} depends on unregistered {dep:?}"))
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(format!(
+                "Unresolved system dependencies: {}",
+                missing.join(", ")
+            ));
         }
 
-        // Phase 1: Preparation - All before_run methods in dependency order
-        for &index in &self.execution_order {
-            self.systems[index].system.before_run(world);
-        }
+        self.build()?;
+        self.is_strict = true;
+        Ok(())
+    }
 
-        // Phase 2: Execution - All run methods in dependency order
-        for &index in &self.execution_order {
-            self.systems[index].system.run(world);
-        }
+    /// Builds the scheduler like `build()` and reports what it resolved.
+    ///
+    /// The report lists the execution order and every dependency on a system
+    /// that was never added, which `build()` ignores. Unlike `build_strict()`,
+    /// missing dependencies don't make the build fail. It also lists the systems
+    /// that write the same resource in no particular order, based on what they
+    /// declare in [`System::resources`].
+    ///
+    /// # Returns
+    /// * `Ok(BuildReport)` if the build succeeded
+    /// * `Err(String)` if circular dependencies were detected
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
+    ///
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<InputSystem>()]
+    /// });
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &MOVEMENT_DEPS
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(MovementSystem).unwrap();
+    ///
+    /// let report = scheduler.build_with_report().unwrap();
+    /// assert!(scheduler.is_built());
+    /// assert_eq!(
+    ///     report.missing_dependencies,
+    ///     vec![("MovementSystem".to_string(), TypeId::of::<InputSystem>())]
+    /// );
+    /// ```
+    pub fn build_with_report(&mut self) -> Result<BuildReport, String> {
+        self.build()?;
+        Ok(BuildReport {
+            execution_order: self
+                .execution_order()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            missing_dependencies: self.missing_dependencies(),
+            unordered_resource_writes: self.unordered_resource_writes()?,
+        })
+    }
 
-        // Phase 3: Cleanup - All after_run methods in dependency order
-        for &index in &self.execution_order {
-            self.systems[index].system.after_run(world);
+    /// Returns the system names in the resolved execution order.
+    ///
+    /// Empty until the scheduler is built. Systems that are disabled or whose
+    /// run condition fails are still listed, in the slot they would run in.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
+    ///
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<InputSystem>()]
+    /// });
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &MOVEMENT_DEPS
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(MovementSystem).unwrap();
+    /// scheduler.add_system(InputSystem).unwrap();
+    /// assert!(scheduler.execution_order().is_empty());
+    ///
+    /// scheduler.build().unwrap();
+    /// assert_eq!(scheduler.execution_order(), vec!["InputSystem", "MovementSystem"]);
+    /// ```
+    pub fn execution_order(&self) -> Vec<&str> {
+        if !self.is_built {
+            return Vec::new();
         }
+        self.execution_order
+            .iter()
+            .map(|&index| self.systems[index].name.as_str())
+            .collect()
+    }
 
-        // Phase 4: Entity cleanup - Remove component data for deleted entities
-        // This ensures clean state for the next tick and prevents memory leaks
-        world.cleanup_deleted_entities();
-
-        // Phase 5: Ephemeral component cleanup - Remove all ephemeral components
-        // This implements the core ephemeral component behavior: components only live for one frame
-        world.clean_ephemeral_storage();
+    /// Returns the `TypeId`s of the systems in the order they run.
+    ///
+    /// The same order as `execution_order()`, for comparing against
+    /// `TypeId::of::<S>()`. Empty until the scheduler is built.
+    pub fn execution_order_types(&self) -> Vec<TypeId> {
+        if !self.is_built {
+            return Vec::new();
+        }
+        self.execution_order
+            .iter()
+            .map(|&index| self.systems[index].type_id)
+            .collect()
     }
 
-    /// Resolves system dependencies and updates execution order.
+    /// Returns the dependencies declared by the system of type `S`.
+    ///
+    /// Unlike `dependency_graph()`, dependencies on systems that were never
+    /// added are included.
+    ///
+    /// # Returns
+    /// * `Some(&[TypeId])` if a system of type `S` is registered
+    /// * `None` otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
+    ///
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<InputSystem>()]
+    /// });
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &MOVEMENT_DEPS
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(MovementSystem).unwrap();
+    ///
+    /// assert_eq!(
+    ///     scheduler.dependencies_of::<MovementSystem>(),
+    ///     Some(&[TypeId::of::<InputSystem>()][..])
+    /// );
+    /// assert_eq!(scheduler.dependencies_of::<InputSystem>(), None);
+    /// ```
+    pub fn dependencies_of<S: System + 'static>(&self) -> Option<&[TypeId]> {
+        let type_id = TypeId::of::<S>();
+        self.systems
+            .iter()
+            .find(|info| info.type_id == type_id)
+            .map(|info| info.dependencies.as_slice())
+    }
+
+    /// Returns each system's name with the names of the systems it depends on.
+    ///
+    /// Systems are listed in the order they were added. Dependencies on systems
+    /// that were never added are left out, see `unresolved_dependencies()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
+    ///
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<InputSystem>()]
+    /// });
+    ///
+    /// struct MovementSystem;
+    /// impl System for MovementSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &MOVEMENT_DEPS
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(MovementSystem).unwrap();
+    /// scheduler.add_system(InputSystem).unwrap();
+    ///
+    /// assert_eq!(
+    ///     scheduler.dependency_graph(),
+    ///     vec![
+    ///         ("MovementSystem".to_string(), vec!["InputSystem".to_string()]),
+    ///         ("InputSystem".to_string(), vec![]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn dependency_graph(&self) -> Vec<(String, Vec<String>)> {
+        self.systems
+            .iter()
+            .map(|info| {
+                let dependencies = info
+                    .dependencies
+                    .iter()
+                    .flat_map(|&dep| {
+                        self.systems
+                            .iter()
+                            .filter(move |other| other.type_id == dep)
+                    })
+                    .map(|dependency| dependency.name.clone())
+                    .collect();
+                (info.name.clone(), dependencies)
+            })
+            .collect()
+    }
+
+    /// Returns the declared dependencies that don't match any registered system.
+    ///
+    /// Each missing `TypeId` is listed once, in the order it is first declared.
+    /// Useful for logging misconfiguration when using the lenient `build()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    /// use std::any::TypeId;
+    /// use std::sync::LazyLock;
+    ///
+    /// struct MissingSystem;
+    /// impl System for MissingSystem {}
+    ///
+    /// static RENDER_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+    ///     vec![TypeId::of::<MissingSystem>()]
+    /// });
+    ///
+    /// struct RenderSystem;
+    /// impl System for RenderSystem {
+    ///     fn dependencies(&self) -> &[TypeId] {
+    ///         &RENDER_DEPS
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(RenderSystem).unwrap();
+    ///
+    /// assert_eq!(
+    ///     scheduler.unresolved_dependencies(),
+    ///     vec![TypeId::of::<MissingSystem>()]
+    /// );
+    /// ```
+    pub fn unresolved_dependencies(&self) -> Vec<TypeId> {
+        let mut missing = Vec::new();
+        for info in &self.systems {
+            for &dep in &info.dependencies {
+                if !self.is_registered(dep) && !missing.contains(&dep) {
+                    missing.push(dep);
+                }
+            }
+        }
+        missing
+    }
+
+    /// Returns each dependency on an unregistered system along with the name of
+    /// the system declaring it.
+    fn missing_dependencies(&self) -> Vec<(String, TypeId)> {
+        self.systems
+            .iter()
+            .flat_map(|info| {
+                info.dependencies
+                    .iter()
+                    .filter(|dep| !self.is_registered(**dep))
+                    .map(move |&dep| (info.name.clone(), dep))
+            })
+            .collect()
+    }
+
+    /// Returns the number of systems currently registered.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    ///
+    /// struct System1;
+    /// impl System for System1 {}
+    ///
+    /// struct System2;
+    /// impl System for System2 {}
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// assert_eq!(scheduler.system_count(), 0);
+    ///
+    /// scheduler.add_system(System1);
+    /// assert_eq!(scheduler.system_count(), 1);
+    ///
+    /// scheduler.add_system(System2);
+    /// assert_eq!(scheduler.system_count(), 2);
+    /// ```
+    pub fn system_count(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Returns the names of the registered systems, see [`System::name`].
+    ///
+    /// Once built, names are listed in execution order; before that, in the
+    /// order the systems were added.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    ///
+    /// struct InputSystem;
+    /// impl System for InputSystem {}
+    ///
+    /// struct CombatSystem;
+    /// impl System for CombatSystem {}
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(InputSystem).unwrap();
+    /// scheduler.add_system(CombatSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// assert_eq!(scheduler.system_names(), vec!["InputSystem", "CombatSystem"]);
+    /// ```
+    pub fn system_names(&self) -> Vec<&str> {
+        if self.is_built {
+            self.execution_order
+                .iter()
+                .map(|&index| self.systems[index].name.as_str())
+                .collect()
+        } else {
+            self.systems.iter().map(|info| info.name.as_str()).collect()
+        }
+    }
+
+    /// Returns `true` if the scheduler is built and ready to run ticks.
+    pub fn is_built(&self) -> bool {
+        self.is_built
+    }
+
+    /// Executes one complete tick of all registered systems.
+    ///
+    /// This method runs all systems through the three execution phases described
+    /// in the [`SequentialSystemScheduler`] documentation, followed by automatic
+    /// cleanup of deleted entities and ephemeral components, the systems'
+    /// `post_cleanup` phase, the reset of change tracking, and finally flushes
+    /// the tick's events to world subscribers. The `Rng`
+    /// resource, if any, moves to the next tick, and worlds with
+    /// `World::enable_history` record the finished tick.
+    ///
+    /// # Panics
+    /// Panics if `build()` has not been called yet. The scheduler must be built
+    /// before it can execute systems.
+    ///
+    /// # Parameters
+    /// * `world` - Mutable reference to the ECS world
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Counter { value: u32 }
+    /// impl Component for Counter {}
+    ///
+    /// struct IncrementSystem;
+    /// impl System for IncrementSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         for entity in world.entities().cloned().collect::<Vec<_>>() {
+    ///             if let Some(counter) = world.get_component::<Counter>(entity) {
+    ///                 let new_counter = Counter { value: counter.value + 1 };
+    ///                 world.replace_component(entity, new_counter);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Counter { value: 0 }).unwrap();
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(IncrementSystem).unwrap();
+    /// scheduler.build().unwrap(); // Must build before running
+    ///
+    /// // Run one tick
+    /// scheduler.run_tick(&mut world);
+    ///
+    /// // Counter should be incremented
+    /// let counter = world.get_component::<Counter>(entity).unwrap();
+    /// assert_eq!(counter.value, 1);
+    /// ```
+    pub fn run_tick(&self, world: &mut World) {
+        self.run_full_tick(world, None);
+    }
+
+    /// Executes one complete tick like `run_tick()`, but catches panicking
+    /// systems instead of letting them take down the whole tick.
+    ///
+    /// Every phase of every system runs inside `catch_unwind`. A system that
+    /// panics is reported with its `TypeId` and the panic payload, and skips
+    /// its remaining phases for this tick; all other systems, the cleanup and
+    /// the rest of the tick run as usual. The panic hook still runs, so the
+    /// panic message is printed as for any other panic.
+    ///
+    /// # Unwind safety
+    /// Systems get `&mut World`, which is not `UnwindSafe`; the world is
+    /// passed across the unwind boundary with `AssertUnwindSafe`. Everything a
+    /// system wrote before panicking stays in the world, so its changes may be
+    /// only half applied. Worse, some world operations run user code halfway
+    /// through: index key functions and `observe_added` callbacks run inside
+    /// `add_component()`, and closures inside `update_component()`. A panic
+    /// there can leave the world's own bookkeeping, like indexes,
+    /// subscriptions and observer state, inconsistent. After catching a
+    /// panic, check the world with `World::check_consistency()` before
+    /// trusting it. State shared outside the world is not repaired either: a
+    /// `Mutex` the system held while panicking stays poisoned.
+    ///
+    /// # Returns
+    /// The panics caught during the tick in execution order, empty if no
+    /// system panicked.
+    ///
+    /// # Panics
+    /// Panics if `build()` has not been called yet.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    /// use std::any::TypeId;
+    ///
+    /// struct BrokenSystem;
+    /// impl System for BrokenSystem {
+    ///     fn run(&self, _world: &mut World) {
+    ///         panic!("unexpected command");
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(BrokenSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// let panics = scheduler.run_tick_catch_unwind(&mut world);
+    ///
+    /// assert_eq!(panics.len(), 1);
+    /// assert_eq!(panics[0].0, TypeId::of::<BrokenSystem>());
+    /// assert_eq!(panics[0].1.downcast_ref::<&str>(), Some(&"unexpected command"));
+    /// ```
+    pub fn run_tick_catch_unwind(&self, world: &mut World) -> Vec<(TypeId, Box<dyn Any + Send>)> {
+        let mut panics = Vec::new();
+        self.run_full_tick(world, Some(&mut panics));
+        panics
+    }
+
+    /// Runs a complete tick, catching system panics into `panics` if given.
+    fn run_full_tick(&self, world: &mut World, mut panics: Option<&mut SystemPanics>) {
+        self.assert_built();
+
+        // Phase 0: Ephemeral components staged during the previous tick become visible
+        world.promote_next_tick_ephemerals();
+
+        let active_systems = self.run_systems(world, panics.as_deref_mut());
+
+        // Phase 4: Entity cleanup - Remove component data for deleted entities
+        // This ensures clean state for the next tick and prevents memory leaks
+        world.cleanup_deleted_entities();
+
+        // Phase 5: Ephemeral component cleanup - Remove all ephemeral components
+        // This implements the core ephemeral component behavior: components only live for one frame
+        world.clean_ephemeral_storage();
+
+        // Phase 6: Observation - All post_cleanup methods see the world the next tick starts from
+        for info in &active_systems {
+            Self::run_phase(info, panics.as_deref_mut(), || {
+                info.system.post_cleanup(&world.view())
+            });
+        }
+
+        // Phase 7: Change tracking reset - The next tick only sees its own additions and changes
+        world.clear_change_tracking();
+
+        // Phase 8: Event flush - Subscribers receive the whole tick as one batch
+        world.flush_world_events();
+
+        // Phase 9: Randomness - Forks of the Rng resource draw fresh numbers next tick
+        if let Some(rng) = world.get_resource_mut_untracked::<Rng>() {
+            rng.advance_tick();
+        }
+
+        // Phase 10: History - Worlds with history enabled close the finished tick
+        world.record_history_tick();
+
+        self.tick.set(self.tick.get() + 1);
+    }
+
+    /// Runs the systems' `before_run`, `run` and `after_run` phases without
+    /// any cleanup afterwards. Without cleanup there is no `post_cleanup` phase.
+    ///
+    /// Soft-deleted entities, ephemeral components, change tracking and pending
+    /// world events are all left in place, so several calls can act as
+    /// sub-steps of one logical tick, e.g. for physics. Finish the tick with
+    /// `run_tick()`, or clean up yourself with `World::cleanup_deleted_entities`,
+    /// `World::clean_ephemeral_storage`, `World::clear_change_tracking` and
+    /// `World::flush_world_events`.
+    ///
+    /// Skipping cleanup across many calls keeps every deleted entity and
+    /// ephemeral component in memory until the next cleanup, so memory grows
+    /// with each call.
+    ///
+    /// Sub-steps don't count as ticks for `add_system_with_rate()`: a
+    /// rate-limited system runs in all or none of the sub-steps of a tick.
+    /// Components staged with `World::add_ephemeral_component_next_tick` are
+    /// only promoted by `run_tick()`.
+    ///
+    /// # Panics
+    /// Panics if `build()` has not been called yet.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Collision;
+    /// impl Component for Collision {}
+    ///
+    /// struct PhysicsSystem;
+    /// impl System for PhysicsSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         let entity = world.spawn_entity();
+    ///         world.add_ephemeral_component(entity, Collision).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(PhysicsSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// for _ in 0..4 {
+    ///     scheduler.run_tick_no_cleanup(&mut world);
+    /// }
+    /// let collisions = |world: &World| {
+    ///     world
+    ///         .entities()
+    ///         .filter(|&&entity| world.has_ephemeral_component::<Collision>(entity))
+    ///         .count()
+    /// };
+    /// assert_eq!(collisions(&world), 4);
+    ///
+    /// world.clean_ephemeral_storage();
+    /// assert_eq!(collisions(&world), 0);
+    /// ```
+    pub fn run_tick_no_cleanup(&self, world: &mut World) {
+        self.assert_built();
+        self.run_systems(world, None);
+    }
+
+    fn assert_built(&self) {
+        if !self.is_built {
+            panic!("SequentialSystemScheduler must be built before running. Call build() first.");
+        }
+    }
+
+    /// Runs pending `on_build` hooks, then phases 1 to 3 of a tick, catching
+    /// system panics into `panics` if given.
+    ///
+    /// Returns the systems that ran without panicking, which are the ones
+    /// `post_cleanup` runs for.
+    fn run_systems(
+        &self,
+        world: &mut World,
+        mut panics: Option<&mut SystemPanics>,
+    ) -> Vec<&SystemInfo> {
+        // One-time initialization on the first tick after build
+        if self.on_build_pending.replace(false) {
+            for &index in &self.execution_order {
+                let info = &self.systems[index];
+                if !info.initialized.replace(true) {
+                    Self::run_phase(info, panics.as_deref_mut(), || info.system.on_build(world));
+                }
+            }
+        }
+
+        // Run conditions are evaluated once, so a system runs either all phases or none
+        let mut active_systems = self.active_systems(world);
+
+        // Phase 1: Preparation - All before_run methods in dependency order
+        active_systems.retain(|info| {
+            Self::run_phase(info, panics.as_deref_mut(), || {
+                info.system.before_run(&world.view())
+            })
+        });
+
+        // Phase 2: Execution - All run methods in dependency order
+        active_systems
+            .retain(|info| Self::run_phase(info, panics.as_deref_mut(), || info.system.run(world)));
+
+        // Phase 3: Cleanup - All after_run methods in dependency order
+        active_systems.retain(|info| {
+            Self::run_phase(info, panics.as_deref_mut(), || {
+                info.system.after_run(&world.view())
+            })
+        });
+
+        active_systems
+    }
+
+    /// Runs one phase of a system. With `panics`, a panic is caught and
+    /// recorded instead of unwinding further.
+    ///
+    /// Returns `false` if the system panicked.
+    fn run_phase(
+        info: &SystemInfo,
+        panics: Option<&mut SystemPanics>,
+        phase: impl FnOnce(),
+    ) -> bool {
+        let Some(panics) = panics else {
+            phase();
+            return true;
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(phase)) {
+            Ok(()) => true,
+            Err(payload) => {
+                panics.push((info.type_id, payload));
+                false
+            }
+        }
+    }
+
+    /// Removes every registered system of type `S`.
+    ///
+    /// Unlike `add_system()`, this also works after `build()`: the execution
+    /// order is recomputed immediately so the next tick runs without the system.
+    ///
+    /// Systems that declared a dependency on the removed system keep running.
+    /// Their dependency is treated like any other missing dependency and is
+    /// silently ignored when resolving the execution order. If the scheduler was
+    /// last built with `build_strict()`, removing a system that others depend on
+    /// fails instead and nothing is removed.
+    ///
+    /// # Returns
+    /// * `Ok(())` if at least one system of type `S` was removed
+    /// * `Err(String)` if no system of type `S` is registered, or in strict mode
+    ///   if other systems depend on it
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    ///
+    /// struct DebugOverlaySystem;
+    /// impl System for DebugOverlaySystem {}
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(DebugOverlaySystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// scheduler.remove_system::<DebugOverlaySystem>().unwrap();
+    /// assert_eq!(scheduler.system_count(), 0);
+    ///
+    /// // Removing it twice fails
+    /// assert!(scheduler.remove_system::<DebugOverlaySystem>().is_err());
+    /// ```
+    pub fn remove_system<S: System + 'static>(&mut self) -> Result<(), String> {
+        let type_id = TypeId::of::<S>();
+
+        if self.is_strict {
+            let dependents: Vec<&str> = self
+                .systems
+                .iter()
+                .filter(|info| info.type_id != type_id && info.dependencies.contains(&type_id))
+                .map(|info| info.name.as_str())
+                .collect();
+
+            if !dependents.is_empty() {
+                return Err(format!(
+                    "Cannot remove system {}: {} depend on it",
+                    std::any::type_name::<S>(),
+                    dependents.join(", ")
+                ));
+            }
+        }
+
+        let count_before = self.systems.len();
+        self.systems.retain(|info| info.type_id != type_id);
+
+        if self.systems.len() == count_before {
+            return Err(format!(
+                "System {} is not registered",
+                std::any::type_name::<S>()
+            ));
+        }
+
+        if self.is_built {
+            // Removing nodes cannot introduce a cycle, but indices have shifted
+            self.resolve_dependencies()?;
+        } else {
+            self.execution_order.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Enables or disables every registered system of type `S`.
+    ///
+    /// Disabled systems stay registered and keep their place in the execution
+    /// order, but none of their `before_run`, `run` or `after_run` methods are
+    /// called until they are enabled again. This can be toggled at any time,
+    /// including between ticks of a built scheduler.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the system was found
+    /// * `Err(String)` if no system of type `S` is registered
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System, World};
+    ///
+    /// struct WeatherSystem;
+    /// impl System for WeatherSystem {}
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(WeatherSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// scheduler.set_system_enabled::<WeatherSystem>(false).unwrap();
+    /// assert_eq!(scheduler.is_system_enabled::<WeatherSystem>(), Some(false));
+    ///
+    /// scheduler.set_system_enabled::<WeatherSystem>(true).unwrap();
+    /// assert_eq!(scheduler.is_system_enabled::<WeatherSystem>(), Some(true));
+    /// ```
+    pub fn set_system_enabled<S: System + 'static>(&mut self, enabled: bool) -> Result<(), String> {
+        let type_id = TypeId::of::<S>();
+        let mut found = false;
+
+        for info in self
+            .systems
+            .iter_mut()
+            .filter(|info| info.type_id == type_id)
+        {
+            info.enabled = enabled;
+            found = true;
+        }
+
+        if found {
+            Ok(())
+        } else {
+            Err(format!(
+                "System {} is not registered",
+                std::any::type_name::<S>()
+            ))
+        }
+    }
+
+    /// Returns whether the systems of type `S` are enabled.
+    ///
+    /// # Returns
+    /// * `Some(bool)` if a system of type `S` is registered
+    /// * `None` otherwise
+    pub fn is_system_enabled<S: System + 'static>(&self) -> Option<bool> {
+        let type_id = TypeId::of::<S>();
+        self.systems
+            .iter()
+            .find(|info| info.type_id == type_id)
+            .map(|info| info.enabled)
+    }
+
+    /// Returns `true` if a system with the given `TypeId` is registered.
+    fn is_registered(&self, type_id: TypeId) -> bool {
+        self.systems.iter().any(|info| info.type_id == type_id)
+    }
+
+    /// Returns the systems that run this tick, in execution order.
+    ///
+    /// A system runs if it is enabled, due this tick according to its rate,
+    /// its run condition, if any, holds and its `should_run()` returns `true`.
+    fn active_systems(&self, world: &World) -> Vec<&SystemInfo> {
+        let view = world.view();
+        let tick = self.tick.get();
+        self.execution_order
+            .iter()
+            .map(|&index| &self.systems[index])
+            .filter(|info| info.enabled)
+            .filter(|info| match info.rate {
+                Some(rate) => rate.is_due(tick),
+                None => true,
+            })
+            .filter(|info| match &info.condition {
+                Some(condition) => condition(world),
+                None => true,
+            })
+            .filter(|info| info.system.should_run(&view))
+            .collect()
+    }
+
+    /// Resolves system dependencies and updates execution order.
     ///
     /// Uses topological sorting to determine the correct execution order
     /// based on system dependencies.
@@ -317,751 +1481,2740 @@ impl SequentialSystemScheduler {
             return Ok(());
         }
 
-        // Build a mapping from TypeId to system index
-        let mut type_to_index: HashMap<TypeId, usize> = HashMap::new();
-        for (index, system_info) in self.systems.iter().enumerate() {
-            type_to_index.insert(system_info.type_id, index);
-        }
+        self.check_resource_initializers()?;
+        let predecessors = self.predecessors()?;
+
+        // Build dependency graph (index -> list of indices that depend on it)
+        let mut in_degree = vec![0; num_systems];
+        let mut graph: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (dependent_index, dependencies) in predecessors.iter().enumerate() {
+            for &dependency_index in dependencies {
+                // dependency_index must run before dependent_index
+                graph
+                    .entry(dependency_index)
+                    .or_default()
+                    .push(dependent_index);
+                in_degree[dependent_index] += 1;
+            }
+        }
+
+        // Topological sort using Kahn's algorithm. Among the systems ready to
+        // run, the highest priority goes first, then the one that became
+        // ready first.
+        let priorities: Vec<i32> = self
+            .systems
+            .iter()
+            .map(|info| info.system.priority())
+            .collect();
+        let mut ready = BinaryHeap::new();
+        let mut readied = 0;
+        let mut execution_order = Vec::new();
+
+        // Start with systems that have no dependencies
+        for (index, &degree) in in_degree.iter().enumerate() {
+            if degree == 0 {
+                ready.push((priorities[index], Reverse(readied), index));
+                readied += 1;
+            }
+        }
+
+        while let Some((_, _, current_index)) = ready.pop() {
+            execution_order.push(current_index);
+
+            // Process all systems that depend on the current system
+            if let Some(dependents) = graph.get(&current_index) {
+                for &dependent_index in dependents {
+                    in_degree[dependent_index] -= 1;
+                    if in_degree[dependent_index] == 0 {
+                        ready.push((
+                            priorities[dependent_index],
+                            Reverse(readied),
+                            dependent_index,
+                        ));
+                        readied += 1;
+                    }
+                }
+            }
+        }
+
+        // Check for circular dependencies
+        if execution_order.len() != num_systems {
+            let cycle: Vec<&str> = self
+                .find_cycle(&in_degree, &predecessors)
+                .into_iter()
+                .map(|index| self.systems[index].name.as_str())
+                .collect();
+            return Err(format!(
+                "Circular dependency detected in system dependencies: {}",
+                cycle.join(" -> ")
+            ));
+        }
+
+        self.execution_order = execution_order;
+        Ok(())
+    }
+
+    /// Returns, for each system, the systems that must run right before it
+    /// because of a dependency or a stage.
+    fn predecessors(&self) -> Result<Vec<Vec<usize>>, String> {
+        // Build a mapping from TypeId to the indices of all its instances
+        let mut type_to_indices: HashMap<TypeId, Vec<usize>> = HashMap::new();
+        for (index, system_info) in self.systems.iter().enumerate() {
+            type_to_indices
+                .entry(system_info.type_id)
+                .or_default()
+                .push(index);
+        }
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); self.systems.len()];
+        for (dependent_index, system_info) in self.systems.iter().enumerate() {
+            for &dep_type_id in &system_info.dependencies {
+                if let Some(dependency_indices) = type_to_indices.get(&dep_type_id) {
+                    predecessors[dependent_index].extend(dependency_indices);
+                } else {
+                    // Dependency not found - ignored here, build_strict() reports it
+                }
+            }
+        }
+        self.add_stage_barriers(&mut predecessors)?;
+        Ok(predecessors)
+    }
+
+    /// Fails if two systems declare that they initialize the same resource.
+    fn check_resource_initializers(&self) -> Result<(), String> {
+        let mut initializers: HashMap<TypeId, &str> = HashMap::new();
+        for info in &self.systems {
+            for &(type_id, type_name) in info.resources.initialized() {
+                if let Some(first) = initializers.insert(type_id, &info.name) {
+                    return Err(format!(
+                        "Resource {type_name} is initialized by both {first} and {}",
+                        info.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the pairs of systems writing the same resource without any
+    /// dependency or stage ordering them, see [`BuildReport`].
+    fn unordered_resource_writes(&self) -> Result<Vec<(String, String, &'static str)>, String> {
+        let predecessors = self.predecessors()?;
+
+        // ancestors[i][j] is true if system j always runs before system i
+        let mut ancestors = vec![vec![false; self.systems.len()]; self.systems.len()];
+        for (index, row) in ancestors.iter_mut().enumerate() {
+            let mut stack = predecessors[index].clone();
+            while let Some(ancestor) = stack.pop() {
+                if !row[ancestor] {
+                    row[ancestor] = true;
+                    stack.extend_from_slice(&predecessors[ancestor]);
+                }
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for (position, &first) in self.execution_order.iter().enumerate() {
+            for &second in &self.execution_order[position + 1..] {
+                if ancestors[first][second] || ancestors[second][first] {
+                    continue;
+                }
+                let second_writes: Vec<TypeId> = self.systems[second]
+                    .resources
+                    .modified()
+                    .map(|(type_id, _)| type_id)
+                    .collect();
+                for (type_id, type_name) in self.systems[first].resources.modified() {
+                    if second_writes.contains(&type_id) {
+                        conflicts.push((
+                            self.systems[first].name.clone(),
+                            self.systems[second].name.clone(),
+                            type_name,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Adds the edges that make every system of a stage run after all systems
+    /// of the previous stage.
+    fn add_stage_barriers(&self, predecessors: &mut [Vec<usize>]) -> Result<(), String> {
+        let mut staged: Vec<(usize, &dyn AnyStage)> = self
+            .systems
+            .iter()
+            .enumerate()
+            .filter_map(|(index, info)| info.stage.as_deref().map(|stage| (index, stage)))
+            .collect();
+        let Some(&(_, first_stage)) = staged.first() else {
+            return Ok(());
+        };
+        if let Some((index, _)) = staged
+            .iter()
+            .find(|(_, stage)| stage.compare(first_stage).is_none())
+        {
+            return Err(format!(
+                "System {} uses a stage of a different type than the other staged systems",
+                self.systems[*index].name
+            ));
+        }
+
+        let stage_cmp = |a: &dyn AnyStage, b: &dyn AnyStage| a.compare(b).expect("same stage type");
+        for (dependent_index, dependencies) in predecessors.iter().enumerate() {
+            let Some(stage) = self.systems[dependent_index].stage.as_deref() else {
+                continue;
+            };
+            for &dependency_index in dependencies {
+                let Some(dependency_stage) = self.systems[dependency_index].stage.as_deref() else {
+                    continue;
+                };
+                if stage_cmp(dependency_stage, stage) == Ordering::Greater {
+                    return Err(format!(
+                        "System {} in stage {} depends on {} in later stage {}",
+                        self.systems[dependent_index].name,
+                        stage.describe(),
+                        self.systems[dependency_index].name,
+                        dependency_stage.describe()
+                    ));
+                }
+            }
+        }
+
+        // Chaining consecutive stages is enough, the order is transitive
+        staged.sort_by(|(_, a), (_, b)| stage_cmp(*a, *b));
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for (position, &(index, stage)) in staged.iter().enumerate() {
+            let same_stage =
+                position > 0 && stage_cmp(staged[position - 1].1, stage) == Ordering::Equal;
+            match groups.last_mut() {
+                Some(group) if same_stage => group.push(index),
+                _ => groups.push(vec![index]),
+            }
+        }
+        for pair in groups.windows(2) {
+            for &later in &pair[1] {
+                predecessors[later].extend_from_slice(&pair[0]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns one dependency cycle among the systems Kahn's algorithm could not
+    /// order, as indices where each system depends on the next. The first
+    /// system is repeated at the end.
+    fn find_cycle(&self, in_degree: &[usize], predecessors: &[Vec<usize>]) -> Vec<usize> {
+        // Every unordered system depends on at least one other unordered
+        // system, so following those dependencies must eventually loop.
+        let unordered_dependency = |index: usize| {
+            predecessors[index]
+                .iter()
+                .copied()
+                .find(|&dep_index| in_degree[dep_index] > 0)
+        };
+
+        let mut path = Vec::new();
+        let mut current = in_degree.iter().position(|&degree| degree > 0);
+        while let Some(index) = current {
+            if let Some(start) = path.iter().position(|&visited| visited == index) {
+                let mut cycle = path.split_off(start);
+                cycle.push(index);
+                return cycle;
+            }
+            path.push(index);
+            current = unordered_dependency(index);
+        }
+        path
+    }
+}
+
+impl Default for SequentialSystemScheduler {
+    /// Creates a new empty system scheduler using the default constructor.
+    ///
+    /// This is equivalent to calling `SequentialSystemScheduler::new()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::SequentialSystemScheduler;
+    ///
+    /// let scheduler1 = SequentialSystemScheduler::new();
+    /// let scheduler2 = SequentialSystemScheduler::default();
+    ///
+    /// assert_eq!(scheduler1.system_count(), scheduler2.system_count());
+    /// ```
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, World, WorldView};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counter {
+        count: u32,
+    }
+    impl Component for Counter {}
+
+    struct TestSystem {
+        name: String,
+        execution_log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TestSystem {
+        fn new(name: &str, log: Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                name: name.to_string(),
+                execution_log: log,
+            }
+        }
+    }
+
+    impl System for TestSystem {
+        fn before_run(&self, _world: &WorldView) {
+            self.execution_log
+                .lock()
+                .unwrap()
+                .push(format!("{}_before", self.name));
+        }
+
+        fn run(&self, _world: &mut World) {
+            self.execution_log
+                .lock()
+                .unwrap()
+                .push(format!("{}_run", self.name));
+        }
+
+        fn after_run(&self, _world: &WorldView) {
+            self.execution_log
+                .lock()
+                .unwrap()
+                .push(format!("{}_after", self.name));
+        }
+    }
+
+    /// A `TestSystem` with a type of its own, for adding several of them
+    /// where duplicate system types are rejected.
+    struct Distinct<const ID: u8>(TestSystem);
+
+    impl<const ID: u8> System for Distinct<ID> {
+        fn before_run(&self, world: &WorldView) {
+            self.0.before_run(world);
+        }
+
+        fn run(&self, world: &mut World) {
+            self.0.run(world);
+        }
+
+        fn after_run(&self, world: &WorldView) {
+            self.0.after_run(world);
+        }
+    }
+
+    #[test]
+    fn test_system_scheduler_new() {
+        let scheduler = SequentialSystemScheduler::new();
+        assert_eq!(scheduler.system_count(), 0);
+    }
+
+    #[test]
+    fn test_system_scheduler_default() {
+        let scheduler = SequentialSystemScheduler::default();
+        assert_eq!(scheduler.system_count(), 0);
+    }
+
+    #[test]
+    fn test_add_system() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        scheduler
+            .add_system(TestSystem::new("system1", log.clone()))
+            .unwrap();
+        assert_eq!(scheduler.system_count(), 1);
+
+        scheduler
+            .add_system_allow_duplicates(TestSystem::new("system2", log.clone()))
+            .unwrap();
+        assert_eq!(scheduler.system_count(), 2);
+
+        // Should be able to build successfully
+        scheduler.build().unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_system_types_are_rejected() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(TestSystem::new("movement", log.clone()))
+            .unwrap();
+
+        let error = scheduler
+            .add_system(TestSystem::new("movement", log.clone()))
+            .unwrap_err();
+        assert!(error.contains("already registered"), "{error}");
+        assert!(error.contains("add_system_allow_duplicates"), "{error}");
+        assert!(scheduler
+            .add_system_to_stage(0, TestSystem::new("movement", log.clone()))
+            .is_err());
+        assert!(scheduler
+            .add_system_if(TestSystem::new("movement", log.clone()), |_: &World| true)
+            .is_err());
+        assert!(scheduler
+            .add_system_with_rate(TestSystem::new("movement", log.clone()), 2, 0)
+            .is_err());
+        assert_eq!(scheduler.system_count(), 1);
+
+        // Opting in once doesn't disable the check for later additions
+        scheduler
+            .add_system_allow_duplicates(TestSystem::new("movement", log.clone()))
+            .unwrap();
+        assert!(scheduler
+            .add_system(TestSystem::new("movement", log.clone()))
+            .is_err());
+        assert_eq!(scheduler.system_count(), 2);
+
+        scheduler.build().unwrap();
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        let runs = log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.ends_with("_run"))
+            .count();
+        assert_eq!(runs, 2);
+    }
+
+    static WAVE_COUNTER_DEPS: std::sync::LazyLock<Vec<TypeId>> =
+        std::sync::LazyLock::new(|| vec![TypeId::of::<WaveSystem>()]);
+
+    /// A system meant to run as several instances with different configs.
+    struct WaveSystem {
+        name: &'static str,
+        priority: i32,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl System for WaveSystem {
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn before_run(&self, _world: &WorldView) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}_before", self.name));
+        }
+
+        fn run(&self, _world: &mut World) {
+            self.log.lock().unwrap().push(format!("{}_run", self.name));
+        }
+    }
+
+    /// Depends on `WaveSystem`, and would run as early as it can.
+    struct WaveCounterSystem {
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl System for WaveCounterSystem {
+        fn dependencies(&self) -> &[TypeId] {
+            &WAVE_COUNTER_DEPS
+        }
+
+        fn priority(&self) -> i32 {
+            10
+        }
+
+        fn before_run(&self, _world: &WorldView) {
+            self.log.lock().unwrap().push("counter_before".to_string());
+        }
+
+        fn run(&self, _world: &mut World) {
+            self.log.lock().unwrap().push("counter_run".to_string());
+        }
+    }
+
+    /// Adds a low priority wave, the counter, then a normal priority wave.
+    fn wave_scheduler(log: &Arc<Mutex<Vec<String>>>) -> SequentialSystemScheduler {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(WaveSystem {
+                name: "goblins",
+                priority: -1,
+                log: log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(WaveCounterSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system_allow_duplicates(WaveSystem {
+                name: "orcs",
+                priority: 0,
+                log: log.clone(),
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+        scheduler
+    }
+
+    #[test]
+    fn test_dependency_on_duplicated_type_covers_every_instance() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let scheduler = wave_scheduler(&log);
+
+        assert_eq!(
+            scheduler.predecessors().unwrap(),
+            vec![vec![], vec![0, 2], vec![]]
+        );
+        assert_eq!(
+            scheduler.dependency_graph()[1],
+            (
+                "WaveCounterSystem".to_string(),
+                vec!["WaveSystem".to_string(), "WaveSystem".to_string()]
+            )
+        );
+        // The counter's priority doesn't let it overtake the low priority wave
+        assert_eq!(
+            scheduler.execution_order_types().last(),
+            Some(&TypeId::of::<WaveCounterSystem>())
+        );
+    }
+
+    #[test]
+    fn test_duplicated_instances_run_in_dependency_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let scheduler = wave_scheduler(&log);
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "orcs_before",
+                "goblins_before",
+                "counter_before",
+                "orcs_run",
+                "goblins_run",
+                "counter_run",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execution_order() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        // Add systems in specific order
+        scheduler
+            .add_system(TestSystem::new("first", log.clone()))
+            .unwrap();
+        scheduler
+            .add_system_allow_duplicates(TestSystem::new("second", log.clone()))
+            .unwrap();
+        scheduler
+            .add_system_allow_duplicates(TestSystem::new("third", log.clone()))
+            .unwrap();
+
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        let execution_order = log.lock().unwrap();
+        let expected = vec![
+            "first_before",
+            "second_before",
+            "third_before", // All before_run
+            "first_run",
+            "second_run",
+            "third_run", // All run
+            "first_after",
+            "second_after",
+            "third_after", // All after_run
+        ];
+
+        assert_eq!(*execution_order, expected);
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Flash;
+    impl Component for Flash {}
+
+    /// Logs every phase along with how many ephemeral `Flash` components and
+    /// entities it sees.
+    struct PhaseProbe {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PhaseProbe {
+        fn record(&self, phase: &str, world: &World) {
+            let flashes = world
+                .entities()
+                .filter(|&&entity| world.has_ephemeral_component::<Flash>(entity))
+                .count();
+            self.log.lock().unwrap().push(format!(
+                "{}_{phase} entities={} flashes={flashes}",
+                self.name,
+                world.entities().count()
+            ));
+        }
+    }
+
+    impl System for PhaseProbe {
+        fn before_run(&self, world: &WorldView) {
+            self.record("before", world);
+        }
+
+        fn run(&self, world: &mut World) {
+            let flashed = world.spawn_entity();
+            world.add_ephemeral_component(flashed, Flash).unwrap();
+            let doomed = world.spawn_entity();
+            world.delete_entity(doomed);
+            self.record("run", world);
+        }
+
+        fn after_run(&self, world: &WorldView) {
+            self.record("after", world);
+        }
+
+        fn post_cleanup(&self, world: &WorldView) {
+            self.record("post_cleanup", world);
+        }
+    }
+
+    #[test]
+    fn test_four_phase_execution() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        scheduler
+            .add_system(PhaseProbe {
+                name: "first",
+                log: log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(TestSystem::new("second", log.clone()))
+            .unwrap();
+
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        // post_cleanup runs last, once the ephemeral component is gone
+        let execution_order = log.lock().unwrap();
+        assert_eq!(
+            *execution_order,
+            vec![
+                "first_before entities=0 flashes=0",
+                "second_before",
+                "first_run entities=1 flashes=1",
+                "second_run",
+                "first_after entities=1 flashes=1",
+                "second_after",
+                "first_post_cleanup entities=1 flashes=0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_post_cleanup_only_runs_for_active_systems_after_cleanup() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(PhaseProbe {
+                name: "probe",
+                log: log.clone(),
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+        let mut world = World::new();
+
+        // Without cleanup there is no post_cleanup phase
+        scheduler.run_tick_no_cleanup(&mut world);
+        assert!(!log
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| line.contains("post_cleanup")));
+
+        log.lock().unwrap().clear();
+        scheduler.set_system_enabled::<PhaseProbe>(false).unwrap();
+        scheduler.run_tick(&mut world);
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    /// Panics in `run`, after logging that it started.
+    struct PanickingSystem {
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl System for PanickingSystem {
+        fn run(&self, _world: &mut World) {
+            self.log.lock().unwrap().push("panicking_run".to_string());
+            panic!("system failed");
+        }
+
+        fn after_run(&self, _world: &WorldView) {
+            self.log.lock().unwrap().push("panicking_after".to_string());
+        }
+    }
+
+    #[test]
+    fn test_run_tick_catch_unwind_reports_panic_and_runs_other_systems() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(PanickingSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system(PhaseProbe {
+                name: "probe",
+                log: log.clone(),
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+        let mut world = World::new();
+
+        let panics = scheduler.run_tick_catch_unwind(&mut world);
+
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].0, TypeId::of::<PanickingSystem>());
+        assert_eq!(panics[0].1.downcast_ref::<&str>(), Some(&"system failed"));
+
+        // The panicking system skips its remaining phases, the probe runs all
+        // of them and the tick still cleans up
+        let phases: Vec<String> = log
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|line| line.split(' ').next().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                "probe_before",
+                "panicking_run",
+                "probe_run",
+                "probe_after",
+                "probe_post_cleanup"
+            ]
+        );
+        assert_eq!(world.entities().count(), 1);
+        assert_eq!(scheduler.tick.get(), 1);
+
+        // The next tick runs the system again
+        log.lock().unwrap().clear();
+        assert_eq!(scheduler.run_tick_catch_unwind(&mut world).len(), 1);
+        assert!(log.lock().unwrap().contains(&"panicking_run".to_string()));
+    }
+
+    struct IncrementSystem;
+    impl System for IncrementSystem {
+        fn run(&self, world: &mut World) {
+            for entity in world.entities().cloned().collect::<Vec<_>>() {
+                if let Some(counter) = world.get_component::<Counter>(entity) {
+                    let new_counter = Counter {
+                        count: counter.count + 1,
+                    };
+                    world.replace_component(entity, new_counter);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_system_modifies_world() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Counter { count: 0 }).unwrap();
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(IncrementSystem).unwrap();
+
+        scheduler.build().unwrap();
+
+        // Run one tick
+        scheduler.run_tick(&mut world);
+
+        // Counter should be incremented
+        let counter = world.get_component::<Counter>(entity).unwrap();
+        assert_eq!(counter.count, 1);
+
+        // Run another tick
+        scheduler.run_tick(&mut world);
+        let counter = world.get_component::<Counter>(entity).unwrap();
+        assert_eq!(counter.count, 2);
+    }
+
+    #[test]
+    fn test_empty_scheduler() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let mut world = World::new();
+
+        scheduler.build().unwrap(); // Even empty scheduler needs to be built
+
+        // Should not panic with no systems
+        scheduler.run_tick(&mut world);
+        assert_eq!(scheduler.system_count(), 0);
+    }
+
+    #[test]
+    fn test_multiple_ticks() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        scheduler
+            .add_system(TestSystem::new("system", log.clone()))
+            .unwrap();
+
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+
+        // Run multiple ticks
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
+
+        let execution_order = log.lock().unwrap();
+        let expected = vec![
+            "system_before",
+            "system_run",
+            "system_after", // Tick 1
+            "system_before",
+            "system_run",
+            "system_after", // Tick 2
+        ];
+
+        assert_eq!(*execution_order, expected);
+    }
+
+    #[test]
+    fn test_automatic_entity_cleanup() {
+        let mut world = World::new();
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        // Create a system that deletes entities
+        struct EntityDeleterSystem;
+        impl System for EntityDeleterSystem {
+            fn run(&self, world: &mut World) {
+                // Delete all entities with counter value >= 5
+                let to_delete: Vec<_> = world
+                    .entities()
+                    .cloned()
+                    .filter(|&entity| {
+                        if let Some(counter) = world.get_component::<Counter>(entity) {
+                            counter.count >= 5
+                        } else {
+                            false
+                        }
+                    })
+                    .collect();
+
+                for entity in to_delete {
+                    world.delete_entity(entity);
+                }
+            }
+        }
+
+        scheduler.add_system(EntityDeleterSystem).unwrap();
+
+        scheduler.build().unwrap();
+
+        // Create some entities with counters
+        let entity1 = world.spawn_entity();
+        let entity2 = world.spawn_entity();
+        let entity3 = world.spawn_entity();
+
+        world.add_component(entity1, Counter { count: 3 }).unwrap();
+        world.add_component(entity2, Counter { count: 5 }).unwrap(); // Will be deleted
+        world.add_component(entity3, Counter { count: 7 }).unwrap(); // Will be deleted
+
+        // Before tick: 3 entities, all have components
+        assert_eq!(world.entities().count(), 3);
+        assert!(world.has_component::<Counter>(entity1));
+        assert!(world.has_component::<Counter>(entity2));
+        assert!(world.has_component::<Counter>(entity3));
+
+        // Run one tick - should delete entities 2 and 3, and automatically clean them up
+        scheduler.run_tick(&mut world);
+
+        // After tick: 1 entity remains, deleted entities are cleaned up
+        assert_eq!(world.entities().count(), 1);
+        assert!(world.has_component::<Counter>(entity1)); // Should remain
+        assert!(!world.has_component::<Counter>(entity2)); // Should be cleaned up
+        assert!(!world.has_component::<Counter>(entity3)); // Should be cleaned up
+
+        // Verify component data was cleaned from storage
+        assert_eq!(world.get_component::<Counter>(entity2), None);
+        assert_eq!(world.get_component::<Counter>(entity3), None);
+
+        // Entities should not be in soft_deleted list anymore (internal verification)
+        // This is verified implicitly by the fact that manual cleanup isn't needed
+    }
+
+    #[test]
+    fn test_dependency_aware_scheduling() {
+        use std::sync::{Arc, LazyLock, Mutex};
+
+        static SYSTEM_B_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<SystemA>()]);
+
+        let execution_log = Arc::new(Mutex::new(Vec::new()));
+
+        struct SystemA {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl System for SystemA {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("A".to_string());
+            }
+        }
+
+        struct SystemB {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl System for SystemB {
+            fn dependencies(&self) -> &[TypeId] {
+                &SYSTEM_B_DEPS
+            }
+
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("B".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        // Add systems in reverse dependency order (B before A)
+        scheduler
+            .add_system(SystemB {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(SystemA {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        let log = execution_log.lock().unwrap();
+        assert_eq!(*log, vec!["A", "B"]); // A should run first despite being added second
+    }
+
+    #[test]
+    fn test_circular_dependency_detection() {
+        use std::sync::LazyLock;
+
+        static SYSTEM_A_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<SystemB>()]);
+        static SYSTEM_B_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<SystemA>()]);
+
+        struct SystemA;
+        impl System for SystemA {
+            fn dependencies(&self) -> &[TypeId] {
+                &SYSTEM_A_DEPS
+            }
+            fn run(&self, _world: &mut World) {}
+        }
+
+        struct SystemB;
+        impl System for SystemB {
+            fn dependencies(&self) -> &[TypeId] {
+                &SYSTEM_B_DEPS
+            }
+            fn run(&self, _world: &mut World) {}
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        // Both systems should be added successfully
+        scheduler.add_system(SystemA).unwrap();
+        scheduler.add_system(SystemB).unwrap();
+
+        // This should fail due to circular dependency
+        let result = scheduler.build();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Circular dependency"));
+    }
+
+    #[test]
+    fn test_circular_dependency_error_names_the_cycle() {
+        use std::sync::LazyLock;
+
+        static INPUT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<CombatSystem>()]);
+        static COMBAT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<InputSystem>(), TypeId::of::<ClockSystem>()]);
+        static RENDER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<CombatSystem>()]);
+
+        struct ClockSystem;
+        impl System for ClockSystem {}
+
+        struct InputSystem;
+        impl System for InputSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &INPUT_DEPS
+            }
+        }
+
+        struct CombatSystem;
+        impl System for CombatSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &COMBAT_DEPS
+            }
+            fn name(&self) -> &str {
+                "combat"
+            }
+        }
+
+        // Depends on the cycle without being part of it
+        struct RenderSystem;
+        impl System for RenderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &RENDER_DEPS
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(RenderSystem).unwrap();
+        scheduler.add_system(ClockSystem).unwrap();
+        scheduler.add_system(InputSystem).unwrap();
+        scheduler.add_system(CombatSystem).unwrap();
+
+        let error = scheduler.build().unwrap_err();
+        assert_eq!(
+            error,
+            "Circular dependency detected in system dependencies: combat -> InputSystem -> combat"
+        );
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum GameStage {
+        Input,
+        Update,
+        Render,
+    }
+
+    #[test]
+    fn test_stages_run_in_order_regardless_of_add_order() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        scheduler
+            .add_system_to_stage(
+                GameStage::Render,
+                Distinct::<0>(TestSystem::new("render", log.clone())),
+            )
+            .unwrap();
+        scheduler
+            .add_system_to_stage(
+                GameStage::Update,
+                Distinct::<1>(TestSystem::new("movement", log.clone())),
+            )
+            .unwrap();
+        scheduler
+            .add_system_to_stage(
+                GameStage::Input,
+                Distinct::<2>(TestSystem::new("time", log.clone())),
+            )
+            .unwrap();
+        scheduler
+            .add_system_to_stage(
+                GameStage::Update,
+                Distinct::<3>(TestSystem::new("combat", log.clone())),
+            )
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        let runs: Vec<String> = log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.ends_with("_run"))
+            .cloned()
+            .collect();
+        assert_eq!(
+            runs,
+            vec!["time_run", "movement_run", "combat_run", "render_run"]
+        );
+    }
+
+    #[test]
+    fn test_dependencies_still_apply_within_a_stage() {
+        use std::sync::LazyLock;
+
+        static MOVEMENT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<CombatSystem>()]);
+
+        struct CombatSystem;
+        impl System for CombatSystem {}
+
+        struct MovementSystem;
+        impl System for MovementSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &MOVEMENT_DEPS
+            }
+        }
+
+        struct RenderSystem;
+        impl System for RenderSystem {}
+
+        struct TimeSystem;
+        impl System for TimeSystem {}
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_to_stage(GameStage::Render, RenderSystem)
+            .unwrap();
+        scheduler
+            .add_system_to_stage(GameStage::Update, MovementSystem)
+            .unwrap();
+        scheduler
+            .add_system_to_stage(GameStage::Update, CombatSystem)
+            .unwrap();
+        scheduler
+            .add_system_to_stage(GameStage::Input, TimeSystem)
+            .unwrap();
+        scheduler.build().unwrap();
+
+        assert_eq!(
+            scheduler.system_names(),
+            vec![
+                "TimeSystem",
+                "CombatSystem",
+                "MovementSystem",
+                "RenderSystem"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stage_errors() {
+        use std::sync::LazyLock;
+
+        static INPUT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<RenderSystem>()]);
+
+        struct InputSystem;
+        impl System for InputSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &INPUT_DEPS
+            }
+        }
+
+        struct RenderSystem;
+        impl System for RenderSystem {}
+
+        // Depending on a later stage can't be satisfied
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_to_stage(GameStage::Input, InputSystem)
+            .unwrap();
+        scheduler
+            .add_system_to_stage(GameStage::Render, RenderSystem)
+            .unwrap();
+        assert_eq!(
+            scheduler.build().unwrap_err(),
+            "System InputSystem in stage Input depends on RenderSystem in later stage Render"
+        );
+
+        // Stages of different types can't be compared
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_to_stage(GameStage::Render, RenderSystem)
+            .unwrap();
+        scheduler.add_system_to_stage(1u8, InputSystem).unwrap();
+        assert!(scheduler
+            .build()
+            .unwrap_err()
+            .contains("InputSystem uses a stage of a different type"));
+    }
+
+    #[test]
+    fn test_complex_dependency_chain() {
+        use std::sync::{Arc, LazyLock, Mutex};
+
+        // Complex dependency chain: Input -> Physics -> Collision -> Render
+        static PHYSICS_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<InputSystem>()]);
+        static COLLISION_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<PhysicsSystem>()]);
+        static RENDER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<CollisionSystem>()]);
+
+        let execution_log = Arc::new(Mutex::new(Vec::new()));
+
+        struct InputSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for InputSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("Input".to_string());
+            }
+        }
+
+        struct PhysicsSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for PhysicsSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &PHYSICS_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("Physics".to_string());
+            }
+        }
+
+        struct CollisionSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for CollisionSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &COLLISION_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("Collision".to_string());
+            }
+        }
+
+        struct RenderSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for RenderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &RENDER_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("Render".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        // Add systems in REVERSE dependency order to test sorting
+        scheduler
+            .add_system(RenderSystem {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(CollisionSystem {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(PhysicsSystem {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+        scheduler
+            .add_system(InputSystem {
+                log: execution_log.clone(),
+            })
+            .unwrap();
+
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+
+        let log = execution_log.lock().unwrap();
+        assert_eq!(*log, vec!["Input", "Physics", "Collision", "Render"]);
+
+        assert_eq!(
+            scheduler.execution_order_types(),
+            vec![
+                TypeId::of::<InputSystem>(),
+                TypeId::of::<PhysicsSystem>(),
+                TypeId::of::<CollisionSystem>(),
+                TypeId::of::<RenderSystem>(),
+            ]
+        );
+        assert_eq!(scheduler.dependencies_of::<InputSystem>(), Some(&[][..]));
+        assert_eq!(
+            scheduler.dependencies_of::<RenderSystem>(),
+            Some(&[TypeId::of::<CollisionSystem>()][..])
+        );
+
+        scheduler.unbuild();
+        assert!(scheduler.execution_order_types().is_empty());
+        assert!(scheduler.dependencies_of::<PhysicsSystem>().is_some());
+    }
+
+    #[test]
+    fn test_build_prevents_adding_systems() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        // Add a system
+        scheduler
+            .add_system(TestSystem::new("system1", log.clone()))
+            .unwrap();
+
+        // Build the scheduler
+        scheduler.build().unwrap();
+
+        // Try to add another system - should fail
+        let result = scheduler.add_system(TestSystem::new("system2", log.clone()));
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot add systems after scheduler has been built"));
+
+        // System count should remain 1
+        assert_eq!(scheduler.system_count(), 1);
+    }
+
+    #[test]
+    fn test_run_tick_requires_build() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::new();
+
+        scheduler
+            .add_system(TestSystem::new("system", log.clone()))
+            .unwrap();
+
+        // Should panic if trying to run without building
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            scheduler.run_tick(&mut world);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_is_idempotent() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        scheduler
+            .add_system(TestSystem::new("system", log.clone()))
+            .unwrap();
+
+        // Build multiple times should work
+        scheduler.build().unwrap();
+        scheduler.build().unwrap();
+        scheduler.build().unwrap();
+
+        // Should still work normally
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+    }
+
+    #[test]
+    fn test_ephemeral_components_cleanup_after_tick() {
+        let mut world = World::new();
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct TempEffect {
+            damage: u32,
+        }
+        impl Component for TempEffect {}
+
+        // System that creates ephemeral components
+        struct CreateEffectSystem;
+        impl System for CreateEffectSystem {
+            fn run(&self, world: &mut World) {
+                for entity in world.entities().cloned().collect::<Vec<_>>() {
+                    world
+                        .add_ephemeral_component(entity, TempEffect { damage: 50 })
+                        .unwrap();
+                }
+            }
+        }
+
+        // Add system and build scheduler
+        scheduler.add_system(CreateEffectSystem).unwrap();
+        scheduler.build().unwrap();
+
+        // Create an entity
+        let entity = world.spawn_entity();
+
+        // First tick - ephemeral components should be created
+        scheduler.run_tick(&mut world);
+
+        // At the end of the tick, ephemeral components should be cleaned up
+        assert!(!world.has_ephemeral_component::<TempEffect>(entity));
+
+        // Second tick - verify cleanup is automatic each tick
+        scheduler.run_tick(&mut world);
+        assert!(!world.has_ephemeral_component::<TempEffect>(entity));
+    }
+
+    #[test]
+    fn test_ephemeral_components_available_during_tick() {
+        let mut world = World::new();
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct DamageEvent {
+            amount: u32,
+        }
+        impl Component for DamageEvent {}
+
+        // System that creates ephemeral components
+        struct DamageSystem;
+        impl System for DamageSystem {
+            fn run(&self, world: &mut World) {
+                for entity in world.entities().cloned().collect::<Vec<_>>() {
+                    world
+                        .add_ephemeral_component(entity, DamageEvent { amount: 25 })
+                        .unwrap();
+                }
+            }
+        }
+
+        // System that reads ephemeral components
+        struct HealthSystem;
+        impl System for HealthSystem {
+            fn run(&self, world: &mut World) {
+                for entity in world.entities().cloned().collect::<Vec<_>>() {
+                    if world.has_ephemeral_component::<DamageEvent>(entity) {
+                        // In practice, this would process the damage
+                        // The test verifies the ephemeral component exists during the tick
+                    }
+                }
+            }
+        }
+
+        // Add systems in order (DamageSystem creates, HealthSystem reads)
+        scheduler.add_system(DamageSystem).unwrap();
+        scheduler.add_system(HealthSystem).unwrap();
+        scheduler.build().unwrap();
+
+        // Create an entity
+        let entity = world.spawn_entity();
+
+        // Run tick - ephemeral components should be available during the tick
+        scheduler.run_tick(&mut world);
+
+        // After tick, ephemeral components should be cleaned up
+        assert!(!world.has_ephemeral_component::<DamageEvent>(entity));
+    }
+
+    #[test]
+    fn test_ephemeral_components_persist_across_system_phases() {
+        let mut world = World::new();
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct SystemEvent {
+            phase: String,
+        }
+        impl Component for SystemEvent {}
+
+        // System that creates ephemeral components in run phase
+        struct SetupSystem;
+        impl System for SetupSystem {
+            fn run(&self, world: &mut World) {
+                for entity in world.entities().cloned().collect::<Vec<_>>() {
+                    world
+                        .add_ephemeral_component(
+                            entity,
+                            SystemEvent {
+                                phase: "run".to_string(),
+                            },
+                        )
+                        .unwrap();
+                }
+            }
+
+            fn after_run(&self, world: &WorldView) {
+                // Verify ephemeral component is still available in after_run
+                for entity in world.entities().cloned().collect::<Vec<_>>() {
+                    if let Some(event) = world.get_ephemeral_component::<SystemEvent>(entity) {
+                        assert_eq!(event.phase, "run");
+                    }
+                }
+            }
+        }
+
+        scheduler.add_system(SetupSystem).unwrap();
+        scheduler.build().unwrap();
+
+        let entity = world.spawn_entity();
+
+        // Run tick - ephemeral components should persist across phases within the same tick
+        scheduler.run_tick(&mut world);
+
+        // After tick, ephemeral components should be cleaned up
+        assert!(!world.has_ephemeral_component::<SystemEvent>(entity));
+    }
+
+    #[test]
+    fn test_run_tick_no_cleanup_keeps_ephemerals_until_full_tick() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Impulse {
+            substep: u32,
+        }
+        impl Component for Impulse {}
+
+        struct PhysicsSystem {
+            substeps: Arc<Mutex<Vec<Option<u32>>>>,
+        }
+        impl System for PhysicsSystem {
+            fn run(&self, world: &mut World) {
+                let entity = *world.entities().next().unwrap();
+                let previous = world
+                    .get_ephemeral_component::<Impulse>(entity)
+                    .map(|impulse| impulse.substep);
+                self.substeps.lock().unwrap().push(previous);
+                world
+                    .add_ephemeral_component(
+                        entity,
+                        Impulse {
+                            substep: previous.map_or(0, |substep| substep + 1),
+                        },
+                    )
+                    .unwrap();
+            }
+        }
+
+        let substeps = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(PhysicsSystem {
+                substeps: substeps.clone(),
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        let doomed = world.spawn_entity();
+        world.delete_entity(doomed);
+
+        scheduler.run_tick_no_cleanup(&mut world);
+        scheduler.run_tick_no_cleanup(&mut world);
+        assert_eq!(
+            world.get_ephemeral_component::<Impulse>(entity),
+            Some(&Impulse { substep: 1 })
+        );
+        assert!(world.was_deleted_this_tick(doomed));
+
+        scheduler.run_tick(&mut world);
+        assert!(!world.has_ephemeral_component::<Impulse>(entity));
+        assert!(!world.was_deleted_this_tick(doomed));
+        assert_eq!(*substeps.lock().unwrap(), vec![None, Some(0), Some(1)]);
+
+        // The next tick starts from scratch
+        scheduler.run_tick_no_cleanup(&mut world);
+        assert_eq!(substeps.lock().unwrap().last(), Some(&None));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be built")]
+    fn test_run_tick_no_cleanup_requires_build() {
+        let scheduler = SequentialSystemScheduler::new();
+        scheduler.run_tick_no_cleanup(&mut World::new());
+    }
+
+    #[test]
+    fn test_disable_system_between_ticks() {
+        struct NoisySystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for NoisySystem {
+            fn before_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("noisy_before".to_string());
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("noisy_run".to_string());
+            }
+            fn after_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("noisy_after".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(TestSystem::new("steady", log.clone()))
+            .unwrap();
+        scheduler
+            .add_system(NoisySystem { log: log.clone() })
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.set_system_enabled::<NoisySystem>(false).unwrap();
+        scheduler.run_tick(&mut world);
+
+        // Disabled systems are skipped in all three phases
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["steady_before", "steady_run", "steady_after"]
+        );
+        assert_eq!(scheduler.is_system_enabled::<NoisySystem>(), Some(false));
+        assert_eq!(scheduler.system_count(), 2);
+
+        log.lock().unwrap().clear();
+        scheduler.set_system_enabled::<NoisySystem>(true).unwrap();
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "steady_before",
+                "noisy_before",
+                "steady_run",
+                "noisy_run",
+                "steady_after",
+                "noisy_after"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_system_enabled_unknown_system() {
+        struct UnregisteredSystem;
+        impl System for UnregisteredSystem {}
+
+        let mut scheduler = SequentialSystemScheduler::new();
+
+        assert!(scheduler
+            .set_system_enabled::<UnregisteredSystem>(false)
+            .is_err());
+        assert_eq!(scheduler.is_system_enabled::<UnregisteredSystem>(), None);
+    }
+
+    #[test]
+    fn test_remove_system_after_build() {
+        struct RemovableSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for RemovableSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("removable".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(RemovableSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system(TestSystem::new("kept", log.clone()))
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        assert!(log.lock().unwrap().contains(&"removable".to_string()));
+
+        scheduler.remove_system::<RemovableSystem>().unwrap();
+        assert_eq!(scheduler.system_count(), 1);
+
+        log.lock().unwrap().clear();
+        scheduler.run_tick(&mut world);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["kept_before", "kept_run", "kept_after"]
+        );
+
+        let result = scheduler.remove_system::<RemovableSystem>();
+        assert!(result.unwrap_err().contains("not registered"));
+    }
+
+    #[test]
+    fn test_remove_system_before_build() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(TestSystem::new("first", log.clone()))
+            .unwrap();
+        scheduler
+            .add_system_allow_duplicates(TestSystem::new("second", log.clone()))
+            .unwrap();
+
+        // All systems of the given type are removed
+        scheduler.remove_system::<TestSystem>().unwrap();
+        assert_eq!(scheduler.system_count(), 0);
+
+        scheduler.build().unwrap();
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_dependency_keeps_dependents_running() {
+        use std::sync::LazyLock;
+
+        static CONSUMER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<ProducerSystem>()]);
+
+        struct ProducerSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for ProducerSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("producer".to_string());
+            }
+        }
+
+        struct ConsumerSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for ConsumerSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &CONSUMER_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("consumer".to_string());
+            }
+        }
+
+        struct BystanderSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for BystanderSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("bystander".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(ConsumerSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system(ProducerSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system(BystanderSystem { log: log.clone() })
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["producer", "bystander", "consumer"]
+        );
+
+        // The missing dependency is ignored, same as a never-registered one
+        scheduler.remove_system::<ProducerSystem>().unwrap();
+        log.lock().unwrap().clear();
+        scheduler.run_tick(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["consumer", "bystander"]);
+    }
+
+    #[test]
+    fn test_on_build_runs_once() {
+        struct SeedSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for SeedSystem {
+            fn on_build(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("seed_on_build".to_string());
+            }
+            fn before_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("seed_before".to_string());
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler
+            .add_system(SeedSystem { log: log.clone() })
+            .unwrap();
+
+        scheduler.build().unwrap();
+        scheduler.build().unwrap();
+        scheduler.build().unwrap();
 
-        // Build dependency graph (index -> list of indices that depend on it)
-        let mut in_degree = vec![0; num_systems];
-        let mut graph: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
 
-        for (dependent_index, system_info) in self.systems.iter().enumerate() {
-            for &dep_type_id in &system_info.dependencies {
-                if let Some(&dependency_index) = type_to_index.get(&dep_type_id) {
-                    // dependency_index must run before dependent_index
-                    graph
-                        .entry(dependency_index)
-                        .or_default()
-                        .push(dependent_index);
-                    in_degree[dependent_index] += 1;
-                } else {
-                    // Dependency not found - this could be a warning in the future
-                    // For now, we'll silently ignore missing dependencies
-                }
-            }
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["seed_on_build", "seed_before", "seed_before"]
+        );
+    }
+
+    #[test]
+    fn test_on_build_runs_in_dependency_order_before_first_tick() {
+        use std::sync::LazyLock;
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct GameStats {
+            seeded_by: Vec<String>,
         }
+        impl Component for GameStats {}
 
-        // Topological sort using Kahn's algorithm
-        let mut queue: VecDeque<usize> = VecDeque::new();
-        let mut execution_order = Vec::new();
+        static STATS_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<TimeSystem>()]);
 
-        // Start with systems that have no dependencies
-        for (index, &degree) in in_degree.iter().enumerate() {
-            if degree == 0 {
-                queue.push_back(index);
+        struct TimeSystem;
+        impl System for TimeSystem {
+            fn on_build(&self, world: &mut World) {
+                world.insert_resource(GameStats {
+                    seeded_by: vec!["time".to_string()],
+                });
             }
         }
 
-        while let Some(current_index) = queue.pop_front() {
-            execution_order.push(current_index);
-
-            // Process all systems that depend on the current system
-            if let Some(dependents) = graph.get(&current_index) {
-                for &dependent_index in dependents {
-                    in_degree[dependent_index] -= 1;
-                    if in_degree[dependent_index] == 0 {
-                        queue.push_back(dependent_index);
-                    }
+        struct StatsSystem {
+            seen_in_before_run: Arc<Mutex<Option<GameStats>>>,
+        }
+        impl System for StatsSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &STATS_DEPS
+            }
+            fn on_build(&self, world: &mut World) {
+                // TimeSystem's hook already ran
+                world
+                    .update_resource::<GameStats, _>(|mut stats| {
+                        stats.seeded_by.push("stats".to_string());
+                        stats
+                    })
+                    .unwrap();
+            }
+            fn before_run(&self, world: &WorldView) {
+                let mut seen = self.seen_in_before_run.lock().unwrap();
+                if seen.is_none() {
+                    *seen = world.get_resource::<GameStats>().cloned();
                 }
             }
         }
 
-        // Check for circular dependencies
-        if execution_order.len() != num_systems {
-            return Err("Circular dependency detected in system dependencies".to_string());
-        }
+        let seen = Arc::new(Mutex::new(None));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(StatsSystem {
+                seen_in_before_run: seen.clone(),
+            })
+            .unwrap();
+        scheduler.add_system(TimeSystem).unwrap();
+        scheduler.build().unwrap();
 
-        self.execution_order = execution_order;
-        Ok(())
-    }
-}
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
 
-impl Default for SequentialSystemScheduler {
-    /// Creates a new empty system scheduler using the default constructor.
-    ///
-    /// This is equivalent to calling `SequentialSystemScheduler::new()`.
-    ///
-    /// # Example
-    /// ```
-    /// use bemudjo_ecs::SequentialSystemScheduler;
-    ///
-    /// let scheduler1 = SequentialSystemScheduler::new();
-    /// let scheduler2 = SequentialSystemScheduler::default();
-    ///
-    /// assert_eq!(scheduler1.system_count(), scheduler2.system_count());
-    /// ```
-    fn default() -> Self {
-        Self::new()
+        assert_eq!(
+            *seen.lock().unwrap(),
+            Some(GameStats {
+                seeded_by: vec!["time".to_string(), "stats".to_string()]
+            })
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{Component, World};
-    use std::sync::{Arc, Mutex};
+    #[test]
+    fn test_build_strict_fails_on_missing_dependency() {
+        use std::sync::LazyLock;
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Counter {
-        count: u32,
-    }
-    impl Component for Counter {}
+        struct AbsentSystem;
+        impl System for AbsentSystem {}
 
-    struct TestSystem {
-        name: String,
-        execution_log: Arc<Mutex<Vec<String>>>,
-    }
+        static RENDER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<AbsentSystem>()]);
 
-    impl TestSystem {
-        fn new(name: &str, log: Arc<Mutex<Vec<String>>>) -> Self {
-            Self {
-                name: name.to_string(),
-                execution_log: log,
+        struct RenderSystem;
+        impl System for RenderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &RENDER_DEPS
             }
         }
-    }
 
-    impl System for TestSystem {
-        fn before_run(&self, _world: &World) {
-            self.execution_log
-                .lock()
-                .unwrap()
-                .push(format!("{}_before", self.name));
-        }
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(RenderSystem).unwrap();
 
-        fn run(&self, _world: &mut World) {
-            self.execution_log
-                .lock()
-                .unwrap()
-                .push(format!("{}_run", self.name));
-        }
+        assert_eq!(
+            scheduler.unresolved_dependencies(),
+            vec![TypeId::of::<AbsentSystem>()]
+        );
 
-        fn after_run(&self, _world: &World) {
-            self.execution_log
-                .lock()
-                .unwrap()
-                .push(format!("{}_after", self.name));
-        }
-    }
+        let error = scheduler.build_strict().unwrap_err();
+        assert!(error.contains("Unresolved system dependencies"));
+        assert!(error.contains("RenderSystem"));
 
-    #[test]
-    fn test_system_scheduler_new() {
-        let scheduler = SequentialSystemScheduler::new();
-        assert_eq!(scheduler.system_count(), 0);
-    }
+        // Still not built, so the missing system can be added
+        scheduler.add_system(AbsentSystem).unwrap();
+        assert!(scheduler.unresolved_dependencies().is_empty());
+        scheduler.build_strict().unwrap();
 
-    #[test]
-    fn test_system_scheduler_default() {
-        let scheduler = SequentialSystemScheduler::default();
-        assert_eq!(scheduler.system_count(), 0);
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
     }
 
     #[test]
-    fn test_add_system() {
-        let mut scheduler = SequentialSystemScheduler::new();
-        let log = Arc::new(Mutex::new(Vec::new()));
+    fn test_build_strict_succeeds_when_dependencies_present() {
+        use std::sync::LazyLock;
 
-        scheduler
-            .add_system(TestSystem::new("system1", log.clone()))
-            .unwrap();
-        assert_eq!(scheduler.system_count(), 1);
+        static SECOND_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<FirstSystem>()]);
 
-        scheduler
-            .add_system(TestSystem::new("system2", log.clone()))
-            .unwrap();
-        assert_eq!(scheduler.system_count(), 2);
+        struct FirstSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for FirstSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("first".to_string());
+            }
+        }
 
-        // Should be able to build successfully
-        scheduler.build().unwrap();
-    }
+        struct SecondSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for SecondSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &SECOND_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("second".to_string());
+            }
+        }
 
-    #[test]
-    fn test_execution_order() {
-        let mut scheduler = SequentialSystemScheduler::new();
         let log = Arc::new(Mutex::new(Vec::new()));
-
-        // Add systems in specific order
-        scheduler
-            .add_system(TestSystem::new("first", log.clone()))
-            .unwrap();
+        let mut scheduler = SequentialSystemScheduler::new();
         scheduler
-            .add_system(TestSystem::new("second", log.clone()))
+            .add_system(SecondSystem { log: log.clone() })
             .unwrap();
         scheduler
-            .add_system(TestSystem::new("third", log.clone()))
+            .add_system(FirstSystem { log: log.clone() })
             .unwrap();
 
-        scheduler.build().unwrap();
-
+        scheduler.build_strict().unwrap();
         let mut world = World::new();
         scheduler.run_tick(&mut world);
 
-        let execution_order = log.lock().unwrap();
-        let expected = vec![
-            "first_before",
-            "second_before",
-            "third_before", // All before_run
-            "first_run",
-            "second_run",
-            "third_run", // All run
-            "first_after",
-            "second_after",
-            "third_after", // All after_run
-        ];
-
-        assert_eq!(*execution_order, expected);
+        assert_eq!(*log.lock().unwrap(), vec!["first", "second"]);
     }
 
     #[test]
-    fn test_three_phase_execution() {
+    fn test_unresolved_dependencies_deduplicated() {
+        use std::sync::LazyLock;
+
+        struct AbsentSystem;
+        impl System for AbsentSystem {}
+
+        static DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| vec![TypeId::of::<AbsentSystem>()]);
+
+        struct SystemA;
+        impl System for SystemA {
+            fn dependencies(&self) -> &[TypeId] {
+                &DEPS
+            }
+        }
+
+        struct SystemB;
+        impl System for SystemB {
+            fn dependencies(&self) -> &[TypeId] {
+                &DEPS
+            }
+        }
+
         let mut scheduler = SequentialSystemScheduler::new();
-        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler.add_system(SystemA).unwrap();
+        scheduler.add_system(SystemB).unwrap();
 
-        scheduler
-            .add_system(TestSystem::new("system", log.clone()))
-            .unwrap();
+        assert_eq!(
+            scheduler.unresolved_dependencies(),
+            vec![TypeId::of::<AbsentSystem>()]
+        );
 
+        // The lenient build still accepts it
         scheduler.build().unwrap();
+    }
 
-        let mut world = World::new();
-        scheduler.run_tick(&mut world);
+    #[test]
+    fn test_duplicate_resource_initializer_fails_build() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct GameConfig;
+        impl crate::Component for GameConfig {}
+
+        struct LoginSystem;
+        impl System for LoginSystem {
+            fn resources(&self) -> ResourceAccess {
+                ResourceAccess::new().initialize::<GameConfig>()
+            }
+        }
 
-        let execution_order = log.lock().unwrap();
+        struct CombatSystem;
+        impl System for CombatSystem {
+            fn resources(&self) -> ResourceAccess {
+                ResourceAccess::new()
+                    .read::<GameConfig>()
+                    .initialize::<GameConfig>()
+            }
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(LoginSystem).unwrap();
+        scheduler.add_system(CombatSystem).unwrap();
+
+        let error = scheduler.build().unwrap_err();
         assert_eq!(
-            *execution_order,
-            vec!["system_before", "system_run", "system_after"]
+            error,
+            "Resource GameConfig is initialized by both LoginSystem and CombatSystem"
         );
+        assert!(!scheduler.is_built());
+        assert!(scheduler.build_with_report().is_err());
     }
 
-    struct IncrementSystem;
-    impl System for IncrementSystem {
-        fn run(&self, world: &mut World) {
-            for entity in world.entities().cloned().collect::<Vec<_>>() {
-                if let Some(counter) = world.get_component::<Counter>(entity) {
-                    let new_counter = Counter {
-                        count: counter.count + 1,
-                    };
-                    world.replace_component(entity, new_counter);
-                }
+    #[test]
+    fn test_build_report_lists_unordered_resource_writes() {
+        use std::sync::LazyLock;
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Weather;
+        impl crate::Component for Weather {}
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct GameConfig;
+        impl crate::Component for GameConfig {}
+
+        static STORM_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<ConfigSystem>()]);
+
+        struct ConfigSystem;
+        impl System for ConfigSystem {
+            fn resources(&self) -> ResourceAccess {
+                ResourceAccess::new()
+                    .initialize::<GameConfig>()
+                    .write::<Weather>()
+            }
+        }
+
+        struct StormSystem;
+        impl System for StormSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &STORM_DEPS
+            }
+            fn resources(&self) -> ResourceAccess {
+                ResourceAccess::new()
+                    .read::<GameConfig>()
+                    .write::<Weather>()
+            }
+        }
+
+        struct SeasonSystem;
+        impl System for SeasonSystem {
+            fn resources(&self) -> ResourceAccess {
+                ResourceAccess::new()
+                    .write::<Weather>()
+                    .write::<GameConfig>()
             }
         }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(ConfigSystem).unwrap();
+        scheduler.add_system(StormSystem).unwrap();
+        scheduler.add_system(SeasonSystem).unwrap();
+
+        // A warning only, the build still succeeds
+        let report = scheduler.build_with_report().unwrap();
+        assert_eq!(
+            report.unordered_resource_writes,
+            vec![
+                (
+                    "ConfigSystem".to_string(),
+                    "SeasonSystem".to_string(),
+                    "Weather"
+                ),
+                (
+                    "ConfigSystem".to_string(),
+                    "SeasonSystem".to_string(),
+                    "GameConfig"
+                ),
+                (
+                    "SeasonSystem".to_string(),
+                    "StormSystem".to_string(),
+                    "Weather"
+                ),
+            ]
+        );
+
+        // Ordering SeasonSystem clears the warnings
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system_to_stage(0, ConfigSystem).unwrap();
+        scheduler.add_system_to_stage(0, StormSystem).unwrap();
+        scheduler.add_system_to_stage(1, SeasonSystem).unwrap();
+        let report = scheduler.build_with_report().unwrap();
+        assert!(report.unordered_resource_writes.is_empty());
     }
 
-    #[test]
-    fn test_system_modifies_world() {
-        let mut world = World::new();
-        let entity = world.spawn_entity();
-        world.add_component(entity, Counter { count: 0 }).unwrap();
+    #[test]
+    fn test_build_with_report_lists_missing_dependencies() {
+        use std::sync::LazyLock;
 
-        let mut scheduler = SequentialSystemScheduler::new();
-        scheduler.add_system(IncrementSystem).unwrap();
+        static RENDER_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+            vec![
+                TypeId::of::<InputSystem>(),
+                TypeId::of::<NeverAddedSystem>(),
+            ]
+        });
 
-        scheduler.build().unwrap();
+        struct NeverAddedSystem;
+        impl System for NeverAddedSystem {}
 
-        // Run one tick
-        scheduler.run_tick(&mut world);
+        struct InputSystem;
+        impl System for InputSystem {}
 
-        // Counter should be incremented
-        let counter = world.get_component::<Counter>(entity).unwrap();
-        assert_eq!(counter.count, 1);
+        struct RenderSystem;
+        impl System for RenderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &RENDER_DEPS
+            }
+        }
 
-        // Run another tick
-        scheduler.run_tick(&mut world);
-        let counter = world.get_component::<Counter>(entity).unwrap();
-        assert_eq!(counter.count, 2);
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(RenderSystem).unwrap();
+        scheduler.add_system(InputSystem).unwrap();
+
+        let report = scheduler.build_with_report().unwrap();
+        assert!(scheduler.is_built());
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.missing_dependencies,
+            vec![("RenderSystem".to_string(), TypeId::of::<NeverAddedSystem>())]
+        );
+        assert_eq!(report.execution_order, vec!["InputSystem", "RenderSystem"]);
+        assert_eq!(
+            scheduler.dependency_graph(),
+            vec![
+                ("RenderSystem".to_string(), vec!["InputSystem".to_string()]),
+                ("InputSystem".to_string(), vec![]),
+            ]
+        );
     }
 
     #[test]
-    fn test_empty_scheduler() {
-        let mut scheduler = SequentialSystemScheduler::new();
-        let mut world = World::new();
+    fn test_execution_order_matches_observed_runs() {
+        use std::sync::LazyLock;
 
-        scheduler.build().unwrap(); // Even empty scheduler needs to be built
+        static FIRST_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<SecondSystem>()]);
 
-        // Should not panic with no systems
-        scheduler.run_tick(&mut world);
-        assert_eq!(scheduler.system_count(), 0);
-    }
+        struct FirstSystem(Arc<Mutex<Vec<String>>>);
+        impl System for FirstSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &FIRST_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.0.lock().unwrap().push(self.name().to_string());
+            }
+        }
 
-    #[test]
-    fn test_multiple_ticks() {
-        let mut scheduler = SequentialSystemScheduler::new();
-        let log = Arc::new(Mutex::new(Vec::new()));
+        struct SecondSystem(Arc<Mutex<Vec<String>>>);
+        impl System for SecondSystem {
+            fn run(&self, _world: &mut World) {
+                self.0.lock().unwrap().push(self.name().to_string());
+            }
+        }
 
-        scheduler
-            .add_system(TestSystem::new("system", log.clone()))
-            .unwrap();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(FirstSystem(log.clone())).unwrap();
+        scheduler.add_system(SecondSystem(log.clone())).unwrap();
+        assert!(scheduler.execution_order().is_empty());
 
-        scheduler.build().unwrap();
+        let report = scheduler.build_with_report().unwrap();
+        assert!(report.is_complete());
 
         let mut world = World::new();
-
-        // Run multiple ticks
         scheduler.run_tick(&mut world);
-        scheduler.run_tick(&mut world);
-
-        let execution_order = log.lock().unwrap();
-        let expected = vec![
-            "system_before",
-            "system_run",
-            "system_after", // Tick 1
-            "system_before",
-            "system_run",
-            "system_after", // Tick 2
-        ];
 
-        assert_eq!(*execution_order, expected);
+        assert_eq!(scheduler.execution_order(), *log.lock().unwrap());
+        assert_eq!(
+            scheduler.execution_order(),
+            vec!["SecondSystem", "FirstSystem"]
+        );
     }
 
     #[test]
-    fn test_automatic_entity_cleanup() {
-        let mut world = World::new();
-        let mut scheduler = SequentialSystemScheduler::new();
+    fn test_priority_orders_independent_systems() {
+        struct LowSystem;
+        impl System for LowSystem {
+            fn priority(&self) -> i32 {
+                -5
+            }
+        }
 
-        // Create a system that deletes entities
-        struct EntityDeleterSystem;
-        impl System for EntityDeleterSystem {
-            fn run(&self, world: &mut World) {
-                // Delete all entities with counter value >= 5
-                let to_delete: Vec<_> = world
-                    .entities()
-                    .cloned()
-                    .filter(|&entity| {
-                        if let Some(counter) = world.get_component::<Counter>(entity) {
-                            counter.count >= 5
-                        } else {
-                            false
-                        }
-                    })
-                    .collect();
+        struct DefaultSystem;
+        impl System for DefaultSystem {}
 
-                for entity in to_delete {
-                    world.delete_entity(entity);
-                }
+        struct HighSystem;
+        impl System for HighSystem {
+            fn priority(&self) -> i32 {
+                5
             }
         }
 
-        scheduler.add_system(EntityDeleterSystem).unwrap();
+        struct OtherDefaultSystem;
+        impl System for OtherDefaultSystem {}
 
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(LowSystem).unwrap();
+        scheduler.add_system(DefaultSystem).unwrap();
+        scheduler.add_system(HighSystem).unwrap();
+        scheduler.add_system(OtherDefaultSystem).unwrap();
         scheduler.build().unwrap();
 
-        // Create some entities with counters
-        let entity1 = world.spawn_entity();
-        let entity2 = world.spawn_entity();
-        let entity3 = world.spawn_entity();
+        // Equal priorities keep the order they were added in
+        assert_eq!(
+            scheduler.execution_order(),
+            vec![
+                "HighSystem",
+                "DefaultSystem",
+                "OtherDefaultSystem",
+                "LowSystem"
+            ]
+        );
+    }
 
-        world.add_component(entity1, Counter { count: 3 }).unwrap();
-        world.add_component(entity2, Counter { count: 5 }).unwrap(); // Will be deleted
-        world.add_component(entity3, Counter { count: 7 }).unwrap(); // Will be deleted
+    #[test]
+    fn test_priority_never_overrides_dependency() {
+        use std::sync::LazyLock;
 
-        // Before tick: 3 entities, all have components
-        assert_eq!(world.entities().count(), 3);
-        assert!(world.has_component::<Counter>(entity1));
-        assert!(world.has_component::<Counter>(entity2));
-        assert!(world.has_component::<Counter>(entity3));
+        static URGENT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<SlowSystem>()]);
 
-        // Run one tick - should delete entities 2 and 3, and automatically clean them up
-        scheduler.run_tick(&mut world);
+        struct SlowSystem;
+        impl System for SlowSystem {
+            fn priority(&self) -> i32 {
+                -10
+            }
+        }
 
-        // After tick: 1 entity remains, deleted entities are cleaned up
-        assert_eq!(world.entities().count(), 1);
-        assert!(world.has_component::<Counter>(entity1)); // Should remain
-        assert!(!world.has_component::<Counter>(entity2)); // Should be cleaned up
-        assert!(!world.has_component::<Counter>(entity3)); // Should be cleaned up
+        struct UrgentSystem;
+        impl System for UrgentSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &URGENT_DEPS
+            }
+            fn priority(&self) -> i32 {
+                10
+            }
+        }
 
-        // Verify component data was cleaned from storage
-        assert_eq!(world.get_component::<Counter>(entity2), None);
-        assert_eq!(world.get_component::<Counter>(entity3), None);
+        struct MiddleSystem;
+        impl System for MiddleSystem {}
 
-        // Entities should not be in soft_deleted list anymore (internal verification)
-        // This is verified implicitly by the fact that manual cleanup isn't needed
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(UrgentSystem).unwrap();
+        scheduler.add_system(MiddleSystem).unwrap();
+        scheduler.add_system(SlowSystem).unwrap();
+        scheduler.build().unwrap();
+
+        // Urgent waits for its dependency, however low that priority is
+        assert_eq!(
+            scheduler.execution_order(),
+            vec!["MiddleSystem", "SlowSystem", "UrgentSystem"]
+        );
     }
 
     #[test]
-    fn test_dependency_aware_scheduling() {
-        use std::sync::{Arc, LazyLock, Mutex};
-
-        static SYSTEM_B_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<SystemA>()]);
+    fn test_unbuild_add_and_rebuild_resolves_new_order() {
+        use std::sync::LazyLock;
 
-        let execution_log = Arc::new(Mutex::new(Vec::new()));
+        static LOOT_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<CombatSystem>()]);
 
-        struct SystemA {
+        struct CombatSystem {
             log: Arc<Mutex<Vec<String>>>,
         }
-
-        impl System for SystemA {
+        impl System for CombatSystem {
+            fn on_build(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("combat_build".to_string());
+            }
             fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("A".to_string());
+                self.log.lock().unwrap().push("combat".to_string());
             }
         }
 
-        struct SystemB {
+        struct LootSystem {
             log: Arc<Mutex<Vec<String>>>,
         }
-
-        impl System for SystemB {
+        impl System for LootSystem {
             fn dependencies(&self) -> &[TypeId] {
-                &SYSTEM_B_DEPS
+                &LOOT_DEPS
+            }
+            fn on_build(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("loot_build".to_string());
             }
-
             fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("B".to_string());
+                self.log.lock().unwrap().push("loot".to_string());
             }
         }
 
+        let log = Arc::new(Mutex::new(Vec::new()));
         let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(CombatSystem { log: log.clone() })
+            .unwrap();
+        scheduler.build().unwrap();
 
-        // Add systems in reverse dependency order (B before A)
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["combat_build", "combat"]);
+
+        // Added before its dependency in the systems list, still runs after it
+        scheduler.unbuild();
+        assert!(!scheduler.is_built());
         scheduler
-            .add_system(SystemB {
-                log: execution_log.clone(),
-            })
+            .add_system(LootSystem { log: log.clone() })
             .unwrap();
+        scheduler.remove_system::<CombatSystem>().unwrap();
         scheduler
-            .add_system(SystemA {
-                log: execution_log.clone(),
-            })
+            .add_system(CombatSystem { log: log.clone() })
             .unwrap();
+        scheduler.rebuild().unwrap();
+        assert!(scheduler.is_built());
 
-        scheduler.build().unwrap();
-
-        let mut world = World::new();
+        log.lock().unwrap().clear();
         scheduler.run_tick(&mut world);
+        // The re-added CombatSystem is a new system and gets its hook again
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["combat_build", "loot_build", "combat", "loot"]
+        );
 
-        let log = execution_log.lock().unwrap();
-        assert_eq!(*log, vec!["A", "B"]); // A should run first despite being added second
+        // Rebuilding an unchanged scheduler doesn't repeat on_build hooks
+        scheduler.rebuild().unwrap();
+        log.lock().unwrap().clear();
+        scheduler.run_tick(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["combat", "loot"]);
     }
 
     #[test]
-    fn test_circular_dependency_detection() {
+    fn test_strict_mode_guards_removing_dependencies() {
         use std::sync::LazyLock;
 
-        static SYSTEM_A_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<SystemB>()]);
-        static SYSTEM_B_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<SystemA>()]);
+        static CONSUMER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<ProducerSystem>()]);
 
-        struct SystemA;
-        impl System for SystemA {
+        struct ProducerSystem;
+        impl System for ProducerSystem {}
+
+        struct ConsumerSystem;
+        impl System for ConsumerSystem {
             fn dependencies(&self) -> &[TypeId] {
-                &SYSTEM_A_DEPS
+                &CONSUMER_DEPS
             }
-            fn run(&self, _world: &mut World) {}
         }
 
-        struct SystemB;
-        impl System for SystemB {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(ProducerSystem).unwrap();
+        scheduler.add_system(ConsumerSystem).unwrap();
+        scheduler.build_strict().unwrap();
+
+        let error = scheduler.remove_system::<ProducerSystem>().unwrap_err();
+        assert!(error.contains("ConsumerSystem"), "{error}");
+        assert_eq!(scheduler.system_count(), 2);
+
+        // Removing the dependent first is fine
+        scheduler.remove_system::<ConsumerSystem>().unwrap();
+        scheduler.remove_system::<ProducerSystem>().unwrap();
+
+        // A lenient rebuild turns strict mode off again
+        scheduler.unbuild();
+        scheduler.add_system(ProducerSystem).unwrap();
+        scheduler.add_system(ConsumerSystem).unwrap();
+        scheduler.build().unwrap();
+        scheduler.remove_system::<ProducerSystem>().unwrap();
+        assert_eq!(scheduler.system_count(), 1);
+    }
+
+    #[test]
+    fn test_strict_rebuild_reports_missing_dependency() {
+        use std::sync::LazyLock;
+
+        static CONSUMER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<ProducerSystem>()]);
+
+        struct ProducerSystem;
+        impl System for ProducerSystem {}
+
+        struct ConsumerSystem;
+        impl System for ConsumerSystem {
             fn dependencies(&self) -> &[TypeId] {
-                &SYSTEM_B_DEPS
+                &CONSUMER_DEPS
             }
-            fn run(&self, _world: &mut World) {}
         }
 
         let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(ProducerSystem).unwrap();
+        scheduler.build_strict().unwrap();
 
-        // Both systems should be added successfully
-        scheduler.add_system(SystemA).unwrap();
-        scheduler.add_system(SystemB).unwrap();
+        scheduler.unbuild();
+        scheduler.remove_system::<ProducerSystem>().unwrap();
+        scheduler.add_system(ConsumerSystem).unwrap();
 
-        // This should fail due to circular dependency
-        let result = scheduler.build();
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Circular dependency"));
+        assert!(scheduler.rebuild().is_err());
+        assert!(!scheduler.is_built());
     }
 
     #[test]
-    fn test_complex_dependency_chain() {
-        use std::sync::{Arc, LazyLock, Mutex};
-
-        // Complex dependency chain: Input -> Physics -> Collision -> Render
-        static PHYSICS_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<InputSystem>()]);
-        static COLLISION_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<PhysicsSystem>()]);
-        static RENDER_DEPS: LazyLock<Vec<TypeId>> =
-            LazyLock::new(|| vec![TypeId::of::<CollisionSystem>()]);
+    fn test_gated_system_runs_only_when_condition_holds() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Paused;
+        impl crate::Component for Paused {}
 
-        let execution_log = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_if(TestSystem::new("spawn", log.clone()), |world: &World| {
+                world.get_resource::<Paused>().is_none()
+            })
+            .unwrap();
+        scheduler.build().unwrap();
 
-        struct InputSystem {
-            log: Arc<Mutex<Vec<String>>>,
-        }
-        impl System for InputSystem {
-            fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("Input".to_string());
+        let mut world = World::new();
+        let mut runs = Vec::new();
+        for paused in [false, true, true, false] {
+            if paused {
+                world.insert_resource(Paused);
+            } else {
+                world.remove_resource::<Paused>();
             }
+            log.lock().unwrap().clear();
+            scheduler.run_tick(&mut world);
+            runs.push(log.lock().unwrap().clone());
         }
 
-        struct PhysicsSystem {
+        let all_phases = vec!["spawn_before", "spawn_run", "spawn_after"];
+        assert_eq!(runs[0], all_phases);
+        assert!(runs[1].is_empty());
+        assert!(runs[2].is_empty());
+        assert_eq!(runs[3], all_phases);
+    }
+
+    #[test]
+    fn test_condition_skips_all_phases_even_if_run_changes_state() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Gate;
+        impl crate::Component for Gate {}
+
+        struct CloseGateSystem {
             log: Arc<Mutex<Vec<String>>>,
         }
-        impl System for PhysicsSystem {
-            fn dependencies(&self) -> &[TypeId] {
-                &PHYSICS_DEPS
+        impl System for CloseGateSystem {
+            fn run(&self, world: &mut World) {
+                world.remove_resource::<Gate>();
+                self.log.lock().unwrap().push("close_run".to_string());
             }
-            fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("Physics".to_string());
+            fn after_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("close_after".to_string());
             }
         }
 
-        struct CollisionSystem {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_if(CloseGateSystem { log: log.clone() }, |world: &World| {
+                world.get_resource::<Gate>().is_some()
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(Gate);
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
+
+        // Evaluated once per tick: after_run still runs on the tick run() closed the gate
+        assert_eq!(*log.lock().unwrap(), vec!["close_run", "close_after"]);
+    }
+
+    #[test]
+    fn test_dependents_of_gated_system_still_run_in_order() {
+        use std::sync::LazyLock;
+
+        static CONSUMER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<ProducerSystem>()]);
+
+        struct ProducerSystem {
             log: Arc<Mutex<Vec<String>>>,
         }
-        impl System for CollisionSystem {
-            fn dependencies(&self) -> &[TypeId] {
-                &COLLISION_DEPS
-            }
+        impl System for ProducerSystem {
             fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("Collision".to_string());
+                self.log.lock().unwrap().push("producer".to_string());
             }
         }
 
-        struct RenderSystem {
+        struct ConsumerSystem {
             log: Arc<Mutex<Vec<String>>>,
         }
-        impl System for RenderSystem {
+        impl System for ConsumerSystem {
             fn dependencies(&self) -> &[TypeId] {
-                &RENDER_DEPS
+                &CONSUMER_DEPS
             }
             fn run(&self, _world: &mut World) {
-                self.log.lock().unwrap().push("Render".to_string());
+                self.log.lock().unwrap().push("consumer".to_string());
             }
         }
 
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let enabled = Arc::new(Mutex::new(true));
         let mut scheduler = SequentialSystemScheduler::new();
-
-        // Add systems in REVERSE dependency order to test sorting
-        scheduler
-            .add_system(RenderSystem {
-                log: execution_log.clone(),
-            })
-            .unwrap();
-        scheduler
-            .add_system(CollisionSystem {
-                log: execution_log.clone(),
-            })
-            .unwrap();
         scheduler
-            .add_system(PhysicsSystem {
-                log: execution_log.clone(),
-            })
+            .add_system(ConsumerSystem { log: log.clone() })
             .unwrap();
+        let condition_flag = enabled.clone();
         scheduler
-            .add_system(InputSystem {
-                log: execution_log.clone(),
+            .add_system_if(ProducerSystem { log: log.clone() }, move |_: &World| {
+                *condition_flag.lock().unwrap()
             })
             .unwrap();
-
-        scheduler.build().unwrap();
+        scheduler.build_strict().unwrap();
 
         let mut world = World::new();
         scheduler.run_tick(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["producer", "consumer"]);
 
-        let log = execution_log.lock().unwrap();
-        assert_eq!(*log, vec!["Input", "Physics", "Collision", "Render"]);
+        log.lock().unwrap().clear();
+        *enabled.lock().unwrap() = false;
+        scheduler.run_tick(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["consumer"]);
     }
 
     #[test]
-    fn test_build_prevents_adding_systems() {
-        let mut scheduler = SequentialSystemScheduler::new();
-        let log = Arc::new(Mutex::new(Vec::new()));
+    fn test_should_run_gates_system_once_per_tick() {
+        use std::sync::LazyLock;
 
-        // Add a system
-        scheduler
-            .add_system(TestSystem::new("system1", log.clone()))
-            .unwrap();
+        static RENDER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<AiSystem>()]);
 
-        // Build the scheduler
-        scheduler.build().unwrap();
+        #[derive(Debug, Clone, PartialEq)]
+        struct GamePaused;
+        impl crate::Component for GamePaused {}
 
-        // Try to add another system - should fail
-        let result = scheduler.add_system(TestSystem::new("system2", log.clone()));
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .contains("Cannot add systems after scheduler has been built"));
+        struct AiSystem {
+            log: Arc<Mutex<Vec<String>>>,
+            checks: Arc<Mutex<usize>>,
+        }
+        impl System for AiSystem {
+            fn should_run(&self, world: &WorldView) -> bool {
+                *self.checks.lock().unwrap() += 1;
+                !world.has_resource::<GamePaused>()
+            }
+            fn before_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("ai_before".to_string());
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("ai_run".to_string());
+            }
+            fn after_run(&self, _world: &WorldView) {
+                self.log.lock().unwrap().push("ai_after".to_string());
+            }
+        }
 
-        // System count should remain 1
-        assert_eq!(scheduler.system_count(), 1);
-    }
+        struct RenderSystem {
+            log: Arc<Mutex<Vec<String>>>,
+        }
+        impl System for RenderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &RENDER_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.lock().unwrap().push("render_run".to_string());
+            }
+        }
 
-    #[test]
-    fn test_run_tick_requires_build() {
-        let mut scheduler = SequentialSystemScheduler::new();
         let log = Arc::new(Mutex::new(Vec::new()));
-        let mut world = World::new();
-
+        let checks = Arc::new(Mutex::new(0));
+        let mut scheduler = SequentialSystemScheduler::new();
         scheduler
-            .add_system(TestSystem::new("system", log.clone()))
+            .add_system(RenderSystem { log: log.clone() })
             .unwrap();
-
-        // Should panic if trying to run without building
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            scheduler.run_tick(&mut world);
-        }));
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_build_is_idempotent() {
-        let mut scheduler = SequentialSystemScheduler::new();
-        let log = Arc::new(Mutex::new(Vec::new()));
-
         scheduler
-            .add_system(TestSystem::new("system", log.clone()))
+            .add_system(AiSystem {
+                log: log.clone(),
+                checks: checks.clone(),
+            })
             .unwrap();
-
-        // Build multiple times should work
-        scheduler.build().unwrap();
-        scheduler.build().unwrap();
         scheduler.build().unwrap();
 
-        // Should still work normally
-        let mut world = World::new();
-        scheduler.run_tick(&mut world);
-    }
-
-    #[test]
-    fn test_ephemeral_components_cleanup_after_tick() {
         let mut world = World::new();
-        let mut scheduler = SequentialSystemScheduler::new();
-
-        #[derive(Clone, Debug, PartialEq)]
-        struct TempEffect {
-            damage: u32,
-        }
-        impl Component for TempEffect {}
-
-        // System that creates ephemeral components
-        struct CreateEffectSystem;
-        impl System for CreateEffectSystem {
-            fn run(&self, world: &mut World) {
-                for entity in world.entities().cloned().collect::<Vec<_>>() {
-                    world
-                        .add_ephemeral_component(entity, TempEffect { damage: 50 })
-                        .unwrap();
-                }
+        let mut runs = Vec::new();
+        for paused in [false, true, false] {
+            if paused {
+                world.insert_resource(GamePaused);
+            } else {
+                world.remove_resource::<GamePaused>();
             }
+            log.lock().unwrap().clear();
+            scheduler.run_tick(&mut world);
+            runs.push(log.lock().unwrap().clone());
         }
 
-        // Add system and build scheduler
-        scheduler.add_system(CreateEffectSystem).unwrap();
-        scheduler.build().unwrap();
-
-        // Create an entity
-        let entity = world.spawn_entity();
+        // Skipping AiSystem doesn't change where RenderSystem runs
+        let running = vec!["ai_before", "ai_run", "render_run", "ai_after"];
+        assert_eq!(runs[0], running);
+        assert_eq!(runs[1], vec!["render_run"]);
+        assert_eq!(runs[2], running);
+        assert_eq!(*checks.lock().unwrap(), 3);
+    }
 
-        // First tick - ephemeral components should be created
-        scheduler.run_tick(&mut world);
+    #[test]
+    fn test_rate_limited_systems_run_on_exact_ticks() {
+        use std::rc::Rc;
+        use std::sync::LazyLock;
 
-        // At the end of the tick, ephemeral components should be cleaned up
-        assert!(!world.has_ephemeral_component::<TempEffect>(entity));
+        static WANDER_DEPS: LazyLock<Vec<TypeId>> =
+            LazyLock::new(|| vec![TypeId::of::<WeatherSystem>()]);
 
-        // Second tick - verify cleanup is automatic each tick
-        scheduler.run_tick(&mut world);
-        assert!(!world.has_ephemeral_component::<TempEffect>(entity));
-    }
+        struct TickLog {
+            tick: Cell<u64>,
+            runs: Mutex<Vec<(&'static str, u64)>>,
+        }
 
-    #[test]
-    fn test_ephemeral_components_available_during_tick() {
-        let mut world = World::new();
-        let mut scheduler = SequentialSystemScheduler::new();
+        impl TickLog {
+            fn record(&self, name: &'static str) {
+                self.runs.lock().unwrap().push((name, self.tick.get()));
+            }
 
-        #[derive(Clone, Debug, PartialEq)]
-        struct DamageEvent {
-            amount: u32,
+            fn ticks_of(&self, name: &str) -> Vec<u64> {
+                self.runs
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|(system, _)| *system == name)
+                    .map(|&(_, tick)| tick)
+                    .collect()
+            }
         }
-        impl Component for DamageEvent {}
 
-        // System that creates ephemeral components
-        struct DamageSystem;
-        impl System for DamageSystem {
-            fn run(&self, world: &mut World) {
-                for entity in world.entities().cloned().collect::<Vec<_>>() {
-                    world
-                        .add_ephemeral_component(entity, DamageEvent { amount: 25 })
-                        .unwrap();
-                }
+        struct AutosaveSystem {
+            log: Rc<TickLog>,
+        }
+        impl System for AutosaveSystem {
+            fn before_run(&self, _world: &WorldView) {
+                self.log.record("autosave_before");
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.record("autosave");
+            }
+            fn after_run(&self, _world: &WorldView) {
+                self.log.record("autosave_after");
             }
         }
 
-        // System that reads ephemeral components
-        struct HealthSystem;
-        impl System for HealthSystem {
-            fn run(&self, world: &mut World) {
-                for entity in world.entities().cloned().collect::<Vec<_>>() {
-                    if world.has_ephemeral_component::<DamageEvent>(entity) {
-                        // In practice, this would process the damage
-                        // The test verifies the ephemeral component exists during the tick
-                    }
-                }
+        struct WeatherSystem {
+            log: Rc<TickLog>,
+        }
+        impl System for WeatherSystem {
+            fn run(&self, _world: &mut World) {
+                self.log.record("weather");
             }
         }
 
-        // Add systems in order (DamageSystem creates, HealthSystem reads)
-        scheduler.add_system(DamageSystem).unwrap();
-        scheduler.add_system(HealthSystem).unwrap();
-        scheduler.build().unwrap();
+        struct WanderSystem {
+            log: Rc<TickLog>,
+        }
+        impl System for WanderSystem {
+            fn dependencies(&self) -> &[TypeId] {
+                &WANDER_DEPS
+            }
+            fn run(&self, _world: &mut World) {
+                self.log.record("wander");
+            }
+        }
 
-        // Create an entity
-        let entity = world.spawn_entity();
+        let log = Rc::new(TickLog {
+            tick: Cell::new(0),
+            runs: Mutex::new(Vec::new()),
+        });
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(WanderSystem { log: log.clone() })
+            .unwrap();
+        scheduler
+            .add_system_with_rate(AutosaveSystem { log: log.clone() }, 10, 0)
+            .unwrap();
+        scheduler
+            .add_system_with_rate(WeatherSystem { log: log.clone() }, 10, 5)
+            .unwrap();
+        scheduler.build_strict().unwrap();
 
-        // Run tick - ephemeral components should be available during the tick
-        scheduler.run_tick(&mut world);
+        let mut world = World::new();
+        for tick in 0..30 {
+            log.tick.set(tick);
+            scheduler.run_tick(&mut world);
+        }
 
-        // After tick, ephemeral components should be cleaned up
-        assert!(!world.has_ephemeral_component::<DamageEvent>(entity));
+        assert_eq!(log.ticks_of("autosave_before"), vec![0, 10, 20]);
+        assert_eq!(log.ticks_of("autosave"), vec![0, 10, 20]);
+        assert_eq!(log.ticks_of("autosave_after"), vec![0, 10, 20]);
+        assert_eq!(log.ticks_of("weather"), vec![5, 15, 25]);
+        // The dependent runs every tick, even when its dependency is skipped
+        assert_eq!(log.ticks_of("wander"), (0..30).collect::<Vec<_>>());
+
+        // ... and still after it on the ticks both run
+        let runs = log.runs.lock().unwrap();
+        let weather = runs.iter().position(|&run| run == ("weather", 5));
+        let wander = runs.iter().position(|&run| run == ("wander", 5));
+        assert!(weather < wander);
     }
 
     #[test]
-    fn test_ephemeral_components_persist_across_system_phases() {
-        let mut world = World::new();
+    fn test_rate_tick_count_survives_rebuild() {
+        let log = Arc::new(Mutex::new(Vec::new()));
         let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system_with_rate(TestSystem::new("npc", log.clone()), 3, 1)
+            .unwrap();
+        scheduler.build().unwrap();
 
-        #[derive(Clone, Debug, PartialEq)]
-        struct SystemEvent {
-            phase: String,
-        }
-        impl Component for SystemEvent {}
-
-        // System that creates ephemeral components in run phase
-        struct SetupSystem;
-        impl System for SetupSystem {
-            fn run(&self, world: &mut World) {
-                for entity in world.entities().cloned().collect::<Vec<_>>() {
-                    world
-                        .add_ephemeral_component(
-                            entity,
-                            SystemEvent {
-                                phase: "run".to_string(),
-                            },
-                        )
-                        .unwrap();
-                }
+        let mut world = World::new();
+        let mut ran_on = Vec::new();
+        for tick in 0..9 {
+            if tick == 4 {
+                scheduler.unbuild();
+                scheduler.build().unwrap();
             }
-
-            fn after_run(&self, world: &World) {
-                // Verify ephemeral component is still available in after_run
-                for entity in world.entities().cloned().collect::<Vec<_>>() {
-                    if let Some(event) = world.get_ephemeral_component::<SystemEvent>(entity) {
-                        assert_eq!(event.phase, "run");
-                    }
-                }
+            log.lock().unwrap().clear();
+            scheduler.run_tick(&mut world);
+            if !log.lock().unwrap().is_empty() {
+                ran_on.push(tick);
             }
         }
 
-        scheduler.add_system(SetupSystem).unwrap();
-        scheduler.build().unwrap();
-
-        let entity = world.spawn_entity();
+        assert_eq!(ran_on, vec![1, 4, 7]);
+    }
 
-        // Run tick - ephemeral components should persist across phases within the same tick
-        scheduler.run_tick(&mut world);
+    #[test]
+    fn test_invalid_rates_are_rejected() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
 
-        // After tick, ephemeral components should be cleaned up
-        assert!(!world.has_ephemeral_component::<SystemEvent>(entity));
+        assert!(scheduler
+            .add_system_with_rate(TestSystem::new("zero", log.clone()), 0, 0)
+            .is_err());
+        assert!(scheduler
+            .add_system_with_rate(TestSystem::new("offset", log.clone()), 10, 10)
+            .is_err());
+        assert_eq!(scheduler.system_count(), 0);
     }
 }
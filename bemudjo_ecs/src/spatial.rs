@@ -0,0 +1,440 @@
+use crate::{Component, Entity, Query, System, World};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// A component that places an entity on the 2D plane.
+///
+/// Implement this for your position component to make it indexable by
+/// [`SpatialIndexSystem`].
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::Component;
+/// use bemudjo_ecs::spatial::SpatialComponent;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// impl SpatialComponent for Position {
+///     fn x(&self) -> f32 { self.x }
+///     fn y(&self) -> f32 { self.y }
+/// }
+/// ```
+pub trait SpatialComponent: Component {
+    /// Horizontal coordinate of the entity.
+    fn x(&self) -> f32;
+
+    /// Vertical coordinate of the entity.
+    fn y(&self) -> f32;
+}
+
+/// A uniform grid that buckets entities by the position stored in component `T`.
+///
+/// The grid is stored as a world resource (one per indexed component type) and is
+/// kept up to date by [`SpatialIndexSystem`]. Region queries only visit the grid
+/// cells overlapping the region instead of scanning every entity, which turns the
+/// usual O(n²) "who is near whom" loop into roughly O(n · k) where k is the number
+/// of entities per visited cell.
+///
+/// Choose a cell size close to the typical query radius: much smaller cells make
+/// queries visit many empty cells, much larger cells put too many entities in each.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, World};
+/// use bemudjo_ecs::spatial::{SpatialComponent, SpatialGrid};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+/// impl SpatialComponent for Position {
+///     fn x(&self) -> f32 { self.x }
+///     fn y(&self) -> f32 { self.y }
+/// }
+///
+/// let mut world = World::new();
+/// let near = world.spawn_entity();
+/// let far = world.spawn_entity();
+///
+/// let mut grid = SpatialGrid::<Position>::new(10.0);
+/// grid.insert(near, 1.0, 1.0);
+/// grid.insert(far, 100.0, 100.0);
+///
+/// assert_eq!(grid.entities_in_radius((0.0, 0.0), 5.0), vec![near]);
+/// assert_eq!(grid.entities_in_rect((50.0, 50.0), (150.0, 150.0)), vec![far]);
+/// ```
+#[derive(Debug)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<GridEntry>>,
+    len: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// An indexed entity together with the position it was indexed at.
+#[derive(Debug, Clone, Copy)]
+struct GridEntry {
+    entity: Entity,
+    x: f32,
+    y: f32,
+}
+
+impl<T> Clone for SpatialGrid<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cell_size: self.cell_size,
+            cells: self.cells.clone(),
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> Component for SpatialGrid<T> {}
+
+impl<T> SpatialGrid<T> {
+    /// Creates an empty grid with square cells of the given size.
+    ///
+    /// # Panics
+    /// Panics if `cell_size` is not a positive, finite number.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "SpatialGrid cell size must be positive and finite, got {cell_size}"
+        );
+
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the side length of the grid cells.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no entity is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds an entity at the given position.
+    ///
+    /// The grid does not deduplicate: inserting the same entity twice makes it
+    /// show up twice in query results. [`SpatialIndexSystem`] clears the grid
+    /// before every rebuild.
+    pub fn insert(&mut self, entity: Entity, x: f32, y: f32) {
+        let cell = self.cell_of(x, y);
+        self.cells
+            .entry(cell)
+            .or_default()
+            .push(GridEntry { entity, x, y });
+        self.len += 1;
+    }
+
+    /// Removes every entity while keeping the allocated cells for reuse.
+    pub fn clear(&mut self) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.len = 0;
+    }
+
+    /// Returns every entity whose position lies within `radius` of `center`.
+    ///
+    /// Entities exactly on the circle's edge are included. The order of the
+    /// returned entities is unspecified.
+    pub fn entities_in_radius(&self, center: (f32, f32), radius: f32) -> Vec<Entity> {
+        let (cx, cy) = center;
+        let radius_squared = radius * radius;
+
+        self.entries_in_cells((cx - radius, cy - radius), (cx + radius, cy + radius))
+            .filter(|entry| {
+                let dx = entry.x - cx;
+                let dy = entry.y - cy;
+                dx * dx + dy * dy <= radius_squared
+            })
+            .map(|entry| entry.entity)
+            .collect()
+    }
+
+    /// Returns every entity whose position lies inside the axis-aligned rectangle
+    /// spanned by `min` and `max`.
+    ///
+    /// Both bounds are inclusive. The order of the returned entities is unspecified.
+    pub fn entities_in_rect(&self, min: (f32, f32), max: (f32, f32)) -> Vec<Entity> {
+        self.entries_in_cells(min, max)
+            .filter(|entry| {
+                entry.x >= min.0 && entry.x <= max.0 && entry.y >= min.1 && entry.y <= max.1
+            })
+            .map(|entry| entry.entity)
+            .collect()
+    }
+
+    /// Iterates over the entries of every cell overlapping the given bounds.
+    fn entries_in_cells<'a>(
+        &'a self,
+        min: (f32, f32),
+        max: (f32, f32),
+    ) -> Box<dyn Iterator<Item = &'a GridEntry> + 'a> {
+        let (min_cx, min_cy) = self.cell_of(min.0, min.1);
+        let (max_cx, max_cy) = self.cell_of(max.0, max.1);
+        if min_cx > max_cx || min_cy > max_cy {
+            return Box::new(std::iter::empty());
+        }
+
+        let span = (i64::from(max_cx) - i64::from(min_cx) + 1)
+            * (i64::from(max_cy) - i64::from(min_cy) + 1);
+
+        if span > self.cells.len() as i64 {
+            // Huge regions: scanning the occupied cells is cheaper than probing every cell
+            Box::new(
+                self.cells
+                    .iter()
+                    .filter(move |&(&(cx, cy), _)| {
+                        (min_cx..=max_cx).contains(&cx) && (min_cy..=max_cy).contains(&cy)
+                    })
+                    .flat_map(|(_, bucket)| bucket),
+            )
+        } else {
+            Box::new(
+                (min_cx..=max_cx)
+                    .flat_map(move |cx| (min_cy..=max_cy).map(move |cy| (cx, cy)))
+                    .filter_map(|cell| self.cells.get(&cell))
+                    .flatten(),
+            )
+        }
+    }
+
+    fn cell_of(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+}
+
+/// Maintains a [`SpatialGrid<T>`] resource from every entity with component `T`.
+///
+/// The grid resource is inserted when the scheduler first runs the system and is
+/// rebuilt at the start of each `run`. Systems that read the grid should declare
+/// a dependency on `SpatialIndexSystem<T>` so they see this tick's positions.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, SequentialSystemScheduler, World};
+/// use bemudjo_ecs::spatial::{SpatialComponent, SpatialGrid, SpatialIndexSystem};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+/// impl SpatialComponent for Position {
+///     fn x(&self) -> f32 { self.x }
+///     fn y(&self) -> f32 { self.y }
+/// }
+///
+/// let mut world = World::new();
+/// let goblin = world.spawn_entity();
+/// world.add_component(goblin, Position { x: 3.0, y: 4.0 }).unwrap();
+///
+/// let mut scheduler = SequentialSystemScheduler::new();
+/// scheduler.add_system(SpatialIndexSystem::<Position>::new(10.0)).unwrap();
+/// scheduler.build().unwrap();
+/// scheduler.run_tick(&mut world);
+///
+/// let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+/// assert_eq!(grid.entities_in_radius((0.0, 0.0), 5.0), vec![goblin]);
+/// ```
+pub struct SpatialIndexSystem<T> {
+    cell_size: f32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SpatialIndexSystem<T> {
+    /// Creates a system maintaining a grid with the given cell size.
+    ///
+    /// # Panics
+    /// Panics if `cell_size` is not a positive, finite number.
+    pub fn new(cell_size: f32) -> Self {
+        // Validate eagerly instead of on the first tick
+        SpatialGrid::<T>::new(cell_size);
+
+        Self {
+            cell_size,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SpatialComponent> System for SpatialIndexSystem<T> {
+    fn on_build(&self, world: &mut World) {
+        world.get_resource_or_insert_with(|| SpatialGrid::<T>::new(self.cell_size));
+    }
+
+    fn run(&self, world: &mut World) {
+        // Reuse the previous grid's cell allocations
+        let mut grid = world
+            .remove_resource::<SpatialGrid<T>>()
+            .unwrap_or_else(|| SpatialGrid::new(self.cell_size));
+        grid.clear();
+
+        for (entity, position) in Query::<T>::new().iter(world) {
+            grid.insert(entity, position.x(), position.y());
+        }
+
+        world.insert_resource(grid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SequentialSystemScheduler;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+    impl SpatialComponent for Position {
+        fn x(&self) -> f32 {
+            self.x
+        }
+        fn y(&self) -> f32 {
+            self.y
+        }
+    }
+
+    fn set(entities: Vec<Entity>) -> HashSet<Entity> {
+        entities.into_iter().collect()
+    }
+
+    #[test]
+    fn test_radius_includes_edge_and_excludes_outside() {
+        let mut world = World::new();
+        let center = world.spawn_entity();
+        let on_edge = world.spawn_entity();
+        let outside = world.spawn_entity();
+
+        let mut grid = SpatialGrid::<Position>::new(2.0);
+        grid.insert(center, 0.0, 0.0);
+        grid.insert(on_edge, 3.0, 4.0);
+        grid.insert(outside, 3.0, 4.1);
+
+        assert_eq!(
+            set(grid.entities_in_radius((0.0, 0.0), 5.0)),
+            HashSet::from([center, on_edge])
+        );
+        assert_eq!(grid.len(), 3);
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        let mut world = World::new();
+        let west = world.spawn_entity();
+        let east = world.spawn_entity();
+
+        let mut grid = SpatialGrid::<Position>::new(1.0);
+        grid.insert(west, -0.5, -0.5);
+        grid.insert(east, 0.5, 0.5);
+
+        assert_eq!(grid.entities_in_radius((-1.0, -1.0), 1.0), vec![west]);
+        assert_eq!(
+            grid.entities_in_rect((-1.0, -1.0), (-0.1, -0.1)),
+            vec![west]
+        );
+        assert_eq!(
+            set(grid.entities_in_rect((-1.0, -1.0), (1.0, 1.0))),
+            HashSet::from([west, east])
+        );
+    }
+
+    #[test]
+    fn test_rect_bounds_inclusive() {
+        let mut world = World::new();
+        let corner = world.spawn_entity();
+
+        let mut grid = SpatialGrid::<Position>::new(4.0);
+        grid.insert(corner, 8.0, 8.0);
+
+        assert_eq!(grid.entities_in_rect((0.0, 0.0), (8.0, 8.0)), vec![corner]);
+        assert!(grid.entities_in_rect((0.0, 0.0), (7.9, 8.0)).is_empty());
+    }
+
+    #[test]
+    fn test_huge_region_query() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        let mut grid = SpatialGrid::<Position>::new(0.5);
+        grid.insert(entity, 1.0e6, -1.0e6);
+
+        assert_eq!(grid.entities_in_radius((0.0, 0.0), 1.0e7), vec![entity]);
+        assert!(grid.entities_in_rect((1.0, 1.0), (0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_clear_empties_grid() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        let mut grid = SpatialGrid::<Position>::new(1.0);
+        grid.insert(entity, 0.0, 0.0);
+        grid.clear();
+
+        assert!(grid.is_empty());
+        assert!(grid.entities_in_radius((0.0, 0.0), 10.0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "cell size must be positive")]
+    fn test_invalid_cell_size_panics() {
+        SpatialIndexSystem::<Position>::new(0.0);
+    }
+
+    #[test]
+    fn test_index_system_tracks_moves_and_deletions() {
+        let mut world = World::new();
+        let mover = world.spawn_entity();
+        let doomed = world.spawn_entity();
+        world
+            .add_component(mover, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world
+            .add_component(doomed, Position { x: 1.0, y: 1.0 })
+            .unwrap();
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(SpatialIndexSystem::<Position>::new(5.0))
+            .unwrap();
+        scheduler.build().unwrap();
+        scheduler.run_tick(&mut world);
+
+        let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+        assert_eq!(
+            set(grid.entities_in_radius((0.0, 0.0), 2.0)),
+            HashSet::from([mover, doomed])
+        );
+
+        world.replace_component(mover, Position { x: 50.0, y: 50.0 });
+        world.delete_entity(doomed);
+        scheduler.run_tick(&mut world);
+
+        let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+        assert!(grid.entities_in_radius((0.0, 0.0), 2.0).is_empty());
+        assert_eq!(grid.entities_in_radius((50.0, 50.0), 1.0), vec![mover]);
+        assert_eq!(grid.len(), 1);
+    }
+}
@@ -1,14 +1,61 @@
-use crate::World;
-use std::any::TypeId;
+//! Systems and the resources they declare.
+//!
+//! # Systems with dependencies
+//!
+//! A system lists the systems it must run after in [`System::dependencies`].
+//! The recommended pattern keeps that list in a `LazyLock` static, which works
+//! with trait objects and stable Rust: const arrays are not an option because
+//! `TypeId::of::<T>()` requires the unstable `const_type_id` feature, and
+//! `LazyLock` initializes the list once, on first access.
+//!
+//! ```
+//! use bemudjo_ecs::{System, World};
+//! use std::any::TypeId;
+//! use std::sync::LazyLock;
+//!
+//! // Define dependencies using LazyLock
+//! static MOVEMENT_SYSTEM_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
+//!     vec![TypeId::of::<InputSystem>()]
+//! });
+//!
+//! // A system with no dependencies
+//! struct InputSystem;
+//! impl System for InputSystem {
+//!     fn run(&self, world: &mut World) {
+//!         // Process input
+//!     }
+//! }
+//!
+//! // A system that depends on InputSystem
+//! struct MovementSystem;
+//! impl System for MovementSystem {
+//!     fn dependencies(&self) -> &[TypeId] {
+//!         &MOVEMENT_SYSTEM_DEPS
+//!     }
+//!
+//!     fn run(&self, world: &mut World) {
+//!         // Movement logic - guaranteed to run after InputSystem
+//!     }
+//! }
+//! ```
+
+use crate::component::short_type_name;
+use crate::{Component, World, WorldView};
+use std::any::{type_name, TypeId};
 
 /// A trait defining the interface for systems that process entities.
 ///
-/// Systems operate in three distinct phases to enable safe parallel execution
+/// Systems operate in four distinct phases to enable safe parallel execution
 /// and clear separation of concerns:
 ///
 /// 1. `before_run` - Read-only preparation phase
 /// 2. `run` - Main logic with world mutations
 /// 3. `after_run` - Read-only cleanup/output phase
+/// 4. `post_cleanup` - Read-only observation of the cleaned-up world
+///
+/// The read-only phases receive a [`WorldView`]. This is a breaking change from
+/// earlier versions, which passed `&World`; since the view dereferences to
+/// `World`, upgrading an implementation only means changing the parameter type.
 ///
 /// # Example
 /// ```
@@ -28,10 +75,7 @@ use std::any::TypeId;
 ///     fn run(&self, world: &mut World) {
 ///         // Process all entities with both Position and Velocity
 ///         for entity in world.entities().cloned().collect::<Vec<_>>() {
-///             if let (Some(pos), Some(vel)) = (
-///                 world.get_component::<Position>(entity),
-///                 world.get_component::<Velocity>(entity)
-///             ) {
+///             if let Some((pos, vel)) = world.get_components2::<Position, Velocity>(entity) {
 ///                 let new_pos = Position {
 ///                     x: pos.x + vel.x,
 ///                     y: pos.y + vel.y,
@@ -93,6 +137,198 @@ pub trait System {
         &[] // Default: no dependencies
     }
 
+    /// Returns the system's priority among systems it has no ordering with.
+    ///
+    /// Whenever dependencies and stages leave the scheduler a choice between
+    /// several systems, the one with the highest priority runs first; systems
+    /// of equal priority run in the order they were added. Priority never
+    /// overrides a dependency or a stage. Defaults to `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{SequentialSystemScheduler, System};
+    ///
+    /// struct AmbientSoundSystem;
+    /// impl System for AmbientSoundSystem {}
+    ///
+    /// struct CombatSystem;
+    /// impl System for CombatSystem {
+    ///     fn priority(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(AmbientSoundSystem).unwrap();
+    /// scheduler.add_system(CombatSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     scheduler.execution_order(),
+    ///     vec!["CombatSystem", "AmbientSoundSystem"]
+    /// );
+    /// ```
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Returns the resources this system reads, writes and initializes.
+    ///
+    /// The declaration is only checked when the scheduler is built: building
+    /// fails if two systems initialize the same resource, and
+    /// [`build_with_report()`] lists systems writing the same resource without
+    /// a dependency or stage ordering them. Nothing stops a system from
+    /// touching resources it didn't declare.
+    ///
+    /// [`build_with_report()`]: crate::SequentialSystemScheduler::build_with_report
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, ResourceAccess, System, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct GameConfig { max_players: u32 }
+    /// impl Component for GameConfig {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct PlayerCount(u32);
+    /// impl Component for PlayerCount {}
+    ///
+    /// struct LoginSystem;
+    /// impl System for LoginSystem {
+    ///     fn resources(&self) -> ResourceAccess {
+    ///         ResourceAccess::new()
+    ///             .initialize::<GameConfig>()
+    ///             .write::<PlayerCount>()
+    ///     }
+    ///
+    ///     fn on_build(&self, world: &mut World) {
+    ///         world.insert_resource(GameConfig { max_players: 64 });
+    ///     }
+    /// }
+    /// ```
+    fn resources(&self) -> ResourceAccess {
+        ResourceAccess::default() // Default: no declared resources
+    }
+
+    /// Returns a human-readable name used in diagnostics and error messages.
+    ///
+    /// Defaults to the type name without its module path. The scheduler
+    /// captures the name once when the system is added, so it should not
+    /// change afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::System;
+    ///
+    /// struct CombatSystem;
+    /// impl System for CombatSystem {}
+    ///
+    /// struct ZoneScript {
+    ///     zone: &'static str,
+    /// }
+    /// impl System for ZoneScript {
+    ///     fn name(&self) -> &str {
+    ///         self.zone
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(CombatSystem.name(), "CombatSystem");
+    /// assert_eq!(ZoneScript { zone: "zone:town" }.name(), "zone:town");
+    /// ```
+    fn name(&self) -> &str {
+        crate::component::short_type_name(std::any::type_name::<Self>())
+    }
+
+    /// Called once when the scheduler starts executing this system.
+    ///
+    /// Use this for one-time initialization such as:
+    /// - Inserting resources the system relies on
+    /// - Seeding initial entities
+    ///
+    /// Hooks are invoked in dependency order, so a system can rely on the
+    /// `on_build` of its dependencies having already run. Since `build()` has no
+    /// access to the world, `SequentialSystemScheduler` runs the hooks at the start
+    /// of the first `run_tick()` after `build()`, before any `before_run`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct GameTime { ticks: u64 }
+    /// impl Component for GameTime {}
+    ///
+    /// struct TimeSystem;
+    /// impl System for TimeSystem {
+    ///     fn on_build(&self, world: &mut World) {
+    ///         world.insert_resource(GameTime { ticks: 0 });
+    ///     }
+    ///
+    ///     fn run(&self, world: &mut World) {
+    ///         // No existence check needed
+    ///         world.update_resource::<GameTime, _>(|t| GameTime { ticks: t.ticks + 1 }).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(TimeSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.get_resource::<GameTime>().unwrap().ticks, 1);
+    /// ```
+    fn on_build(&self, _world: &mut World) {}
+
+    /// Decides whether the system runs this tick.
+    ///
+    /// The scheduler asks once per tick, before any `before_run`; if this returns
+    /// `false`, the system's `before_run`, `run` and `after_run` are all skipped
+    /// for that tick. Skipping doesn't change the execution order, so systems
+    /// depending on this one still run after its place in the order.
+    ///
+    /// Use this instead of an early return in each phase when the condition
+    /// belongs to the system itself; for conditions chosen when wiring up the
+    /// scheduler, see
+    /// [`SequentialSystemScheduler::add_system_if`](crate::SequentialSystemScheduler::add_system_if).
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct GamePaused;
+    /// impl Component for GamePaused {}
+    ///
+    /// struct SpawnSystem;
+    /// impl System for SpawnSystem {
+    ///     fn should_run(&self, world: &WorldView) -> bool {
+    ///         !world.has_resource::<GamePaused>()
+    ///     }
+    ///
+    ///     fn run(&self, world: &mut World) {
+    ///         world.spawn_entity();
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(SpawnSystem).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// world.insert_resource(GamePaused);
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.entities().count(), 0);
+    ///
+    /// world.remove_resource::<GamePaused>();
+    /// scheduler.run_tick(&mut world);
+    /// assert_eq!(world.entities().count(), 1);
+    /// ```
+    fn should_run(&self, _world: &WorldView) -> bool {
+        true
+    }
+
     /// Called before the main execution phase.
     ///
     /// Use this for read-only preparation work such as:
@@ -100,8 +336,9 @@ pub trait System {
     /// - Preparing data structures
     /// - Input validation
     ///
-    /// This phase is safe for parallel execution since it only reads world state.
-    fn before_run(&self, _world: &World) {}
+    /// This phase is safe for parallel execution since it only reads world state,
+    /// which the [`WorldView`] parameter enforces.
+    fn before_run(&self, _world: &WorldView) {}
 
     /// Main system execution phase with mutable world access.
     ///
@@ -121,51 +358,135 @@ pub trait System {
     /// - Network updates
     /// - Statistics collection
     ///
-    /// This phase is safe for parallel execution since it only reads world state.
-    fn after_run(&self, _world: &World) {}
+    /// This phase is safe for parallel execution since it only reads world state,
+    /// which the [`WorldView`] parameter enforces.
+    fn after_run(&self, _world: &WorldView) {}
+
+    /// Called once the scheduler has cleaned up after the tick.
+    ///
+    /// Runs after deleted entities were removed and ephemeral components,
+    /// resources and events were cleared, so the world is in the state the
+    /// next tick starts from. Changes made during the tick can still be seen
+    /// with `World::was_added` and `World::was_changed`. Use this for metrics
+    /// and persistence that must not count data about to disappear.
+    ///
+    /// Only `run_tick()` calls this phase, `run_tick_no_cleanup()` does not.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Hit;
+    /// impl Component for Hit {}
+    ///
+    /// struct CombatSystem;
+    /// impl System for CombatSystem {
+    ///     fn run(&self, world: &mut World) {
+    ///         let target = world.spawn_entity();
+    ///         world.add_ephemeral_component(target, Hit).unwrap();
+    ///     }
+    /// }
+    ///
+    /// // (entities, entities with a Hit)
+    /// struct MetricsSystem(Arc<Mutex<(usize, usize)>>);
+    /// impl System for MetricsSystem {
+    ///     fn post_cleanup(&self, world: &WorldView) {
+    ///         let hits = world
+    ///             .entities()
+    ///             .filter(|&&entity| world.has_ephemeral_component::<Hit>(entity))
+    ///             .count();
+    ///         *self.0.lock().unwrap() = (world.entities().count(), hits);
+    ///     }
+    /// }
+    ///
+    /// let metrics = Arc::new(Mutex::new((0, 0)));
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.add_system(CombatSystem).unwrap();
+    /// scheduler.add_system(MetricsSystem(metrics.clone())).unwrap();
+    /// scheduler.build().unwrap();
+    ///
+    /// let mut world = World::new();
+    /// scheduler.run_tick(&mut world);
+    ///
+    /// // The Hit was already cleared when the metrics were taken
+    /// assert_eq!(*metrics.lock().unwrap(), (1, 0));
+    /// ```
+    fn post_cleanup(&self, _world: &WorldView) {}
 }
 
-/// Example implementation of a system with dependencies.
-///
-/// This demonstrates the recommended pattern for creating systems with static dependencies
-/// while maintaining compatibility with trait objects and stable Rust.
-///
-/// # Why LazyLock?
-/// We use `LazyLock` instead of const arrays because `TypeId::of::<T>()` requires
-/// the unstable `const_type_id` feature. LazyLock provides thread-safe lazy initialization
-/// that works on stable Rust with minimal performance overhead.
-///
-/// # Example
-/// ```
-/// use bemudjo_ecs::{System, World};
-/// use std::any::TypeId;
-/// use std::sync::LazyLock;
-///
-/// // Define dependencies using LazyLock
-/// static MOVEMENT_SYSTEM_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| {
-///     vec![TypeId::of::<InputSystem>()]
-/// });
-///
-/// // A system with no dependencies
-/// struct InputSystem;
-/// impl System for InputSystem {
-///     fn run(&self, world: &mut World) {
-///         // Process input
-///     }
-/// }
-///
-/// // A system that depends on InputSystem
-/// struct MovementSystem;
-/// impl System for MovementSystem {
-///     fn dependencies(&self) -> &[TypeId] {
-///         &MOVEMENT_SYSTEM_DEPS
-///     }
+/// The resources a system declares through [`System::resources`].
 ///
-///     fn run(&self, world: &mut World) {
-///         // Movement logic - guaranteed to run after InputSystem
-///     }
-/// }
-/// ```
+/// Built by chaining `read`, `write` and `initialize`; declaring the same type
+/// twice in one list has no further effect.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceAccess {
+    reads: Vec<(TypeId, &'static str)>,
+    writes: Vec<(TypeId, &'static str)>,
+    initializes: Vec<(TypeId, &'static str)>,
+}
+
+impl ResourceAccess {
+    /// Creates an empty declaration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares that the system reads the resource `T`.
+    pub fn read<T: Component>(mut self) -> Self {
+        Self::declare::<T>(&mut self.reads);
+        self
+    }
+
+    /// Declares that the system modifies the resource `T`.
+    pub fn write<T: Component>(mut self) -> Self {
+        Self::declare::<T>(&mut self.writes);
+        self
+    }
+
+    /// Declares that the system inserts the resource `T` if it is missing.
+    ///
+    /// At most one system of a scheduler may initialize a given resource.
+    /// Initializing counts as writing.
+    pub fn initialize<T: Component>(mut self) -> Self {
+        Self::declare::<T>(&mut self.initializes);
+        self
+    }
+
+    /// Returns the resources declared with `read`.
+    pub fn reads(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.reads.iter().map(|&(type_id, _)| type_id)
+    }
+
+    /// Returns the resources declared with `write`.
+    pub fn writes(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.writes.iter().map(|&(type_id, _)| type_id)
+    }
+
+    /// Returns the resources declared with `initialize`.
+    pub fn initializes(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.initializes.iter().map(|&(type_id, _)| type_id)
+    }
+
+    /// Returns the written and initialized resources with their type names.
+    pub(crate) fn modified(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.writes.iter().chain(&self.initializes).copied()
+    }
+
+    /// Returns the initialized resources with their type names.
+    pub(crate) fn initialized(&self) -> &[(TypeId, &'static str)] {
+        &self.initializes
+    }
+
+    fn declare<T: Component>(list: &mut Vec<(TypeId, &'static str)>) {
+        let type_id = TypeId::of::<T>();
+        if !list.iter().any(|&(declared, _)| declared == type_id) {
+            list.push((type_id, short_type_name(type_name::<T>())));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,341 @@
+use crate::{Component, FixedTimestep, SequentialSystemScheduler, World};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Global simulation clock maintained by [`TickRunner`].
+///
+/// The runner updates this resource before every tick, so systems can read it
+/// with `world.get_resource::<Time>()` instead of keeping their own clock.
+/// `delta` is always the fixed tick duration, regardless of how irregular the
+/// real loop timing is.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{System, Time, World};
+///
+/// struct RegenerationSystem;
+/// impl System for RegenerationSystem {
+///     fn run(&self, world: &mut World) {
+///         let time = world.get_resource::<Time>().unwrap();
+///         let _regen = 2.0 * time.delta_secs();
+///         // apply regeneration...
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Time {
+    /// Simulated time covered by the current tick.
+    pub delta: Duration,
+    /// Total simulated time, including the current tick.
+    pub elapsed: Duration,
+    /// Number of ticks run so far, including the current one.
+    pub tick_count: u64,
+}
+
+impl Component for Time {}
+
+impl Time {
+    /// Returns `delta` in seconds.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Returns `elapsed` in seconds.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+}
+
+/// Runs a scheduler at a fixed tick rate and keeps the [`Time`] resource current.
+///
+/// The runner composes a built [`SequentialSystemScheduler`] with a
+/// [`FixedTimestep`]: real elapsed time is accumulated and turned into whole
+/// ticks, and before each tick the `Time` resource is advanced by exactly one
+/// tick duration.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{SequentialSystemScheduler, TickRunner, Time, World};
+/// use std::time::Duration;
+///
+/// let mut scheduler = SequentialSystemScheduler::new();
+/// scheduler.build().unwrap();
+///
+/// let mut world = World::new();
+/// let mut runner = TickRunner::new(scheduler, 10.0); // 10 ticks per second
+///
+/// // 250ms of real time: two 100ms ticks, 50ms carried over
+/// assert_eq!(runner.step(&mut world, Duration::from_millis(250)), 2);
+///
+/// let time = world.get_resource::<Time>().unwrap();
+/// assert_eq!(time.tick_count, 2);
+/// assert_eq!(time.delta, Duration::from_millis(100));
+/// assert_eq!(time.elapsed, Duration::from_millis(200));
+/// ```
+///
+/// # Server main loop
+/// A server typically dedicates a thread to the simulation and stops it through
+/// a shared flag, e.g. from a Ctrl-C handler or an admin `shutdown` command:
+///
+/// ```no_run
+/// use bemudjo_ecs::{SequentialSystemScheduler, TickRunner, World};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let stop = Arc::new(AtomicBool::new(false));
+///
+/// let game_stop = stop.clone();
+/// let game_thread = std::thread::spawn(move || {
+///     let mut world = World::new();
+///     let mut scheduler = SequentialSystemScheduler::new();
+///     // scheduler.add_system(NetworkInputSystem { .. }).unwrap();
+///     // scheduler.add_system(CommandSystem).unwrap();
+///     // scheduler.add_system(NetworkOutputSystem { .. }).unwrap();
+///     scheduler.build().unwrap();
+///
+///     // Sleeps between ticks and returns once `stop` is set
+///     TickRunner::new(scheduler, 20.0).run_blocking(&mut world, &game_stop);
+/// });
+///
+/// // ... accept telnet connections and forward input to the game thread ...
+///
+/// stop.store(true, Ordering::Relaxed);
+/// game_thread.join().unwrap();
+/// ```
+pub struct TickRunner {
+    scheduler: SequentialSystemScheduler,
+    timestep: FixedTimestep,
+}
+
+impl TickRunner {
+    /// Creates a runner ticking `scheduler` `tick_rate_hz` times per second.
+    ///
+    /// # Panics
+    /// Panics if `tick_rate_hz` is not a positive, finite number.
+    pub fn new(scheduler: SequentialSystemScheduler, tick_rate_hz: f64) -> Self {
+        assert!(
+            tick_rate_hz.is_finite() && tick_rate_hz > 0.0,
+            "TickRunner tick rate must be positive and finite, got {tick_rate_hz}"
+        );
+
+        Self {
+            scheduler,
+            timestep: FixedTimestep::new(Duration::from_secs_f64(1.0 / tick_rate_hz)),
+        }
+    }
+
+    /// Replaces the fixed-timestep settings, e.g. to change the step cap.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{FixedTimestep, SequentialSystemScheduler, TickRunner};
+    /// use std::time::Duration;
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.build().unwrap();
+    ///
+    /// let runner = TickRunner::new(scheduler, 10.0)
+    ///     .with_timestep(FixedTimestep::new(Duration::from_millis(50)).with_max_steps(2));
+    /// assert_eq!(runner.tick_duration(), Duration::from_millis(50));
+    /// ```
+    pub fn with_timestep(mut self, timestep: FixedTimestep) -> Self {
+        self.timestep = timestep;
+        self
+    }
+
+    /// Returns the fixed duration of one tick.
+    pub fn tick_duration(&self) -> Duration {
+        self.timestep.step()
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn scheduler(&self) -> &SequentialSystemScheduler {
+        &self.scheduler
+    }
+
+    /// Returns the wrapped scheduler mutably, e.g. to toggle systems.
+    pub fn scheduler_mut(&mut self) -> &mut SequentialSystemScheduler {
+        &mut self.scheduler
+    }
+
+    /// Consumes the runner and returns the wrapped scheduler.
+    pub fn into_scheduler(self) -> SequentialSystemScheduler {
+        self.scheduler
+    }
+
+    /// Adds `real_dt` of real time and runs every tick that became due.
+    ///
+    /// Before each tick the [`Time`] resource is advanced by one tick duration;
+    /// it is inserted on the first tick if missing.
+    ///
+    /// # Returns
+    /// The number of ticks that were run
+    ///
+    /// # Panics
+    /// Panics if the scheduler has not been built and at least one tick is due.
+    pub fn step(&mut self, world: &mut World, real_dt: Duration) -> u32 {
+        let delta = self.timestep.step();
+        let scheduler = &self.scheduler;
+
+        self.timestep.advance_with(real_dt, || {
+            let previous = world.get_resource::<Time>().copied().unwrap_or_default();
+            world.insert_resource(Time {
+                delta,
+                elapsed: previous.elapsed + delta,
+                tick_count: previous.tick_count + 1,
+            });
+            scheduler.run_tick(world);
+        })
+    }
+
+    /// Runs ticks in real time until `stop` is set.
+    ///
+    /// Measures real elapsed time with a monotonic clock, runs due ticks through
+    /// [`TickRunner::step`] and sleeps until the next tick is due. `stop` is
+    /// checked once per loop iteration, so the call returns at most one tick
+    /// duration after it was set.
+    pub fn run_blocking(&mut self, world: &mut World, stop: &AtomicBool) {
+        let mut last = Instant::now();
+
+        while !stop.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            self.step(world, now - last);
+            last = now;
+
+            let until_next_tick = self
+                .timestep
+                .step()
+                .saturating_sub(self.timestep.accumulated());
+            std::thread::sleep(until_next_tick);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::System;
+    use std::sync::{Arc, Mutex};
+
+    /// Records the Time resource seen by every tick.
+    struct TimeRecorder {
+        seen: Arc<Mutex<Vec<Time>>>,
+    }
+    impl System for TimeRecorder {
+        fn run(&self, world: &mut World) {
+            let time = *world.get_resource::<Time>().unwrap();
+            self.seen.lock().unwrap().push(time);
+        }
+    }
+
+    fn recording_runner(tick_rate_hz: f64) -> (TickRunner, Arc<Mutex<Vec<Time>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(TimeRecorder { seen: seen.clone() })
+            .unwrap();
+        scheduler.build().unwrap();
+        (TickRunner::new(scheduler, tick_rate_hz), seen)
+    }
+
+    #[test]
+    fn test_irregular_real_dt_fires_correct_tick_count() {
+        let (mut runner, seen) = recording_runner(50.0); // 20ms ticks
+        let mut world = World::new();
+
+        let fired: Vec<u32> = [5, 30, 1, 44, 0, 20]
+            .iter()
+            .map(|&ms| runner.step(&mut world, Duration::from_millis(ms)))
+            .collect();
+
+        // Accumulated: 5, 35->15, 16, 60->0, 0, 20->0
+        assert_eq!(fired, vec![0, 1, 0, 3, 0, 1]);
+        assert_eq!(seen.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_time_delta_constant_and_elapsed_accumulates() {
+        let (mut runner, seen) = recording_runner(50.0);
+        let mut world = World::new();
+
+        for ms in [13, 27, 61, 9, 90] {
+            runner.step(&mut world, Duration::from_millis(ms));
+        }
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 10);
+        for (index, time) in seen.iter().enumerate() {
+            assert_eq!(time.delta, Duration::from_millis(20));
+            assert_eq!(time.tick_count, index as u64 + 1);
+            assert_eq!(time.elapsed, Duration::from_millis(20) * (index as u32 + 1));
+        }
+
+        assert_eq!(world.get_resource::<Time>(), seen.last());
+    }
+
+    #[test]
+    fn test_existing_time_resource_is_continued() {
+        let (mut runner, seen) = recording_runner(10.0);
+        let mut world = World::new();
+        world.insert_resource(Time {
+            delta: Duration::from_millis(100),
+            elapsed: Duration::from_secs(5),
+            tick_count: 50,
+        });
+
+        runner.step(&mut world, Duration::from_millis(100));
+
+        let time = seen.lock().unwrap()[0];
+        assert_eq!(time.tick_count, 51);
+        assert_eq!(time.elapsed, Duration::from_millis(5100));
+    }
+
+    #[test]
+    fn test_run_blocking_stops_on_flag() {
+        struct StopAfter {
+            ticks: u64,
+            stop: Arc<AtomicBool>,
+        }
+        impl System for StopAfter {
+            fn run(&self, world: &mut World) {
+                if world.get_resource::<Time>().unwrap().tick_count >= self.ticks {
+                    self.stop.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(StopAfter {
+                ticks: 3,
+                stop: stop.clone(),
+            })
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        let mut runner = TickRunner::new(scheduler, 500.0);
+        runner.run_blocking(&mut world, &stop);
+
+        // The flag is checked between steps, so a late step may finish its ticks
+        let ticks = world.get_resource::<Time>().unwrap().tick_count;
+        assert!(ticks >= 3, "ran {ticks} ticks");
+    }
+
+    #[test]
+    fn test_run_blocking_returns_immediately_when_stopped() {
+        let (mut runner, seen) = recording_runner(1.0);
+        let mut world = World::new();
+
+        runner.run_blocking(&mut world, &AtomicBool::new(true));
+
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "tick rate must be positive")]
+    fn test_invalid_tick_rate_panics() {
+        TickRunner::new(SequentialSystemScheduler::new(), 0.0);
+    }
+}
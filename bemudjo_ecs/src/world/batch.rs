@@ -0,0 +1,274 @@
+use crate::{Component, ComponentError, Entity};
+
+use super::World;
+
+/// Outcome of [`World::add_component_batch`].
+///
+/// A batch never aborts halfway: every entity that can take the component gets
+/// it, and the others are reported in `failures`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchResult {
+    /// Number of entities the component was added to.
+    pub added: usize,
+    /// One error per entity that was skipped, in input order.
+    ///
+    /// `ComponentError::EntityNotFound`, `ComponentError::AlreadyExists` or,
+    /// for ephemeral-only components, `ComponentError::EphemeralOnly`.
+    pub failures: Vec<ComponentError>,
+}
+
+impl BatchResult {
+    /// Returns `true` if the component was added to every entity of the batch.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl World {
+    /// Adds a copy of `component` to every entity in `entities`.
+    ///
+    /// Equivalent to calling [`add_component`](World::add_component) for each
+    /// entity, but the storage is looked up once and its capacity reserved up
+    /// front, which makes tagging thousands of entities much cheaper.
+    ///
+    /// Entities that don't exist, have been deleted or already have a `T` are
+    /// skipped and reported in [`BatchResult::failures`]; the rest of the batch
+    /// is still applied.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Wet;
+    /// impl Component for Wet {}
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..3).map(|_| world.spawn_entity()).collect();
+    /// world.add_component(entities[1], Wet).unwrap();
+    ///
+    /// let result = world.add_component_batch(&entities, Wet);
+    /// assert_eq!(result.added, 2);
+    /// assert_eq!(result.failures.len(), 1);
+    /// assert!(entities.iter().all(|&entity| world.has_component::<Wet>(entity)));
+    /// ```
+    pub fn add_component_batch<T: Component + Clone>(
+        &mut self,
+        entities: &[Entity],
+        component: T,
+    ) -> BatchResult {
+        if T::ephemeral_only() {
+            let failures = entities
+                .iter()
+                .map(|&entity| ComponentError::EphemeralOnly {
+                    entity,
+                    type_name: T::type_name(),
+                })
+                .collect();
+            return BatchResult { added: 0, failures };
+        }
+
+        let active: Vec<bool> = entities
+            .iter()
+            .map(|&entity| self.is_entity_active(entity))
+            .collect();
+
+        let mut added = Vec::with_capacity(entities.len());
+        let mut failures = Vec::new();
+        let storage = self.get_storage_mut::<T>();
+        storage.reserve(entities.len());
+        for (&entity, active) in entities.iter().zip(active) {
+            if !active {
+                failures.push(ComponentError::EntityNotFound {
+                    entity,
+                    type_name: T::type_name(),
+                });
+                continue;
+            }
+            match storage.insert(entity, component.clone()) {
+                Ok(()) => added.push(entity),
+                Err(error) => failures.push(error),
+            }
+        }
+
+        if added.is_empty() {
+            return BatchResult { added: 0, failures };
+        }
+
+        self.get_or_create_reverse_index::<T>()
+            .extend(added.iter().copied());
+        self.bump_generation();
+        for &entity in &added {
+            self.record_component_added::<T>(entity);
+            self.mark_added::<T>(entity);
+            self.reindex::<T>(entity);
+            self.subscriptions.component_added::<T>(entity);
+        }
+        for &entity in &added {
+            self.notify_added::<T>(entity);
+        }
+
+        BatchResult {
+            added: added.len(),
+            failures,
+        }
+    }
+
+    /// Removes component `T` from every entity in `entities`.
+    ///
+    /// Entities that don't exist, have been deleted or don't have a `T` are
+    /// ignored, exactly like [`remove_component`](World::remove_component).
+    ///
+    /// # Returns
+    /// The number of components that were removed
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Wet;
+    /// impl Component for Wet {}
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..3).map(|_| world.spawn_entity()).collect();
+    /// world.add_component_batch(&entities[..2], Wet);
+    ///
+    /// assert_eq!(world.remove_component_batch::<Wet>(&entities), 2);
+    /// assert!(!world.has_component::<Wet>(entities[0]));
+    /// ```
+    pub fn remove_component_batch<T: Component>(&mut self, entities: &[Entity]) -> usize {
+        let active: Vec<Entity> = entities
+            .iter()
+            .copied()
+            .filter(|&entity| self.is_entity_active(entity))
+            .collect();
+        for &entity in &active {
+            self.record_component::<T>(entity);
+        }
+
+        let storage = self.get_storage_mut::<T>();
+        let removed: Vec<(Entity, T)> = active
+            .into_iter()
+            .filter_map(|entity| storage.remove(entity).map(|component| (entity, component)))
+            .collect();
+
+        if removed.is_empty() {
+            return 0;
+        }
+
+        let reverse_index = self.get_or_create_reverse_index::<T>();
+        for (entity, _) in &removed {
+            reverse_index.remove(entity);
+        }
+        self.bump_generation();
+        for (entity, component) in &removed {
+            self.unmark::<T>(*entity);
+            self.reindex::<T>(*entity);
+            self.subscriptions.component_removed::<T>(*entity);
+            self.notify_removed(*entity, component);
+        }
+
+        removed.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Query;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Wet;
+    impl Component for Wet {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Temperature {
+        celsius: i32,
+    }
+    impl Component for Temperature {}
+
+    #[test]
+    fn test_add_component_batch_reports_partial_failures() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..5).map(|_| world.spawn_entity()).collect();
+        world
+            .add_component(entities[1], Temperature { celsius: 30 })
+            .unwrap();
+        world.delete_entity(entities[3]);
+
+        let result = world.add_component_batch(&entities, Temperature { celsius: 10 });
+
+        assert_eq!(result.added, 3);
+        assert!(!result.is_complete());
+        assert_eq!(
+            result.failures,
+            vec![
+                ComponentError::AlreadyExists {
+                    entity: entities[1],
+                    type_name: std::any::type_name::<Temperature>(),
+                },
+                ComponentError::EntityNotFound {
+                    entity: entities[3],
+                    type_name: std::any::type_name::<Temperature>(),
+                },
+            ]
+        );
+
+        // The existing component is untouched, the others got a copy
+        assert_eq!(
+            world.get_component::<Temperature>(entities[1]),
+            Some(&Temperature { celsius: 30 })
+        );
+        for entity in [entities[0], entities[2], entities[4]] {
+            assert_eq!(
+                world.get_component::<Temperature>(entity),
+                Some(&Temperature { celsius: 10 })
+            );
+            assert!(world.was_added::<Temperature>(entity));
+        }
+        assert_eq!(Query::<Temperature>::new().iter(&world).count(), 4);
+    }
+
+    #[test]
+    fn test_add_component_batch_duplicate_entity_fails_once() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        let result = world.add_component_batch(&[entity, entity], Wet);
+
+        assert_eq!(result.added, 1);
+        assert!(matches!(
+            result.failures.as_slice(),
+            [ComponentError::AlreadyExists { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_add_component_batch_empty() {
+        let mut world = World::new();
+        let generation = world.generation();
+
+        let result = world.add_component_batch(&[], Wet);
+
+        assert!(result.is_complete());
+        assert_eq!(result.added, 0);
+        assert_eq!(world.generation(), generation);
+    }
+
+    #[test]
+    fn test_remove_component_batch() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..4).map(|_| world.spawn_entity()).collect();
+        world.add_component_batch(&entities[..3], Wet);
+        world.delete_entity(entities[0]);
+
+        let removed = world.remove_component_batch::<Wet>(&entities);
+
+        assert_eq!(removed, 2);
+        assert!(!world.has_component::<Wet>(entities[1]));
+        assert!(!world.has_component::<Wet>(entities[2]));
+        assert_eq!(Query::<Wet>::new().iter(&world).count(), 0);
+        assert_eq!(world.remove_component_batch::<Wet>(&entities), 0);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{Bundle, ComponentError, Entity};
+
+use super::World;
+
+impl World {
+    /// Spawns a new entity and inserts every component of a bundle into it.
+    ///
+    /// If the bundle fails to insert, the freshly spawned entity is deleted
+    /// again and the error is returned.
+    ///
+    /// # Parameters
+    /// * `bundle` - The bundle of components to attach to the new entity
+    ///
+    /// # Returns
+    /// * `Ok(Entity)` - The newly spawned entity
+    /// * `Err(ComponentError)` - If the bundle could not be inserted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world
+    ///     .spawn_bundle((Position { x: 1.0, y: 2.0 }, Health { value: 100 }))
+    ///     .unwrap();
+    ///
+    /// assert!(world.has_component::<Position>(entity));
+    /// assert!(world.has_component::<Health>(entity));
+    /// ```
+    pub fn spawn_bundle<B: Bundle>(&mut self, bundle: B) -> Result<Entity, ComponentError> {
+        let entity = self.spawn_entity();
+        match bundle.insert(self, entity) {
+            Ok(()) => Ok(entity),
+            Err(error) => {
+                self.delete_entity(entity);
+                Err(error)
+            }
+        }
+    }
+
+    /// Inserts every component of a bundle into an existing entity.
+    ///
+    /// This is the way to attach several components to an already spawned
+    /// entity in one call. The operation is all-or-nothing: if the entity
+    /// doesn't exist or already has one of the components, nothing is inserted
+    /// and no observer runs. Should a later insert still fail, the components
+    /// added by this call are removed again and the entity is left as it was.
+    ///
+    /// # Parameters
+    /// * `entity` - The entity to add the components to
+    /// * `bundle` - The bundle of components to add
+    ///
+    /// # Returns
+    /// * `Ok(())` if every component was added
+    /// * `Err(ComponentError)` if the bundle could not be inserted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 50 }).unwrap();
+    ///
+    /// // Health already exists, so Position is rolled back as well
+    /// let result = world.insert_bundle(entity, (Position { x: 0.0, y: 0.0 }, Health { value: 100 }));
+    /// assert!(result.is_err());
+    /// assert!(!world.has_component::<Position>(entity));
+    /// assert_eq!(world.get_component::<Health>(entity).unwrap().value, 50);
+    /// ```
+    pub fn insert_bundle<B: Bundle>(
+        &mut self,
+        entity: Entity,
+        bundle: B,
+    ) -> Result<(), ComponentError> {
+        bundle.insert(self, entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Component;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Velocity {
+        dx: f32,
+        dy: f32,
+    }
+    impl Component for Velocity {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name {
+        value: String,
+    }
+    impl Component for Name {}
+
+    struct Goblin {
+        x: f32,
+        y: f32,
+    }
+
+    impl Bundle for Goblin {
+        fn insert(self, world: &mut World, entity: Entity) -> Result<(), ComponentError> {
+            (
+                Position {
+                    x: self.x,
+                    y: self.y,
+                },
+                Health { value: 30 },
+                Name {
+                    value: "Goblin".to_string(),
+                },
+            )
+                .insert(world, entity)
+        }
+    }
+
+    #[test]
+    fn test_spawn_tuple_bundle() {
+        let mut world = World::new();
+
+        let entity = world
+            .spawn_bundle((
+                Position { x: 1.0, y: 2.0 },
+                Health { value: 100 },
+                Velocity { dx: 0.5, dy: 0.0 },
+            ))
+            .unwrap();
+
+        assert_eq!(world.entities().count(), 1);
+        assert_eq!(
+            world.get_component::<Position>(entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 100 })
+        );
+        assert_eq!(
+            world.get_component::<Velocity>(entity),
+            Some(&Velocity { dx: 0.5, dy: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_single_element_tuple_bundle() {
+        let mut world = World::new();
+
+        let entity = world.spawn_bundle((Health { value: 10 },)).unwrap();
+
+        assert!(world.has_component::<Health>(entity));
+    }
+
+    #[test]
+    fn test_spawn_custom_struct_bundle() {
+        let mut world = World::new();
+
+        let goblin1 = world.spawn_bundle(Goblin { x: 1.0, y: 1.0 }).unwrap();
+        let goblin2 = world.spawn_bundle(Goblin { x: 5.0, y: 5.0 }).unwrap();
+
+        assert_ne!(goblin1, goblin2);
+        assert_eq!(world.get_component::<Health>(goblin1).unwrap().value, 30);
+        assert_eq!(
+            world.get_component::<Name>(goblin2).unwrap().value,
+            "Goblin"
+        );
+        assert_eq!(world.get_component::<Position>(goblin2).unwrap().x, 5.0);
+    }
+
+    #[test]
+    fn test_insert_bundle_into_existing_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Velocity { dx: 1.0, dy: 1.0 })
+            .unwrap();
+
+        world
+            .insert_bundle(entity, (Position { x: 0.0, y: 0.0 }, Health { value: 5 }))
+            .unwrap();
+
+        assert!(world.has_component::<Position>(entity));
+        assert!(world.has_component::<Health>(entity));
+        assert!(world.has_component::<Velocity>(entity));
+    }
+
+    #[test]
+    fn test_insert_bundle_rolls_back_on_conflict() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 50 }).unwrap();
+
+        let result = world.insert_bundle(
+            entity,
+            (
+                Position { x: 1.0, y: 1.0 },
+                Velocity { dx: 1.0, dy: 1.0 },
+                Health { value: 100 },
+                Name {
+                    value: "never added".to_string(),
+                },
+            ),
+        );
+
+        assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
+        assert!(!world.has_component::<Position>(entity));
+        assert!(!world.has_component::<Velocity>(entity));
+        assert!(!world.has_component::<Name>(entity));
+        // The pre-existing component is untouched
+        assert_eq!(world.get_component::<Health>(entity).unwrap().value, 50);
+    }
+
+    #[test]
+    fn test_conflicting_bundle_leaves_world_untouched() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 50 }).unwrap();
+        world.clear_change_tracking();
+
+        let notifications = Arc::new(AtomicUsize::new(0));
+        let added = Arc::clone(&notifications);
+        world.observe_added::<Position, _>(move |_, _, _| {
+            added.fetch_add(1, Ordering::Relaxed);
+        });
+        let removed = Arc::clone(&notifications);
+        world.observe_removed::<Position, _>(move |_, _, _| {
+            removed.fetch_add(1, Ordering::Relaxed);
+        });
+        let generation = world.generation();
+
+        let result =
+            world.insert_bundle(entity, (Position { x: 1.0, y: 1.0 }, Health { value: 100 }));
+
+        // The conflict is found before anything is inserted
+        assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
+        assert_eq!(notifications.load(Ordering::Relaxed), 0);
+        assert_eq!(world.generation(), generation);
+        assert!(!world.was_changed::<Position>(entity));
+    }
+
+    #[test]
+    fn test_custom_bundle_rolls_back_on_conflict() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Name {
+                    value: "Existing".to_string(),
+                },
+            )
+            .unwrap();
+
+        let result = world.insert_bundle(entity, Goblin { x: 0.0, y: 0.0 });
+
+        assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
+        assert!(!world.has_component::<Position>(entity));
+        assert!(!world.has_component::<Health>(entity));
+        assert_eq!(
+            world.get_component::<Name>(entity).unwrap().value,
+            "Existing"
+        );
+    }
+
+    #[test]
+    fn test_insert_bundle_on_deleted_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.delete_entity(entity);
+
+        let result = world.insert_bundle(entity, (Position { x: 0.0, y: 0.0 },));
+
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
+    }
+
+    #[test]
+    fn test_spawn_bundle_with_duplicate_types_deletes_entity() {
+        let mut world = World::new();
+
+        let result = world.spawn_bundle((Health { value: 1 }, Health { value: 2 }));
+
+        assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
+        assert_eq!(world.entities().count(), 0);
+    }
+}
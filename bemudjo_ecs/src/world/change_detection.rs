@@ -0,0 +1,205 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+use crate::{Component, Entity};
+
+use super::World;
+
+impl World {
+    /// Returns the entities whose component `T` was added this tick.
+    ///
+    /// A component counts as added when it appears on an entity that didn't have
+    /// it: through `add_component()`, bundle insertion, or `replace_component()`
+    /// on an entity without the component. An entity whose component was added
+    /// and removed again within the same tick is not included. Deleted entities
+    /// are skipped.
+    ///
+    /// The set is cleared by [`World::clear_change_tracking`], which the
+    /// scheduler calls at the end of every tick.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Dead).unwrap();
+    ///
+    /// assert!(world.was_added::<Dead>(goblin));
+    ///
+    /// world.clear_change_tracking();
+    /// assert!(!world.was_added::<Dead>(goblin));
+    /// assert!(world.has_component::<Dead>(goblin));
+    /// ```
+    pub fn was_added<T: Component>(&self, entity: Entity) -> bool {
+        self.is_entity_active(entity)
+            && self
+                .added_this_tick
+                .get(&TypeId::of::<T>())
+                .is_some_and(|entities| entities.contains(&entity))
+    }
+
+    /// Returns `true` if the entity's component `T` was added or changed this tick.
+    ///
+    /// Besides additions, this covers every write to an existing component
+    /// through `update_component()` or `replace_component()`, even if the new
+    /// value equals the old one.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let hero = world.spawn_entity();
+    /// world.add_component(hero, Health { value: 10 }).unwrap();
+    /// world.clear_change_tracking();
+    ///
+    /// world.update_component::<Health, _>(hero, |h| Health { value: h.value - 1 }).unwrap();
+    /// assert!(world.was_changed::<Health>(hero));
+    /// assert!(!world.was_added::<Health>(hero));
+    /// ```
+    pub fn was_changed<T: Component>(&self, entity: Entity) -> bool {
+        self.is_entity_active(entity)
+            && self
+                .changed_this_tick
+                .get(&TypeId::of::<T>())
+                .is_some_and(|entities| entities.contains(&entity))
+    }
+
+    /// Forgets which components were added or changed, starting a new tick.
+    ///
+    /// Called by [`crate::SequentialSystemScheduler::run_tick`] after all systems
+    /// ran. Call it yourself when driving a world without a scheduler.
+    pub fn clear_change_tracking(&mut self) {
+        self.added_this_tick.clear();
+        self.changed_this_tick.clear();
+    }
+
+    /// Returns the entities whose component `T` was added this tick.
+    pub(crate) fn entities_with_added_component<T: Component>(&self) -> HashSet<Entity> {
+        self.tracked_entities(&self.added_this_tick, TypeId::of::<T>())
+    }
+
+    /// Returns the entities whose component `T` was added or changed this tick.
+    pub(crate) fn entities_with_changed_component<T: Component>(&self) -> HashSet<Entity> {
+        self.tracked_entities(&self.changed_this_tick, TypeId::of::<T>())
+    }
+
+    /// Records that component `T` was added to an entity.
+    pub(super) fn mark_added<T: Component>(&mut self, entity: Entity) {
+        let type_id = TypeId::of::<T>();
+        self.added_this_tick
+            .entry(type_id)
+            .or_default()
+            .insert(entity);
+        self.changed_this_tick
+            .entry(type_id)
+            .or_default()
+            .insert(entity);
+    }
+
+    /// Records that an existing component `T` was written.
+    pub(super) fn mark_changed<T: Component>(&mut self, entity: Entity) {
+        self.changed_this_tick
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(entity);
+    }
+
+    /// Forgets any addition or change of component `T` after it was removed.
+    pub(super) fn unmark<T: Component>(&mut self, entity: Entity) {
+        let type_id = TypeId::of::<T>();
+        if let Some(entities) = self.added_this_tick.get_mut(&type_id) {
+            entities.remove(&entity);
+        }
+        if let Some(entities) = self.changed_this_tick.get_mut(&type_id) {
+            entities.remove(&entity);
+        }
+    }
+
+    /// Returns the active entities recorded for `type_id` in `tracked`.
+    fn tracked_entities(
+        &self,
+        tracked: &HashMap<TypeId, HashSet<Entity>>,
+        type_id: TypeId,
+    ) -> HashSet<Entity> {
+        tracked
+            .get(&type_id)
+            .map(|entities| {
+                entities
+                    .iter()
+                    .filter(|entity| self.is_entity_active(**entity))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[test]
+    fn test_add_replace_update_tracking() {
+        let mut world = World::new();
+        let added = world.spawn_entity();
+        let replaced = world.spawn_entity();
+        let updated = world.spawn_entity();
+        world.add_component(replaced, Health { value: 1 }).unwrap();
+        world.add_component(updated, Health { value: 1 }).unwrap();
+        world.clear_change_tracking();
+
+        world.add_component(added, Health { value: 1 }).unwrap();
+        world.replace_component(replaced, Health { value: 2 });
+        world
+            .update_component::<Health, _>(updated, |h| Health { value: h.value + 1 })
+            .unwrap();
+
+        assert!(world.was_added::<Health>(added));
+        assert!(!world.was_added::<Health>(replaced));
+        assert!(!world.was_added::<Health>(updated));
+        for entity in [added, replaced, updated] {
+            assert!(world.was_changed::<Health>(entity));
+        }
+    }
+
+    #[test]
+    fn test_add_then_remove_same_tick_is_not_tracked() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        world.add_component(entity, Health { value: 1 }).unwrap();
+        world.remove_component::<Health>(entity);
+
+        assert!(!world.was_added::<Health>(entity));
+        assert!(!world.was_changed::<Health>(entity));
+        assert!(world.entities_with_added_component::<Health>().is_empty());
+    }
+
+    #[test]
+    fn test_deleted_entities_are_not_reported() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 1 }).unwrap();
+
+        world.delete_entity(entity);
+
+        assert!(!world.was_added::<Health>(entity));
+        assert!(world.entities_with_changed_component::<Health>().is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::{AnyStorage, Component};
+
+use super::World;
+
+/// Collections below this capacity are never shrunk automatically; the memory
+/// saved wouldn't be worth reallocating them over and over.
+const MIN_AUTO_SHRINK_CAPACITY: usize = 64;
+
+/// Decides whether a collection should be shrunk: always with no threshold,
+/// otherwise if less than `threshold` of its capacity is in use.
+fn should_shrink(len: usize, capacity: usize, threshold: Option<f32>) -> bool {
+    match threshold {
+        None => capacity > len,
+        Some(threshold) => {
+            capacity >= MIN_AUTO_SHRINK_CAPACITY && (len as f32) < threshold * capacity as f32
+        }
+    }
+}
+
+fn shrink_storage(storage: &mut dyn AnyStorage, threshold: Option<f32>) {
+    if should_shrink(storage.len(), storage.allocated_capacity(), threshold) {
+        storage.shrink();
+    }
+}
+
+fn shrink_set<T: Eq + Hash>(set: &mut HashSet<T>, threshold: Option<f32>) {
+    if should_shrink(set.len(), set.capacity(), threshold) {
+        set.shrink_to_fit();
+    }
+}
+
+impl World {
+    /// Releases the memory held by storages and entity sets beyond what their
+    /// current contents need.
+    ///
+    /// Storages keep their capacity after components are removed, so a world
+    /// that spiked to many entities keeps the memory of the spike. Shrinking
+    /// covers component, ephemeral and resource storages, the reverse component
+    /// indexes and the entity sets. Run it after `cleanup_deleted_entities()`,
+    /// since the data of soft-deleted entities still occupies the storages.
+    ///
+    /// Shrinking reallocates, so it is meant for quiet moments, not every tick.
+    /// See [`set_auto_shrink`](World::set_auto_shrink) for an automatic policy.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let raid: Vec<_> = (0..10_000).map(|_| world.spawn_entity()).collect();
+    /// for &entity in &raid {
+    ///     world.add_component(entity, Health { value: 100 }).unwrap();
+    /// }
+    /// for &entity in &raid {
+    ///     world.delete_entity(entity);
+    /// }
+    /// world.cleanup_deleted_entities();
+    /// let before = world.storage_capacity::<Health>();
+    ///
+    /// world.shrink_to_fit();
+    /// assert!(world.storage_capacity::<Health>() < before);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_collections(None);
+    }
+
+    /// Makes `cleanup_deleted_entities()` shrink collections that became sparse.
+    ///
+    /// After removing the deleted entities, every storage, reverse index and
+    /// entity set using less than `threshold` of its capacity is shrunk, as in
+    /// [`shrink_to_fit`](World::shrink_to_fit). Collections with a capacity
+    /// below 64 are left alone. A threshold of 0.0, the default, disables
+    /// automatic shrinking.
+    ///
+    /// # Panics
+    /// Panics if `threshold` is not between 0.0 and 1.0.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// // Shrink whatever is less than a quarter full
+    /// world.set_auto_shrink(0.25);
+    ///
+    /// let raid: Vec<_> = (0..10_000).map(|_| world.spawn_entity()).collect();
+    /// for &entity in &raid {
+    ///     world.add_component(entity, Health { value: 100 }).unwrap();
+    /// }
+    /// let before = world.storage_capacity::<Health>();
+    ///
+    /// for &entity in &raid[100..] {
+    ///     world.delete_entity(entity);
+    /// }
+    /// world.cleanup_deleted_entities();
+    /// assert!(world.storage_capacity::<Health>() < before);
+    /// ```
+    pub fn set_auto_shrink(&mut self, threshold: f32) {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "auto shrink threshold must be between 0.0 and 1.0, got {threshold}"
+        );
+        self.auto_shrink_threshold = threshold;
+    }
+
+    /// Returns the number of `T` components the storage can hold without
+    /// reallocating, or 0 if no `T` was ever added.
+    ///
+    /// Meant for debugging memory use, see [`shrink_to_fit`](World::shrink_to_fit).
+    pub fn storage_capacity<T: Component>(&self) -> usize {
+        self.get_storage::<T>()
+            .map_or(0, |storage| storage.capacity())
+    }
+
+    /// Applies the policy set with `set_auto_shrink()`.
+    pub(super) fn auto_shrink(&mut self) {
+        if self.auto_shrink_threshold > 0.0 {
+            self.shrink_collections(Some(self.auto_shrink_threshold));
+        }
+    }
+
+    /// Shrinks every collection, or with a threshold only the sparse ones.
+    fn shrink_collections(&mut self, threshold: Option<f32>) {
+        let storages = self
+            .component_storages
+            .values_mut()
+            .chain(self.ephemeral_component_storages.values_mut())
+            .chain(self.resource_storages.values_mut())
+            .chain(self.ephemeral_resource_storages.values_mut())
+            .chain(self.ephemeral_event_storages.values_mut());
+        for storage in storages {
+            shrink_storage(storage.as_mut(), threshold);
+        }
+
+        let reverse_indexes = self
+            .reverse_component_index
+            .values_mut()
+            .chain(self.reverse_ephemeral_component_index.values_mut());
+        for entities in reverse_indexes {
+            shrink_set(entities, threshold);
+        }
+
+        shrink_set(&mut self.entities, threshold);
+        shrink_set(&mut self.soft_deleted_entities, threshold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Boss;
+    impl Component for Boss {}
+
+    fn spawn_raid(world: &mut World, count: u32) -> Vec<Entity> {
+        (0..count)
+            .map(|value| {
+                let entity = world.spawn_entity();
+                world.add_component(entity, Health { value }).unwrap();
+                entity
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shrink_to_fit_releases_capacity_and_keeps_data() {
+        let mut world = World::new();
+        let raid = spawn_raid(&mut world, 5_000);
+        let boss = raid[42];
+        world.add_component(boss, Boss).unwrap();
+
+        for &entity in &raid {
+            if entity != boss {
+                world.delete_entity(entity);
+            }
+        }
+        world.cleanup_deleted_entities();
+        let before = world.storage_capacity::<Health>();
+        assert!(before >= 5_000);
+
+        world.shrink_to_fit();
+
+        assert!(world.storage_capacity::<Health>() < 64);
+        assert!(world.entities.capacity() < 64);
+        assert_eq!(
+            world.get_component::<Health>(boss),
+            Some(&Health { value: 42 })
+        );
+        assert!(world.has_component::<Boss>(boss));
+        assert_eq!(world.storage_len::<Health>(), 1);
+
+        // Storages grow again as usual
+        let newcomer = world.spawn_entity();
+        world.add_component(newcomer, Health { value: 7 }).unwrap();
+        assert_eq!(world.storage_len::<Health>(), 2);
+    }
+
+    #[test]
+    fn test_auto_shrink_only_after_going_below_threshold() {
+        let mut world = World::new();
+        world.set_auto_shrink(0.25);
+        let raid = spawn_raid(&mut world, 1_000);
+
+        // Three quarters still in use: a shrink would go below 1000
+        for &entity in &raid[750..] {
+            world.delete_entity(entity);
+        }
+        world.cleanup_deleted_entities();
+        assert!(world.storage_capacity::<Health>() >= 1_000);
+
+        for &entity in &raid[10..750] {
+            world.delete_entity(entity);
+        }
+        world.cleanup_deleted_entities();
+        assert!(world.storage_capacity::<Health>() < 64);
+        assert_eq!(world.storage_len::<Health>(), 10);
+    }
+
+    #[test]
+    fn test_auto_shrink_disabled_by_default() {
+        let mut world = World::new();
+        let raid = spawn_raid(&mut world, 1_000);
+
+        for &entity in &raid {
+            world.delete_entity(entity);
+        }
+        world.cleanup_deleted_entities();
+
+        assert!(world.storage_capacity::<Health>() >= 64);
+        assert_eq!(world.storage_capacity::<Boss>(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "auto shrink threshold")]
+    fn test_auto_shrink_rejects_invalid_threshold() {
+        World::new().set_auto_shrink(1.5);
+    }
+}
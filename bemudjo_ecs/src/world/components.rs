@@ -1,13 +1,25 @@
-use crate::{Component, ComponentError, ComponentStorage};
+use crate::component::downcast_storage_mut;
+use crate::{Component, ComponentError};
 
 use super::World;
 
+/// What [`World::upsert_component`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// The entity didn't have the component, it was added.
+    Added,
+    /// The entity's existing component was overwritten.
+    Replaced,
+}
+
 impl World {
     /// Adds a component to an entity.
     ///
     /// If the entity already has a component of this type, the operation will fail
-    /// with `ComponentError::ComponentAlreadyExists`. If the entity doesn't exist
-    /// or has been deleted, it will fail with `ComponentError::ComponentNotFound`.
+    /// with `ComponentError::AlreadyExists`. If the entity doesn't exist
+    /// or has been deleted, it will fail with `ComponentError::EntityNotFound`.
+    /// Ephemeral-only components (see [`Component::ephemeral_only`]) fail with
+    /// `ComponentError::EphemeralOnly`.
     ///
     /// # Parameters
     /// * `entity` - The entity to add the component to
@@ -41,14 +53,50 @@ impl World {
         component: T,
     ) -> Result<(), ComponentError> {
         if !self.is_entity_active(entity) {
-            return Err(ComponentError::ComponentNotFound);
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+        if T::ephemeral_only() {
+            return Err(ComponentError::EphemeralOnly {
+                entity,
+                type_name: T::type_name(),
+            });
         }
 
         let entities_in_reverse_index = self.get_or_create_reverse_index::<T>();
         entities_in_reverse_index.insert(entity);
 
         let storage = self.get_storage_mut::<T>();
-        storage.insert(entity, component)
+        storage.insert(entity, component)?;
+        self.record_component_added::<T>(entity);
+        self.bump_generation();
+        self.mark_added::<T>(entity);
+        self.reindex::<T>(entity);
+        self.subscriptions.component_added::<T>(entity);
+
+        self.notify_added::<T>(entity);
+        Ok(())
+    }
+
+    /// Returns the error `add_component::<T>` would fail with on `entity`
+    /// without changing anything, so bundles can be checked up front.
+    pub(crate) fn check_add_component<T: Component>(
+        &self,
+        entity: crate::Entity,
+    ) -> Result<(), ComponentError> {
+        let type_name = T::type_name();
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound { entity, type_name });
+        }
+        if T::ephemeral_only() {
+            return Err(ComponentError::EphemeralOnly { entity, type_name });
+        }
+        if self.has_component::<T>(entity) {
+            return Err(ComponentError::AlreadyExists { entity, type_name });
+        }
+        Ok(())
     }
 
     /// Gets a reference to a component attached to an entity.
@@ -88,6 +136,111 @@ impl World {
         self.get_storage::<T>()?.get(entity)
     }
 
+    /// Gets references to two components of an entity at once.
+    ///
+    /// Equivalent to two [`get_component`](World::get_component) calls, but the
+    /// entity is only checked once.
+    ///
+    /// # Returns
+    /// * `Some((&A, &B))` if the entity is alive and has both components
+    /// * `None` if either component is missing or the entity is invalid
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Velocity { x: f32, y: f32 }
+    /// impl Component for Velocity {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Position { x: 0.0, y: 0.0 }).unwrap();
+    /// assert!(world.get_components2::<Position, Velocity>(entity).is_none());
+    ///
+    /// world.add_component(entity, Velocity { x: 1.0, y: 2.0 }).unwrap();
+    /// let (position, velocity) = world.get_components2::<Position, Velocity>(entity).unwrap();
+    /// assert_eq!(position.x + velocity.x, 1.0);
+    /// ```
+    pub fn get_components2<A: Component, B: Component>(
+        &self,
+        entity: crate::Entity,
+    ) -> Option<(&A, &B)> {
+        if !self.is_entity_active(entity) {
+            return None;
+        }
+
+        Some((
+            self.get_storage::<A>()?.get(entity)?,
+            self.get_storage::<B>()?.get(entity)?,
+        ))
+    }
+
+    /// Gets references to three components of an entity at once.
+    ///
+    /// The three-component version of
+    /// [`get_components2`](World::get_components2).
+    ///
+    /// # Returns
+    /// * `Some((&A, &B, &C))` if the entity is alive and has all three components
+    /// * `None` if any component is missing or the entity is invalid
+    pub fn get_components3<A: Component, B: Component, C: Component>(
+        &self,
+        entity: crate::Entity,
+    ) -> Option<(&A, &B, &C)> {
+        if !self.is_entity_active(entity) {
+            return None;
+        }
+
+        Some((
+            self.get_storage::<A>()?.get(entity)?,
+            self.get_storage::<B>()?.get(entity)?,
+            self.get_storage::<C>()?.get(entity)?,
+        ))
+    }
+
+    /// Gets a component of an entity, even if the entity was deleted this tick.
+    ///
+    /// Deleted entities keep their component data until
+    /// `cleanup_deleted_entities()` runs, which the scheduler does after all
+    /// systems' `after_run`. Until then this method can still read it, e.g. to
+    /// drop a corpse with the loot of an enemy killed earlier in the tick. After
+    /// cleanup it returns `None`, like `get_component()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Loot { gold: u32 }
+    /// impl Component for Loot {}
+    ///
+    /// let mut world = World::new();
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Loot { gold: 12 }).unwrap();
+    /// world.delete_entity(goblin);
+    ///
+    /// assert_eq!(world.get_component::<Loot>(goblin), None);
+    /// assert_eq!(world.get_component_including_deleted::<Loot>(goblin), Some(&Loot { gold: 12 }));
+    ///
+    /// world.cleanup_deleted_entities();
+    /// assert_eq!(world.get_component_including_deleted::<Loot>(goblin), None);
+    /// ```
+    pub fn get_component_including_deleted<T: Component>(
+        &self,
+        entity: crate::Entity,
+    ) -> Option<&T> {
+        if !self.is_entity_active(entity) && !self.was_deleted_this_tick(entity) {
+            return None;
+        }
+
+        self.get_storage::<T>()?.get(entity)
+    }
+
     /// Updates a component using a functional transformation.
     ///
     /// This method provides immutable component updates by taking the current component,
@@ -104,7 +257,8 @@ impl World {
     ///
     /// # Returns
     /// * `Ok(T)` - The new component value after update
-    /// * `Err(ComponentError::ComponentNotFound)` - If entity or component doesn't exist
+    /// * `Err(ComponentError::EntityNotFound { .. })` - If the entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::NotFound { .. })` - If the entity doesn't have the component
     ///
     /// # Example
     /// ```
@@ -138,24 +292,165 @@ impl World {
         F: FnOnce(T) -> T,
     {
         if !self.is_entity_active(entity) {
-            return Err(ComponentError::ComponentNotFound);
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
         }
 
+        self.record_component::<T>(entity);
         let storage = self.get_storage_mut::<T>();
         match storage.get(entity) {
             Some(old_component) => {
                 let new_component = f(old_component.clone());
                 storage.insert_or_update(entity, new_component.clone());
+                self.mark_changed::<T>(entity);
+                self.reindex::<T>(entity);
                 Ok(new_component)
             }
-            None => Err(ComponentError::ComponentNotFound),
+            None => Err(ComponentError::NotFound {
+                entity,
+                type_name: T::type_name(),
+            }),
+        }
+    }
+
+    /// Mutates a component where it is stored.
+    ///
+    /// Unlike [`World::update_component`], the component is neither cloned
+    /// nor moved, and `T` doesn't need to be `Clone`. Prefer this for large
+    /// components like inventories, where a copy per update adds up. The
+    /// component is marked as changed.
+    ///
+    /// # Returns
+    /// * `Ok(())` - `f` was applied to the component
+    /// * `Err(ComponentError::EntityNotFound { .. })` - If the entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::NotFound { .. })` - If the entity doesn't have the component
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Inventory { items: Vec<String> }
+    /// impl Component for Inventory {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Inventory { items: Vec::new() }).unwrap();
+    ///
+    /// world
+    ///     .update_component_in_place::<Inventory, _>(player, |inventory| {
+    ///         inventory.items.push("torch".to_string());
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(world.get_component::<Inventory>(player).unwrap().items, ["torch"]);
+    /// ```
+    pub fn update_component_in_place<T, F>(
+        &mut self,
+        entity: crate::Entity,
+        f: F,
+    ) -> Result<(), ComponentError>
+    where
+        T: Component,
+        F: FnOnce(&mut T),
+    {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        self.record_component::<T>(entity);
+        match self.get_storage_mut::<T>().get_mut(entity) {
+            Some(component) => {
+                f(component);
+                self.mark_changed::<T>(entity);
+                self.reindex::<T>(entity);
+                Ok(())
+            }
+            None => Err(ComponentError::NotFound {
+                entity,
+                type_name: T::type_name(),
+            }),
+        }
+    }
+
+    /// Applies `f` to the `T` component of every entity matched by `query`.
+    ///
+    /// Sugar for the collect-then-`update_component()` loop: the matching
+    /// entities are computed once with the query's filters, then each component
+    /// is replaced by `f` applied to a copy of it. Entities that are no longer
+    /// active when their turn comes are skipped. Every updated component is
+    /// marked as changed.
+    ///
+    /// # Parameters
+    /// * `query` - Selects the entities to update
+    /// * `f` - Maps the current component value to the new one
+    ///
+    /// # Returns
+    /// The number of components that were updated
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, Query, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Lifetime { ticks: u32 }
+    /// impl Component for Lifetime {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Projectile;
+    /// impl Component for Projectile {}
+    ///
+    /// let mut world = World::new();
+    /// let arrow = world.spawn_entity();
+    /// world.add_component(arrow, Lifetime { ticks: 3 }).unwrap();
+    /// world.add_component(arrow, Projectile).unwrap();
+    /// let buff = world.spawn_entity();
+    /// world.add_component(buff, Lifetime { ticks: 3 }).unwrap();
+    ///
+    /// let projectiles = Query::<Lifetime>::new().with::<Projectile>();
+    /// let updated = world.update_all(&projectiles, |lifetime| Lifetime {
+    ///     ticks: lifetime.ticks.saturating_sub(1),
+    /// });
+    ///
+    /// assert_eq!(updated, 1);
+    /// assert_eq!(world.get_component::<Lifetime>(arrow), Some(&Lifetime { ticks: 2 }));
+    /// assert_eq!(world.get_component::<Lifetime>(buff), Some(&Lifetime { ticks: 3 }));
+    /// ```
+    pub fn update_all<T, F>(&mut self, query: &crate::Query<T>, mut f: F) -> usize
+    where
+        T: Component + Clone,
+        F: FnMut(T) -> T,
+    {
+        let entities = query.matching_entities(self);
+        let mut updated = 0;
+
+        for entity in entities {
+            if !self.is_entity_active(entity) {
+                continue;
+            }
+
+            self.record_component::<T>(entity);
+            if let Some(component) = self.get_storage_mut::<T>().get_mut(entity) {
+                *component = f(component.clone());
+                self.mark_changed::<T>(entity);
+                self.reindex::<T>(entity);
+                updated += 1;
+            }
         }
+
+        updated
     }
 
     /// Replaces a component with a new value, returning the old value if it existed.
     ///
     /// If the entity doesn't have the component type, the new component is added
-    /// and `None` is returned. If the entity has been deleted, `None` is returned
+    /// and `None` is returned. If the entity has been deleted, or `T` is
+    /// ephemeral-only (see [`Component::ephemeral_only`]), `None` is returned
     /// and no action is taken.
     ///
     /// # Parameters
@@ -190,19 +485,364 @@ impl World {
         entity: crate::Entity,
         component: T,
     ) -> Option<T> {
-        if !self.is_entity_active(entity) {
+        if !self.is_entity_active(entity) || T::ephemeral_only() {
             return None;
         }
 
+        self.record_component::<T>(entity);
         let entities_in_reverse_index = self.get_or_create_reverse_index::<T>();
         entities_in_reverse_index.insert(entity);
 
         let storage = self.get_storage_mut::<T>();
         let old_component = storage.get(entity).cloned();
         storage.insert_or_update(entity, component);
+
+        if old_component.is_none() {
+            self.bump_generation();
+            self.mark_added::<T>(entity);
+            self.reindex::<T>(entity);
+            self.subscriptions.component_added::<T>(entity);
+            self.notify_added::<T>(entity);
+        } else {
+            self.mark_changed::<T>(entity);
+            self.reindex::<T>(entity);
+        }
         old_component
     }
 
+    /// Adds a component, or replaces the existing one and returns it.
+    ///
+    /// Behaves like [`World::replace_component`], but reports a deleted or
+    /// unknown entity as an error instead of returning `None`, so the result
+    /// tells apart all three outcomes.
+    ///
+    /// # Returns
+    /// * `Ok(Some(T))` - The component was replaced; contains the previous value
+    /// * `Ok(None)` - The entity didn't have the component; it was added
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::EphemeralOnly { .. })` if `T` is ephemeral-only
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component, ComponentError};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Title(&'static str);
+    /// impl Component for Title {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    ///
+    /// assert_eq!(world.add_or_replace_component(player, Title("Squire")), Ok(None));
+    /// assert_eq!(
+    ///     world.add_or_replace_component(player, Title("Knight")),
+    ///     Ok(Some(Title("Squire")))
+    /// );
+    ///
+    /// world.delete_entity(player);
+    /// assert!(matches!(
+    ///     world.add_or_replace_component(player, Title("Ghost")),
+    ///     Err(ComponentError::EntityNotFound { .. })
+    /// ));
+    /// ```
+    pub fn add_or_replace_component<T: Component + Clone>(
+        &mut self,
+        entity: crate::Entity,
+        component: T,
+    ) -> Result<Option<T>, ComponentError> {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+        if T::ephemeral_only() {
+            return Err(ComponentError::EphemeralOnly {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        Ok(self.replace_component(entity, component))
+    }
+
+    /// Adds a component, or overwrites the one the entity already has.
+    ///
+    /// Unlike [`World::replace_component`], `T` doesn't need to be `Clone` and
+    /// the previous value is dropped instead of being returned. Adding the
+    /// component triggers the same bookkeeping as `add_component()`, while
+    /// overwriting it marks it as changed.
+    ///
+    /// # Returns
+    /// * `Ok(UpsertOutcome::Added)` - The entity didn't have the component
+    /// * `Ok(UpsertOutcome::Replaced)` - The existing component was overwritten
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::EphemeralOnly { .. })` if `T` is ephemeral-only
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, UpsertOutcome, World};
+    ///
+    /// // Not Clone, so replace_component() can't be used
+    /// struct Connection { socket_id: u32 }
+    /// impl Component for Connection {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    ///
+    /// assert_eq!(
+    ///     world.upsert_component(player, Connection { socket_id: 1 }),
+    ///     Ok(UpsertOutcome::Added)
+    /// );
+    /// assert_eq!(
+    ///     world.upsert_component(player, Connection { socket_id: 2 }),
+    ///     Ok(UpsertOutcome::Replaced)
+    /// );
+    /// assert_eq!(world.get_component::<Connection>(player).unwrap().socket_id, 2);
+    /// ```
+    pub fn upsert_component<T: Component>(
+        &mut self,
+        entity: crate::Entity,
+        component: T,
+    ) -> Result<UpsertOutcome, ComponentError> {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+        if T::ephemeral_only() {
+            return Err(ComponentError::EphemeralOnly {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        self.record_component::<T>(entity);
+        if let Some(existing) = self.get_storage_mut::<T>().get_mut(entity) {
+            *existing = component;
+            self.mark_changed::<T>(entity);
+            self.reindex::<T>(entity);
+            return Ok(UpsertOutcome::Replaced);
+        }
+
+        self.add_component(entity, component)?;
+        Ok(UpsertOutcome::Added)
+    }
+
+    /// Replaces a component the entity is expected to have, returning the old value.
+    ///
+    /// Unlike [`World::replace_component`], a missing component is an error
+    /// rather than being added, so systems can assert that it exists.
+    ///
+    /// # Returns
+    /// * `Ok(T)` - The previous component value
+    /// * `Err(ComponentError::NotFound { .. })` if the entity doesn't have the component
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component, ComponentError};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    ///
+    /// assert!(matches!(
+    ///     world.replace_existing_component(player, Health { value: 100 }),
+    ///     Err(ComponentError::NotFound { .. })
+    /// ));
+    /// assert!(!world.has_component::<Health>(player));
+    ///
+    /// world.add_component(player, Health { value: 100 }).unwrap();
+    /// assert_eq!(
+    ///     world.replace_existing_component(player, Health { value: 80 }),
+    ///     Ok(Health { value: 100 })
+    /// );
+    /// ```
+    pub fn replace_existing_component<T: Component + Clone>(
+        &mut self,
+        entity: crate::Entity,
+        component: T,
+    ) -> Result<T, ComponentError> {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        if !self.has_component::<T>(entity) {
+            return Err(ComponentError::NotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        self.record_component::<T>(entity);
+        let storage = self.get_storage_mut::<T>();
+        let old_component = storage.get(entity).cloned().expect("component is present");
+        storage.insert_or_update(entity, component);
+        self.mark_changed::<T>(entity);
+        self.reindex::<T>(entity);
+        Ok(old_component)
+    }
+
+    /// Moves a component from one entity to another.
+    ///
+    /// Equivalent to `remove_component()` on `from` followed by
+    /// `add_component()` on `to`, but every check happens up front, so the
+    /// value is never left outside the world, and `T` doesn't need to be
+    /// `Clone`. Removed-observers run for `from` and added-observers for `to`
+    /// once the move is complete.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The component now belongs to `to`
+    /// * `Err(ComponentError::EntityNotFound { .. })` if either entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::NotFound { .. })` if `from` doesn't have the component
+    /// * `Err(ComponentError::AlreadyExists { .. })` if `to` already has one
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, World};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Gold { amount: u32 }
+    /// impl Component for Gold {}
+    ///
+    /// let mut world = World::new();
+    /// let corpse = world.spawn_entity();
+    /// let player = world.spawn_entity();
+    /// world.add_component(corpse, Gold { amount: 12 }).unwrap();
+    ///
+    /// world.transfer_component::<Gold>(corpse, player).unwrap();
+    ///
+    /// assert!(!world.has_component::<Gold>(corpse));
+    /// assert_eq!(world.get_component::<Gold>(player), Some(&Gold { amount: 12 }));
+    /// ```
+    pub fn transfer_component<T: Component>(
+        &mut self,
+        from: crate::Entity,
+        to: crate::Entity,
+    ) -> Result<(), ComponentError> {
+        for entity in [from, to] {
+            if !self.is_entity_active(entity) {
+                return Err(ComponentError::EntityNotFound {
+                    entity,
+                    type_name: T::type_name(),
+                });
+            }
+        }
+        if !self.has_component::<T>(from) {
+            return Err(ComponentError::NotFound {
+                entity: from,
+                type_name: T::type_name(),
+            });
+        }
+        if self.has_component::<T>(to) {
+            return Err(ComponentError::AlreadyExists {
+                entity: to,
+                type_name: T::type_name(),
+            });
+        }
+
+        self.record_component::<T>(from);
+        self.record_component::<T>(to);
+        let entities_in_reverse_index = self.get_or_create_reverse_index::<T>();
+        entities_in_reverse_index.remove(&from);
+        entities_in_reverse_index.insert(to);
+
+        let storage = self.get_storage_mut::<T>();
+        let component = storage.remove(from).expect("component is present");
+        storage.insert(to, component)?;
+        self.bump_generation();
+        self.unmark::<T>(from);
+        self.mark_added::<T>(to);
+        self.reindex::<T>(from);
+        self.reindex::<T>(to);
+        self.subscriptions.component_removed::<T>(from);
+        self.subscriptions.component_added::<T>(to);
+
+        if let Some(component) = self.get_storage::<T>().and_then(|s| s.get(to)) {
+            self.notify_removed(from, component);
+        }
+        self.notify_added::<T>(to);
+        Ok(())
+    }
+
+    /// Exchanges the `T` components of two entities.
+    ///
+    /// Both entities keep a `T`, so indexes and observers are unaffected and
+    /// the values are moved rather than cloned. Both components are marked as
+    /// changed. Swapping an entity with itself is a no-op.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The components were swapped
+    /// * `Err(ComponentError::EntityNotFound { .. })` if either entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::NotFound { .. })` if either entity doesn't have the component
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, World};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Priority(u32);
+    /// impl Component for Priority {}
+    ///
+    /// let mut world = World::new();
+    /// let north_tower = world.spawn_entity();
+    /// let south_tower = world.spawn_entity();
+    /// world.add_component(north_tower, Priority(1)).unwrap();
+    /// world.add_component(south_tower, Priority(2)).unwrap();
+    ///
+    /// world.swap_component::<Priority>(north_tower, south_tower).unwrap();
+    ///
+    /// assert_eq!(world.get_component::<Priority>(north_tower), Some(&Priority(2)));
+    /// assert_eq!(world.get_component::<Priority>(south_tower), Some(&Priority(1)));
+    /// ```
+    pub fn swap_component<T: Component>(
+        &mut self,
+        a: crate::Entity,
+        b: crate::Entity,
+    ) -> Result<(), ComponentError> {
+        for entity in [a, b] {
+            if !self.is_entity_active(entity) {
+                return Err(ComponentError::EntityNotFound {
+                    entity,
+                    type_name: T::type_name(),
+                });
+            }
+        }
+        for entity in [a, b] {
+            if !self.has_component::<T>(entity) {
+                return Err(ComponentError::NotFound {
+                    entity,
+                    type_name: T::type_name(),
+                });
+            }
+        }
+        if a == b {
+            return Ok(());
+        }
+
+        self.record_component::<T>(a);
+        self.record_component::<T>(b);
+        let storage = self.get_storage_mut::<T>();
+        let component_a = storage.remove(a).expect("component is present");
+        let component_b = storage
+            .insert_or_update(b, component_a)
+            .expect("component is present");
+        storage.insert_or_update(a, component_b);
+        for entity in [a, b] {
+            self.mark_changed::<T>(entity);
+            self.reindex::<T>(entity);
+        }
+        Ok(())
+    }
+
     /// Checks if an entity has a specific component type.
     ///
     /// Returns `false` if the entity doesn't exist, has been deleted, or doesn't
@@ -287,26 +927,245 @@ impl World {
             return None;
         }
 
+        self.record_component::<T>(entity);
         let entities_in_reverse_index = self.get_or_create_reverse_index::<T>();
         entities_in_reverse_index.remove(&entity);
-        self.get_storage_mut::<T>().remove(entity)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{Component, ComponentError};
-
-    #[derive(Debug, Clone, PartialEq)]
-    struct Position {
-        x: f32,
-        y: f32,
+        let removed = self.get_storage_mut::<T>().remove(entity)?;
+        self.bump_generation();
+        self.unmark::<T>(entity);
+        self.reindex::<T>(entity);
+        self.subscriptions.component_removed::<T>(entity);
+
+        self.notify_removed(entity, &removed);
+        Some(removed)
     }
-    impl Component for Position {}
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Health {
+    /// Returns the component types currently attached to an entity.
+    ///
+    /// Useful for debugging and inspection tools. Ephemeral components and
+    /// resources are not included. The order of the returned types is unspecified.
+    ///
+    /// # Parameters
+    /// * `entity` - The entity to inspect
+    ///
+    /// # Returns
+    /// The `TypeId` of every component the entity holds, or an empty vector if the
+    /// entity has no components, doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    /// use std::any::TypeId;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Position { x: 1.0, y: 2.0 }).unwrap();
+    ///
+    /// assert_eq!(world.entity_component_types(entity), vec![TypeId::of::<Position>()]);
+    /// ```
+    pub fn entity_component_types(&self, entity: crate::Entity) -> Vec<std::any::TypeId> {
+        if !self.is_entity_active(entity) {
+            return Vec::new();
+        }
+
+        self.reverse_component_index
+            .iter()
+            .filter(|(_, entities)| entities.contains(&entity))
+            .map(|(&type_id, _)| type_id)
+            .collect()
+    }
+
+    /// Returns the type names of the components currently attached to an entity.
+    ///
+    /// This is the human-readable counterpart of [`World::entity_component_types`],
+    /// meant for display (e.g. an `examine` command or debug dumps). Names are the
+    /// full paths reported by `std::any::type_name` and are sorted alphabetically.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    ///
+    /// let names = world.entity_component_names(entity);
+    /// assert_eq!(names.len(), 1);
+    /// assert!(names[0].ends_with("Health"));
+    /// ```
+    pub fn entity_component_names(&self, entity: crate::Entity) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self
+            .entity_component_types(entity)
+            .iter()
+            .filter_map(|type_id| self.component_storages.get(type_id))
+            .map(|storage| storage.component_type_name())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Checks if an entity has at least one component.
+    ///
+    /// # Returns
+    /// * `true` if the entity is alive and holds any component
+    /// * `false` if it holds none, doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// assert!(!world.has_any_component(entity));
+    ///
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    /// assert!(world.has_any_component(entity));
+    /// ```
+    pub fn has_any_component(&self, entity: crate::Entity) -> bool {
+        self.is_entity_active(entity)
+            && self
+                .reverse_component_index
+                .values()
+                .any(|entities| entities.contains(&entity))
+    }
+
+    /// Iterates over every live entity that has a `T` component.
+    ///
+    /// A fast path for unfiltered single-component iteration: it walks the
+    /// component storage directly instead of building the candidate set from the
+    /// reverse index like [`crate::Query::iter`] does. It yields the same pairs
+    /// as `Query::<T>::new().iter(world)`, in unspecified order. Use a `Query` as
+    /// soon as filters are needed.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let alive = world.spawn_entity();
+    /// world.add_component(alive, Health { value: 10 }).unwrap();
+    /// let dead = world.spawn_entity();
+    /// world.add_component(dead, Health { value: 0 }).unwrap();
+    /// world.delete_entity(dead);
+    ///
+    /// let total: u32 = world.iter_components::<Health>().map(|(_, h)| h.value).sum();
+    /// assert_eq!(total, 10);
+    /// ```
+    pub fn iter_components<T: Component>(&self) -> impl Iterator<Item = (crate::Entity, &T)> {
+        self.get_storage::<T>()
+            .into_iter()
+            .flat_map(|storage| storage.iter())
+            .filter(|(entity, _)| !self.soft_deleted_entities.contains(entity))
+    }
+
+    /// Iterates mutably over every live entity that has a `T` component.
+    ///
+    /// The mutable counterpart of [`World::iter_components`], for bulk in-place
+    /// edits without a `Query`. Only values change, never which entities have
+    /// the component, so the world's generation stays the same. Every yielded
+    /// component is marked as changed for [`crate::Query::iter_changed`], whether
+    /// or not it is actually written to.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let hero = world.spawn_entity();
+    /// world.add_component(hero, Health { value: 10 }).unwrap();
+    ///
+    /// for (_, health) in world.iter_components_mut::<Health>() {
+    ///     health.value += 5;
+    /// }
+    ///
+    /// assert_eq!(world.get_component::<Health>(hero), Some(&Health { value: 15 }));
+    /// ```
+    pub fn iter_components_mut<T: Component>(
+        &mut self,
+    ) -> impl Iterator<Item = (crate::Entity, &mut T)> {
+        self.invalidate_index::<T>();
+        self.record_components::<T>();
+        let type_id = std::any::TypeId::of::<T>();
+        let soft_deleted_entities = &self.soft_deleted_entities;
+        let changed = self.changed_this_tick.entry(type_id).or_default();
+
+        self.component_storages
+            .get_mut(&type_id)
+            .and_then(|storage| downcast_storage_mut::<T>(storage.as_mut()))
+            .into_iter()
+            .flat_map(|storage| storage.iter_mut())
+            .filter(move |(entity, _)| !soft_deleted_entities.contains(entity))
+            .inspect(move |(entity, _)| {
+                changed.insert(*entity);
+            })
+    }
+
+    /// Reserves capacity for at least `additional` more components of type `T`.
+    ///
+    /// Call this before adding many components of the same type to avoid repeated
+    /// reallocations of the underlying storage. This is purely a performance hint
+    /// and has no observable effect on the world's contents.
+    ///
+    /// # Parameters
+    /// * `additional` - The number of components expected to be added
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.reserve_entities(1000);
+    /// world.reserve::<Position>(1000);
+    ///
+    /// for i in 0..1000 {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Position { x: i as f32, y: 0.0 }).unwrap();
+    /// }
+    /// ```
+    pub fn reserve<T: Component>(&mut self, additional: usize) {
+        self.get_or_create_reverse_index::<T>().reserve(additional);
+        self.get_storage_mut::<T>().reserve(additional);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, ComponentError};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
         value: u32,
     }
     impl Component for Health {}
@@ -342,10 +1201,7 @@ mod tests {
 
         // Add same component type again - should fail
         let result = world.add_component(entity, Position { x: 2.0, y: 2.0 });
-        assert!(matches!(
-            result,
-            Err(ComponentError::ComponentAlreadyExists)
-        ));
+        assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
     }
 
     #[test]
@@ -356,7 +1212,7 @@ mod tests {
 
         // Try to add component to entity from different world
         let result = world.add_component(other_entity, Position { x: 1.0, y: 1.0 });
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
     }
 
     #[test]
@@ -367,7 +1223,7 @@ mod tests {
         world.delete_entity(entity);
 
         let result = world.add_component(entity, Position { x: 1.0, y: 1.0 });
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
     }
 
     #[test]
@@ -438,7 +1294,7 @@ mod tests {
         let entity = world.spawn_entity();
 
         let result = world.update_component::<Health, _>(entity, |health| health);
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::NotFound { .. })));
     }
 
     #[test]
@@ -448,7 +1304,7 @@ mod tests {
         let other_entity = other_world.spawn_entity();
 
         let result = world.update_component::<Health, _>(other_entity, |health| health);
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
     }
 
     #[test]
@@ -460,7 +1316,56 @@ mod tests {
         world.delete_entity(entity);
 
         let result = world.update_component::<Health, _>(entity, |health| health);
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
+    }
+
+    #[test]
+    fn test_update_component_in_place() {
+        #[derive(Debug, PartialEq)]
+        struct Inventory {
+            items: Vec<u32>,
+        }
+        impl Component for Inventory {}
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Inventory { items: vec![1] })
+            .unwrap();
+        world.clear_change_tracking();
+        let generation = world.generation();
+
+        assert_eq!(
+            world.update_component_in_place::<Inventory, _>(entity, |inventory| {
+                inventory.items.push(2)
+            }),
+            Ok(())
+        );
+        assert_eq!(
+            world.get_component::<Inventory>(entity),
+            Some(&Inventory { items: vec![1, 2] })
+        );
+        assert!(world.was_changed::<Inventory>(entity));
+        assert!(!world.was_added::<Inventory>(entity));
+        assert_eq!(world.generation(), generation);
+
+        let bare = world.spawn_entity();
+        assert_eq!(
+            world.update_component_in_place::<Inventory, _>(bare, |_| unreachable!()),
+            Err(ComponentError::NotFound {
+                entity: bare,
+                type_name: std::any::type_name::<Inventory>(),
+            })
+        );
+
+        world.delete_entity(entity);
+        assert_eq!(
+            world.update_component_in_place::<Inventory, _>(entity, |_| unreachable!()),
+            Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: std::any::type_name::<Inventory>(),
+            })
+        );
     }
 
     #[test]
@@ -514,6 +1419,24 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[derive(Debug, Clone, PartialEq)]
+    struct HitEvent;
+    impl Component for HitEvent {
+        fn ephemeral_only() -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_replace_component_rejects_ephemeral_only() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        assert_eq!(world.replace_component(entity, HitEvent), None);
+        assert!(!world.has_component::<HitEvent>(entity));
+        assert_eq!(crate::Query::<HitEvent>::new().iter(&world).count(), 0);
+    }
+
     #[test]
     fn test_has_component_exists() {
         let mut world = World::new();
@@ -712,9 +1635,743 @@ mod tests {
         );
 
         let result = world.add_component(entity, Position { x: 3.0, y: 3.0 });
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
         let result = world.update_component::<Position, _>(entity, |pos| pos);
-        assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
+    }
+
+    #[test]
+    fn test_error_variants_distinguish_missing_entity_from_missing_component() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        // Live entity without the component
+        let result = world.update_component::<Health, _>(entity, |h| h);
+        assert_eq!(
+            result,
+            Err(ComponentError::NotFound {
+                entity,
+                type_name: std::any::type_name::<Health>()
+            })
+        );
+
+        // Live entity that already has the component
+        world.add_component(entity, Health { value: 10 }).unwrap();
+        let result = world.add_component(entity, Health { value: 20 });
+        assert_eq!(
+            result,
+            Err(ComponentError::AlreadyExists {
+                entity,
+                type_name: std::any::type_name::<Health>()
+            })
+        );
+
+        // Deleted entity, whether or not it had the component
+        world.delete_entity(entity);
+        let result = world.update_component::<Health, _>(entity, |h| h);
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
+        let result = world.update_component::<Velocity, _>(entity, |v| v);
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
+        let result = world.add_component(entity, Velocity { dx: 0.0, dy: 0.0 });
+        assert_eq!(
+            result,
+            Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: std::any::type_name::<Velocity>()
+            })
+        );
+    }
+
+    #[test]
+    fn test_reserve_grows_storage_capacity() {
+        let mut world = World::new();
+
+        world.reserve::<Position>(1000);
+
+        let storage = world.get_storage::<Position>().unwrap();
+        assert!(storage.capacity() >= 1000);
+        assert_eq!(storage.entities().count(), 0);
+
+        let reverse_index = world
+            .reverse_component_index
+            .get(&std::any::TypeId::of::<Position>())
+            .unwrap();
+        assert!(reverse_index.capacity() >= 1000);
+    }
+
+    #[test]
+    fn test_reserve_has_no_behavior_change() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 10 }).unwrap();
+
+        world.reserve::<Health>(500);
+        world.reserve::<Velocity>(0);
+
+        assert_eq!(world.get_component::<Health>(entity).unwrap().value, 10);
+        assert!(!world.has_component::<Velocity>(entity));
+        assert!(world.get_storage::<Health>().unwrap().capacity() >= 501);
+    }
+
+    #[test]
+    fn test_entity_component_types_multiple_components() {
+        use std::any::TypeId;
+        use std::collections::HashSet;
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        let other = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_component(entity, Health { value: 1 }).unwrap();
+        world
+            .add_component(other, Velocity { dx: 1.0, dy: 1.0 })
+            .unwrap();
+
+        let types: HashSet<TypeId> = world.entity_component_types(entity).into_iter().collect();
+        assert_eq!(
+            types,
+            HashSet::from([TypeId::of::<Position>(), TypeId::of::<Health>()])
+        );
+        assert!(world.has_any_component(entity));
+
+        let names = world.entity_component_names(entity);
+        assert_eq!(names.len(), 2);
+        assert!(names[0].ends_with("Health"));
+        assert!(names[1].ends_with("Position"));
+
+        // Removed components disappear from the listing
+        world.remove_component::<Health>(entity);
+        assert_eq!(
+            world.entity_component_types(entity),
+            vec![TypeId::of::<Position>()]
+        );
+    }
+
+    #[test]
+    fn test_entity_component_types_no_components() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.insert_resource(Health { value: 5 });
+
+        assert!(world.entity_component_types(entity).is_empty());
+        assert!(world.entity_component_names(entity).is_empty());
+        assert!(!world.has_any_component(entity));
+    }
+
+    #[test]
+    fn test_entity_component_types_deleted_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 1 }).unwrap();
+
+        world.delete_entity(entity);
+
+        // Soft-deleted: still in the reverse index but must not be reported
+        assert!(world.entity_component_types(entity).is_empty());
+        assert!(!world.has_any_component(entity));
+
+        world.cleanup_deleted_entities();
+        assert!(world.entity_component_types(entity).is_empty());
+    }
+
+    #[test]
+    fn test_iter_components_matches_query() {
+        let mut world = World::new();
+        let mut expected = Vec::new();
+        for i in 0..20 {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    Position {
+                        x: i as f32,
+                        y: 0.0,
+                    },
+                )
+                .unwrap();
+            expected.push(entity);
+        }
+        let bare = world.spawn_entity();
+        world.add_component(bare, Health { value: 1 }).unwrap();
+        // Soft-deleted and removed components are skipped
+        world.delete_entity(expected[3]);
+        world.remove_component::<Position>(expected[7]);
+
+        let fast: std::collections::HashSet<_> = world
+            .iter_components::<Position>()
+            .map(|(entity, position)| (entity, position.x as u32))
+            .collect();
+        let queried: std::collections::HashSet<_> = crate::Query::<Position>::new()
+            .iter(&world)
+            .map(|(entity, position)| (entity, position.x as u32))
+            .collect();
+
+        assert_eq!(fast, queried);
+        assert_eq!(fast.len(), 18);
+    }
+
+    #[test]
+    fn test_iter_components_unknown_type_is_empty() {
+        let world = World::new();
+        assert_eq!(world.iter_components::<Health>().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_components_mut_matches_query_and_skips_deleted() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..10)
+            .map(|i| {
+                let entity = world.spawn_entity();
+                world.add_component(entity, Health { value: i }).unwrap();
+                entity
+            })
+            .collect();
+        world.delete_entity(entities[2]);
+        world.clear_change_tracking();
+        let generation = world.generation();
+
+        for (_, health) in world.iter_components_mut::<Health>() {
+            health.value += 100;
+        }
+
+        let queried: std::collections::HashSet<_> = crate::Query::<Health>::new()
+            .iter(&world)
+            .map(|(entity, health)| (entity, health.value))
+            .collect();
+        let fast: std::collections::HashSet<_> = world
+            .iter_components::<Health>()
+            .map(|(entity, health)| (entity, health.value))
+            .collect();
+        assert_eq!(fast, queried);
+        assert_eq!(fast.len(), 9);
+        assert!(fast.iter().all(|(_, value)| *value >= 100));
+
+        world.cleanup_deleted_entities();
+        assert_eq!(world.generation(), generation);
+        assert!(world.was_changed::<Health>(entities[0]));
+        assert!(!world.was_added::<Health>(entities[0]));
+        assert_eq!(crate::Query::<Health>::new().count_changed(&world), 9);
+    }
+
+    #[test]
+    fn test_iter_components_mut_unknown_type_is_empty() {
+        let mut world = World::new();
+        assert_eq!(world.iter_components_mut::<Health>().count(), 0);
+    }
+
+    #[test]
+    fn test_add_or_replace_component_outcomes() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        // Freshly added
+        let generation = world.generation();
+        assert_eq!(
+            world.add_or_replace_component(entity, Health { value: 10 }),
+            Ok(None)
+        );
+        assert_ne!(world.generation(), generation);
+        assert!(world.was_added::<Health>(entity));
+
+        // Replaced
+        world.clear_change_tracking();
+        let generation = world.generation();
+        assert_eq!(
+            world.add_or_replace_component(entity, Health { value: 20 }),
+            Ok(Some(Health { value: 10 }))
+        );
+        assert_eq!(world.generation(), generation);
+        assert!(!world.was_added::<Health>(entity));
+        assert!(world.was_changed::<Health>(entity));
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 20 })
+        );
+
+        // Dead entity
+        world.delete_entity(entity);
+        let result = world.add_or_replace_component(entity, Health { value: 30 });
+        assert_eq!(
+            result,
+            Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_or_replace_component_rejects_ephemeral_only() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        assert_eq!(
+            world.add_or_replace_component(entity, HitEvent),
+            Err(ComponentError::EphemeralOnly {
+                entity,
+                type_name: std::any::type_name::<HitEvent>(),
+            })
+        );
+        assert!(!world.has_component::<HitEvent>(entity));
+    }
+
+    #[test]
+    fn test_errors_report_component_type_name() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Stamina(u32);
+        impl Component for Stamina {
+            fn type_name() -> &'static str {
+                "stamina"
+            }
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        assert_eq!(
+            world.update_component::<Stamina, _>(entity, |stamina| stamina),
+            Err(ComponentError::NotFound {
+                entity,
+                type_name: "stamina",
+            })
+        );
+        world.add_component(entity, Stamina(5)).unwrap();
+        assert_eq!(
+            world.add_component(entity, Stamina(6)),
+            Err(ComponentError::AlreadyExists {
+                entity,
+                type_name: "stamina",
+            })
+        );
+        assert_eq!(
+            world.component_type_name(std::any::TypeId::of::<Stamina>()),
+            Some("stamina")
+        );
+    }
+
+    #[test]
+    fn test_upsert_component_outcomes() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        let generation = world.generation();
+        assert_eq!(
+            world.upsert_component(entity, Health { value: 10 }),
+            Ok(UpsertOutcome::Added)
+        );
+        assert_ne!(world.generation(), generation);
+        assert!(world.was_added::<Health>(entity));
+        assert_eq!(
+            crate::Query::<Health>::new().iter(&world).count(),
+            1,
+            "added components are visible to queries"
+        );
+
+        world.clear_change_tracking();
+        let generation = world.generation();
+        assert_eq!(
+            world.upsert_component(entity, Health { value: 20 }),
+            Ok(UpsertOutcome::Replaced)
+        );
+        assert_eq!(world.generation(), generation);
+        assert!(world.was_changed::<Health>(entity));
+        assert!(!world.was_added::<Health>(entity));
+        assert_eq!(world.get_component::<Health>(entity).unwrap().value, 20);
+
+        world.delete_entity(entity);
+        assert!(matches!(
+            world.upsert_component(entity, Health { value: 30 }),
+            Err(ComponentError::EntityNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_upsert_component_without_clone() {
+        #[derive(Debug, PartialEq)]
+        struct Session {
+            token: String,
+        }
+        impl Component for Session {}
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        assert_eq!(
+            world.upsert_component(
+                entity,
+                Session {
+                    token: "first".to_string()
+                }
+            ),
+            Ok(UpsertOutcome::Added)
+        );
+        assert_eq!(
+            world.upsert_component(
+                entity,
+                Session {
+                    token: "second".to_string()
+                }
+            ),
+            Ok(UpsertOutcome::Replaced)
+        );
+        assert_eq!(
+            world.get_component::<Session>(entity).unwrap().token,
+            "second"
+        );
+    }
+
+    #[test]
+    fn test_replace_existing_component_outcomes() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        // Absent: nothing is added
+        assert_eq!(
+            world.replace_existing_component(entity, Health { value: 10 }),
+            Err(ComponentError::NotFound {
+                entity,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+        assert!(!world.has_component::<Health>(entity));
+        assert!(!world.was_added::<Health>(entity));
+
+        // Present
+        world.add_component(entity, Health { value: 10 }).unwrap();
+        world.clear_change_tracking();
+        assert_eq!(
+            world.replace_existing_component(entity, Health { value: 20 }),
+            Ok(Health { value: 10 })
+        );
+        assert!(world.was_changed::<Health>(entity));
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 20 })
+        );
+
+        // Dead entity
+        world.delete_entity(entity);
+        assert_eq!(
+            world.replace_existing_component(entity, Health { value: 30 }),
+            Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_transfer_component_moves_value_and_index() {
+        let mut world = World::new();
+        let corpse = world.spawn_entity();
+        let player = world.spawn_entity();
+        world.add_component(corpse, Health { value: 7 }).unwrap();
+        world.clear_change_tracking();
+        let generation = world.generation();
+
+        assert_eq!(world.transfer_component::<Health>(corpse, player), Ok(()));
+
+        assert!(!world.has_component::<Health>(corpse));
+        assert_eq!(
+            world.get_component::<Health>(player),
+            Some(&Health { value: 7 })
+        );
+        assert_ne!(world.generation(), generation);
+        assert!(world.was_added::<Health>(player));
+        let holders: Vec<_> = crate::Query::<Health>::new()
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(holders, vec![player]);
+    }
+
+    #[test]
+    fn test_transfer_component_to_entity_that_has_it_fails() {
+        let mut world = World::new();
+        let corpse = world.spawn_entity();
+        let player = world.spawn_entity();
+        world.add_component(corpse, Health { value: 7 }).unwrap();
+        world.add_component(player, Health { value: 50 }).unwrap();
+
+        assert_eq!(
+            world.transfer_component::<Health>(corpse, player),
+            Err(ComponentError::AlreadyExists {
+                entity: player,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+        assert_eq!(
+            world.transfer_component::<Position>(corpse, player),
+            Err(ComponentError::NotFound {
+                entity: corpse,
+                type_name: std::any::type_name::<Position>(),
+            })
+        );
+
+        // Nothing moved
+        assert_eq!(
+            world.get_component::<Health>(corpse),
+            Some(&Health { value: 7 })
+        );
+        assert_eq!(
+            world.get_component::<Health>(player),
+            Some(&Health { value: 50 })
+        );
+    }
+
+    #[test]
+    fn test_transfer_component_with_inactive_entity_fails() {
+        let mut world = World::new();
+        let corpse = world.spawn_entity();
+        let player = world.spawn_entity();
+        world.add_component(corpse, Health { value: 7 }).unwrap();
+
+        world.delete_entity(player);
+        assert_eq!(
+            world.transfer_component::<Health>(corpse, player),
+            Err(ComponentError::EntityNotFound {
+                entity: player,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+        assert!(world.has_component::<Health>(corpse));
+
+        let looter = world.spawn_entity();
+        world.delete_entity(corpse);
+        assert_eq!(
+            world.transfer_component::<Health>(corpse, looter),
+            Err(ComponentError::EntityNotFound {
+                entity: corpse,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+        assert!(!world.has_component::<Health>(looter));
+    }
+
+    #[test]
+    fn test_swap_component_exchanges_values() {
+        let mut world = World::new();
+        let north = world.spawn_entity();
+        let south = world.spawn_entity();
+        world.add_component(north, Health { value: 1 }).unwrap();
+        world.add_component(south, Health { value: 2 }).unwrap();
+        world.clear_change_tracking();
+        let generation = world.generation();
+
+        assert_eq!(world.swap_component::<Health>(north, south), Ok(()));
+
+        assert_eq!(
+            world.get_component::<Health>(north),
+            Some(&Health { value: 2 })
+        );
+        assert_eq!(
+            world.get_component::<Health>(south),
+            Some(&Health { value: 1 })
+        );
+        assert!(world.was_changed::<Health>(north));
+        assert!(world.was_changed::<Health>(south));
+        assert_eq!(world.generation(), generation);
+        assert_eq!(crate::Query::<Health>::new().iter(&world).len(), 2);
+
+        assert_eq!(world.swap_component::<Health>(north, north), Ok(()));
+        assert_eq!(
+            world.get_component::<Health>(north),
+            Some(&Health { value: 2 })
+        );
+    }
+
+    #[test]
+    fn test_swap_component_without_component_fails() {
+        let mut world = World::new();
+        let north = world.spawn_entity();
+        let south = world.spawn_entity();
+        world.add_component(north, Health { value: 1 }).unwrap();
+
+        let missing = Err(ComponentError::NotFound {
+            entity: south,
+            type_name: std::any::type_name::<Health>(),
+        });
+        assert_eq!(world.swap_component::<Health>(north, south), missing);
+        assert_eq!(world.swap_component::<Health>(south, north), missing);
+        assert_eq!(
+            world.swap_component::<Position>(north, south),
+            Err(ComponentError::NotFound {
+                entity: north,
+                type_name: std::any::type_name::<Position>(),
+            })
+        );
+
+        // Nothing moved
+        assert_eq!(
+            world.get_component::<Health>(north),
+            Some(&Health { value: 1 })
+        );
+        assert!(!world.has_component::<Health>(south));
+    }
+
+    #[test]
+    fn test_swap_component_with_inactive_entity_fails() {
+        let mut world = World::new();
+        let north = world.spawn_entity();
+        let south = world.spawn_entity();
+        world.add_component(north, Health { value: 1 }).unwrap();
+        world.add_component(south, Health { value: 2 }).unwrap();
+
+        world.delete_entity(south);
+        let inactive = Err(ComponentError::EntityNotFound {
+            entity: south,
+            type_name: std::any::type_name::<Health>(),
+        });
+        assert_eq!(world.swap_component::<Health>(north, south), inactive);
+        assert_eq!(world.swap_component::<Health>(south, north), inactive);
+
+        world.delete_entity(north);
+        assert_eq!(
+            world.swap_component::<Health>(north, south),
+            Err(ComponentError::EntityNotFound {
+                entity: north,
+                type_name: std::any::type_name::<Health>(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_all_only_touches_matching_entities() {
+        let mut world = World::new();
+        let moving: Vec<_> = (0..5)
+            .map(|i| {
+                let entity = world.spawn_entity();
+                world
+                    .add_component(entity, Health { value: 10 + i })
+                    .unwrap();
+                world
+                    .add_component(entity, Velocity { dx: 1.0, dy: 0.0 })
+                    .unwrap();
+                entity
+            })
+            .collect();
+        let still = world.spawn_entity();
+        world.add_component(still, Health { value: 10 }).unwrap();
+        let deleted = world.spawn_entity();
+        world.add_component(deleted, Health { value: 10 }).unwrap();
+        world
+            .add_component(deleted, Velocity { dx: 1.0, dy: 0.0 })
+            .unwrap();
+        world.delete_entity(deleted);
+        world.clear_change_tracking();
+        let generation = world.generation();
+
+        let query = crate::Query::<Health>::new().with::<Velocity>();
+        let updated = world.update_all(&query, |health| Health {
+            value: health.value - 1,
+        });
+
+        assert_eq!(updated, 5);
+        for (i, entity) in moving.iter().enumerate() {
+            assert_eq!(
+                world.get_component::<Health>(*entity),
+                Some(&Health {
+                    value: 9 + i as u32
+                })
+            );
+            assert!(world.was_changed::<Health>(*entity));
+        }
+        assert_eq!(
+            world.get_component::<Health>(still),
+            Some(&Health { value: 10 })
+        );
+        assert!(!world.was_changed::<Health>(still));
+        assert_eq!(
+            world.get_component_including_deleted::<Health>(deleted),
+            Some(&Health { value: 10 })
+        );
+        assert_eq!(world.generation(), generation);
+    }
+
+    #[test]
+    fn test_update_all_without_matches() {
+        let mut world = World::new();
+        let mut calls = 0;
+
+        let updated = world.update_all(&crate::Query::<Health>::new(), |health| {
+            calls += 1;
+            health
+        });
+
+        assert_eq!((updated, calls), (0, 0));
+    }
+
+    #[test]
+    fn test_get_components2() {
+        let mut world = World::new();
+        let both = world.spawn_entity();
+        let only_position = world.spawn_entity();
+        let dead = world.spawn_entity();
+        for entity in [both, only_position, dead] {
+            world
+                .add_component(entity, Position { x: 1.0, y: 2.0 })
+                .unwrap();
+        }
+        for entity in [both, dead] {
+            world
+                .add_component(entity, Velocity { dx: 3.0, dy: 4.0 })
+                .unwrap();
+        }
+        world.delete_entity(dead);
+
+        assert_eq!(
+            world.get_components2::<Position, Velocity>(both),
+            Some((&Position { x: 1.0, y: 2.0 }, &Velocity { dx: 3.0, dy: 4.0 }))
+        );
+        assert_eq!(
+            world.get_components2::<Velocity, Position>(both),
+            Some((&Velocity { dx: 3.0, dy: 4.0 }, &Position { x: 1.0, y: 2.0 }))
+        );
+        assert_eq!(
+            world.get_components2::<Position, Velocity>(only_position),
+            None
+        );
+        assert_eq!(world.get_components2::<Position, Health>(both), None);
+        assert_eq!(world.get_components2::<Position, Velocity>(dead), None);
+    }
+
+    #[test]
+    fn test_get_components3() {
+        let mut world = World::new();
+        let all = world.spawn_entity();
+        let two = world.spawn_entity();
+        let dead = world.spawn_entity();
+        for entity in [all, two, dead] {
+            world
+                .add_component(entity, Position { x: 1.0, y: 2.0 })
+                .unwrap();
+            world
+                .add_component(entity, Velocity { dx: 3.0, dy: 4.0 })
+                .unwrap();
+        }
+        for entity in [all, dead] {
+            world.add_component(entity, Health { value: 7 }).unwrap();
+        }
+        world.delete_entity(dead);
+
+        assert_eq!(
+            world.get_components3::<Position, Velocity, Health>(all),
+            Some((
+                &Position { x: 1.0, y: 2.0 },
+                &Velocity { dx: 3.0, dy: 4.0 },
+                &Health { value: 7 }
+            ))
+        );
+        assert_eq!(
+            world.get_components3::<Position, Velocity, Health>(two),
+            None
+        );
+        assert_eq!(
+            world.get_components3::<Position, Velocity, Health>(dead),
+            None
+        );
     }
 }
@@ -0,0 +1,435 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{AnyStorage, Entity};
+
+use super::World;
+
+/// The kind of broken invariant a [`ConsistencyViolation`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ViolationKind {
+    /// A storage holds a component of an entity that is neither live nor
+    /// awaiting cleanup.
+    OrphanedComponent,
+    /// A storage holds a component the reverse index doesn't list, so
+    /// queries miss it.
+    MissingFromIndex,
+    /// The reverse index lists a component no storage holds, so queries
+    /// yield an entity whose component can't be fetched.
+    DanglingIndexEntry,
+    /// The hidden resource entity appears in a reverse index, so queries
+    /// would see a resource as a component.
+    IndexedResourceEntity,
+    /// Ephemeral data was left behind by `clean_ephemeral_storage()`.
+    EphemeralNotCleaned,
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            ViolationKind::OrphanedComponent => "orphaned component",
+            ViolationKind::MissingFromIndex => "component missing from reverse index",
+            ViolationKind::DanglingIndexEntry => "dangling reverse index entry",
+            ViolationKind::IndexedResourceEntity => "resource entity in reverse index",
+            ViolationKind::EphemeralNotCleaned => "ephemeral data not cleaned",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// One broken invariant found by [`World::check_consistency`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyViolation {
+    pub kind: ViolationKind,
+    pub entity: Entity,
+    /// The component's type name, see [`Component::type_name`](crate::Component::type_name).
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for ConsistencyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: entity {} component {}",
+            self.kind, self.entity, self.type_name
+        )
+    }
+}
+
+/// The result of [`World::check_consistency`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// Every violation found, sorted by type name, then kind.
+    pub violations: Vec<ConsistencyViolation>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no violation was found.
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for ConsistencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_consistent() {
+            return writeln!(f, "World is consistent");
+        }
+        writeln!(f, "Consistency violations ({}):", self.violations.len())?;
+        for violation in &self.violations {
+            writeln!(f, "  {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl World {
+    /// Cross-checks the world's internal bookkeeping and reports every
+    /// inconsistency found.
+    ///
+    /// Verifies that storages only hold components of live or soft-deleted
+    /// entities, that the reverse component indexes agree with the storages
+    /// in both directions, for regular and ephemeral components alike, that
+    /// the hidden resource entity is never indexed, and that no ephemeral data
+    /// survived `clean_ephemeral_storage()`.
+    ///
+    /// A correct world never reports anything; a violation means a bug in the
+    /// world itself. This walks every storage and index, so it is meant for
+    /// tests and periodic health checks of long-running servers, not for hot
+    /// loops. Builds with debug assertions also run it automatically after
+    /// every `cleanup_deleted_entities()`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    /// world.delete_entity(entity);
+    ///
+    /// let report = world.check_consistency();
+    /// assert!(report.is_consistent(), "{report}");
+    /// ```
+    pub fn check_consistency(&self) -> ConsistencyReport {
+        let mut violations = Vec::new();
+
+        for storages in [&self.component_storages, &self.ephemeral_component_storages] {
+            for storage in storages.values() {
+                for entity in storage.stored_entities() {
+                    if !self.entities.contains(&entity)
+                        && !self.soft_deleted_entities.contains(&entity)
+                    {
+                        violations.push(ConsistencyViolation {
+                            kind: ViolationKind::OrphanedComponent,
+                            entity,
+                            type_name: storage.component_type_name(),
+                        });
+                    }
+                }
+            }
+        }
+
+        self.check_reverse_index(
+            &self.component_storages,
+            &self.reverse_component_index,
+            &mut violations,
+        );
+        self.check_reverse_index(
+            &self.ephemeral_component_storages,
+            &self.reverse_ephemeral_component_index,
+            &mut violations,
+        );
+
+        if self.ephemerals_cleaned {
+            for storages in [
+                &self.ephemeral_component_storages,
+                &self.ephemeral_resource_storages,
+                &self.ephemeral_event_storages,
+            ] {
+                for storage in storages.values() {
+                    for entity in storage.stored_entities() {
+                        violations.push(ConsistencyViolation {
+                            kind: ViolationKind::EphemeralNotCleaned,
+                            entity,
+                            type_name: storage.component_type_name(),
+                        });
+                    }
+                }
+            }
+        }
+
+        violations.sort_by(|a, b| {
+            (a.type_name, a.kind, a.entity.id()).cmp(&(b.type_name, b.kind, b.entity.id()))
+        });
+        ConsistencyReport { violations }
+    }
+
+    /// Checks that `index` lists exactly the entities `storages` hold.
+    fn check_reverse_index(
+        &self,
+        storages: &HashMap<TypeId, Box<dyn AnyStorage>>,
+        index: &HashMap<TypeId, HashSet<Entity>>,
+        violations: &mut Vec<ConsistencyViolation>,
+    ) {
+        let empty = HashSet::new();
+
+        for (type_id, storage) in storages {
+            let indexed = index.get(type_id).unwrap_or(&empty);
+            for entity in storage.stored_entities() {
+                if !indexed.contains(&entity) {
+                    violations.push(ConsistencyViolation {
+                        kind: ViolationKind::MissingFromIndex,
+                        entity,
+                        type_name: storage.component_type_name(),
+                    });
+                }
+            }
+        }
+
+        for (type_id, indexed) in index {
+            let storage = storages.get(type_id);
+            let type_name = storage
+                .map(|storage| storage.component_type_name())
+                .or_else(|| self.component_type_name(*type_id))
+                .unwrap_or("<unknown>");
+            for &entity in indexed {
+                if entity == self.resource_entity {
+                    violations.push(ConsistencyViolation {
+                        kind: ViolationKind::IndexedResourceEntity,
+                        entity,
+                        type_name,
+                    });
+                }
+                if !storage.is_some_and(|storage| storage.contains_entity(entity)) {
+                    violations.push(ConsistencyViolation {
+                        kind: ViolationKind::DanglingIndexEntry,
+                        entity,
+                        type_name,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Panics if [`World::check_consistency`] finds a violation.
+    #[cfg(debug_assertions)]
+    pub(super) fn assert_consistent(&self, context: &str) {
+        let report = self.check_consistency();
+        assert!(report.is_consistent(), "{context}: {report}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, HashMapComponentStorage};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Stunned {
+        turns: u32,
+    }
+    impl Component for Stunned {}
+
+    /// Writes `component` straight into a storage map, bypassing all bookkeeping.
+    fn corrupt_storage<T: Component>(
+        storages: &mut HashMap<TypeId, Box<dyn AnyStorage>>,
+        entity: Entity,
+        component: T,
+    ) {
+        use crate::ComponentStorage;
+
+        storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(HashMapComponentStorage::<T>::new()))
+            .as_any_mut()
+            .downcast_mut::<HashMapComponentStorage<T>>()
+            .unwrap()
+            .insert_or_update(entity, component);
+    }
+
+    fn kinds(world: &World) -> Vec<(ViolationKind, Entity)> {
+        world
+            .check_consistency()
+            .violations
+            .into_iter()
+            .map(|violation| (violation.kind, violation.entity))
+            .collect()
+    }
+
+    fn populated_world() -> (World, Entity) {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 10 }).unwrap();
+        world
+            .add_ephemeral_component(entity, Stunned { turns: 1 })
+            .unwrap();
+        world.insert_resource(Health { value: 99 });
+        (world, entity)
+    }
+
+    #[test]
+    fn test_normal_operations_stay_consistent() {
+        let (mut world, entity) = populated_world();
+        let other = world.spawn_entity();
+        world.add_component(other, Health { value: 5 }).unwrap();
+        assert!(world.check_consistency().is_consistent());
+
+        world.delete_entity(entity);
+        assert!(world.check_consistency().is_consistent());
+        world.cleanup_deleted_entities();
+        world.clean_ephemeral_storage();
+        world.remove_component::<Health>(other);
+
+        let report = world.check_consistency();
+        assert!(report.is_consistent(), "{report}");
+        assert_eq!(report.to_string(), "World is consistent\n");
+    }
+
+    #[test]
+    fn test_detects_orphaned_component() {
+        let (mut world, _) = populated_world();
+        let ghost = Entity::new();
+        corrupt_storage(&mut world.component_storages, ghost, Health { value: 1 });
+        world
+            .reverse_component_index
+            .get_mut(&TypeId::of::<Health>())
+            .unwrap()
+            .insert(ghost);
+
+        let report = world.check_consistency();
+        assert_eq!(
+            report.violations,
+            vec![ConsistencyViolation {
+                kind: ViolationKind::OrphanedComponent,
+                entity: ghost,
+                type_name: std::any::type_name::<Health>(),
+            }]
+        );
+        assert!(report
+            .to_string()
+            .contains(&format!("orphaned component: entity {ghost} component")));
+    }
+
+    #[test]
+    fn test_detects_reverse_index_disagreeing_with_storage() {
+        let (mut world, entity) = populated_world();
+        let other = world.spawn_entity();
+        world
+            .reverse_component_index
+            .get_mut(&TypeId::of::<Health>())
+            .unwrap()
+            .remove(&entity);
+        world
+            .reverse_component_index
+            .entry(TypeId::of::<Stunned>())
+            .or_default()
+            .insert(other);
+
+        assert_eq!(
+            kinds(&world),
+            vec![
+                (ViolationKind::MissingFromIndex, entity),
+                (ViolationKind::DanglingIndexEntry, other),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detects_ephemeral_index_disagreeing_with_storage() {
+        let (mut world, entity) = populated_world();
+        world
+            .reverse_ephemeral_component_index
+            .get_mut(&TypeId::of::<Stunned>())
+            .unwrap()
+            .remove(&entity);
+
+        assert_eq!(
+            kinds(&world),
+            vec![(ViolationKind::MissingFromIndex, entity)]
+        );
+    }
+
+    #[test]
+    fn test_detects_indexed_resource_entity() {
+        let (mut world, _) = populated_world();
+        let resource_entity = world.resource_entity;
+        world
+            .reverse_component_index
+            .get_mut(&TypeId::of::<Health>())
+            .unwrap()
+            .insert(resource_entity);
+
+        // The resource lives in the resource storage, not the component one
+        assert_eq!(
+            kinds(&world),
+            vec![
+                (ViolationKind::DanglingIndexEntry, resource_entity),
+                (ViolationKind::IndexedResourceEntity, resource_entity),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detects_ephemeral_data_surviving_clean() {
+        let (mut world, entity) = populated_world();
+        world.clean_ephemeral_storage();
+        assert!(world.check_consistency().is_consistent());
+
+        corrupt_storage(
+            &mut world.ephemeral_resource_storages,
+            world.resource_entity,
+            Stunned { turns: 2 },
+        );
+        corrupt_storage(
+            &mut world.ephemeral_component_storages,
+            entity,
+            Stunned { turns: 2 },
+        );
+        world
+            .reverse_ephemeral_component_index
+            .entry(TypeId::of::<Stunned>())
+            .or_default()
+            .insert(entity);
+
+        let mut found = kinds(&world);
+        found.sort_by_key(|(_, entity)| entity.id());
+        assert_eq!(
+            found,
+            vec![
+                (ViolationKind::EphemeralNotCleaned, world.resource_entity),
+                (ViolationKind::EphemeralNotCleaned, entity),
+            ]
+        );
+
+        // Ephemeral data written after the clean is expected
+        let other = world.spawn_entity();
+        world
+            .add_ephemeral_component(other, Stunned { turns: 3 })
+            .unwrap();
+        assert!(world.check_consistency().is_consistent());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "cleanup_deleted_entities")]
+    fn test_cleanup_asserts_consistency() {
+        let (mut world, entity) = populated_world();
+        let ghost = Entity::new();
+        corrupt_storage(&mut world.component_storages, ghost, Health { value: 1 });
+
+        world.delete_entity(entity);
+        world.cleanup_deleted_entities();
+    }
+}
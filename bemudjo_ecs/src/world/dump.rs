@@ -0,0 +1,357 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{AnyStorage, Component, Entity};
+
+use super::World;
+
+/// Marks a component or resource type whose `Debug` output may appear in a
+/// [`WorldDump`].
+///
+/// Opting in is explicit so that types holding secrets or huge buffers stay
+/// out of debug output. Implement the trait, then register the type with
+/// [`World::register_debug`].
+pub trait DebugComponent: Component + fmt::Debug {}
+
+/// Formats a type-erased component whose type is known to be a `DebugComponent`.
+pub(super) type DebugFn = fn(&dyn Any) -> String;
+
+fn debug_component<T: DebugComponent>(component: &dyn Any) -> String {
+    let component = component
+        .downcast_ref::<T>()
+        .expect("debug formatters are keyed by the TypeId of their component");
+    format!("{component:?}")
+}
+
+/// One component or resource in a [`WorldDump`], or in [`World::components_of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDump {
+    /// The type name, see [`Component::type_name`].
+    pub type_name: &'static str,
+    /// The `Debug` representation, or `None` if the type was not registered
+    /// with [`World::register_debug`].
+    pub debug: Option<String>,
+}
+
+/// One live entity in a [`WorldDump`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityDump {
+    pub entity: Entity,
+    /// The entity's label, see [`World::set_entity_label`].
+    pub label: Option<String>,
+    /// The entity's components, sorted by type name.
+    pub components: Vec<ComponentDump>,
+}
+
+/// A structured listing of a world's contents, as returned by [`World::dump`].
+///
+/// The `Display` implementation renders one line per entity and component,
+/// which is the format to print from a failing test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldDump {
+    /// Live entities, sorted by id.
+    pub entities: Vec<EntityDump>,
+    /// Global resources, sorted by type name.
+    pub resources: Vec<ComponentDump>,
+}
+
+impl WorldDump {
+    /// Returns the dump of `entity`, if it was live when the dump was taken.
+    pub fn entity(&self, entity: Entity) -> Option<&EntityDump> {
+        self.entities.iter().find(|dump| dump.entity == entity)
+    }
+}
+
+impl fmt::Display for ComponentDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.debug {
+            Some(debug) => write!(f, "{debug}"),
+            None => write!(f, "{} (not registered for debug)", self.type_name),
+        }
+    }
+}
+
+impl fmt::Display for WorldDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Entities ({}):", self.entities.len())?;
+        for dump in &self.entities {
+            match &dump.label {
+                Some(label) => writeln!(f, "  {} \"{label}\"", dump.entity)?,
+                None => writeln!(f, "  {}", dump.entity)?,
+            }
+            for component in &dump.components {
+                writeln!(f, "    {component}")?;
+            }
+        }
+
+        writeln!(f, "Resources ({}):", self.resources.len())?;
+        for resource in &self.resources {
+            writeln!(f, "  {resource}")?;
+        }
+        Ok(())
+    }
+}
+
+impl World {
+    /// Allows the `Debug` output of components and resources of type `T` to be
+    /// included in [`World::dump`].
+    ///
+    /// Storages are type-erased, so the world can only format types it has
+    /// been told about. Registering is cheap and idempotent.
+    pub fn register_debug<T: DebugComponent>(&mut self) {
+        self.debug_formatters
+            .insert(TypeId::of::<T>(), debug_component::<T>);
+    }
+
+    /// Lists every live entity with its components, and the global resources.
+    ///
+    /// Components and resources of types registered with
+    /// [`World::register_debug`] carry their `Debug` output, the others only
+    /// their type name. Ephemeral components and resources are not included.
+    /// Meant for diagnosing failing tests, not for use in hot loops.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, DebugComponent, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    /// impl DebugComponent for Health {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_debug::<Health>();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Health { value: 7 }).unwrap();
+    ///
+    /// let dump = world.dump();
+    /// assert!(dump.to_string().contains("Health { value: 7 }"));
+    /// ```
+    pub fn dump(&self) -> WorldDump {
+        let mut live: Vec<Entity> = self.entities.iter().copied().collect();
+        live.sort_unstable_by_key(|entity| entity.id());
+
+        let entities = live
+            .into_iter()
+            .map(|entity| EntityDump {
+                entity,
+                label: self.entity_to_label.get(&entity).cloned(),
+                components: self.dump_storages(&self.component_storages, entity),
+            })
+            .collect();
+
+        WorldDump {
+            entities,
+            resources: self.dump_storages(&self.resource_storages, self.resource_entity),
+        }
+    }
+
+    /// Lists the components of one entity, sorted by type name.
+    ///
+    /// This is the per-entity part of [`World::dump`], for tools like an admin
+    /// console that inspect entities without knowing their component types at
+    /// compile time. Components of types registered with
+    /// [`World::register_debug`] carry their `Debug` output. Returns an empty
+    /// list if the entity doesn't exist or has been deleted.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, DebugComponent, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    /// impl DebugComponent for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Secret(String);
+    /// impl Component for Secret {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_debug::<Health>();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Health { value: 7 }).unwrap();
+    /// world.add_component(goblin, Secret("loot".to_string())).unwrap();
+    ///
+    /// let components = world.components_of(goblin);
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[0].debug.as_deref(), Some("Health { value: 7 }"));
+    /// assert_eq!(components[1].type_name, std::any::type_name::<Secret>());
+    /// assert_eq!(components[1].debug, None);
+    /// ```
+    pub fn components_of(&self, entity: Entity) -> Vec<ComponentDump> {
+        if !self.is_entity_active(entity) {
+            return Vec::new();
+        }
+        self.dump_storages(&self.component_storages, entity)
+    }
+
+    /// Dumps the components `entity` holds in `storages`, sorted by type name.
+    fn dump_storages(
+        &self,
+        storages: &HashMap<TypeId, Box<dyn AnyStorage>>,
+        entity: Entity,
+    ) -> Vec<ComponentDump> {
+        let mut dumps: Vec<ComponentDump> = storages
+            .iter()
+            .filter_map(|(type_id, storage)| {
+                let component = storage.get_any(entity)?;
+                Some(ComponentDump {
+                    type_name: storage.component_type_name(),
+                    debug: self
+                        .debug_formatters
+                        .get(type_id)
+                        .map(|format| format(component)),
+                })
+            })
+            .collect();
+        dumps.sort_unstable_by_key(|dump| dump.type_name);
+        dumps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+    impl Component for Position {}
+    impl DebugComponent for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+    impl DebugComponent for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Password(String);
+    impl Component for Password {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Turn(u32);
+    impl Component for Turn {}
+    impl DebugComponent for Turn {}
+
+    #[test]
+    fn test_dump_lists_entities_components_and_resources() {
+        let mut world = World::new();
+        world.register_debug::<Position>();
+        world.register_debug::<Health>();
+        world.register_debug::<Turn>();
+
+        let hero = world.spawn_entity();
+        world.add_component(hero, Position { x: 1, y: 2 }).unwrap();
+        world.add_component(hero, Health { value: 30 }).unwrap();
+        world
+            .add_component(hero, Password("hunter2".to_string()))
+            .unwrap();
+        world.set_entity_label(hero, "hero").unwrap();
+        let rock = world.spawn_entity();
+        let gone = world.spawn_entity();
+        world.add_component(gone, Health { value: 0 }).unwrap();
+        world.delete_entity(gone);
+        world.insert_resource(Turn(3));
+
+        let dump = world.dump();
+
+        assert_eq!(dump.entities.len(), 2);
+        assert!(dump.entity(gone).is_none());
+        assert!(dump.entity(rock).unwrap().components.is_empty());
+
+        let hero_dump = dump.entity(hero).unwrap();
+        assert_eq!(hero_dump.label.as_deref(), Some("hero"));
+        let debugs: Vec<Option<&str>> = hero_dump
+            .components
+            .iter()
+            .map(|component| component.debug.as_deref())
+            .collect();
+        assert_eq!(
+            debugs,
+            vec![
+                Some("Health { value: 30 }"),
+                None,
+                Some("Position { x: 1, y: 2 }")
+            ]
+        );
+        assert_eq!(
+            dump.resources,
+            vec![ComponentDump {
+                type_name: std::any::type_name::<Turn>(),
+                debug: Some("Turn(3)".to_string()),
+            }]
+        );
+
+        let text = dump.to_string();
+        assert!(text.contains(&format!("{hero} \"hero\"")));
+        assert!(text.contains("    Position { x: 1, y: 2 }"));
+        assert!(!text.contains("hunter2"));
+        assert!(text.contains("Password (not registered for debug)"));
+        assert!(text.contains("Resources (1):\n  Turn(3)"));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name(String);
+    impl Component for Name {}
+    impl DebugComponent for Name {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Hostile;
+    impl Component for Hostile {}
+
+    #[test]
+    fn test_components_of_lists_every_component_type() {
+        let mut world = World::new();
+        world.register_debug::<Position>();
+        world.register_debug::<Health>();
+        world.register_debug::<Name>();
+
+        let goblin = world.spawn_entity();
+        world
+            .add_component(goblin, Position { x: 4, y: 2 })
+            .unwrap();
+        world.add_component(goblin, Health { value: 12 }).unwrap();
+        world
+            .add_component(goblin, Name("Grub".to_string()))
+            .unwrap();
+        world
+            .add_component(goblin, Password("hunter2".to_string()))
+            .unwrap();
+        world.add_component(goblin, Hostile).unwrap();
+        let other = world.spawn_entity();
+        world.add_component(other, Health { value: 1 }).unwrap();
+
+        let mut expected = vec![
+            (
+                std::any::type_name::<Position>(),
+                Some("Position { x: 4, y: 2 }"),
+            ),
+            (
+                std::any::type_name::<Health>(),
+                Some("Health { value: 12 }"),
+            ),
+            (std::any::type_name::<Name>(), Some("Name(\"Grub\")")),
+            (std::any::type_name::<Password>(), None),
+            (std::any::type_name::<Hostile>(), None),
+        ];
+        expected.sort_unstable_by_key(|(type_name, _)| *type_name);
+
+        let components = world.components_of(goblin);
+        let found: Vec<(&str, Option<&str>)> = components
+            .iter()
+            .map(|component| (component.type_name, component.debug.as_deref()))
+            .collect();
+        assert_eq!(found, expected);
+
+        world.delete_entity(goblin);
+        assert!(world.components_of(goblin).is_empty());
+    }
+}
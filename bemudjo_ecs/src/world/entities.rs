@@ -1,4 +1,4 @@
-use std::{any::TypeId, collections::HashSet};
+use std::{any::TypeId, cmp::Reverse, collections::HashSet};
 
 use crate::Entity;
 
@@ -25,11 +25,56 @@ impl World {
     /// assert_eq!(world.entities().count(), 2);
     /// ```
     pub fn spawn_entity(&mut self) -> Entity {
-        let entity = Entity::new();
+        self.record_allocator();
+        let entity = self.allocate_entity();
+        self.record_entity(entity);
         self.entities.insert(entity);
+        self.subscriptions.entity_spawned(entity);
         entity
     }
 
+    /// Returns a fresh entity id according to the world's [`EntityAllocator`],
+    /// reusing the id of a cleaned up entity when there is one.
+    ///
+    /// [`EntityAllocator`]: crate::EntityAllocator
+    fn allocate_entity(&mut self) -> Entity {
+        if let Some(entity) = self.free_entities.pop() {
+            return entity;
+        }
+
+        let Some(next_id) = self.next_local_entity_id.as_mut() else {
+            return Entity::new();
+        };
+
+        let entity = Entity::from_id(*next_id);
+        *next_id += 1;
+        entity
+    }
+
+    /// Reserves capacity for at least `additional` more entities.
+    ///
+    /// This is a performance hint for bulk spawning and has no observable effect
+    /// on the world's contents.
+    ///
+    /// # Parameters
+    /// * `additional` - The number of entities expected to be spawned
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// world.reserve_entities(100);
+    ///
+    /// for _ in 0..100 {
+    ///     world.spawn_entity();
+    /// }
+    /// assert_eq!(world.entities().count(), 100);
+    /// ```
+    pub fn reserve_entities(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
     /// Returns an iterator over all active entities (excludes deleted entities).
     ///
     /// The iterator yields references to `Entity` objects that are currently active
@@ -80,6 +125,14 @@ impl World {
             .unwrap_or_default()
     }
 
+    /// Returns the reverse index entry of a component type without copying it.
+    ///
+    /// Unlike `entities_with_component_by_type_id()`, the set still contains
+    /// soft-deleted entities, so callers must filter them out themselves.
+    pub(crate) fn component_index_by_type_id(&self, type_id: TypeId) -> Option<&HashSet<Entity>> {
+        self.reverse_component_index.get(&type_id)
+    }
+
     /// Gets all entities that have an ephemeral component with the specified TypeId.
     ///
     /// This is an internal method used by the query system for set operations.
@@ -133,8 +186,11 @@ impl World {
     /// ```
     pub fn delete_entity(&mut self, entity: Entity) {
         if self.entities.contains(&entity) {
+            self.record_entity(entity);
             self.entities.remove(&entity);
             self.soft_deleted_entities.insert(entity);
+            self.bump_generation();
+            self.subscriptions.entity_deleted(entity);
         }
     }
 
@@ -145,6 +201,15 @@ impl World {
     /// (end of frame, maintenance cycles, etc.) but can be called manually if needed.
     /// Multiple calls are safe and efficient.
     ///
+    /// Ephemeral components of the deleted entities are dropped as well, so they
+    /// don't linger until `clean_ephemeral_storage()` runs at the end of the tick.
+    ///
+    /// Callbacks registered with [`World::observe_removed`] run for every component
+    /// of the deleted entities before the data is dropped.
+    ///
+    /// With [`World::set_auto_shrink`] enabled, collections left sparse by the
+    /// cleanup are shrunk afterwards.
+    ///
     /// # Example
     /// ```
     /// use bemudjo_ecs::{World, Component};
@@ -167,6 +232,10 @@ impl World {
             return; // Early exit optimization
         }
 
+        // Let removal observers see the components before they are dropped
+        self.notify_removed_for_deleted_entities();
+        self.record_cleanup();
+
         // Batch removal with reversed loop order for better cache performance
         // Remove from component storages
         for storage in self.component_storages.values_mut() {
@@ -182,8 +251,161 @@ impl World {
             }
         }
 
+        // Ephemeral data of entities deleted mid-tick would otherwise linger
+        // until the end of the tick
+        for storage in self.ephemeral_component_storages.values_mut() {
+            for &entity in &self.soft_deleted_entities {
+                storage.remove_entity(entity);
+            }
+        }
+        self.reverse_ephemeral_component_index
+            .retain(|_, entities_set| {
+                for entity in &self.soft_deleted_entities {
+                    entities_set.remove(entity);
+                }
+                !entities_set.is_empty()
+            });
+
+        // Drop deleted entities from value indexes
+        for index in self.component_indexes.values_mut() {
+            for &entity in &self.soft_deleted_entities {
+                index.remove_entity(entity);
+            }
+        }
+
+        // Release labels of deleted entities
+        self.cleanup_deleted_labels();
+        self.cleanup_deleted_tags();
+        self.cleanup_deleted_hierarchy();
+
+        if let Some(cleaned) = &mut self.cleaned_entities {
+            cleaned.extend(self.soft_deleted_entities.iter().copied());
+        }
+
+        self.recycle_deleted_entity_ids();
+
         // Nuclear cleanup of deleted entities tracking
         self.soft_deleted_entities = HashSet::new();
+
+        self.auto_shrink();
+
+        #[cfg(debug_assertions)]
+        self.assert_consistent("world is inconsistent after cleanup_deleted_entities");
+    }
+
+    /// Starts recording the entities removed by `cleanup_deleted_entities()`.
+    ///
+    /// Once enabled, every cleaned-up entity is kept until it is collected with
+    /// [`World::drain_recently_cleaned`]. Tracking is off by default, so worlds
+    /// that never drain don't accumulate entities. Calling this again has no
+    /// effect.
+    pub fn track_cleaned_entities(&mut self) {
+        self.cleaned_entities.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns and forgets the entities cleaned up since the last drain.
+    ///
+    /// A cleaned-up entity is gone for good, so anything keyed by it outside
+    /// the world, like client-side ids or caches, can be released. Returns an
+    /// empty list unless [`World::track_cleaned_entities`] was called.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// world.track_cleaned_entities();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.delete_entity(goblin);
+    /// assert!(world.drain_recently_cleaned().is_empty()); // not cleaned up yet
+    ///
+    /// world.cleanup_deleted_entities();
+    /// assert_eq!(world.drain_recently_cleaned(), vec![goblin]);
+    /// assert!(world.drain_recently_cleaned().is_empty());
+    /// ```
+    pub fn drain_recently_cleaned(&mut self) -> Vec<Entity> {
+        self.cleaned_entities
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of ids of cleaned up entities waiting to be reused.
+    ///
+    /// `cleanup_deleted_entities()` hands the ids of deleted entities back to
+    /// the world, and `spawn_entity()` reuses them before allocating new ones.
+    /// A reused id comes with a new generation, so handles of the deleted entity
+    /// keep being rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let goblin = world.spawn_entity();
+    /// world.delete_entity(goblin);
+    /// world.cleanup_deleted_entities();
+    /// assert_eq!(world.recycled_id_count(), 1);
+    ///
+    /// let orc = world.spawn_entity();
+    /// assert_eq!(world.recycled_id_count(), 0);
+    /// assert_ne!(orc, goblin);
+    ///
+    /// // The stale goblin handle doesn't reach the orc
+    /// world.add_component(orc, Health { value: 80 }).unwrap();
+    /// assert!(world.add_component(goblin, Health { value: 30 }).is_err());
+    /// assert_eq!(world.get_component::<Health>(goblin), None);
+    /// ```
+    pub fn recycled_id_count(&self) -> usize {
+        self.free_entities.len()
+    }
+
+    /// Hands the ids of the soft-deleted entities over to `spawn_entity()`.
+    fn recycle_deleted_entity_ids(&mut self) {
+        let start = self.free_entities.len();
+        self.free_entities.extend(
+            self.soft_deleted_entities
+                .iter()
+                .filter_map(|entity| entity.next_generation()),
+        );
+        // Set order is random; sorting keeps deterministic worlds deterministic.
+        // Reversed so that the lowest id is reused first.
+        self.free_entities[start..].sort_unstable_by_key(|entity| Reverse(entity.id()));
+    }
+
+    /// Returns `true` if the entity was deleted and is still awaiting cleanup.
+    ///
+    /// Deleted entities stay in this state until the next
+    /// `cleanup_deleted_entities()`, which the scheduler runs at the end of every
+    /// tick, so this tells whether the entity was deleted during the current tick.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// assert!(!world.was_deleted_this_tick(entity));
+    ///
+    /// world.delete_entity(entity);
+    /// assert!(world.was_deleted_this_tick(entity));
+    ///
+    /// world.cleanup_deleted_entities();
+    /// assert!(!world.was_deleted_this_tick(entity));
+    /// ```
+    pub fn was_deleted_this_tick(&self, entity: Entity) -> bool {
+        self.soft_deleted_entities.contains(&entity)
+    }
+
+    /// Returns an iterator over the entities deleted this tick, see
+    /// [`World::was_deleted_this_tick`].
+    pub fn deleted_entities(&self) -> impl Iterator<Item = &Entity> {
+        self.soft_deleted_entities.iter()
     }
 
     /// Checks if an entity is active (exists and hasn't been soft-deleted).
@@ -598,6 +820,46 @@ mod tests {
         assert!(world2.is_entity_active(entity2));
     }
 
+    #[test]
+    fn test_deterministic_worlds_share_ids_but_not_entities() {
+        let mut world1 = World::new_with_allocator(crate::EntityAllocator::Deterministic);
+        let mut world2 = World::new_with_allocator(crate::EntityAllocator::Deterministic);
+
+        let shared1 = world1.spawn_entity();
+        let shared2 = world2.spawn_entity();
+        let only_in_world1 = world1.spawn_entity();
+
+        // Equal ids are the same Entity value, whichever world spawned them
+        assert_eq!(shared1, shared2);
+        assert!(world2.is_entity_active(shared1));
+
+        // An id the other world never spawned is still rejected
+        assert!(!world2.is_entity_active(only_in_world1));
+        world2
+            .add_component(only_in_world1, Position { x: 0.0, y: 0.0 })
+            .unwrap_err();
+        world2.delete_entity(only_in_world1);
+        world2.cleanup_deleted_entities();
+        assert!(world1.is_entity_active(only_in_world1));
+        assert!(world2.is_entity_active(shared2));
+    }
+
+    #[test]
+    fn test_deterministic_ids_are_reused_and_rewind_on_restore() {
+        let mut world = World::new_with_allocator(crate::EntityAllocator::Deterministic);
+        let first = world.spawn_entity();
+        world.delete_entity(first);
+        world.cleanup_deleted_entities();
+
+        let snapshot = world.snapshot().unwrap();
+        let second = world.spawn_entity();
+        assert_ne!(first, second);
+        assert_eq!(first.id(), second.id());
+
+        world.restore(&snapshot);
+        assert_eq!(world.spawn_entity(), second);
+    }
+
     #[test]
     fn test_entities_with_component_by_type_id_empty() {
         let world = World::new();
@@ -921,6 +1183,16 @@ mod tests {
         assert_eq!(entities.len(), 1);
         assert!(!entities.contains(&entity1));
         assert!(entities.contains(&entity2));
+
+        // Cleanup purges the deleted entity's ephemeral data mid-tick
+        world.cleanup_deleted_entities();
+        assert_eq!(world.ephemeral_storage_len::<TestEphemeral>(), 1);
+        assert_eq!(world.reverse_ephemeral_component_index[&type_id].len(), 1);
+
+        world.delete_entity(entity2);
+        world.cleanup_deleted_entities();
+        assert_eq!(world.ephemeral_storage_len::<TestEphemeral>(), 0);
+        assert!(world.reverse_ephemeral_component_index.is_empty());
     }
 
     #[test]
@@ -970,4 +1242,97 @@ mod tests {
     }
 
     // ...existing code...
+
+    #[test]
+    fn test_reserve_entities_grows_capacity() {
+        let mut world = World::new();
+
+        world.reserve_entities(1000);
+
+        assert!(world.entities.capacity() >= 1000);
+        assert_eq!(world.entities().count(), 0);
+    }
+
+    #[test]
+    fn test_drain_recently_cleaned_requires_tracking() {
+        let mut world = World::new();
+        let untracked = world.spawn_entity();
+        world.delete_entity(untracked);
+        world.cleanup_deleted_entities();
+        assert!(world.drain_recently_cleaned().is_empty());
+
+        world.track_cleaned_entities();
+        let a = world.spawn_entity();
+        let b = world.spawn_entity();
+        world.delete_entity(a);
+        world.cleanup_deleted_entities();
+        world.delete_entity(b);
+        world.cleanup_deleted_entities();
+
+        let drained: HashSet<_> = world.drain_recently_cleaned().into_iter().collect();
+        assert_eq!(drained, HashSet::from([a, b]));
+        assert!(world.drain_recently_cleaned().is_empty());
+    }
+
+    #[test]
+    fn test_reused_id_rejects_stale_handle() {
+        let mut world = World::new();
+        let goblin = world.spawn_entity();
+        world
+            .add_component(goblin, Position { x: 1.0, y: 1.0 })
+            .unwrap();
+        world.delete_entity(goblin);
+        world.cleanup_deleted_entities();
+
+        let orc = world.spawn_entity();
+        assert_eq!(orc.id(), goblin.id());
+        world
+            .add_component(orc, Position { x: 2.0, y: 2.0 })
+            .unwrap();
+
+        assert!(!world.is_entity_active(goblin));
+        assert_eq!(world.get_component::<Position>(goblin), None);
+        assert!(world
+            .add_component(goblin, Position { x: 3.0, y: 3.0 })
+            .is_err());
+        assert_eq!(world.remove_component::<Position>(goblin), None);
+
+        // Deleting through the stale handle must not touch the orc
+        world.delete_entity(goblin);
+        world.cleanup_deleted_entities();
+        assert!(world.is_entity_active(orc));
+        assert_eq!(
+            world.get_component::<Position>(orc),
+            Some(&Position { x: 2.0, y: 2.0 })
+        );
+        assert_eq!(world.recycled_id_count(), 0);
+    }
+
+    #[test]
+    fn test_spawn_despawn_loop_keeps_ids_bounded() {
+        let mut world = World::new();
+        let mut ids = HashSet::new();
+        let mut handles = HashSet::new();
+
+        for _ in 0..1_000 {
+            let wave: Vec<Entity> = (0..50).map(|_| world.spawn_entity()).collect();
+            for &entity in &wave {
+                world
+                    .add_component(entity, Position { x: 0.0, y: 0.0 })
+                    .unwrap();
+                ids.insert(entity.id());
+                assert!(
+                    handles.insert(entity),
+                    "handle {entity} was handed out twice"
+                );
+                world.delete_entity(entity);
+            }
+            world.cleanup_deleted_entities();
+            assert_eq!(world.recycled_id_count(), 50);
+        }
+
+        assert_eq!(ids.len(), 50);
+        assert_eq!(handles.len(), 50_000);
+        assert_eq!(world.entities().count(), 0);
+    }
 }
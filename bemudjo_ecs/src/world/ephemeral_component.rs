@@ -1,9 +1,17 @@
 use std::collections::HashMap;
+use std::sync::PoisonError;
 
-use crate::{Component, ComponentError, ComponentStorage};
+use crate::{Component, ComponentError};
 
 use super::World;
 
+/// Adds an ephemeral component staged with
+/// [`World::add_ephemeral_component_next_tick`] to the world.
+#[cfg(feature = "thread-safe-components")]
+pub(super) type StagedEphemeral = Box<dyn FnOnce(&mut World) + Send>;
+#[cfg(not(feature = "thread-safe-components"))]
+pub(super) type StagedEphemeral = Box<dyn FnOnce(&mut World)>;
+
 impl World {
     /// Adds an ephemeral component to an entity.
     ///
@@ -22,7 +30,7 @@ impl World {
     ///
     /// # Returns
     /// * `Ok(())` if the component was successfully added
-    /// * `Err(ComponentError::ComponentNotFound)` if the entity doesn't exist or has been deleted
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
     ///
     /// # Example
     /// ```
@@ -50,11 +58,16 @@ impl World {
         component: T,
     ) -> Result<(), ComponentError> {
         if !self.is_entity_active(entity) {
-            return Err(ComponentError::ComponentNotFound);
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
         }
 
         let entities_in_reverse_index = self.get_or_create_ephemeral_reverse_index::<T>();
-        entities_in_reverse_index.insert(entity);
+        if entities_in_reverse_index.insert(entity) {
+            self.bump_generation();
+        }
 
         let storage = self.get_ephemeral_storage_mut::<T>();
         // For ephemeral components, we allow replacement (insert_or_update)
@@ -62,6 +75,82 @@ impl World {
         Ok(())
     }
 
+    /// Stages an ephemeral component that appears at the start of the next tick.
+    ///
+    /// Ephemeral components added in a system's `after_run` would be cleared
+    /// by the end-of-tick cleanup before anyone could read them. A staged
+    /// component is instead kept aside until
+    /// [`promote_next_tick_ephemerals()`](World::promote_next_tick_ephemerals),
+    /// which `SequentialSystemScheduler::run_tick()` calls before running any
+    /// system, adds it like `add_ephemeral_component()`. It is then cleared at
+    /// the end of that tick as usual.
+    ///
+    /// Only needs `&self`, so read-only phases can call it through their
+    /// [`WorldView`](crate::WorldView). If the entity is deleted before the
+    /// component is promoted, the component is dropped.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the component was staged
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct WasRendered;
+    /// impl Component for WasRendered {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    ///
+    /// world.add_ephemeral_component_next_tick(entity, WasRendered).unwrap();
+    /// world.clean_ephemeral_storage(); // End of this tick
+    /// assert!(!world.has_ephemeral_component::<WasRendered>(entity));
+    ///
+    /// world.promote_next_tick_ephemerals(); // Start of the next one
+    /// assert!(world.has_ephemeral_component::<WasRendered>(entity));
+    /// ```
+    pub fn add_ephemeral_component_next_tick<T: Component>(
+        &self,
+        entity: crate::Entity,
+        component: T,
+    ) -> Result<(), ComponentError> {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        self.next_tick_ephemerals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(move |world: &mut World| {
+                // The entity may have been deleted since
+                let _ = world.add_ephemeral_component(entity, component);
+            }));
+        Ok(())
+    }
+
+    /// Adds the ephemeral components staged with
+    /// [`add_ephemeral_component_next_tick()`](World::add_ephemeral_component_next_tick).
+    ///
+    /// Components are added in the order they were staged, so a later value
+    /// replaces an earlier one for the same entity. Called by the scheduler at
+    /// the start of every tick; call it yourself when driving the world
+    /// without a scheduler.
+    pub fn promote_next_tick_ephemerals(&mut self) {
+        let staged = std::mem::take(
+            self.next_tick_ephemerals
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        for add in staged {
+            add(self);
+        }
+    }
+
     /// Gets a reference to an ephemeral component attached to an entity.
     ///
     /// Returns `None` if the entity doesn't exist, has been deleted, or doesn't
@@ -139,12 +228,14 @@ impl World {
             .unwrap_or(false)
     }
 
-    /// Clears all ephemeral component storages.
+    /// Clears all ephemeral component storages and ephemeral resources.
     ///
     /// This implements the "nuclear cleanup" pattern - an O(1) operation that
     /// replaces the entire ephemeral storage HashMap with a new one, letting
     /// Rust's Drop trait handle all memory cleanup automatically.
     ///
+    /// Ephemeral resources and ephemeral events are cleared at the same time.
+    ///
     /// This function is typically called by the system scheduler at the end of
     /// each frame to ensure ephemeral components only live for one frame cycle.
     ///
@@ -167,9 +258,17 @@ impl World {
     /// assert!(!world.has_ephemeral_component::<TempEffect>(entity));
     /// ```
     pub fn clean_ephemeral_storage(&mut self) {
+        // Only a structural change if some entity actually had an ephemeral component
+        if !self.reverse_ephemeral_component_index.is_empty() {
+            self.bump_generation();
+        }
+
         // Nuclear cleanup - O(1) operation
         self.ephemeral_component_storages = HashMap::new();
         self.reverse_ephemeral_component_index = HashMap::new();
+        self.ephemeral_resource_storages = HashMap::new();
+        self.ephemeral_event_storages = HashMap::new();
+        self.ephemerals_cleaned = true;
     }
 }
 
@@ -233,7 +332,7 @@ mod tests {
         );
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), ComponentError::ComponentNotFound);
+        assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
     }
 
     #[test]
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+
+use crate::{Component, ComponentError, Entity};
+
+use super::World;
+
+/// The per-entity list of ephemeral events of one type, in push order.
+pub(super) struct EventQueue<T>(pub(super) Vec<T>);
+
+impl<T: Component> Component for EventQueue<T> {}
+
+impl World {
+    /// Appends an ephemeral event to an entity's event queue for type `T`.
+    ///
+    /// Ephemeral events complement ephemeral components for things that naturally
+    /// happen several times per tick: every push is kept, in order, instead of
+    /// replacing the previous value. Like ephemeral components, all events are
+    /// removed by `clean_ephemeral_storage()` at the end of the tick.
+    ///
+    /// # Parameters
+    /// * `entity` - The entity the event targets
+    /// * `event` - The event to append
+    ///
+    /// # Returns
+    /// * `Ok(())` if the event was queued
+    /// * `Err(ComponentError::EntityNotFound { .. })` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct DamageEvent { amount: u32, attacker: &'static str }
+    /// impl Component for DamageEvent {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    ///
+    /// world.push_ephemeral_event(player, DamageEvent { amount: 5, attacker: "goblin" }).unwrap();
+    /// world.push_ephemeral_event(player, DamageEvent { amount: 8, attacker: "orc" }).unwrap();
+    ///
+    /// let events = world.ephemeral_events::<DamageEvent>(player);
+    /// assert_eq!(events.len(), 2);
+    /// assert_eq!(events[1].attacker, "orc");
+    /// ```
+    pub fn push_ephemeral_event<T: Component>(
+        &mut self,
+        entity: Entity,
+        event: T,
+    ) -> Result<(), ComponentError> {
+        if !self.is_entity_active(entity) {
+            return Err(ComponentError::EntityNotFound {
+                entity,
+                type_name: T::type_name(),
+            });
+        }
+
+        let storage = self.get_ephemeral_event_storage_mut::<T>();
+        match storage.get_mut(entity) {
+            Some(queue) => queue.0.push(event),
+            None => {
+                storage.insert_or_update(entity, EventQueue(vec![event]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ephemeral events of type `T` pushed to an entity this tick.
+    ///
+    /// Events are returned in the order they were pushed. The slice is empty if
+    /// no event was pushed, or if the entity doesn't exist or has been deleted.
+    pub fn ephemeral_events<T: Component>(&self, entity: Entity) -> &[T] {
+        if !self.is_entity_active(entity) {
+            return &[];
+        }
+
+        self.get_ephemeral_event_storage::<T>()
+            .and_then(|storage| storage.get(entity))
+            .map_or(&[], |queue| queue.0.as_slice())
+    }
+
+    /// Gets all active entities that have at least one ephemeral event of type `T`.
+    ///
+    /// This is an internal method used by the query system.
+    pub(crate) fn entities_with_ephemeral_events<T: Component>(&self) -> HashSet<Entity> {
+        self.get_ephemeral_event_storage::<T>()
+            .map(|storage| {
+                storage
+                    .entities()
+                    .filter(|entity| self.is_entity_active(*entity))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Query, SequentialSystemScheduler, System};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct DamageEvent {
+        amount: u32,
+        attacker: &'static str,
+    }
+    impl Component for DamageEvent {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    fn hit(amount: u32, attacker: &'static str) -> DamageEvent {
+        DamageEvent { amount, attacker }
+    }
+
+    #[test]
+    fn test_multiple_producers_keep_every_event_in_order() {
+        struct Attacker(&'static str, u32);
+        impl System for Attacker {
+            fn run(&self, world: &mut World) {
+                let target = world.entity_by_label("player").unwrap();
+                world
+                    .push_ephemeral_event(target, hit(self.1, self.0))
+                    .unwrap();
+            }
+        }
+
+        struct Recorder(std::rc::Rc<std::cell::RefCell<Vec<DamageEvent>>>);
+        impl System for Recorder {
+            fn run(&self, world: &mut World) {
+                let target = world.entity_by_label("player").unwrap();
+                self.0
+                    .borrow_mut()
+                    .extend_from_slice(world.ephemeral_events::<DamageEvent>(target));
+            }
+        }
+
+        let mut world = World::new();
+        let player = world.spawn_entity();
+        world.set_entity_label(player, "player").unwrap();
+
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(Attacker("goblin", 3)).unwrap();
+        scheduler
+            .add_system_allow_duplicates(Attacker("orc", 7))
+            .unwrap();
+        scheduler
+            .add_system_allow_duplicates(Attacker("troll", 11))
+            .unwrap();
+        scheduler.add_system(Recorder(seen.clone())).unwrap();
+        scheduler.build().unwrap();
+
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(
+            *seen.borrow(),
+            vec![hit(3, "goblin"), hit(7, "orc"), hit(11, "troll")]
+        );
+        // Cleared by the scheduler's ephemeral cleanup phase
+        assert!(world.ephemeral_events::<DamageEvent>(player).is_empty());
+    }
+
+    #[test]
+    fn test_events_cleared_by_clean_ephemeral_storage() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.push_ephemeral_event(entity, hit(1, "a")).unwrap();
+
+        world.clean_ephemeral_storage();
+
+        assert!(world.ephemeral_events::<DamageEvent>(entity).is_empty());
+        world.push_ephemeral_event(entity, hit(2, "b")).unwrap();
+        assert_eq!(
+            world.ephemeral_events::<DamageEvent>(entity),
+            &[hit(2, "b")]
+        );
+    }
+
+    #[test]
+    fn test_events_and_entity_deletion_mid_tick() {
+        let mut world = World::new();
+        let victim = world.spawn_entity();
+        let survivor = world.spawn_entity();
+        world.push_ephemeral_event(victim, hit(1, "a")).unwrap();
+        world.push_ephemeral_event(survivor, hit(2, "b")).unwrap();
+
+        world.delete_entity(victim);
+
+        assert!(world.ephemeral_events::<DamageEvent>(victim).is_empty());
+        assert!(matches!(
+            world.push_ephemeral_event(victim, hit(3, "c")),
+            Err(ComponentError::EntityNotFound { .. })
+        ));
+        let targets: Vec<_> = Query::<DamageEvent>::new()
+            .iter_ephemeral_events(&world)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(targets, vec![survivor]);
+    }
+
+    #[test]
+    fn test_events_are_separate_from_ephemeral_components() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.push_ephemeral_event(entity, hit(1, "a")).unwrap();
+
+        assert!(!world.has_ephemeral_component::<DamageEvent>(entity));
+        assert_eq!(
+            Query::<DamageEvent>::new().iter_ephemeral(&world).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_query_events_respects_filters() {
+        let mut world = World::new();
+        let alive = world.spawn_entity();
+        let dead = world.spawn_entity();
+        world.add_component(dead, Dead).unwrap();
+        for entity in [alive, dead] {
+            world.push_ephemeral_event(entity, hit(1, "a")).unwrap();
+            world.push_ephemeral_event(entity, hit(2, "b")).unwrap();
+        }
+
+        let query = Query::<DamageEvent>::new().without::<Dead>();
+        let results: Vec<_> = query.iter_ephemeral_events(&world).collect();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, alive);
+        let total: u32 = results[0].1.iter().map(|event| event.amount).sum();
+        assert_eq!(total, 3);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{Component, ComponentError};
+
+use super::World;
+
+impl World {
+    /// Inserts an ephemeral global resource.
+    ///
+    /// Ephemeral resources are the global counterpart of ephemeral components: they
+    /// are visible to every system for the rest of the current tick and are removed
+    /// when `clean_ephemeral_storage()` is called at the end of the tick. They are a
+    /// good fit for one-off global events such as "server shutdown requested".
+    ///
+    /// Each ephemeral resource type can only be inserted once per tick. Use
+    /// [`World::update_ephemeral_resource`] to merge further data into an existing one.
+    ///
+    /// # Parameters
+    /// * `resource` - The ephemeral resource instance to store
+    ///
+    /// # Returns
+    /// * `Ok(())` if the ephemeral resource was inserted
+    /// * `Err(ComponentError::ResourceAlreadyExists { .. })` if it was already inserted this tick
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct WeatherChanged { raining: bool }
+    /// impl Component for WeatherChanged {}
+    ///
+    /// let mut world = World::new();
+    /// world.insert_ephemeral_resource(WeatherChanged { raining: true }).unwrap();
+    /// assert!(world.has_ephemeral_resource::<WeatherChanged>());
+    ///
+    /// // A second insert in the same tick fails
+    /// assert!(world.insert_ephemeral_resource(WeatherChanged { raining: false }).is_err());
+    ///
+    /// // Gone after the end-of-tick cleanup
+    /// world.clean_ephemeral_storage();
+    /// assert!(!world.has_ephemeral_resource::<WeatherChanged>());
+    /// ```
+    pub fn insert_ephemeral_resource<T: Component>(
+        &mut self,
+        resource: T,
+    ) -> Result<(), ComponentError> {
+        let resource_entity = self.resource_entity;
+        let storage = self.get_ephemeral_resource_storage_mut::<T>();
+        storage.insert(resource_entity, resource).map_err(|_| {
+            ComponentError::ResourceAlreadyExists {
+                type_name: T::type_name(),
+            }
+        })
+    }
+
+    /// Gets an immutable reference to an ephemeral global resource.
+    ///
+    /// # Returns
+    /// * `Some(&T)` if the ephemeral resource was inserted during the current tick
+    /// * `None` otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct ShutdownRequested { reason: String }
+    /// impl Component for ShutdownRequested {}
+    ///
+    /// let mut world = World::new();
+    /// assert!(world.get_ephemeral_resource::<ShutdownRequested>().is_none());
+    ///
+    /// world.insert_ephemeral_resource(ShutdownRequested { reason: "maintenance".to_string() }).unwrap();
+    /// let event = world.get_ephemeral_resource::<ShutdownRequested>().unwrap();
+    /// assert_eq!(event.reason, "maintenance");
+    /// ```
+    pub fn get_ephemeral_resource<T: Component>(&self) -> Option<&T> {
+        let resource_entity = self.resource_entity;
+        self.get_ephemeral_resource_storage::<T>()?
+            .get(resource_entity)
+    }
+
+    /// Checks if an ephemeral global resource exists for the current tick.
+    ///
+    /// # Returns
+    /// * `true` if the ephemeral resource was inserted during the current tick
+    /// * `false` otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct DayStarted;
+    /// impl Component for DayStarted {}
+    ///
+    /// let mut world = World::new();
+    /// assert!(!world.has_ephemeral_resource::<DayStarted>());
+    ///
+    /// world.insert_ephemeral_resource(DayStarted).unwrap();
+    /// assert!(world.has_ephemeral_resource::<DayStarted>());
+    /// ```
+    pub fn has_ephemeral_resource<T: Component>(&self) -> bool {
+        let resource_entity = self.resource_entity;
+        self.get_ephemeral_resource_storage::<T>()
+            .is_some_and(|s| s.contains(resource_entity))
+    }
+
+    /// Updates an ephemeral global resource using a closure and returns the new value.
+    ///
+    /// This is the way to merge additional data into an ephemeral resource that was
+    /// already inserted during the current tick, e.g. when several systems report
+    /// into the same global event.
+    ///
+    /// # Parameters
+    /// * `f` - A closure that takes the current value and returns the merged value
+    ///
+    /// # Returns
+    /// * `Ok(T)` - The updated ephemeral resource value
+    /// * `Err(ComponentError::ResourceNotFound { .. })` - If the ephemeral resource doesn't exist
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Announcements { messages: Vec<String> }
+    /// impl Component for Announcements {}
+    ///
+    /// let mut world = World::new();
+    /// world.insert_ephemeral_resource(Announcements { messages: vec!["hello".to_string()] }).unwrap();
+    ///
+    /// let merged = world.update_ephemeral_resource::<Announcements, _>(|mut a| {
+    ///     a.messages.push("world".to_string());
+    ///     a
+    /// }).unwrap();
+    ///
+    /// assert_eq!(merged.messages.len(), 2);
+    /// ```
+    pub fn update_ephemeral_resource<T, F>(&mut self, f: F) -> Result<T, ComponentError>
+    where
+        T: Component + Clone,
+        F: FnOnce(T) -> T,
+    {
+        let resource_entity = self.resource_entity;
+        let storage = self.get_ephemeral_resource_storage_mut::<T>();
+
+        match storage.get(resource_entity).cloned() {
+            Some(current) => {
+                let updated = f(current);
+                storage.insert_or_update(resource_entity, updated.clone());
+                Ok(updated)
+            }
+            None => Err(ComponentError::ResourceNotFound {
+                type_name: T::type_name(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Component;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct WeatherChanged {
+        raining: bool,
+    }
+    impl Component for WeatherChanged {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Announcements {
+        messages: Vec<String>,
+    }
+    impl Component for Announcements {}
+
+    #[test]
+    fn test_insert_and_get_ephemeral_resource() {
+        let mut world = World::new();
+
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+
+        assert!(world.has_ephemeral_resource::<WeatherChanged>());
+        assert_eq!(
+            world.get_ephemeral_resource::<WeatherChanged>(),
+            Some(&WeatherChanged { raining: true })
+        );
+    }
+
+    #[test]
+    fn test_ephemeral_resource_missing() {
+        let world = World::new();
+
+        assert!(!world.has_ephemeral_resource::<WeatherChanged>());
+        assert!(world.get_ephemeral_resource::<WeatherChanged>().is_none());
+    }
+
+    #[test]
+    fn test_insert_ephemeral_resource_twice_fails() {
+        let mut world = World::new();
+
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+        let result = world.insert_ephemeral_resource(WeatherChanged { raining: false });
+
+        assert!(matches!(
+            result,
+            Err(ComponentError::ResourceAlreadyExists { .. })
+        ));
+        // First value wins
+        assert!(
+            world
+                .get_ephemeral_resource::<WeatherChanged>()
+                .unwrap()
+                .raining
+        );
+    }
+
+    #[test]
+    fn test_update_ephemeral_resource_merges() {
+        let mut world = World::new();
+        world
+            .insert_ephemeral_resource(Announcements {
+                messages: vec!["first".to_string()],
+            })
+            .unwrap();
+
+        let updated = world
+            .update_ephemeral_resource::<Announcements, _>(|mut a| {
+                a.messages.push("second".to_string());
+                a
+            })
+            .unwrap();
+
+        assert_eq!(updated.messages, vec!["first", "second"]);
+        assert_eq!(
+            world.get_ephemeral_resource::<Announcements>().unwrap(),
+            &updated
+        );
+    }
+
+    #[test]
+    fn test_update_ephemeral_resource_missing() {
+        let mut world = World::new();
+
+        let result = world.update_ephemeral_resource::<Announcements, _>(|a| a);
+
+        assert!(matches!(
+            result,
+            Err(ComponentError::ResourceNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_clean_ephemeral_storage_removes_ephemeral_resources() {
+        let mut world = World::new();
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+
+        world.clean_ephemeral_storage();
+
+        assert!(!world.has_ephemeral_resource::<WeatherChanged>());
+        // Can be inserted again in the next tick
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: false })
+            .unwrap();
+        assert!(
+            !world
+                .get_ephemeral_resource::<WeatherChanged>()
+                .unwrap()
+                .raining
+        );
+    }
+
+    #[test]
+    fn test_clean_ephemeral_storage_wipes_entity_and_resource_ephemerals_together() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_ephemeral_component(entity, WeatherChanged { raining: true })
+            .unwrap();
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: false })
+            .unwrap();
+
+        world.clean_ephemeral_storage();
+
+        assert!(!world.has_ephemeral_component::<WeatherChanged>(entity));
+        assert!(!world.has_ephemeral_resource::<WeatherChanged>());
+        // The resource never shows up as an entity ephemeral and vice versa
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+        assert!(!world.has_ephemeral_component::<WeatherChanged>(entity));
+    }
+
+    #[test]
+    fn test_ephemeral_resources_independent_of_regular_resources() {
+        let mut world = World::new();
+        world.insert_resource(WeatherChanged { raining: false });
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+
+        assert!(!world.get_resource::<WeatherChanged>().unwrap().raining);
+        assert!(
+            world
+                .get_ephemeral_resource::<WeatherChanged>()
+                .unwrap()
+                .raining
+        );
+
+        world.clean_ephemeral_storage();
+
+        assert!(world.has_resource::<WeatherChanged>());
+        assert!(!world.has_ephemeral_resource::<WeatherChanged>());
+    }
+
+    #[test]
+    fn test_ephemeral_resources_not_visible_as_entities() {
+        let mut world = World::new();
+        world
+            .insert_ephemeral_resource(WeatherChanged { raining: true })
+            .unwrap();
+
+        assert_eq!(world.entities().count(), 0);
+        assert!(world
+            .entities_with_ephemeral_component_by_type_id(std::any::TypeId::of::<WeatherChanged>())
+            .is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::Component;
+
+use super::World;
+
+/// The global buffer of events of type `E` sent during the current tick.
+///
+/// Filled by [`World::send_event`] and read with [`World::drain_events`]. The
+/// buffer is stored as an ephemeral resource, so like every ephemeral resource
+/// it is removed by `clean_ephemeral_storage()` at the end of the tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Events<E> {
+    events: Vec<E>,
+}
+
+impl<E> Events<E> {
+    /// Returns the events in the order they were sent.
+    pub fn iter(&self) -> std::slice::Iter<'_, E> {
+        self.events.iter()
+    }
+
+    /// Returns the number of events sent this tick.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no event was sent this tick.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<E> Default for Events<E> {
+    fn default() -> Self {
+        Self { events: Vec::new() }
+    }
+}
+
+impl<E: Component> Component for Events<E> {}
+
+impl World {
+    /// Sends a global event of type `E`.
+    ///
+    /// Unlike ephemeral components and ephemeral events, global events are not
+    /// attached to an entity: every system running later in the same tick can
+    /// read them with [`drain_events`](World::drain_events). All events are
+    /// removed by `clean_ephemeral_storage()` at the end of the tick.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct PlayerJoined { name: String }
+    /// impl Component for PlayerJoined {}
+    ///
+    /// let mut world = World::new();
+    /// world.send_event(PlayerJoined { name: "Alice".to_string() });
+    /// world.send_event(PlayerJoined { name: "Bob".to_string() });
+    ///
+    /// let names: Vec<_> = world.drain_events::<PlayerJoined>().map(|e| e.name.as_str()).collect();
+    /// assert_eq!(names, vec!["Alice", "Bob"]);
+    ///
+    /// // Gone after the end-of-tick cleanup
+    /// world.clean_ephemeral_storage();
+    /// assert_eq!(world.drain_events::<PlayerJoined>().count(), 0);
+    /// ```
+    pub fn send_event<E: Component>(&mut self, event: E) {
+        let resource_entity = self.resource_entity;
+        let storage = self.get_ephemeral_resource_storage_mut::<Events<E>>();
+        match storage.get_mut(resource_entity) {
+            Some(events) => events.events.push(event),
+            None => {
+                storage.insert_or_update(
+                    resource_entity,
+                    Events {
+                        events: vec![event],
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns the events of type `E` sent this tick, in the order they were sent.
+    ///
+    /// Reading doesn't consume the events: every system that drains them during
+    /// the tick sees all of them.
+    pub fn drain_events<E: Component>(&self) -> impl Iterator<Item = &E> {
+        self.get_ephemeral_resource_storage::<Events<E>>()
+            .and_then(|storage| storage.get(self.resource_entity))
+            .into_iter()
+            .flat_map(Events::iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SequentialSystemScheduler, System, WorldView};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct DamageEvent {
+        amount: u32,
+    }
+    impl Component for DamageEvent {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct HealEvent;
+    impl Component for HealEvent {}
+
+    #[test]
+    fn test_events_are_kept_per_type_in_order() {
+        let mut world = World::new();
+        world.send_event(DamageEvent { amount: 3 });
+        world.send_event(HealEvent);
+        world.send_event(DamageEvent { amount: 5 });
+
+        let amounts: Vec<u32> = world
+            .drain_events::<DamageEvent>()
+            .map(|event| event.amount)
+            .collect();
+        assert_eq!(amounts, vec![3, 5]);
+        assert_eq!(world.drain_events::<HealEvent>().count(), 1);
+
+        // Reading twice sees the same events
+        assert_eq!(world.drain_events::<DamageEvent>().count(), 2);
+        assert_eq!(
+            world
+                .get_ephemeral_resource::<Events<DamageEvent>>()
+                .map(Events::len),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_events_sent_by_a_system_are_read_later_in_the_tick() {
+        struct Attacker;
+        impl System for Attacker {
+            fn run(&self, world: &mut World) {
+                world.send_event(DamageEvent { amount: 4 });
+                world.send_event(DamageEvent { amount: 6 });
+            }
+        }
+
+        struct DamageLog(Rc<RefCell<Vec<u32>>>);
+        impl System for DamageLog {
+            fn after_run(&self, world: &WorldView) {
+                let total = world.drain_events::<DamageEvent>().map(|e| e.amount).sum();
+                self.0.borrow_mut().push(total);
+            }
+        }
+
+        let totals = Rc::new(RefCell::new(Vec::new()));
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(DamageLog(totals.clone())).unwrap();
+        scheduler.add_system(Attacker).unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
+
+        // Each tick only sees its own events
+        assert_eq!(*totals.borrow(), vec![10, 10]);
+        assert_eq!(world.drain_events::<DamageEvent>().count(), 0);
+    }
+}
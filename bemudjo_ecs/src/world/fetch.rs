@@ -0,0 +1,297 @@
+use std::any::TypeId;
+
+use crate::component::downcast_storage_mut;
+use crate::{Component, Entity};
+
+use super::World;
+
+/// A tuple of component types fetched together by [`World::get_components`]
+/// and [`World::get_components_mut`].
+///
+/// Implemented for tuples of one to eight components.
+pub trait ComponentRefTuple {
+    /// Shared references to each component, e.g. `(&A, &B)` for `(A, B)`.
+    type Refs<'w>;
+    /// Mutable references to each component, e.g. `(&mut A, &mut B)` for `(A, B)`.
+    type Muts<'w>;
+
+    /// Returns the `TypeId` of every component type in the tuple.
+    fn type_ids() -> Vec<TypeId>;
+
+    /// Fetches every component of an active entity.
+    fn fetch(world: &World, entity: Entity) -> Option<Self::Refs<'_>>;
+
+    /// Fetches every component of an active entity mutably.
+    fn fetch_mut(world: &mut World, entity: Entity) -> Option<Self::Muts<'_>>;
+}
+
+macro_rules! impl_component_ref_tuple {
+    ($($name:ident => $component:ident),+) => {
+        impl<$($name: Component),+> ComponentRefTuple for ($($name,)+) {
+            type Refs<'w> = ($(&'w $name,)+);
+            type Muts<'w> = ($(&'w mut $name,)+);
+
+            fn type_ids() -> Vec<TypeId> {
+                vec![$(TypeId::of::<$name>()),+]
+            }
+
+            fn fetch(world: &World, entity: Entity) -> Option<Self::Refs<'_>> {
+                if !world.is_entity_active(entity) {
+                    return None;
+                }
+
+                Some(($(world.get_storage::<$name>()?.get(entity)?,)+))
+            }
+
+            fn fetch_mut(world: &mut World, entity: Entity) -> Option<Self::Muts<'_>> {
+                assert_distinct(&Self::type_ids());
+                if !world.is_entity_active(entity) {
+                    return None;
+                }
+
+                // Check everything first so a miss doesn't mark anything as changed
+                $(
+                    if !world.get_storage::<$name>()?.contains(entity) {
+                        return None;
+                    }
+                )+
+                $(
+                    world.invalidate_index::<$name>();
+                    world.mark_changed::<$name>(entity);
+                    world.record_component::<$name>(entity);
+                )+
+
+                let storages = &mut world.component_storages;
+                $(
+                    let $component: *mut $name = {
+                        let storage = storages.get_mut(&TypeId::of::<$name>())?;
+                        downcast_storage_mut::<$name>(storage.as_mut())?.get_mut(entity)?
+                    };
+                )+
+
+                // SAFETY: every component type has its own boxed storage, and the
+                // types are distinct (asserted above), so each pointer targets a
+                // different allocation. The storage map isn't touched again while
+                // the references, which borrow `world` mutably, are alive.
+                Some(unsafe { ($(&mut *$component,)+) })
+            }
+        }
+    };
+}
+
+/// Panics if a component type appears twice, which would alias `&mut` references.
+fn assert_distinct(type_ids: &[TypeId]) {
+    for (index, type_id) in type_ids.iter().enumerate() {
+        assert!(
+            !type_ids[index + 1..].contains(type_id),
+            "get_components_mut requires distinct component types"
+        );
+    }
+}
+
+impl_component_ref_tuple!(A => a);
+impl_component_ref_tuple!(A => a, B => b);
+impl_component_ref_tuple!(A => a, B => b, C => c);
+impl_component_ref_tuple!(A => a, B => b, C => c, D => d);
+impl_component_ref_tuple!(A => a, B => b, C => c, D => d, E => e);
+impl_component_ref_tuple!(A => a, B => b, C => c, D => d, E => e, F => f);
+impl_component_ref_tuple!(A => a, B => b, C => c, D => d, E => e, F => f, G => g);
+impl_component_ref_tuple!(A => a, B => b, C => c, D => d, E => e, F => f, G => g, H => h);
+
+impl World {
+    /// Gets references to several components of an entity at once.
+    ///
+    /// Generalizes [`get_components2`](World::get_components2) to any tuple of
+    /// up to eight component types. The entity is only checked once.
+    ///
+    /// # Returns
+    /// * `Some((&A, &B, ...))` if the entity is alive and has every component
+    /// * `None` if any component is missing or the entity is invalid
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Weapon { damage: u32 }
+    /// impl Component for Weapon {}
+    ///
+    /// let mut world = World::new();
+    /// let knight = world.spawn_entity();
+    /// world.add_component(knight, Position { x: 1.0 }).unwrap();
+    /// world.add_component(knight, Health { value: 30 }).unwrap();
+    /// assert!(world.get_components::<(Position, Health, Weapon)>(knight).is_none());
+    ///
+    /// world.add_component(knight, Weapon { damage: 7 }).unwrap();
+    /// let (_, health, weapon) = world.get_components::<(Position, Health, Weapon)>(knight).unwrap();
+    /// assert_eq!(health.value + weapon.damage, 37);
+    /// ```
+    pub fn get_components<T: ComponentRefTuple>(&self, entity: Entity) -> Option<T::Refs<'_>> {
+        T::fetch(self, entity)
+    }
+
+    /// Gets mutable references to several components of an entity at once.
+    ///
+    /// Each component type lives in its own storage, so the references are
+    /// disjoint and can all be written at the same time. Like
+    /// [`iter_components_mut`](World::iter_components_mut), every fetched
+    /// component counts as changed for [`was_changed`](World::was_changed).
+    ///
+    /// # Returns
+    /// * `Some((&mut A, &mut B, ...))` if the entity is alive and has every component
+    /// * `None` if any component is missing or the entity is invalid
+    ///
+    /// # Panics
+    /// Panics if the same component type appears twice in `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Poison { damage: u32 }
+    /// impl Component for Poison {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Health { value: 20 }).unwrap();
+    /// world.add_component(player, Poison { damage: 3 }).unwrap();
+    ///
+    /// let (health, poison) = world.get_components_mut::<(Health, Poison)>(player).unwrap();
+    /// health.value -= poison.damage;
+    /// poison.damage -= 1;
+    ///
+    /// assert_eq!(world.get_component::<Health>(player), Some(&Health { value: 17 }));
+    /// assert_eq!(world.get_component::<Poison>(player), Some(&Poison { damage: 2 }));
+    /// ```
+    pub fn get_components_mut<T: ComponentRefTuple>(
+        &mut self,
+        entity: Entity,
+    ) -> Option<T::Muts<'_>> {
+        T::fetch_mut(self, entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: i32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Weapon {
+        damage: u32,
+    }
+    impl Component for Weapon {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Stunned;
+    impl Component for Stunned {}
+
+    fn knight(world: &mut World) -> Entity {
+        let entity = world.spawn_entity();
+        world.add_component(entity, Position { x: 0 }).unwrap();
+        world.add_component(entity, Health { value: 30 }).unwrap();
+        world.add_component(entity, Weapon { damage: 5 }).unwrap();
+        entity
+    }
+
+    #[test]
+    fn test_get_components_is_all_or_nothing() {
+        let mut world = World::new();
+        let entity = knight(&mut world);
+
+        let (position, health, weapon) = world
+            .get_components::<(Position, Health, Weapon)>(entity)
+            .unwrap();
+        assert_eq!((position.x, health.value, weapon.damage), (0, 30, 5));
+        assert!(world.get_components::<(Health,)>(entity).is_some());
+
+        assert!(world
+            .get_components::<(Position, Stunned)>(entity)
+            .is_none());
+        assert!(world
+            .get_components_mut::<(Position, Stunned)>(entity)
+            .is_none());
+
+        world.delete_entity(entity);
+        assert!(world.get_components::<(Position, Health)>(entity).is_none());
+        assert!(world
+            .get_components_mut::<(Position, Health)>(entity)
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_components_mut_returns_disjoint_references() {
+        let mut world = World::new();
+        let entity = knight(&mut world);
+        world.add_component(entity, Stunned).unwrap();
+        world.clear_change_tracking();
+
+        let (position, health, weapon, _) = world
+            .get_components_mut::<(Position, Health, Weapon, Stunned)>(entity)
+            .unwrap();
+        // Writing Health while holding &mut Position and &mut Weapon
+        position.x += 2;
+        health.value -= weapon.damage;
+        weapon.damage *= 2;
+        position.x += 1;
+
+        assert_eq!(
+            world.get_component::<Position>(entity),
+            Some(&Position { x: 3 })
+        );
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 25 })
+        );
+        assert_eq!(
+            world.get_component::<Weapon>(entity),
+            Some(&Weapon { damage: 10 })
+        );
+        assert!(world.was_changed::<Health>(entity));
+    }
+
+    #[test]
+    fn test_get_components_mut_miss_marks_nothing_changed() {
+        let mut world = World::new();
+        let entity = knight(&mut world);
+        world.clear_change_tracking();
+
+        assert!(world
+            .get_components_mut::<(Health, Stunned)>(entity)
+            .is_none());
+        assert!(!world.was_changed::<Health>(entity));
+    }
+
+    #[test]
+    #[should_panic(expected = "distinct component types")]
+    fn test_get_components_mut_rejects_duplicate_types() {
+        let mut world = World::new();
+        let entity = knight(&mut world);
+
+        let _ = world.get_components_mut::<(Health, Position, Health)>(entity);
+    }
+}
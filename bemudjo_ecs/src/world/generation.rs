@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::World;
+
+/// Source of generation values, shared by all worlds.
+///
+/// Drawing every generation from one counter means two different worlds never
+/// report the same generation, so a cache filled from one world is never
+/// mistaken as valid for another.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a generation value that has never been handed out before.
+pub(super) fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+impl World {
+    /// Returns the structural generation of the world.
+    ///
+    /// The generation changes whenever the set of components attached to entities
+    /// may have changed: a component or ephemeral component was added or removed,
+    /// an entity was deleted, or ephemeral storage holding components was cleaned
+    /// up. Updating the value of an existing component does not change it, and
+    /// neither does spawning an entity that has no components yet.
+    ///
+    /// Caches keyed on entity membership, like [`crate::CachedQuery`], compare
+    /// generations to detect when they must be recomputed. Generations are unique
+    /// across all worlds in the process.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    ///
+    /// let before = world.generation();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    /// assert_ne!(world.generation(), before);
+    ///
+    /// // Changing a value in place is not a structural change
+    /// let before = world.generation();
+    /// world.update_component::<Health, _>(entity, |h| Health { value: h.value + 1 }).unwrap();
+    /// assert_eq!(world.generation(), before);
+    /// ```
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Marks a structural change. Every path that changes component membership calls this.
+    pub(super) fn bump_generation(&mut self) {
+        self.generation = next_generation();
+    }
+}
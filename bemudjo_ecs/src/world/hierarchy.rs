@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+
+use crate::Entity;
+
+use super::World;
+
+/// Errors that can occur when linking entities as parent and child.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// The entity does not exist or has been deleted.
+    EntityNotFound { entity: Entity },
+    /// The link would make an entity its own ancestor.
+    Cycle { child: Entity, parent: Entity },
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchyError::EntityNotFound { entity } => {
+                write!(f, "entity {entity} does not exist or has been deleted")
+            }
+            HierarchyError::Cycle { child, parent } => write!(
+                f,
+                "cannot make {parent} the parent of {child}: {child} is its ancestor"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
+impl World {
+    /// Makes `parent` the parent of `child`, replacing any previous parent.
+    ///
+    /// Parent links model containment, like the items and characters in a
+    /// room, and can be queried with [`Query::with_parent`](crate::Query::with_parent)
+    /// and [`Query::with_children`](crate::Query::with_children). Each entity
+    /// has at most one parent and any number of children.
+    ///
+    /// Deleting a parent does not delete its children: they stop appearing as
+    /// its children immediately and lose the link during
+    /// `cleanup_deleted_entities()`.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the link was made
+    /// * `Err(HierarchyError::EntityNotFound)` if either entity doesn't exist or has been deleted
+    /// * `Err(HierarchyError::Cycle)` if `child` is `parent` or one of its ancestors
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let tavern = world.spawn_entity();
+    /// let barkeep = world.spawn_entity();
+    ///
+    /// world.set_parent(barkeep, tavern).unwrap();
+    /// assert_eq!(world.parent_of(barkeep), Some(tavern));
+    /// assert_eq!(world.children_of(tavern), vec![barkeep]);
+    ///
+    /// // A room can't end up inside its own contents
+    /// assert!(world.set_parent(tavern, barkeep).is_err());
+    /// ```
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) -> Result<(), HierarchyError> {
+        for entity in [child, parent] {
+            if !self.is_entity_active(entity) {
+                return Err(HierarchyError::EntityNotFound { entity });
+            }
+        }
+
+        let mut ancestor = Some(parent);
+        while let Some(entity) = ancestor {
+            if entity == child {
+                return Err(HierarchyError::Cycle { child, parent });
+            }
+            ancestor = self.entity_parents.get(&entity).copied();
+        }
+
+        if self.entity_parents.get(&child) == Some(&parent) {
+            return Ok(());
+        }
+        self.record_parent(child);
+        self.unlink_parent(child);
+        self.entity_parents.insert(child, parent);
+        self.entity_children
+            .entry(parent)
+            .or_default()
+            .insert(child);
+        self.bump_generation();
+        Ok(())
+    }
+
+    /// Removes the parent link of `child` and returns the former parent.
+    ///
+    /// Returns `None` if the entity had no parent, or doesn't exist or has
+    /// been deleted.
+    pub fn remove_parent(&mut self, child: Entity) -> Option<Entity> {
+        if !self.is_entity_active(child) {
+            return None;
+        }
+
+        self.record_parent(child);
+        let parent = self.unlink_parent(child)?;
+        self.bump_generation();
+        Some(parent)
+    }
+
+    /// Returns the parent of `child`, if both are live.
+    pub fn parent_of(&self, child: Entity) -> Option<Entity> {
+        if !self.is_entity_active(child) {
+            return None;
+        }
+        self.entity_parents
+            .get(&child)
+            .copied()
+            .filter(|&parent| self.is_entity_active(parent))
+    }
+
+    /// Returns the live children of `parent`, in no particular order.
+    ///
+    /// Returns an empty list if the parent doesn't exist or has been deleted.
+    pub fn children_of(&self, parent: Entity) -> Vec<Entity> {
+        self.children_index(parent)
+            .map(|children| {
+                children
+                    .iter()
+                    .copied()
+                    .filter(|&child| self.is_entity_active(child))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the children of a live `parent`.
+    ///
+    /// The set may still hold soft-deleted children, so callers must check
+    /// them or only probe it with active entities, like the query system's
+    /// candidates.
+    pub(crate) fn children_index(&self, parent: Entity) -> Option<&HashSet<Entity>> {
+        if !self.is_entity_active(parent) {
+            return None;
+        }
+        self.entity_children.get(&parent)
+    }
+
+    /// Returns `true` if `entity` has at least one live child.
+    pub(crate) fn has_children(&self, entity: Entity) -> bool {
+        self.children_index(entity)
+            .is_some_and(|children| children.iter().any(|&child| self.is_entity_active(child)))
+    }
+
+    /// Drops the parent link of `child` from both maps, returning the parent.
+    pub(super) fn unlink_parent(&mut self, child: Entity) -> Option<Entity> {
+        let parent = self.entity_parents.remove(&child)?;
+        if let Some(siblings) = self.entity_children.get_mut(&parent) {
+            siblings.remove(&child);
+            if siblings.is_empty() {
+                self.entity_children.remove(&parent);
+            }
+        }
+        Some(parent)
+    }
+
+    /// Drops the links of soft-deleted entities, orphaning their children.
+    ///
+    /// Called during `cleanup_deleted_entities()`.
+    pub(super) fn cleanup_deleted_hierarchy(&mut self) {
+        if self.entity_parents.is_empty() {
+            return;
+        }
+
+        let deleted: Vec<Entity> = self.soft_deleted_entities.iter().copied().collect();
+        for entity in deleted {
+            self.unlink_parent(entity);
+            for child in self.entity_children.remove(&entity).unwrap_or_default() {
+                self.entity_parents.remove(&child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_parent_replaces_previous_parent() {
+        let mut world = World::new();
+        let tavern = world.spawn_entity();
+        let street = world.spawn_entity();
+        let barkeep = world.spawn_entity();
+
+        world.set_parent(barkeep, tavern).unwrap();
+        world.set_parent(barkeep, street).unwrap();
+
+        assert_eq!(world.parent_of(barkeep), Some(street));
+        assert!(world.children_of(tavern).is_empty());
+        assert_eq!(world.children_of(street), vec![barkeep]);
+
+        assert_eq!(world.remove_parent(barkeep), Some(street));
+        assert_eq!(world.remove_parent(barkeep), None);
+        assert!(world.entity_parents.is_empty());
+        assert!(world.entity_children.is_empty());
+    }
+
+    #[test]
+    fn test_set_parent_rejects_cycles_and_missing_entities() {
+        let mut world = World::new();
+        let room = world.spawn_entity();
+        let chest = world.spawn_entity();
+        let coin = world.spawn_entity();
+        world.set_parent(chest, room).unwrap();
+        world.set_parent(coin, chest).unwrap();
+
+        assert_eq!(
+            world.set_parent(room, coin),
+            Err(HierarchyError::Cycle {
+                child: room,
+                parent: coin
+            })
+        );
+        assert_eq!(
+            world.set_parent(room, room),
+            Err(HierarchyError::Cycle {
+                child: room,
+                parent: room
+            })
+        );
+
+        let gone = world.spawn_entity();
+        world.delete_entity(gone);
+        assert_eq!(
+            world.set_parent(coin, gone),
+            Err(HierarchyError::EntityNotFound { entity: gone })
+        );
+        assert_eq!(world.parent_of(coin), Some(chest));
+    }
+
+    #[test]
+    fn test_deleting_a_parent_orphans_its_children() {
+        let mut world = World::new();
+        let room = world.spawn_entity();
+        let sword = world.spawn_entity();
+        world.set_parent(sword, room).unwrap();
+
+        world.delete_entity(room);
+        assert_eq!(world.parent_of(sword), None);
+        assert!(world.children_of(room).is_empty());
+
+        world.cleanup_deleted_entities();
+        assert!(world.is_entity_active(sword));
+        assert!(world.entity_parents.is_empty());
+        assert!(world.entity_children.is_empty());
+    }
+
+    #[test]
+    fn test_links_change_generation() {
+        let mut world = World::new();
+        let room = world.spawn_entity();
+        let sword = world.spawn_entity();
+
+        let before = world.generation();
+        world.set_parent(sword, room).unwrap();
+        let linked = world.generation();
+        assert_ne!(linked, before);
+
+        world.set_parent(sword, room).unwrap();
+        assert_eq!(world.generation(), linked);
+        world.remove_parent(sword);
+        assert_ne!(world.generation(), linked);
+    }
+}
@@ -0,0 +1,871 @@
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+
+use crate::component::downcast_storage;
+use crate::{AnyStorage, Component, Entity};
+
+use super::{SnapshotError, World};
+
+/// Errors that can occur when rolling a world back with [`World::rollback_ticks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollbackError {
+    /// History was never enabled with [`World::enable_history`].
+    HistoryDisabled,
+    /// The world has not recorded enough ticks to go back that far.
+    NotEnoughHistory { requested: usize, available: usize },
+    /// A value that would have to be restored was changed or removed while
+    /// its type was not registered with [`World::register_cloneable`].
+    Unrecorded { type_name: &'static str },
+}
+
+impl std::fmt::Display for RollbackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollbackError::HistoryDisabled => write!(f, "history is not enabled"),
+            RollbackError::NotEnoughHistory {
+                requested,
+                available,
+            } => write!(
+                f,
+                "cannot roll back {requested} ticks: only {available} recorded"
+            ),
+            RollbackError::Unrecorded { type_name } => write!(
+                f,
+                "cannot roll back: a change to {type_name} was not recorded, type is not registered as cloneable"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RollbackError {}
+
+/// A component or resource value copied before it changed.
+#[cfg(feature = "thread-safe-components")]
+type SavedValue = Box<dyn Any + Send + Sync>;
+#[cfg(not(feature = "thread-safe-components"))]
+type SavedValue = Box<dyn Any>;
+
+/// Copies values of one cloneable type in and out of the world, see
+/// [`World::register_cloneable`].
+#[derive(Clone, Copy)]
+pub(super) struct ValueFns {
+    save: fn(&dyn AnyStorage, Entity) -> Option<SavedValue>,
+    restore_component: fn(&mut World, Entity, SavedValue),
+    restore_resource: fn(&mut World, SavedValue),
+}
+
+impl ValueFns {
+    pub(super) fn of<T: Component + Clone>() -> Self {
+        Self {
+            save: save_value::<T>,
+            restore_component: restore_component::<T>,
+            restore_resource: restore_resource::<T>,
+        }
+    }
+}
+
+fn save_value<T: Component + Clone>(
+    storage: &dyn AnyStorage,
+    entity: Entity,
+) -> Option<SavedValue> {
+    let value = downcast_storage::<T>(storage)?.get(entity)?.clone();
+    Some(Box::new(value))
+}
+
+fn restore_component<T: Component>(world: &mut World, entity: Entity, value: SavedValue) {
+    let value = value
+        .downcast::<T>()
+        .expect("saved values are keyed by the TypeId of their type");
+    world.get_or_create_reverse_index::<T>().insert(entity);
+    world
+        .get_storage_mut::<T>()
+        .insert_or_update(entity, *value);
+}
+
+fn restore_resource<T: Component>(world: &mut World, value: SavedValue) {
+    let value = value
+        .downcast::<T>()
+        .expect("saved values are keyed by the TypeId of their type");
+    let resource_entity = world.resource_entity;
+    world
+        .get_resource_storage_mut::<T>()
+        .insert_or_update(resource_entity, *value);
+}
+
+/// Where an entity stood before a tick touched it.
+#[derive(Debug, Clone, Copy)]
+enum EntityState {
+    Absent,
+    Active,
+    SoftDeleted,
+}
+
+/// What one tick changed, as the values it started from.
+///
+/// Only the first change to each slot is kept, so undoing a tick puts back the
+/// state at its start, whatever happened in between. `None` stands for a
+/// component, resource, label or parent that did not exist yet.
+#[derive(Default)]
+struct TickUndo {
+    entities: HashMap<Entity, EntityState>, // spawned, deleted and cleaned up entities
+    components: HashMap<(TypeId, Entity), Option<SavedValue>>,
+    resources: HashMap<TypeId, Option<SavedValue>>,
+    labels: HashMap<Entity, Option<String>>,
+    tags: HashMap<Entity, u64>,
+    parents: HashMap<Entity, Option<Entity>>,
+    allocator: Option<(Option<u64>, Vec<Entity>)>, // next_local_entity_id and free_entities
+    unrecorded: Option<&'static str>,              // first type whose old value couldn't be saved
+}
+
+/// A bounded log of the changes made by each recent tick.
+///
+/// Created by [`World::enable_history`] and fed by the scheduler once per
+/// completed tick. Every operation saves what it is about to change: component
+/// and resource values before an update, replacement or removal, and the
+/// entities spawned, deleted and cleaned up. Labels, tags, parent links and the
+/// ids awaiting reuse are tracked the same way. Values are copied through the
+/// types registered with [`World::register_cloneable`]. At most `max_ticks`
+/// past ticks are kept; recording another one drops the oldest.
+pub struct HistoryRecorder {
+    ticks: VecDeque<TickUndo>, // oldest first, the back entry undoes `tick`
+    current: TickUndo,         // changes since the last recorded tick
+    max_ticks: usize,
+    tick: u64,
+}
+
+impl HistoryRecorder {
+    /// Returns the number of completed ticks since history was enabled,
+    /// minus the ones rolled back.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the maximum number of ticks that can be rolled back.
+    pub fn max_ticks(&self) -> usize {
+        self.max_ticks
+    }
+
+    /// Returns the number of ticks that can currently be rolled back.
+    pub fn available_ticks(&self) -> usize {
+        self.ticks.len()
+    }
+
+    fn push(&mut self) {
+        self.ticks.push_back(std::mem::take(&mut self.current));
+        if self.ticks.len() > self.max_ticks {
+            self.ticks.pop_front();
+        }
+        self.tick += 1;
+    }
+}
+
+impl std::fmt::Debug for HistoryRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryRecorder")
+            .field("tick", &self.tick)
+            .field("available_ticks", &self.available_ticks())
+            .field("max_ticks", &self.max_ticks)
+            .finish()
+    }
+}
+
+impl World {
+    /// Starts recording the changes of every tick run by the scheduler,
+    /// keeping the last `max_ticks` of them for [`World::rollback_ticks`].
+    ///
+    /// Ticks can be undone back to the current state. Enabling history again
+    /// starts a fresh log. Memory grows with the number of values changed in
+    /// the last `max_ticks` ticks, not with the size of the world.
+    ///
+    /// Changes to a type that was not registered with
+    /// [`World::register_cloneable`] can't be saved. Adding such a component or
+    /// resource can still be undone, but updating or removing one blocks
+    /// rolling back past it with [`RollbackError::Unrecorded`].
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError::NotCloneable`] if a component or resource type
+    /// already holding data was not registered with [`World::register_cloneable`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, SequentialSystemScheduler, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Turn(u32);
+    /// impl Component for Turn {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_cloneable::<Turn>();
+    /// world.insert_resource(Turn(0));
+    /// world.enable_history(10).unwrap();
+    ///
+    /// let mut scheduler = SequentialSystemScheduler::new();
+    /// scheduler.build().unwrap();
+    ///
+    /// for turn in 1..=3 {
+    ///     world.insert_resource(Turn(turn));
+    ///     scheduler.run_tick(&mut world);
+    /// }
+    ///
+    /// // Undo the last turn
+    /// world.rollback_ticks(1).unwrap();
+    /// assert_eq!(world.get_resource::<Turn>(), Some(&Turn(2)));
+    /// ```
+    pub fn enable_history(&mut self, max_ticks: usize) -> Result<(), SnapshotError> {
+        let unregistered = self
+            .component_storages
+            .iter()
+            .chain(&self.resource_storages)
+            .find(|(type_id, storage)| {
+                !storage.is_empty() && !self.value_cloners.contains_key(type_id)
+            });
+        if let Some((_, storage)) = unregistered {
+            return Err(SnapshotError::NotCloneable {
+                type_name: storage.component_type_name(),
+            });
+        }
+
+        self.history = Some(HistoryRecorder {
+            ticks: VecDeque::with_capacity(max_ticks),
+            current: TickUndo::default(),
+            max_ticks,
+            tick: 0,
+        });
+        Ok(())
+    }
+
+    /// Stops recording and frees the recorded history.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Returns the history recorder, if history is enabled.
+    pub fn history(&self) -> Option<&HistoryRecorder> {
+        self.history.as_ref()
+    }
+
+    /// Restores the world to its state at the end of the tick `n` ticks ago.
+    ///
+    /// Everything that happened since, including changes made outside of a
+    /// tick, is undone: entities spawned since disappear, deleted ones come
+    /// back, and components, resources, labels, tags and parent links get their
+    /// old values. Like [`World::restore`], the ephemeral state of the current
+    /// tick is discarded and observers do not run. The rolled back ticks are
+    /// dropped from the history, and the next recorded tick continues from the
+    /// restored one. Rolling back 0 ticks restores the state at the end of the
+    /// last tick.
+    ///
+    /// # Errors
+    /// Returns [`RollbackError::HistoryDisabled`] if history is not enabled,
+    /// [`RollbackError::NotEnoughHistory`] if fewer than `n` ticks are
+    /// recorded, or [`RollbackError::Unrecorded`] if a value to restore was
+    /// changed while its type wasn't registered as cloneable. The world is left
+    /// untouched in all cases.
+    pub fn rollback_ticks(&mut self, n: usize) -> Result<(), RollbackError> {
+        let history = self
+            .history
+            .as_mut()
+            .ok_or(RollbackError::HistoryDisabled)?;
+        let available = history.available_ticks();
+        if n > available {
+            return Err(RollbackError::NotEnoughHistory {
+                requested: n,
+                available,
+            });
+        }
+        let undone = std::iter::once(&history.current).chain(history.ticks.iter().rev().take(n));
+        if let Some(type_name) = undone.filter_map(|undo| undo.unrecorded).next() {
+            return Err(RollbackError::Unrecorded { type_name });
+        }
+
+        let mut history = self.history.take().expect("checked above");
+        self.undo_tick(std::mem::take(&mut history.current));
+        for _ in 0..n {
+            let undo = history.ticks.pop_back().expect("checked above");
+            self.undo_tick(undo);
+        }
+        history.tick -= n as u64;
+        self.history = Some(history);
+
+        self.reset_transient_state();
+
+        #[cfg(debug_assertions)]
+        self.assert_consistent("world is inconsistent after rollback_ticks");
+        Ok(())
+    }
+
+    /// Closes the current tick of the history log, if history is enabled.
+    pub(crate) fn record_history_tick(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.push();
+        }
+    }
+
+    /// Forgets every recorded change, for when the world state is replaced
+    /// as a whole.
+    pub(super) fn clear_history(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.ticks.clear();
+            history.current = TickUndo::default();
+        }
+    }
+
+    /// Saves the state of `entity` before it is spawned, deleted or cleaned up.
+    pub(super) fn record_entity(&mut self, entity: Entity) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        history.current.entities.entry(entity).or_insert_with(|| {
+            if self.entities.contains(&entity) {
+                EntityState::Active
+            } else if self.soft_deleted_entities.contains(&entity) {
+                EntityState::SoftDeleted
+            } else {
+                EntityState::Absent
+            }
+        });
+    }
+
+    /// Saves the `T` component of `entity` before it changes or is removed.
+    pub(super) fn record_component<T: Component>(&mut self, entity: Entity) {
+        self.record_component_by_type_id(TypeId::of::<T>(), entity);
+    }
+
+    /// Saves every `T` component before all of them may change.
+    pub(super) fn record_components<T: Component>(&mut self) {
+        if self.history.is_none() {
+            return;
+        }
+        let type_id = TypeId::of::<T>();
+        let stored = self
+            .component_storages
+            .get(&type_id)
+            .map(|storage| storage.stored_entities())
+            .unwrap_or_default();
+        for entity in stored {
+            self.record_component_by_type_id(type_id, entity);
+        }
+    }
+
+    /// Notes that `entity` had no `T` component before one was just added.
+    pub(super) fn record_component_added<T: Component>(&mut self, entity: Entity) {
+        if let Some(history) = &mut self.history {
+            history
+                .current
+                .components
+                .entry((TypeId::of::<T>(), entity))
+                .or_insert(None);
+        }
+    }
+
+    /// Saves every component of `entity` before they are all removed.
+    pub(super) fn record_entity_components(&mut self, entity: Entity) {
+        if self.history.is_none() {
+            return;
+        }
+        let type_ids: Vec<TypeId> = self
+            .component_storages
+            .iter()
+            .filter(|(_, storage)| storage.contains_entity(entity))
+            .map(|(&type_id, _)| type_id)
+            .collect();
+        for type_id in type_ids {
+            self.record_component_by_type_id(type_id, entity);
+        }
+    }
+
+    fn record_component_by_type_id(&mut self, type_id: TypeId, entity: Entity) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        let undo = &mut history.current;
+        if undo.components.contains_key(&(type_id, entity)) {
+            return;
+        }
+
+        let saved = match self.component_storages.get(&type_id) {
+            Some(storage) if storage.contains_entity(entity) => {
+                match self.value_cloners.get(&type_id) {
+                    Some(fns) => (fns.save)(storage.as_ref(), entity),
+                    None => {
+                        undo.unrecorded.get_or_insert(storage.component_type_name());
+                        return;
+                    }
+                }
+            }
+            _ => None,
+        };
+        undo.components.insert((type_id, entity), saved);
+    }
+
+    /// Saves the `T` resource before it changes or is removed.
+    pub(super) fn record_resource<T: Component>(&mut self) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        let type_id = TypeId::of::<T>();
+        let undo = &mut history.current;
+        if undo.resources.contains_key(&type_id) {
+            return;
+        }
+
+        let saved = match self.resource_storages.get(&type_id) {
+            Some(storage) if storage.contains_entity(self.resource_entity) => {
+                match self.value_cloners.get(&type_id) {
+                    Some(fns) => (fns.save)(storage.as_ref(), self.resource_entity),
+                    None => {
+                        undo.unrecorded.get_or_insert(storage.component_type_name());
+                        return;
+                    }
+                }
+            }
+            _ => None,
+        };
+        undo.resources.insert(type_id, saved);
+    }
+
+    /// Saves the label of `entity` before it changes.
+    pub(super) fn record_label(&mut self, entity: Entity) {
+        if let Some(history) = &mut self.history {
+            history
+                .current
+                .labels
+                .entry(entity)
+                .or_insert_with(|| self.entity_to_label.get(&entity).cloned());
+        }
+    }
+
+    /// Saves the tags of `entity` before they change.
+    pub(super) fn record_tags(&mut self, entity: Entity) {
+        if let Some(history) = &mut self.history {
+            history
+                .current
+                .tags
+                .entry(entity)
+                .or_insert_with(|| self.entity_tags.get(&entity).copied().unwrap_or(0));
+        }
+    }
+
+    /// Saves the parent link of `child` before it changes.
+    pub(super) fn record_parent(&mut self, child: Entity) {
+        if let Some(history) = &mut self.history {
+            history
+                .current
+                .parents
+                .entry(child)
+                .or_insert_with(|| self.entity_parents.get(&child).copied());
+        }
+    }
+
+    /// Saves the id allocator before an entity is spawned or an id recycled.
+    pub(super) fn record_allocator(&mut self) {
+        if let Some(history) = &mut self.history {
+            history
+                .current
+                .allocator
+                .get_or_insert_with(|| (self.next_local_entity_id, self.free_entities.clone()));
+        }
+    }
+
+    /// Saves everything `cleanup_deleted_entities()` is about to drop.
+    pub(super) fn record_cleanup(&mut self) {
+        if self.history.is_none() {
+            return;
+        }
+        self.record_allocator();
+
+        let deleted: Vec<Entity> = self.soft_deleted_entities.iter().copied().collect();
+        for entity in deleted {
+            self.record_entity(entity);
+            self.record_entity_components(entity);
+            self.record_label(entity);
+            self.record_tags(entity);
+            self.record_parent(entity);
+            let children: Vec<Entity> = self
+                .entity_children
+                .get(&entity)
+                .map(|children| children.iter().copied().collect())
+                .unwrap_or_default();
+            for child in children {
+                self.record_parent(child);
+            }
+        }
+    }
+
+    /// Puts back the state `undo` saved, undoing one tick.
+    fn undo_tick(&mut self, undo: TickUndo) {
+        for (entity, state) in undo.entities {
+            let (active, soft_deleted) = match state {
+                EntityState::Absent => (false, false),
+                EntityState::Active => (true, false),
+                EntityState::SoftDeleted => (false, true),
+            };
+            if active {
+                self.entities.insert(entity);
+            } else {
+                self.entities.remove(&entity);
+            }
+            if soft_deleted {
+                self.soft_deleted_entities.insert(entity);
+            } else {
+                self.soft_deleted_entities.remove(&entity);
+            }
+        }
+
+        for ((type_id, entity), saved) in undo.components {
+            match saved {
+                Some(value) => {
+                    let restore = self.value_cloners[&type_id].restore_component;
+                    restore(self, entity, value);
+                }
+                None => {
+                    if let Some(storage) = self.component_storages.get_mut(&type_id) {
+                        storage.remove_entity(entity);
+                    }
+                    if let Some(entities) = self.reverse_component_index.get_mut(&type_id) {
+                        entities.remove(&entity);
+                    }
+                }
+            }
+        }
+
+        for (type_id, saved) in undo.resources {
+            match saved {
+                Some(value) => {
+                    let restore = self.value_cloners[&type_id].restore_resource;
+                    restore(self, value);
+                }
+                None => {
+                    if let Some(storage) = self.resource_storages.get_mut(&type_id) {
+                        storage.remove_entity(self.resource_entity);
+                    }
+                }
+            }
+        }
+
+        // Drop the current labels first, so labels moving between entities
+        // don't get removed again after being put back
+        for entity in undo.labels.keys() {
+            if let Some(label) = self.entity_to_label.remove(entity) {
+                if self.label_to_entity.get(&label) == Some(entity) {
+                    self.label_to_entity.remove(&label);
+                }
+            }
+        }
+        for (entity, label) in undo.labels {
+            if let Some(label) = label {
+                self.label_to_entity.insert(label.clone(), entity);
+                self.entity_to_label.insert(entity, label);
+            }
+        }
+
+        for (entity, bits) in undo.tags {
+            if bits == 0 {
+                self.entity_tags.remove(&entity);
+            } else {
+                self.entity_tags.insert(entity, bits);
+            }
+        }
+
+        for child in undo.parents.keys() {
+            self.unlink_parent(*child);
+        }
+        for (child, parent) in undo.parents {
+            if let Some(parent) = parent {
+                self.entity_parents.insert(child, parent);
+                self.entity_children
+                    .entry(parent)
+                    .or_default()
+                    .insert(child);
+            }
+        }
+
+        if let Some((next_local_entity_id, free_entities)) = undo.allocator {
+            if self.next_local_entity_id.is_some() {
+                self.next_local_entity_id = next_local_entity_id;
+            }
+            self.free_entities = free_entities;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Entity, Query, SequentialSystemScheduler, System};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: i32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Turn(u32);
+    impl Component for Turn {}
+
+    #[derive(Debug, PartialEq)]
+    struct Socket;
+    impl Component for Socket {}
+
+    /// Scripted turns: move everyone, spawn on even turns, delete on turn 3.
+    struct ScriptSystem;
+    impl System for ScriptSystem {
+        fn run(&self, world: &mut World) {
+            let turn = world.get_resource::<Turn>().unwrap().0 + 1;
+            world.insert_resource(Turn(turn));
+
+            let movers: Vec<Entity> = Query::<Position>::new()
+                .iter(world)
+                .map(|(entity, _)| entity)
+                .collect();
+            for entity in movers {
+                world
+                    .update_component::<Position, _>(entity, |p| Position { x: p.x + 1 })
+                    .unwrap();
+            }
+            if matches!(turn, 2 | 4) {
+                let spawned = world.spawn_entity();
+                world
+                    .add_component(
+                        spawned,
+                        Position {
+                            x: turn as i32 * 10,
+                        },
+                    )
+                    .unwrap();
+            }
+            if turn == 3 {
+                let first = *world.entities().min_by_key(|e| e.id()).unwrap();
+                world.delete_entity(first);
+            }
+        }
+    }
+
+    fn positions(world: &World) -> Vec<(Entity, Position)> {
+        let mut positions: Vec<_> = Query::<Position>::new()
+            .iter(world)
+            .map(|(entity, position)| (entity, position.clone()))
+            .collect();
+        positions.sort_by_key(|(entity, _)| entity.id());
+        positions
+    }
+
+    fn scripted_world() -> (World, SequentialSystemScheduler) {
+        let mut world = World::new();
+        world.register_cloneable::<Position>();
+        world.register_cloneable::<Turn>();
+        world.insert_resource(Turn(0));
+        for x in 0..3 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Position { x }).unwrap();
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(ScriptSystem).unwrap();
+        scheduler.build().unwrap();
+        (world, scheduler)
+    }
+
+    #[test]
+    fn test_rollback_matches_snapshot_taken_at_that_tick() {
+        let (mut world, scheduler) = scripted_world();
+        world.enable_history(8).unwrap();
+
+        let mut snapshots = Vec::new();
+        for _ in 0..5 {
+            scheduler.run_tick(&mut world);
+            snapshots.push(world.snapshot().unwrap());
+        }
+        assert_eq!(world.history().unwrap().tick(), 5);
+
+        // Expected state after tick 3, captured in a separate world
+        let mut expected = World::new();
+        expected.restore(&snapshots[2]);
+
+        world.rollback_ticks(2).unwrap();
+
+        assert_eq!(positions(&world), positions(&expected));
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(3)));
+        assert_eq!(
+            world.get_resource::<Turn>(),
+            expected.get_resource::<Turn>()
+        );
+        assert_eq!(world.entities().count(), expected.entities().count());
+        assert_eq!(world.history().unwrap().tick(), 3);
+        assert_eq!(world.history().unwrap().available_ticks(), 3);
+
+        // Replaying continues from the restored tick
+        scheduler.run_tick(&mut world);
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(4)));
+        assert_eq!(world.history().unwrap().tick(), 4);
+    }
+
+    #[test]
+    fn test_history_keeps_at_most_max_ticks() {
+        let (mut world, scheduler) = scripted_world();
+        world.enable_history(2).unwrap();
+
+        for _ in 0..5 {
+            scheduler.run_tick(&mut world);
+        }
+
+        let history = world.history().unwrap();
+        assert_eq!(history.available_ticks(), 2);
+        assert_eq!(history.max_ticks(), 2);
+        assert_eq!(
+            world.rollback_ticks(3),
+            Err(RollbackError::NotEnoughHistory {
+                requested: 3,
+                available: 2
+            })
+        );
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(5)));
+
+        world.rollback_ticks(2).unwrap();
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(3)));
+    }
+
+    #[test]
+    fn test_rollback_zero_discards_changes_outside_ticks() {
+        let (mut world, scheduler) = scripted_world();
+        world.enable_history(4).unwrap();
+        scheduler.run_tick(&mut world);
+        let expected = positions(&world);
+
+        let intruder = world.spawn_entity();
+        world.add_component(intruder, Position { x: 99 }).unwrap();
+        world.insert_resource(Turn(42));
+
+        world.rollback_ticks(0).unwrap();
+
+        assert_eq!(positions(&world), expected);
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(1)));
+    }
+
+    #[test]
+    fn test_enable_history_requires_cloneable_types() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Socket).unwrap();
+
+        assert!(matches!(
+            world.enable_history(4),
+            Err(SnapshotError::NotCloneable { .. })
+        ));
+        assert!(world.history().is_none());
+        assert_eq!(world.rollback_ticks(1), Err(RollbackError::HistoryDisabled));
+    }
+
+    #[test]
+    fn test_without_history_ticks_are_not_recorded() {
+        let (mut world, scheduler) = scripted_world();
+        scheduler.run_tick(&mut world);
+
+        assert!(world.history().is_none());
+
+        world.enable_history(4).unwrap();
+        scheduler.run_tick(&mut world);
+        world.disable_history();
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(world.rollback_ticks(1), Err(RollbackError::HistoryDisabled));
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(3)));
+    }
+
+    #[test]
+    fn test_unregistered_type_added_after_enable_history() {
+        struct Connect;
+        impl System for Connect {
+            fn run(&self, world: &mut World) {
+                let player = world.spawn_entity();
+                world.add_component(player, Socket).unwrap();
+            }
+        }
+
+        let mut world = World::new();
+        world.enable_history(4).unwrap();
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(Connect).unwrap();
+        scheduler.build().unwrap();
+
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
+        assert_eq!(world.history().unwrap().available_ticks(), 2);
+        assert_eq!(Query::<Socket>::new().iter(&world).count(), 2);
+
+        // Additions are undone without copying anything
+        world.rollback_ticks(1).unwrap();
+        assert_eq!(Query::<Socket>::new().iter(&world).count(), 1);
+        assert_eq!(world.entities().count(), 1);
+    }
+
+    #[test]
+    fn test_rollback_over_unrecorded_change_fails() {
+        let (mut world, scheduler) = scripted_world();
+        world.enable_history(4).unwrap();
+        let player = world.spawn_entity();
+        world.add_component(player, Socket).unwrap();
+        scheduler.run_tick(&mut world);
+
+        world.remove_component::<Socket>(player);
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(
+            world.rollback_ticks(1),
+            Err(RollbackError::Unrecorded {
+                type_name: std::any::type_name::<Socket>()
+            })
+        );
+        assert!(!world.has_component::<Socket>(player));
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(2)));
+
+        // Ticks after the unrecorded change can still be undone
+        scheduler.run_tick(&mut world);
+        world.rollback_ticks(1).unwrap();
+        assert_eq!(world.get_resource::<Turn>(), Some(&Turn(2)));
+    }
+
+    #[test]
+    fn test_rollback_restores_cleaned_up_entity() {
+        let mut world = World::new_with_allocator(crate::EntityAllocator::Deterministic);
+        world.register_cloneable::<Position>();
+        let hidden = world.register_tag("hidden").unwrap();
+        let room = world.spawn_entity();
+        let goblin = world.spawn_entity();
+        let dagger = world.spawn_entity();
+        world.add_component(goblin, Position { x: 7 }).unwrap();
+        world.set_entity_label(goblin, "goblin").unwrap();
+        world.set_tag(goblin, hidden).unwrap();
+        world.set_parent(goblin, room).unwrap();
+        world.set_parent(dagger, goblin).unwrap();
+        world.enable_history(4).unwrap();
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.build().unwrap();
+        world.delete_entity(goblin);
+        scheduler.run_tick(&mut world);
+        let orc = world.spawn_entity();
+        world.set_entity_label(orc, "goblin").unwrap();
+        scheduler.run_tick(&mut world);
+        assert_eq!(world.parent_of(dagger), None);
+
+        world.rollback_ticks(2).unwrap();
+
+        assert!(world.entities().any(|&entity| entity == goblin));
+        assert_eq!(
+            world.get_component::<Position>(goblin),
+            Some(&Position { x: 7 })
+        );
+        assert_eq!(world.entity_by_label("goblin"), Some(goblin));
+        assert!(world.has_tag(goblin, hidden));
+        assert_eq!(world.parent_of(goblin), Some(room));
+        assert_eq!(world.children_of(goblin), vec![dagger]);
+        assert_eq!(world.recycled_id_count(), 0);
+
+        // The deterministic allocator hands out the same id again
+        world.delete_entity(goblin);
+        scheduler.run_tick(&mut world);
+        assert_eq!(world.spawn_entity(), orc);
+    }
+}
@@ -0,0 +1,385 @@
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::component::downcast_storage;
+use crate::{Component, Entity};
+
+use super::World;
+
+/// Type-erased operations every component index supports.
+pub(super) trait AnyIndex: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    /// Drops `entity` from the index.
+    fn remove_entity(&mut self, entity: Entity);
+    /// Marks the index as out of date after components were written in place.
+    fn invalidate(&mut self);
+}
+
+/// The index of one component type, whatever its key type.
+trait TypedIndex<T: Component>: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    /// Re-keys `entity` from its current component, or drops it if it has none.
+    fn update(&mut self, entity: Entity, component: Option<&T>);
+    /// Forgets all keys and indexes `components` from scratch.
+    fn rebuild(&mut self, components: &mut dyn Iterator<Item = (Entity, &T)>);
+}
+
+struct ValueIndex<T, K> {
+    key_fn: Box<dyn Fn(&T) -> K + Send + Sync>,
+    entities_by_key: HashMap<K, HashSet<Entity>>,
+    key_by_entity: HashMap<Entity, K>,
+}
+
+impl<T, K: Hash + Eq + Clone> ValueIndex<T, K> {
+    fn remove(&mut self, entity: Entity) {
+        if let Some(key) = self.key_by_entity.remove(&entity) {
+            if let Some(entities) = self.entities_by_key.get_mut(&key) {
+                entities.remove(&entity);
+                if entities.is_empty() {
+                    self.entities_by_key.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, key: K) {
+        self.entities_by_key
+            .entry(key.clone())
+            .or_default()
+            .insert(entity);
+        self.key_by_entity.insert(entity, key);
+    }
+}
+
+impl<T: Component, K: Hash + Eq + Clone + Send + Sync + 'static> TypedIndex<T>
+    for ValueIndex<T, K>
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn update(&mut self, entity: Entity, component: Option<&T>) {
+        let key = component.map(|component| (self.key_fn)(component));
+        if key.is_some() && self.key_by_entity.get(&entity) == key.as_ref() {
+            return;
+        }
+
+        self.remove(entity);
+        if let Some(key) = key {
+            self.insert(entity, key);
+        }
+    }
+
+    fn rebuild(&mut self, components: &mut dyn Iterator<Item = (Entity, &T)>) {
+        self.entities_by_key.clear();
+        self.key_by_entity.clear();
+        for (entity, component) in components {
+            let key = (self.key_fn)(component);
+            self.insert(entity, key);
+        }
+    }
+}
+
+/// The index registered for component `T`.
+struct IndexSlot<T: Component> {
+    index: Box<dyn TypedIndex<T>>,
+    stale: bool,
+}
+
+impl<T: Component> AnyIndex for IndexSlot<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.index.update(entity, None);
+    }
+
+    fn invalidate(&mut self) {
+        self.stale = true;
+    }
+}
+
+impl World {
+    /// Maintains a lookup table from a key derived from component `T` to the
+    /// entities holding it.
+    ///
+    /// The index is built from the existing components and kept up to date by
+    /// every later add, replace, update and removal of `T`, so
+    /// [`find_by_index`](World::find_by_index) doesn't have to scan all
+    /// entities. A component type has at most one index; calling this again
+    /// replaces it.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Name { value: String }
+    /// impl Component for Name {}
+    ///
+    /// let mut world = World::new();
+    /// world.index_component_by::<Name, String>(|name| name.value.clone());
+    ///
+    /// let guard = world.spawn_entity();
+    /// world.add_component(guard, Name { value: "Guard".to_string() }).unwrap();
+    ///
+    /// let found: Vec<_> = world.find_by_index::<Name>(&"Guard".to_string()).collect();
+    /// assert_eq!(found, vec![guard]);
+    /// ```
+    pub fn index_component_by<T, K>(&mut self, key_fn: impl Fn(&T) -> K + Send + Sync + 'static)
+    where
+        T: Component,
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+    {
+        let mut index = ValueIndex {
+            key_fn: Box::new(key_fn),
+            entities_by_key: HashMap::new(),
+            key_by_entity: HashMap::new(),
+        };
+        index.rebuild(&mut self.iter_components::<T>());
+
+        self.component_indexes.insert(
+            TypeId::of::<T>(),
+            Box::new(IndexSlot::<T> {
+                index: Box::new(index),
+                stale: false,
+            }),
+        );
+    }
+
+    /// Removes the index of component `T`.
+    ///
+    /// # Returns
+    /// `true` if `T` was indexed
+    pub fn remove_component_index<T: Component>(&mut self) -> bool {
+        self.component_indexes.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Returns the entities whose component `T` has the index key `key`.
+    ///
+    /// Deleted entities are skipped. The iterator is empty if `T` has no index
+    /// or `key` is not of the index's key type. Order is unspecified.
+    pub fn find_by_index<T: Component>(
+        &self,
+        key: &(impl Hash + Eq + 'static),
+    ) -> impl Iterator<Item = Entity> + '_ {
+        let mut found = Vec::new();
+
+        let slot = self
+            .component_indexes
+            .get(&TypeId::of::<T>())
+            .and_then(|slot| slot.as_any().downcast_ref::<IndexSlot<T>>());
+        if let Some(slot) = slot {
+            self.collect_matches(slot, key, &mut found);
+        }
+
+        found
+            .into_iter()
+            .filter(move |&entity| self.is_entity_active(entity))
+    }
+
+    fn collect_matches<T: Component, K: Hash + Eq + 'static>(
+        &self,
+        slot: &IndexSlot<T>,
+        key: &K,
+        found: &mut Vec<Entity>,
+    ) {
+        let Some(index) = slot.index.as_any().downcast_ref::<ValueIndex<T, K>>() else {
+            return;
+        };
+
+        if slot.stale {
+            // Components were written in place since the last rebuild
+            found.extend(
+                self.iter_components::<T>()
+                    .filter(|(_, component)| (index.key_fn)(component) == *key)
+                    .map(|(entity, _)| entity),
+            );
+        } else if let Some(entities) = index.entities_by_key.get(key) {
+            found.extend(entities.iter().copied());
+        }
+    }
+
+    /// Brings the index of `T` up to date after `entity`'s component changed.
+    pub(super) fn reindex<T: Component>(&mut self, entity: Entity) {
+        if self.component_indexes.is_empty() {
+            return;
+        }
+
+        let type_id = TypeId::of::<T>();
+        let Some(slot) = self
+            .component_indexes
+            .get_mut(&type_id)
+            .and_then(|slot| slot.as_any_mut().downcast_mut::<IndexSlot<T>>())
+        else {
+            return;
+        };
+        let storage = self
+            .component_storages
+            .get(&type_id)
+            .and_then(|storage| downcast_storage::<T>(storage.as_ref()));
+
+        if slot.stale {
+            slot.index
+                .rebuild(&mut storage.into_iter().flat_map(|storage| storage.iter()));
+            slot.stale = false;
+        } else {
+            slot.index
+                .update(entity, storage.and_then(|storage| storage.get(entity)));
+        }
+    }
+
+    /// Marks the index of `T` as out of date.
+    pub(super) fn invalidate_index<T: Component>(&mut self) {
+        if let Some(slot) = self.component_indexes.get_mut(&TypeId::of::<T>()) {
+            slot.invalidate();
+        }
+    }
+
+    /// Drops `entity` from every component index.
+    pub(super) fn unindex_entity(&mut self, entity: Entity) {
+        for slot in self.component_indexes.values_mut() {
+            slot.remove_entity(entity);
+        }
+    }
+
+    /// Marks every component index as out of date.
+    pub(super) fn invalidate_all_indexes(&mut self) {
+        for slot in self.component_indexes.values_mut() {
+            slot.invalidate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tag {
+        name: String,
+    }
+    impl Component for Tag {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Npc;
+    impl Component for Npc {}
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            name: name.to_string(),
+        }
+    }
+
+    fn find(world: &World, name: &str) -> HashSet<Entity> {
+        world.find_by_index::<Tag>(&name.to_string()).collect()
+    }
+
+    fn indexed_world() -> World {
+        let mut world = World::new();
+        world.index_component_by::<Tag, String>(|tag| tag.name.clone());
+        world
+    }
+
+    #[test]
+    fn test_index_finds_inserted_components() {
+        let mut world = World::new();
+        let existing = world.spawn_entity();
+        world.add_component(existing, tag("Guard")).unwrap();
+
+        // Components added before the index are picked up when it is built
+        world.index_component_by::<Tag, String>(|tag| tag.name.clone());
+
+        let second = world.spawn_entity();
+        world.add_component(second, tag("Guard")).unwrap();
+        let merchant = world.spawn_entity();
+        world.add_component(merchant, tag("Merchant")).unwrap();
+
+        assert_eq!(find(&world, "Guard"), HashSet::from([existing, second]));
+        assert_eq!(find(&world, "Merchant"), HashSet::from([merchant]));
+        assert!(find(&world, "Dragon").is_empty());
+    }
+
+    #[test]
+    fn test_index_rekeys_after_update() {
+        let mut world = indexed_world();
+        let entity = world.spawn_entity();
+        world.add_component(entity, tag("Guard")).unwrap();
+
+        world
+            .update_component::<Tag, _>(entity, |_| tag("Captain"))
+            .unwrap();
+        assert!(find(&world, "Guard").is_empty());
+        assert_eq!(find(&world, "Captain"), HashSet::from([entity]));
+
+        world.replace_component(entity, tag("Deserter"));
+        assert!(find(&world, "Captain").is_empty());
+        assert_eq!(find(&world, "Deserter"), HashSet::from([entity]));
+
+        let query = crate::Query::<Tag>::new();
+        world.update_all(&query, |_| tag("Ghost"));
+        assert_eq!(find(&world, "Ghost"), HashSet::from([entity]));
+    }
+
+    #[test]
+    fn test_index_sees_in_place_writes() {
+        let mut world = indexed_world();
+        let entity = world.spawn_entity();
+        world.add_component(entity, tag("Guard")).unwrap();
+
+        for (_, tag) in world.iter_components_mut::<Tag>() {
+            tag.name = "Sleeping guard".to_string();
+        }
+        assert!(find(&world, "Guard").is_empty());
+        assert_eq!(find(&world, "Sleeping guard"), HashSet::from([entity]));
+
+        // The next write rebuilds the index
+        let other = world.spawn_entity();
+        world.add_component(other, tag("Guard")).unwrap();
+        assert_eq!(find(&world, "Sleeping guard"), HashSet::from([entity]));
+        assert_eq!(find(&world, "Guard"), HashSet::from([other]));
+    }
+
+    #[test]
+    fn test_index_drops_removed_and_deleted_entities() {
+        let mut world = indexed_world();
+        let removed = world.spawn_entity();
+        let deleted = world.spawn_entity();
+        let kept = world.spawn_entity();
+        for entity in [removed, deleted, kept] {
+            world.add_component(entity, tag("Guard")).unwrap();
+        }
+
+        world.remove_component::<Tag>(removed);
+        assert_eq!(find(&world, "Guard").len(), 2);
+
+        world.delete_entity(deleted);
+        assert_eq!(find(&world, "Guard"), HashSet::from([kept]));
+        world.cleanup_deleted_entities();
+        assert_eq!(find(&world, "Guard"), HashSet::from([kept]));
+    }
+
+    #[test]
+    fn test_find_by_index_without_index_or_with_wrong_key_type() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, tag("Guard")).unwrap();
+        world.add_component(entity, Npc).unwrap();
+
+        assert_eq!(world.find_by_index::<Tag>(&"Guard".to_string()).count(), 0);
+
+        world.index_component_by::<Tag, String>(|tag| tag.name.clone());
+        assert_eq!(world.find_by_index::<Tag>(&7u32).count(), 0);
+        assert_eq!(world.find_by_index::<Npc>(&"Guard".to_string()).count(), 0);
+
+        assert!(world.remove_component_index::<Tag>());
+        assert_eq!(world.find_by_index::<Tag>(&"Guard".to_string()).count(), 0);
+    }
+}
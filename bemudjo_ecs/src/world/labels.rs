@@ -0,0 +1,334 @@
+use crate::Entity;
+
+use super::World;
+
+/// Errors that can occur when labeling entities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelError {
+    /// The label is already used by another live entity.
+    DuplicateLabel,
+    /// The entity does not exist or has been deleted.
+    EntityNotFound,
+}
+
+impl std::fmt::Display for LabelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelError::DuplicateLabel => write!(f, "label is already used by another entity"),
+            LabelError::EntityNotFound => write!(f, "entity does not exist or has been deleted"),
+        }
+    }
+}
+
+impl std::error::Error for LabelError {}
+
+impl World {
+    /// Assigns a unique, human-readable label to an entity.
+    ///
+    /// Labels let content scripts and debugging tools refer to entities by stable
+    /// names such as `"town_square"` instead of opaque handles. Each label maps to
+    /// at most one live entity and each entity has at most one label; labeling an
+    /// already labeled entity replaces its previous label.
+    ///
+    /// Labels of deleted entities stop resolving immediately and are released for
+    /// reuse; their bookkeeping is dropped during `cleanup_deleted_entities()`.
+    ///
+    /// # Parameters
+    /// * `entity` - The entity to label
+    /// * `label` - The label to assign
+    ///
+    /// # Returns
+    /// * `Ok(())` if the label was assigned
+    /// * `Err(LabelError::DuplicateLabel)` if another live entity already uses the label
+    /// * `Err(LabelError::EntityNotFound)` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{LabelError, World};
+    ///
+    /// let mut world = World::new();
+    /// let square = world.spawn_entity();
+    /// let smith = world.spawn_entity();
+    ///
+    /// world.set_entity_label(square, "town_square").unwrap();
+    /// assert_eq!(world.entity_by_label("town_square"), Some(square));
+    /// assert_eq!(world.label_of(square), Some("town_square"));
+    ///
+    /// // Labels are unique
+    /// assert_eq!(
+    ///     world.set_entity_label(smith, "town_square"),
+    ///     Err(LabelError::DuplicateLabel)
+    /// );
+    /// ```
+    pub fn set_entity_label(
+        &mut self,
+        entity: Entity,
+        label: impl Into<String>,
+    ) -> Result<(), LabelError> {
+        if !self.is_entity_active(entity) {
+            return Err(LabelError::EntityNotFound);
+        }
+
+        let label = label.into();
+        if let Some(&owner) = self.label_to_entity.get(&label) {
+            if owner == entity {
+                return Ok(());
+            }
+            if self.is_entity_active(owner) {
+                return Err(LabelError::DuplicateLabel);
+            }
+            // The previous owner is gone, release the label
+            self.record_label(owner);
+            self.entity_to_label.remove(&owner);
+        }
+
+        self.record_label(entity);
+
+        if let Some(previous) = self.entity_to_label.remove(&entity) {
+            self.label_to_entity.remove(&previous);
+        }
+
+        self.label_to_entity.insert(label.clone(), entity);
+        self.entity_to_label.insert(entity, label);
+        Ok(())
+    }
+
+    /// Looks up a live entity by its label.
+    ///
+    /// # Returns
+    /// * `Some(Entity)` if a live entity carries the label
+    /// * `None` if the label is unknown or its entity has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let npc = world.spawn_entity();
+    /// world.set_entity_label(npc, "blacksmith_npc").unwrap();
+    ///
+    /// assert_eq!(world.entity_by_label("blacksmith_npc"), Some(npc));
+    ///
+    /// world.delete_entity(npc);
+    /// assert_eq!(world.entity_by_label("blacksmith_npc"), None);
+    /// ```
+    pub fn entity_by_label(&self, label: &str) -> Option<Entity> {
+        self.label_to_entity
+            .get(label)
+            .copied()
+            .filter(|&entity| self.is_entity_active(entity))
+    }
+
+    /// Returns the label of a live entity.
+    ///
+    /// # Returns
+    /// * `Some(&str)` if the entity is alive and labeled
+    /// * `None` if the entity has no label or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// assert_eq!(world.label_of(entity), None);
+    ///
+    /// world.set_entity_label(entity, "town_square").unwrap();
+    /// assert_eq!(world.label_of(entity), Some("town_square"));
+    /// ```
+    pub fn label_of(&self, entity: Entity) -> Option<&str> {
+        if !self.is_entity_active(entity) {
+            return None;
+        }
+
+        self.entity_to_label.get(&entity).map(String::as_str)
+    }
+
+    /// Drops the labels of soft-deleted entities.
+    ///
+    /// Called from `cleanup_deleted_entities()`.
+    pub(super) fn cleanup_deleted_labels(&mut self) {
+        if self.entity_to_label.is_empty() {
+            return;
+        }
+
+        for entity in &self.soft_deleted_entities {
+            if let Some(label) = self.entity_to_label.remove(entity) {
+                // Only release the label if it wasn't reassigned to a live entity
+                if self.label_to_entity.get(&label) == Some(entity) {
+                    self.label_to_entity.remove(&label);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SequentialSystemScheduler, System};
+
+    #[test]
+    fn test_set_and_lookup_label() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        world.set_entity_label(entity, "town_square").unwrap();
+
+        assert_eq!(world.entity_by_label("town_square"), Some(entity));
+        assert_eq!(world.label_of(entity), Some("town_square"));
+        assert_eq!(world.entity_by_label("unknown"), None);
+    }
+
+    #[test]
+    fn test_set_same_label_twice_is_ok() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+
+        world.set_entity_label(entity, "town_square").unwrap();
+        world
+            .set_entity_label(entity, String::from("town_square"))
+            .unwrap();
+
+        assert_eq!(world.entity_by_label("town_square"), Some(entity));
+    }
+
+    #[test]
+    fn test_relabel_releases_previous_label() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        let other = world.spawn_entity();
+
+        world.set_entity_label(entity, "old_name").unwrap();
+        world.set_entity_label(entity, "new_name").unwrap();
+
+        assert_eq!(world.label_of(entity), Some("new_name"));
+        assert_eq!(world.entity_by_label("new_name"), Some(entity));
+        assert_eq!(world.entity_by_label("old_name"), None);
+
+        // The old label is free again
+        world.set_entity_label(other, "old_name").unwrap();
+        assert_eq!(world.entity_by_label("old_name"), Some(other));
+    }
+
+    #[test]
+    fn test_duplicate_label_rejected() {
+        let mut world = World::new();
+        let first = world.spawn_entity();
+        let second = world.spawn_entity();
+
+        world.set_entity_label(first, "blacksmith_npc").unwrap();
+        let result = world.set_entity_label(second, "blacksmith_npc");
+
+        assert_eq!(result, Err(LabelError::DuplicateLabel));
+        assert_eq!(world.entity_by_label("blacksmith_npc"), Some(first));
+        assert_eq!(world.label_of(second), None);
+    }
+
+    #[test]
+    fn test_label_deleted_entity_fails() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.delete_entity(entity);
+
+        let result = world.set_entity_label(entity, "ghost");
+
+        assert_eq!(result, Err(LabelError::EntityNotFound));
+        assert_eq!(world.entity_by_label("ghost"), None);
+    }
+
+    #[test]
+    fn test_lookup_after_deletion() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.set_entity_label(entity, "doomed").unwrap();
+
+        world.delete_entity(entity);
+        assert_eq!(world.entity_by_label("doomed"), None);
+        assert_eq!(world.label_of(entity), None);
+
+        world.cleanup_deleted_entities();
+        assert_eq!(world.entity_by_label("doomed"), None);
+        assert!(world.label_to_entity.is_empty());
+        assert!(world.entity_to_label.is_empty());
+    }
+
+    #[test]
+    fn test_label_reusable_after_owner_deleted() {
+        let mut world = World::new();
+        let old_owner = world.spawn_entity();
+        let new_owner = world.spawn_entity();
+        world.set_entity_label(old_owner, "boss").unwrap();
+
+        // Reuse before cleanup ran
+        world.delete_entity(old_owner);
+        world.set_entity_label(new_owner, "boss").unwrap();
+        assert_eq!(world.entity_by_label("boss"), Some(new_owner));
+
+        // Cleanup must not drop the label from its new owner
+        world.cleanup_deleted_entities();
+        assert_eq!(world.entity_by_label("boss"), Some(new_owner));
+        assert_eq!(world.label_of(new_owner), Some("boss"));
+    }
+
+    #[test]
+    fn test_labels_survive_cleanup_for_live_entities() {
+        let mut world = World::new();
+        let alive = world.spawn_entity();
+        let dead = world.spawn_entity();
+        world.set_entity_label(alive, "alive").unwrap();
+        world.set_entity_label(dead, "dead").unwrap();
+
+        world.delete_entity(dead);
+        world.cleanup_deleted_entities();
+
+        assert_eq!(world.entity_by_label("alive"), Some(alive));
+        assert_eq!(world.label_of(alive), Some("alive"));
+        assert_eq!(world.entity_by_label("dead"), None);
+    }
+
+    #[test]
+    fn test_labels_cleaned_up_by_scheduler() {
+        struct DespawnLabeledSystem;
+        impl System for DespawnLabeledSystem {
+            fn run(&self, world: &mut World) {
+                if let Some(entity) = world.entity_by_label("temporary") {
+                    world.delete_entity(entity);
+                }
+            }
+        }
+
+        let mut world = World::new();
+        let permanent = world.spawn_entity();
+        let temporary = world.spawn_entity();
+        world.set_entity_label(permanent, "permanent").unwrap();
+        world.set_entity_label(temporary, "temporary").unwrap();
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(DespawnLabeledSystem).unwrap();
+        scheduler.build().unwrap();
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(world.entity_by_label("temporary"), None);
+        assert!(!world.entity_to_label.contains_key(&temporary));
+        assert_eq!(world.entity_by_label("permanent"), Some(permanent));
+
+        // The label can be handed to a new entity on the next tick
+        let replacement = world.spawn_entity();
+        world.set_entity_label(replacement, "temporary").unwrap();
+        scheduler.run_tick(&mut world);
+        assert_eq!(world.entity_by_label("temporary"), None);
+    }
+
+    #[test]
+    fn test_label_error_display() {
+        assert_eq!(
+            LabelError::DuplicateLabel.to_string(),
+            "label is already used by another entity"
+        );
+        assert_eq!(
+            LabelError::EntityNotFound.to_string(),
+            "entity does not exist or has been deleted"
+        );
+    }
+}
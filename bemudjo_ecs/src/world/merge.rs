@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+
+use crate::{ComponentRegistry, Entity};
+
+use super::World;
+
+/// What [`World::merge`] does with a resource type both worlds hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceMergePolicy {
+    /// Keep the resource of the receiving world and drop the incoming one.
+    Skip,
+    /// Replace the resource of the receiving world with the incoming one.
+    Overwrite,
+    /// Fail with [`MergeError::ConflictingResources`] before anything is merged.
+    Error,
+}
+
+/// The outcome of [`World::merge`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Every live entity of the merged world, mapped to its new handle.
+    pub entities: HashMap<Entity, Entity>,
+    /// Type names of the resources moved into the receiving world, sorted.
+    pub merged_resources: Vec<&'static str>,
+    /// Type names of the incoming resources dropped by
+    /// [`ResourceMergePolicy::Skip`], sorted.
+    pub skipped_resources: Vec<&'static str>,
+}
+
+/// Errors that can occur when merging one world into another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    /// Entities of the merged world have components whose types are not in the registry.
+    UnregisteredComponents { type_names: Vec<&'static str> },
+    /// Both worlds hold resources of these types and the policy is
+    /// [`ResourceMergePolicy::Error`].
+    ConflictingResources { type_names: Vec<&'static str> },
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeError::UnregisteredComponents { type_names } => write!(
+                f,
+                "cannot merge world: unregistered components {}",
+                type_names.join(", ")
+            ),
+            MergeError::ConflictingResources { type_names } => write!(
+                f,
+                "cannot merge world: both worlds hold resources {}",
+                type_names.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl World {
+    /// Moves every live entity of `other`, with its components, into this
+    /// world, along with its resources.
+    ///
+    /// Meant for loading content built offline, like a zone instantiated from
+    /// prefabs, into a running world. Entities get new handles here; the
+    /// report maps each old handle to its new one so references kept outside
+    /// the world can be fixed up. Parent links between merged entities are
+    /// remapped as well. Components are added one by one, so callbacks
+    /// registered with [`World::observe_added`] run for each of them.
+    ///
+    /// Like [`World::transfer_entity`], every component type must be
+    /// registered in `registry`. Resource types both worlds hold are handled
+    /// according to `resources`. Ephemeral components, resources and events,
+    /// labels and tags are not merged.
+    ///
+    /// # Returns
+    /// * `Ok(MergeReport)` - The entity mapping and what happened to the resources
+    /// * `Err(MergeError::UnregisteredComponents)` if a live entity has unregistered components
+    /// * `Err(MergeError::ConflictingResources)` if resources conflict under [`ResourceMergePolicy::Error`]
+    ///
+    /// Errors are detected before anything is merged, so on error this world
+    /// is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, ComponentRegistry, Query, ResourceMergePolicy, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Room { name: String }
+    /// impl Component for Room {}
+    ///
+    /// let registry = ComponentRegistry::new().with::<Room>();
+    ///
+    /// let mut live = World::new();
+    /// let town = live.spawn_entity();
+    /// live.add_component(town, Room { name: "Town".to_string() }).unwrap();
+    ///
+    /// let mut zone = World::new();
+    /// let cave = zone.spawn_entity();
+    /// zone.add_component(cave, Room { name: "Cave".to_string() }).unwrap();
+    ///
+    /// let report = live.merge(zone, &registry, ResourceMergePolicy::Error).unwrap();
+    ///
+    /// let cave = report.entities[&cave];
+    /// assert_eq!(live.get_component::<Room>(cave).unwrap().name, "Cave");
+    /// assert_eq!(Query::<Room>::new().iter(&live).count(), 2);
+    /// ```
+    pub fn merge(
+        &mut self,
+        mut other: World,
+        registry: &ComponentRegistry,
+        resources: ResourceMergePolicy,
+    ) -> Result<MergeReport, MergeError> {
+        let mut unregistered: Vec<_> = other
+            .component_storages
+            .iter()
+            .filter(|(type_id, storage)| {
+                !registry.contains_type_id(**type_id)
+                    && other
+                        .entities
+                        .iter()
+                        .any(|&entity| storage.contains_entity(entity))
+            })
+            .map(|(_, storage)| storage.component_type_name())
+            .collect();
+        if !unregistered.is_empty() {
+            unregistered.sort_unstable();
+            return Err(MergeError::UnregisteredComponents {
+                type_names: unregistered,
+            });
+        }
+
+        let conflicting = self.conflicting_resources(&other);
+        if resources == ResourceMergePolicy::Error && !conflicting.is_empty() {
+            let mut type_names: Vec<_> = conflicting.into_values().collect();
+            type_names.sort_unstable();
+            return Err(MergeError::ConflictingResources { type_names });
+        }
+
+        let mut report = MergeReport::default();
+
+        // Spawn in id order, so new handles follow the order of the merged world
+        let mut incoming: Vec<Entity> = other.entities.iter().copied().collect();
+        incoming.sort_unstable_by_key(|entity| entity.id());
+        let links: Vec<(Entity, Entity)> = incoming
+            .iter()
+            .filter_map(|&child| Some((child, other.parent_of(child)?)))
+            .collect();
+
+        for entity in incoming {
+            let bundle = other
+                .extract_entity(entity)
+                .expect("entities of the merged world are live");
+            report
+                .entities
+                .insert(entity, self.insert_entity_bundle(bundle));
+        }
+        for (child, parent) in links {
+            self.set_parent(report.entities[&child], report.entities[&parent])
+                .expect("merged links can't form cycles");
+        }
+
+        let other_resource_entity = other.resource_entity;
+        for (type_id, storage) in &mut other.resource_storages {
+            let Some(resource) = storage.take_boxed(other_resource_entity) else {
+                continue;
+            };
+            let type_name = storage.component_type_name();
+            if resources == ResourceMergePolicy::Skip && conflicting.contains_key(type_id) {
+                report.skipped_resources.push(type_name);
+            } else {
+                resource.insert_resource_into(self);
+                report.merged_resources.push(type_name);
+            }
+        }
+        report.merged_resources.sort_unstable();
+        report.skipped_resources.sort_unstable();
+
+        Ok(report)
+    }
+
+    /// Returns the resource types both worlds hold, with their type names.
+    fn conflicting_resources(&self, other: &World) -> HashMap<std::any::TypeId, &'static str> {
+        other
+            .resource_storages
+            .iter()
+            .filter(|(type_id, storage)| {
+                storage.contains_entity(other.resource_entity)
+                    && self
+                        .resource_storages
+                        .get(type_id)
+                        .is_some_and(|own| own.contains_entity(self.resource_entity))
+            })
+            .map(|(type_id, storage)| (*type_id, storage.component_type_name()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Query};
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Npc {
+        name: String,
+    }
+    impl Component for Npc {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct ZoneName(String);
+    impl Component for ZoneName {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Cursed;
+    impl Component for Cursed {}
+
+    fn registry() -> ComponentRegistry {
+        ComponentRegistry::new().with::<Position>().with::<Npc>()
+    }
+
+    /// Builds a content world with `count` positioned entities, every tenth an NPC.
+    fn content_world(count: i32) -> World {
+        let mut zone = World::new();
+        for i in 0..count {
+            let entity = zone.spawn_entity();
+            zone.add_component(entity, Position { x: i, y: -i })
+                .unwrap();
+            if i % 10 == 0 {
+                zone.add_component(
+                    entity,
+                    Npc {
+                        name: format!("npc{i}"),
+                    },
+                )
+                .unwrap();
+            }
+        }
+        zone.insert_resource(ZoneName("caves".to_string()));
+        zone
+    }
+
+    #[test]
+    fn test_merge_content_world_into_live_world() {
+        let mut live = World::new();
+        for i in 0..20 {
+            let entity = live.spawn_entity();
+            live.add_component(entity, Position { x: 1000 + i, y: 0 })
+                .unwrap();
+        }
+        // Mid-simulation: a deleted entity still awaiting cleanup
+        let doomed = live.spawn_entity();
+        live.delete_entity(doomed);
+
+        let zone = content_world(500);
+        let expected: HashMap<Entity, Position> = Query::<Position>::new()
+            .iter(&zone)
+            .map(|(entity, position)| (entity, position.clone()))
+            .collect();
+
+        let report = live
+            .merge(zone, &registry(), ResourceMergePolicy::Error)
+            .unwrap();
+
+        // The map is complete and injective
+        assert_eq!(report.entities.len(), 500);
+        let new_handles: HashSet<Entity> = report.entities.values().copied().collect();
+        assert_eq!(new_handles.len(), 500);
+        for (old, new) in &report.entities {
+            assert_eq!(live.get_component::<Position>(*new), expected.get(old));
+        }
+
+        // Queries see the union
+        assert_eq!(Query::<Position>::new().iter(&live).count(), 520);
+        assert_eq!(Query::<Npc>::new().iter(&live).count(), 50);
+        assert_eq!(
+            live.get_resource::<ZoneName>(),
+            Some(&ZoneName("caves".to_string()))
+        );
+        assert_eq!(
+            report.merged_resources,
+            vec![std::any::type_name::<ZoneName>()]
+        );
+        assert!(live.check_consistency().is_consistent());
+    }
+
+    #[test]
+    fn test_merge_remaps_parent_links() {
+        let mut zone = World::new();
+        let room = zone.spawn_entity();
+        let chest = zone.spawn_entity();
+        zone.set_parent(chest, room).unwrap();
+
+        let mut live = World::new();
+        live.spawn_entity();
+        let report = live
+            .merge(zone, &registry(), ResourceMergePolicy::Error)
+            .unwrap();
+
+        assert_eq!(
+            live.parent_of(report.entities[&chest]),
+            Some(report.entities[&room])
+        );
+    }
+
+    #[test]
+    fn test_merge_resource_policies() {
+        let merge = |policy| {
+            let mut live = World::new();
+            live.insert_resource(ZoneName("town".to_string()));
+            let result = live.merge(content_world(3), &registry(), policy);
+            (live, result)
+        };
+
+        let (live, result) = merge(ResourceMergePolicy::Skip);
+        let report = result.unwrap();
+        assert_eq!(
+            report.skipped_resources,
+            vec![std::any::type_name::<ZoneName>()]
+        );
+        assert_eq!(
+            live.get_resource::<ZoneName>(),
+            Some(&ZoneName("town".to_string()))
+        );
+
+        let (live, result) = merge(ResourceMergePolicy::Overwrite);
+        assert!(result.unwrap().skipped_resources.is_empty());
+        assert_eq!(
+            live.get_resource::<ZoneName>(),
+            Some(&ZoneName("caves".to_string()))
+        );
+
+        // Conflicts are detected before anything moves
+        let (live, result) = merge(ResourceMergePolicy::Error);
+        assert_eq!(
+            result,
+            Err(MergeError::ConflictingResources {
+                type_names: vec![std::any::type_name::<ZoneName>()]
+            })
+        );
+        assert_eq!(live.entities().count(), 0);
+    }
+
+    #[test]
+    fn test_merge_with_unregistered_components_fails() {
+        let mut zone = content_world(3);
+        let cursed = zone.spawn_entity();
+        zone.add_component(cursed, Cursed).unwrap();
+
+        let mut live = World::new();
+        let result = live.merge(zone, &registry(), ResourceMergePolicy::Overwrite);
+
+        assert_eq!(
+            result,
+            Err(MergeError::UnregisteredComponents {
+                type_names: vec![std::any::type_name::<Cursed>()]
+            })
+        );
+        assert_eq!(live.entities().count(), 0);
+        assert!(live.get_resource::<ZoneName>().is_none());
+    }
+}
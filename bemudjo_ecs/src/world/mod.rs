@@ -1,15 +1,59 @@
 use std::{
     any::TypeId,
     collections::{HashMap, HashSet},
+    sync::Mutex,
 };
 
-use crate::{AnyStorage, Entity};
+use crate::{AnyStorage, Entity, EntityAllocator};
 
+mod batch;
+mod bundles;
+mod change_detection;
+mod compaction;
 mod components;
+mod consistency;
+mod dump;
 mod entities;
 mod ephemeral_component;
+mod ephemeral_events;
+mod ephemeral_resources;
+mod events;
+mod fetch;
+mod generation;
+mod hierarchy;
+mod history;
+mod indexes;
+mod labels;
+mod merge;
+mod named_queries;
+mod observers;
 mod resources;
+mod snapshot;
+mod stats;
 mod storage;
+mod subscriptions;
+mod tags;
+mod transfer;
+mod view;
+
+pub use batch::BatchResult;
+pub use components::UpsertOutcome;
+pub use consistency::{ConsistencyReport, ConsistencyViolation, ViolationKind};
+pub use dump::{ComponentDump, DebugComponent, EntityDump, WorldDump};
+pub use events::Events;
+pub use fetch::ComponentRefTuple;
+pub use hierarchy::HierarchyError;
+pub use history::{HistoryRecorder, RollbackError};
+pub use labels::LabelError;
+pub use merge::{MergeError, MergeReport, ResourceMergePolicy};
+pub use named_queries::{NamedQueryError, QueryDef};
+pub use resources::ResourceError;
+pub use snapshot::{SnapshotError, WorldSnapshot};
+pub use stats::{ComponentStats, WorldStats};
+pub use subscriptions::{EventBatch, EventFilter, EventReceiver, WorldEvent};
+pub use tags::{TagError, TagId};
+pub use transfer::{EntityBundle, TransferError};
+pub use view::WorldView;
 
 /// The central World container that manages entities and components.
 ///
@@ -34,13 +78,42 @@ mod storage;
 /// assert!(!world.has_component::<Position>(entity));
 /// ```
 pub struct World {
-    resource_entity: Entity, // we want to store here all the resources (global state, e.g Time component)
+    resource_entity: Entity, // hidden owner of all resources (global state, e.g Time component)
+    resource_storages: HashMap<TypeId, Box<dyn AnyStorage>>, // separate from components so queries never see resources
     entities: HashSet<Entity>,
     soft_deleted_entities: HashSet<Entity>,
+    cleaned_entities: Option<Vec<Entity>>, // Some while tracking, see World::track_cleaned_entities
     component_storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    storage_factories: HashMap<TypeId, storage::StorageFactory>, // see World::set_storage
+    component_type_names: HashMap<TypeId, &'static str>,         // see World::component_type_name
     reverse_component_index: HashMap<TypeId, HashSet<Entity>>,
     ephemeral_component_storages: HashMap<TypeId, Box<dyn AnyStorage>>,
     reverse_ephemeral_component_index: HashMap<TypeId, HashSet<Entity>>,
+    next_tick_ephemerals: Mutex<Vec<ephemeral_component::StagedEphemeral>>, // see World::add_ephemeral_component_next_tick
+    ephemeral_resource_storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    ephemeral_event_storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    ephemerals_cleaned: bool, // no ephemeral write since clean_ephemeral_storage, see World::check_consistency
+    tag_names: Vec<String>,   // indexed by TagId, see World::register_tag
+    entity_tags: HashMap<Entity, u64>, // tag bitsets, entities without tags are absent
+    entity_parents: HashMap<Entity, Entity>, // see World::set_parent
+    entity_children: HashMap<Entity, HashSet<Entity>>, // inverse of entity_parents, never holds empty sets
+    label_to_entity: HashMap<String, Entity>,
+    entity_to_label: HashMap<Entity, String>,
+    added_observers: HashMap<TypeId, Vec<observers::Observer>>,
+    removed_observers: HashMap<TypeId, Vec<observers::Observer>>,
+    added_this_tick: HashMap<TypeId, HashSet<Entity>>,
+    changed_this_tick: HashMap<TypeId, HashSet<Entity>>, // superset of added_this_tick
+    generation: u64, // bumped on every structural change, see World::generation
+    storage_cloners: HashMap<TypeId, snapshot::StorageCloneFn>,
+    value_cloners: HashMap<TypeId, history::ValueFns>, // single values, see World::enable_history
+    debug_formatters: HashMap<TypeId, dump::DebugFn>,  // see World::register_debug
+    component_indexes: HashMap<TypeId, Box<dyn indexes::AnyIndex>>, // see World::index_component_by
+    named_queries: HashMap<String, named_queries::QueryDef>, // see World::register_query
+    next_local_entity_id: Option<u64>,                 // Some for EntityAllocator::Deterministic
+    free_entities: Vec<Entity>, // cleaned up entities whose ids spawn_entity reuses
+    subscriptions: subscriptions::Subscriptions, // see World::subscribe
+    auto_shrink_threshold: f32, // 0.0 disables, see World::set_auto_shrink
+    history: Option<history::HistoryRecorder>, // see World::enable_history
 }
 
 impl World {
@@ -54,14 +127,71 @@ impl World {
     /// assert_eq!(world.entities().count(), 0);
     /// ```
     pub fn new() -> Self {
+        Self::new_with_allocator(EntityAllocator::Global)
+    }
+
+    /// Creates a new empty World that assigns entity ids with `allocator`.
+    ///
+    /// [`World::new`] uses [`EntityAllocator::Global`].
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{EntityAllocator, World};
+    ///
+    /// let world = World::new_with_allocator(EntityAllocator::Deterministic);
+    /// assert_eq!(world.entity_allocator(), EntityAllocator::Deterministic);
+    /// ```
+    pub fn new_with_allocator(allocator: EntityAllocator) -> Self {
+        let (resource_entity, next_local_entity_id) = match allocator {
+            EntityAllocator::Global => (Entity::new(), None),
+            EntityAllocator::Deterministic => (Entity::from_id(0), Some(1)),
+        };
+
         Self {
-            resource_entity: Entity::new(),
+            resource_entity,
+            resource_storages: HashMap::new(),
             entities: HashSet::new(),
             soft_deleted_entities: HashSet::new(),
+            cleaned_entities: None,
             component_storages: HashMap::new(),
+            storage_factories: HashMap::new(),
+            component_type_names: HashMap::new(),
             reverse_component_index: HashMap::new(),
             ephemeral_component_storages: HashMap::new(),
             reverse_ephemeral_component_index: HashMap::new(),
+            next_tick_ephemerals: Mutex::new(Vec::new()),
+            ephemeral_resource_storages: HashMap::new(),
+            ephemeral_event_storages: HashMap::new(),
+            ephemerals_cleaned: true,
+            tag_names: Vec::new(),
+            entity_tags: HashMap::new(),
+            entity_parents: HashMap::new(),
+            entity_children: HashMap::new(),
+            label_to_entity: HashMap::new(),
+            entity_to_label: HashMap::new(),
+            added_observers: HashMap::new(),
+            removed_observers: HashMap::new(),
+            added_this_tick: HashMap::new(),
+            changed_this_tick: HashMap::new(),
+            generation: generation::next_generation(),
+            storage_cloners: HashMap::new(),
+            value_cloners: HashMap::new(),
+            debug_formatters: HashMap::new(),
+            component_indexes: HashMap::new(),
+            named_queries: HashMap::new(),
+            next_local_entity_id,
+            free_entities: Vec::new(),
+            subscriptions: subscriptions::Subscriptions::default(),
+            auto_shrink_threshold: 0.0,
+            history: None,
+        }
+    }
+
+    /// Returns how the world assigns entity ids.
+    pub fn entity_allocator(&self) -> EntityAllocator {
+        match self.next_local_entity_id {
+            Some(_) => EntityAllocator::Deterministic,
+            None => EntityAllocator::Global,
         }
     }
 
@@ -0,0 +1,396 @@
+use std::any::TypeId;
+use std::fmt;
+
+use crate::{Component, ComponentRegistry, Entity};
+
+use super::World;
+
+/// A type-erased query definition that can be stored on a [`World`] under a
+/// name, see [`World::register_query`].
+///
+/// Built like a [`Query`](crate::Query), but the component types are kept as
+/// TypeIds next to their type names, so definitions of different queries can
+/// live in one map and be listed by admin and debugging tools.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, QueryDef};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Position { x: i32, y: i32 }
+/// impl Component for Position {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Dead;
+/// impl Component for Dead {}
+///
+/// let def = QueryDef::new::<Position>().without::<Dead>();
+/// assert_eq!(def.without_names(), vec![std::any::type_name::<Dead>()]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryDef {
+    /// The component type every matching entity has
+    primary: (TypeId, &'static str),
+    /// Component types that entities must have (in addition to the primary type)
+    with_components: Vec<(TypeId, &'static str)>,
+    /// Component types that entities must NOT have
+    without_components: Vec<(TypeId, &'static str)>,
+}
+
+impl QueryDef {
+    /// Creates a definition matching the entities that have component `T`.
+    pub fn new<T: Component>() -> Self {
+        Self {
+            primary: Self::erase::<T>(),
+            with_components: Vec::new(),
+            without_components: Vec::new(),
+        }
+    }
+
+    /// Also requires entities to have component `C`.
+    pub fn with<C: Component>(mut self) -> Self {
+        let erased = Self::erase::<C>();
+        if !self.with_components.contains(&erased) {
+            self.with_components.push(erased);
+        }
+        self
+    }
+
+    /// Excludes entities that have component `C`.
+    pub fn without<C: Component>(mut self) -> Self {
+        let erased = Self::erase::<C>();
+        if !self.without_components.contains(&erased) {
+            self.without_components.push(erased);
+        }
+        self
+    }
+
+    /// Returns the type name of the primary component.
+    pub fn primary_name(&self) -> &'static str {
+        self.primary.1
+    }
+
+    /// Returns the type names of the `with` components, in the order they were added.
+    pub fn with_names(&self) -> Vec<&'static str> {
+        self.with_components.iter().map(|(_, name)| *name).collect()
+    }
+
+    /// Returns the type names of the `without` components, in the order they were added.
+    pub fn without_names(&self) -> Vec<&'static str> {
+        self.without_components
+            .iter()
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    fn erase<C: Component>() -> (TypeId, &'static str) {
+        (TypeId::of::<C>(), C::type_name())
+    }
+
+    /// Returns every component type the definition refers to.
+    fn component_types(&self) -> impl Iterator<Item = &(TypeId, &'static str)> {
+        std::iter::once(&self.primary)
+            .chain(&self.with_components)
+            .chain(&self.without_components)
+    }
+
+    /// Returns the live entities matching the definition, in no particular order.
+    fn matching_entities(&self, world: &World) -> Vec<Entity> {
+        let mut entities = world.entities_with_component_by_type_id(self.primary.0);
+
+        for &(type_id, _) in &self.with_components {
+            match world.component_index_by_type_id(type_id) {
+                Some(with_component) => entities.retain(|entity| with_component.contains(entity)),
+                None => entities.clear(),
+            }
+        }
+
+        for &(type_id, _) in &self.without_components {
+            if let Some(with_component) = world.component_index_by_type_id(type_id) {
+                entities.retain(|entity| !with_component.contains(entity));
+            }
+        }
+
+        entities.into_iter().collect()
+    }
+}
+
+impl fmt::Display for QueryDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.primary.1)?;
+        if !self.with_components.is_empty() {
+            write!(f, " with {}", self.with_names().join(", "))?;
+        }
+        if !self.without_components.is_empty() {
+            write!(f, " without {}", self.without_names().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when registering or running named queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamedQueryError {
+    /// No query is registered under the name.
+    UnknownQuery { name: String },
+    /// The definition refers to component types that are not in the registry.
+    UnregisteredComponents {
+        name: String,
+        type_names: Vec<&'static str>,
+    },
+}
+
+impl fmt::Display for NamedQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamedQueryError::UnknownQuery { name } => {
+                write!(f, "no query is registered as \"{name}\"")
+            }
+            NamedQueryError::UnregisteredComponents { name, type_names } => write!(
+                f,
+                "cannot register query \"{name}\": unregistered components {}",
+                type_names.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NamedQueryError {}
+
+impl World {
+    /// Stores `def` under `name`, so it can be run by name with
+    /// [`World::run_named_query`] and listed with [`World::query_names`].
+    ///
+    /// Named queries let admin commands and scripts pick entities without
+    /// being compiled against the component types. Every component type of
+    /// the definition must be registered in `registry`, which catches a
+    /// definition referring to a type the game no longer uses. Registering a
+    /// name again replaces its previous definition.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the query was stored
+    /// * `Err(NamedQueryError::UnregisteredComponents)` if the definition refers to unregistered types
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, ComponentRegistry, QueryDef, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: i32, y: i32 }
+    /// impl Component for Position {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Enemy;
+    /// impl Component for Enemy {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let registry = ComponentRegistry::new()
+    ///     .with::<Position>()
+    ///     .with::<Enemy>()
+    ///     .with::<Dead>();
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .register_query(
+    ///         "living_enemies",
+    ///         QueryDef::new::<Position>().with::<Enemy>().without::<Dead>(),
+    ///         &registry,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Position { x: 0, y: 0 }).unwrap();
+    /// world.add_component(goblin, Enemy).unwrap();
+    /// assert_eq!(world.run_named_query("living_enemies").unwrap(), vec![goblin]);
+    ///
+    /// world.add_component(goblin, Dead).unwrap();
+    /// assert!(world.run_named_query("living_enemies").unwrap().is_empty());
+    /// ```
+    pub fn register_query(
+        &mut self,
+        name: impl Into<String>,
+        def: QueryDef,
+        registry: &ComponentRegistry,
+    ) -> Result<(), NamedQueryError> {
+        let name = name.into();
+
+        let mut unregistered: Vec<_> = def
+            .component_types()
+            .filter(|(type_id, _)| !registry.contains_type_id(*type_id))
+            .map(|(_, type_name)| *type_name)
+            .collect();
+        if !unregistered.is_empty() {
+            unregistered.sort_unstable();
+            unregistered.dedup();
+            return Err(NamedQueryError::UnregisteredComponents {
+                name,
+                type_names: unregistered,
+            });
+        }
+
+        self.named_queries.insert(name, def);
+        Ok(())
+    }
+
+    /// Runs the query registered as `name` and returns the matching entities,
+    /// in no particular order.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Entity>)` - The live entities matching the definition
+    /// * `Err(NamedQueryError::UnknownQuery)` if no query is registered under the name
+    pub fn run_named_query(&self, name: &str) -> Result<Vec<Entity>, NamedQueryError> {
+        self.named_queries
+            .get(name)
+            .map(|def| def.matching_entities(self))
+            .ok_or_else(|| NamedQueryError::UnknownQuery {
+                name: name.to_string(),
+            })
+    }
+
+    /// Returns the definition registered as `name`, if any.
+    pub fn named_query(&self, name: &str) -> Option<&QueryDef> {
+        self.named_queries.get(name)
+    }
+
+    /// Returns the names of all registered queries, sorted.
+    pub fn query_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.named_queries.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Removes the query registered as `name` and returns its definition.
+    pub fn unregister_query(&mut self, name: &str) -> Option<QueryDef> {
+        self.named_queries.remove(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Enemy;
+    impl Component for Enemy {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Player;
+    impl Component for Player {}
+
+    fn registry() -> ComponentRegistry {
+        ComponentRegistry::new()
+            .with::<Position>()
+            .with::<Enemy>()
+            .with::<Dead>()
+    }
+
+    fn living_enemies() -> QueryDef {
+        QueryDef::new::<Position>()
+            .with::<Enemy>()
+            .without::<Dead>()
+    }
+
+    fn run(world: &World, name: &str) -> HashSet<Entity> {
+        world.run_named_query(name).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn test_named_query_follows_world_changes() {
+        let mut world = World::new();
+        world
+            .register_query("living_enemies", living_enemies(), &registry())
+            .unwrap();
+
+        let goblin = world.spawn_entity();
+        world
+            .add_component(goblin, Position { x: 1, y: 1 })
+            .unwrap();
+        world.add_component(goblin, Enemy).unwrap();
+        let orc = world.spawn_entity();
+        world.add_component(orc, Position { x: 2, y: 2 }).unwrap();
+        world.add_component(orc, Enemy).unwrap();
+        let rock = world.spawn_entity();
+        world.add_component(rock, Position { x: 3, y: 3 }).unwrap();
+
+        assert_eq!(run(&world, "living_enemies"), HashSet::from([goblin, orc]));
+
+        world.add_component(goblin, Dead).unwrap();
+        world.delete_entity(orc);
+        world.add_component(rock, Enemy).unwrap();
+        assert_eq!(run(&world, "living_enemies"), HashSet::from([rock]));
+    }
+
+    #[test]
+    fn test_named_query_introspection() {
+        let mut world = World::new();
+        world
+            .register_query("living_enemies", living_enemies(), &registry())
+            .unwrap();
+        world
+            .register_query("positioned", QueryDef::new::<Position>(), &registry())
+            .unwrap();
+
+        assert_eq!(world.query_names(), vec!["living_enemies", "positioned"]);
+        let def = world.named_query("living_enemies").unwrap();
+        assert_eq!(def.primary_name(), std::any::type_name::<Position>());
+        assert_eq!(
+            def.to_string(),
+            format!(
+                "{} with {} without {}",
+                std::any::type_name::<Position>(),
+                std::any::type_name::<Enemy>(),
+                std::any::type_name::<Dead>()
+            )
+        );
+
+        assert_eq!(
+            world.unregister_query("positioned"),
+            Some(QueryDef::new::<Position>())
+        );
+        assert_eq!(world.query_names(), vec!["living_enemies"]);
+    }
+
+    #[test]
+    fn test_run_unknown_query_fails() {
+        let world = World::new();
+        assert_eq!(
+            world.run_named_query("missing"),
+            Err(NamedQueryError::UnknownQuery {
+                name: "missing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_register_with_unregistered_components_fails() {
+        let mut world = World::new();
+        let def = QueryDef::new::<Player>()
+            .with::<Position>()
+            .without::<Player>();
+
+        let result = world.register_query("players", def, &registry());
+        assert_eq!(
+            result,
+            Err(NamedQueryError::UnregisteredComponents {
+                name: "players".to_string(),
+                type_names: vec![std::any::type_name::<Player>()],
+            })
+        );
+        assert!(world.named_query("players").is_none());
+    }
+}
@@ -0,0 +1,361 @@
+use std::any::{Any, TypeId};
+
+use crate::{Component, Entity};
+
+use super::World;
+
+/// A type-erased component observer callback.
+pub(super) type Observer = Box<dyn Fn(&World, Entity, &dyn Any) + Send + Sync>;
+
+/// Wraps a typed callback so it can be stored next to observers of other types.
+fn erase<T: Component, F>(callback: F) -> Observer
+where
+    F: Fn(&World, Entity, &T) + Send + Sync + 'static,
+{
+    Box::new(move |world, entity, component| {
+        if let Some(component) = component.downcast_ref::<T>() {
+            callback(world, entity, component);
+        }
+    })
+}
+
+impl World {
+    /// Registers a callback that runs whenever a component of type `T` is added.
+    ///
+    /// The callback runs synchronously inside the call that made the component
+    /// appear: `add_component()`, `replace_component()` when the entity didn't
+    /// have the component yet, and bundle insertion. Updating an existing
+    /// component does not trigger it. Callbacks for the same type run in
+    /// registration order, after the component has been stored.
+    ///
+    /// Callbacks only get read access to the world, so they cannot add or remove
+    /// components while the world is in the middle of an operation. Record the
+    /// work to do instead (e.g. in an `Arc<Mutex<_>>` shared with a system) and
+    /// apply it from a system. Like components, callbacks must be `Send + Sync`.
+    ///
+    /// # Parameters
+    /// * `callback` - Called with the world, the entity and the added component
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let deaths = Arc::new(Mutex::new(Vec::new()));
+    /// let mut world = World::new();
+    ///
+    /// let log = deaths.clone();
+    /// world.observe_added::<Dead, _>(move |_world, entity, _dead| {
+    ///     log.lock().unwrap().push(entity);
+    /// });
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Dead).unwrap();
+    ///
+    /// assert_eq!(*deaths.lock().unwrap(), vec![goblin]);
+    /// ```
+    pub fn observe_added<T, F>(&mut self, callback: F)
+    where
+        T: Component,
+        F: Fn(&World, Entity, &T) + Send + Sync + 'static,
+    {
+        self.added_observers
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(erase(callback));
+    }
+
+    /// Registers a callback that runs whenever a component of type `T` is removed.
+    ///
+    /// The callback runs synchronously for every component that disappears:
+    /// - In `remove_component()`, after the component was taken out of the
+    ///   world; the callback receives the removed value.
+    /// - In `cleanup_deleted_entities()`, once for each component of type `T`
+    ///   owned by each deleted entity, before the component data is dropped.
+    ///   The entity is already deleted at that point, so it is no longer
+    ///   visible through `get_component()`.
+    ///
+    /// Callbacks for the same type run in registration order. Like
+    /// [`World::observe_added`], callbacks only get read access to the world.
+    ///
+    /// # Parameters
+    /// * `callback` - Called with the world, the entity and the removed component
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Weapon { damage: u32 }
+    /// impl Component for Weapon {}
+    ///
+    /// let dropped = Arc::new(Mutex::new(Vec::new()));
+    /// let mut world = World::new();
+    ///
+    /// let log = dropped.clone();
+    /// world.observe_removed::<Weapon, _>(move |_world, _entity, weapon| {
+    ///     log.lock().unwrap().push(weapon.damage);
+    /// });
+    ///
+    /// let hero = world.spawn_entity();
+    /// world.add_component(hero, Weapon { damage: 7 }).unwrap();
+    /// world.remove_component::<Weapon>(hero);
+    ///
+    /// let villain = world.spawn_entity();
+    /// world.add_component(villain, Weapon { damage: 12 }).unwrap();
+    /// world.delete_entity(villain);
+    /// world.cleanup_deleted_entities();
+    ///
+    /// assert_eq!(*dropped.lock().unwrap(), vec![7, 12]);
+    /// ```
+    pub fn observe_removed<T, F>(&mut self, callback: F)
+    where
+        T: Component,
+        F: Fn(&World, Entity, &T) + Send + Sync + 'static,
+    {
+        self.removed_observers
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(erase(callback));
+    }
+
+    /// Runs the added-observers of `T` for a component that was just stored.
+    pub(super) fn notify_added<T: Component>(&self, entity: Entity) {
+        if self.added_observers.is_empty() {
+            return;
+        }
+
+        if let Some(observers) = self.added_observers.get(&TypeId::of::<T>()) {
+            if let Some(component) = self.get_storage::<T>().and_then(|s| s.get(entity)) {
+                for observer in observers {
+                    observer(self, entity, component);
+                }
+            }
+        }
+    }
+
+    /// Runs the removed-observers of `T` for a component that was just taken out.
+    pub(super) fn notify_removed<T: Component>(&self, entity: Entity, component: &T) {
+        if let Some(observers) = self.removed_observers.get(&TypeId::of::<T>()) {
+            for observer in observers {
+                observer(self, entity, component);
+            }
+        }
+    }
+
+    /// Runs the removed-observers of a type-erased component that was just taken out.
+    pub(super) fn notify_removed_any(&self, type_id: TypeId, entity: Entity, component: &dyn Any) {
+        if let Some(observers) = self.removed_observers.get(&type_id) {
+            for observer in observers {
+                observer(self, entity, component);
+            }
+        }
+    }
+
+    /// Runs the removed-observers for every component of every soft-deleted entity.
+    ///
+    /// Called from `cleanup_deleted_entities()` before the component data is dropped.
+    pub(super) fn notify_removed_for_deleted_entities(&self) {
+        for (type_id, observers) in &self.removed_observers {
+            let Some(storage) = self.component_storages.get(type_id) else {
+                continue;
+            };
+
+            for &entity in &self.soft_deleted_entities {
+                if let Some(component) = storage.get_any(entity) {
+                    for observer in observers {
+                        observer(self, entity, component);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SequentialSystemScheduler, System};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Weapon {
+        damage: u32,
+    }
+    impl Component for Weapon {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    fn log_added<T: Component>(world: &mut World, log: &Log, name: &'static str) {
+        let log = log.clone();
+        world.observe_added::<T, _>(move |_, _, _| log.lock().unwrap().push(name.to_string()));
+    }
+
+    fn log_removed<T: Component>(world: &mut World, log: &Log, name: &'static str) {
+        let log = log.clone();
+        world.observe_removed::<T, _>(move |_, _, _| log.lock().unwrap().push(name.to_string()));
+    }
+
+    #[test]
+    fn test_added_observers_run_in_registration_order() {
+        let mut world = World::new();
+        let log = Log::default();
+        log_added::<Weapon>(&mut world, &log, "first");
+        log_added::<Weapon>(&mut world, &log, "second");
+        log_added::<Dead>(&mut world, &log, "dead");
+
+        let entity = world.spawn_entity();
+        world.add_component(entity, Weapon { damage: 1 }).unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_added_observer_sees_stored_component() {
+        let mut world = World::new();
+        let seen = Arc::new(Mutex::new(None));
+
+        let seen_clone = seen.clone();
+        world.observe_added::<Weapon, _>(move |world, entity, weapon| {
+            let stored = world.get_component::<Weapon>(entity).cloned();
+            assert_eq!(stored.as_ref(), Some(weapon));
+            *seen_clone.lock().unwrap() = stored;
+        });
+
+        let entity = world.spawn_entity();
+        world.add_component(entity, Weapon { damage: 9 }).unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), Some(Weapon { damage: 9 }));
+    }
+
+    #[test]
+    fn test_added_observer_not_run_on_failed_add_or_update() {
+        let mut world = World::new();
+        let log = Log::default();
+        log_added::<Weapon>(&mut world, &log, "added");
+
+        let entity = world.spawn_entity();
+        world.add_component(entity, Weapon { damage: 1 }).unwrap();
+        assert!(world.add_component(entity, Weapon { damage: 2 }).is_err());
+        world
+            .update_component::<Weapon, _>(entity, |w| Weapon {
+                damage: w.damage + 1,
+            })
+            .unwrap();
+        world.replace_component(entity, Weapon { damage: 5 });
+
+        let deleted = world.spawn_entity();
+        world.delete_entity(deleted);
+        assert!(world.add_component(deleted, Weapon { damage: 1 }).is_err());
+
+        assert_eq!(*log.lock().unwrap(), vec!["added"]);
+    }
+
+    #[test]
+    fn test_added_observer_runs_for_replace_and_bundles() {
+        let mut world = World::new();
+        let log = Log::default();
+        log_added::<Weapon>(&mut world, &log, "weapon");
+        log_added::<Dead>(&mut world, &log, "dead");
+
+        let entity = world.spawn_entity();
+        world.replace_component(entity, Weapon { damage: 3 });
+        world.spawn_bundle((Weapon { damage: 1 }, Dead)).unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec!["weapon", "weapon", "dead"]);
+    }
+
+    #[test]
+    fn test_removed_observer_receives_removed_value() {
+        let mut world = World::new();
+        let removed = Arc::new(Mutex::new(Vec::new()));
+
+        let removed_clone = removed.clone();
+        world.observe_removed::<Weapon, _>(move |world, entity, weapon| {
+            // Already gone from the world
+            assert!(!world.has_component::<Weapon>(entity));
+            removed_clone.lock().unwrap().push(weapon.damage);
+        });
+
+        let entity = world.spawn_entity();
+        world.add_component(entity, Weapon { damage: 4 }).unwrap();
+        world.remove_component::<Weapon>(entity);
+        // Removing again is a no-op and doesn't notify
+        world.remove_component::<Weapon>(entity);
+
+        assert_eq!(*removed.lock().unwrap(), vec![4]);
+    }
+
+    #[test]
+    fn test_removed_observers_run_for_each_component_on_cleanup() {
+        let mut world = World::new();
+        let log = Log::default();
+        log_removed::<Weapon>(&mut world, &log, "weapon");
+        log_removed::<Dead>(&mut world, &log, "dead");
+
+        let survivor = world.spawn_entity();
+        world.add_component(survivor, Weapon { damage: 1 }).unwrap();
+
+        let victim = world.spawn_entity();
+        world.add_component(victim, Weapon { damage: 2 }).unwrap();
+        world.add_component(victim, Dead).unwrap();
+
+        world.delete_entity(victim);
+        assert!(
+            log.lock().unwrap().is_empty(),
+            "fires on cleanup, not on delete"
+        );
+
+        world.cleanup_deleted_entities();
+        let mut fired = log.lock().unwrap().clone();
+        fired.sort();
+        assert_eq!(fired, vec!["dead", "weapon"]);
+
+        // A second cleanup has nothing left to report
+        world.cleanup_deleted_entities();
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_removed_observers_run_during_scheduler_cleanup() {
+        struct KillSystem;
+        impl System for KillSystem {
+            fn run(&self, world: &mut World) {
+                let dead: Vec<_> = crate::Query::<Dead>::new()
+                    .iter(world)
+                    .map(|(entity, _)| entity)
+                    .collect();
+                for entity in dead {
+                    world.delete_entity(entity);
+                }
+            }
+        }
+
+        let mut world = World::new();
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let dropped_clone = dropped.clone();
+        world.observe_removed::<Weapon, _>(move |_, entity, weapon| {
+            dropped_clone.lock().unwrap().push((entity, weapon.damage));
+        });
+
+        let goblin = world.spawn_entity();
+        world.add_component(goblin, Weapon { damage: 3 }).unwrap();
+        world.add_component(goblin, Dead).unwrap();
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(KillSystem).unwrap();
+        scheduler.build().unwrap();
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(*dropped.lock().unwrap(), vec![(goblin, 3)]);
+    }
+}
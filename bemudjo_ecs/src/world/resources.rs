@@ -1,12 +1,55 @@
-use crate::{Component, ComponentError, ComponentStorage};
+use crate::component::{downcast_storage_mut, short_type_name};
+use crate::{Component, ComponentError};
 
 use super::World;
 
+/// Errors that can occur when accessing global resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceError {
+    /// The resource has not been inserted.
+    NotFound { type_name: &'static str },
+    /// The resource has already been inserted.
+    AlreadyExists { type_name: &'static str },
+}
+
+impl ResourceError {
+    /// Returns the full type name of the resource involved in the error.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::NotFound { type_name } | Self::AlreadyExists { type_name } => type_name,
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = short_type_name(self.type_name());
+        match self {
+            Self::NotFound { .. } => write!(f, "resource {name} not found"),
+            Self::AlreadyExists { .. } => write!(f, "resource {name} already exists"),
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {}
+
+impl From<ResourceError> for ComponentError {
+    fn from(error: ResourceError) -> Self {
+        match error {
+            ResourceError::NotFound { type_name } => ComponentError::ResourceNotFound { type_name },
+            ResourceError::AlreadyExists { type_name } => {
+                ComponentError::ResourceAlreadyExists { type_name }
+            }
+        }
+    }
+}
+
 impl World {
     /// Inserts or replaces a global resource.
     ///
     /// Resources are global singleton data that can be accessed by all systems.
-    /// Unlike entity components, resources can be replaced multiple times without error.
+    /// Unlike entity components, resources can be replaced multiple times without error;
+    /// use [`try_insert_resource`](World::try_insert_resource) to reject duplicates.
     ///
     /// # Parameters
     /// * `resource` - The resource instance to store globally
@@ -24,9 +67,47 @@ impl World {
     /// world.insert_resource(GameTime { delta: 0.033 }); // Replaces previous
     /// ```
     pub fn insert_resource<T: Component>(&mut self, resource: T) {
+        self.record_resource::<T>();
         let resource_entity = self.resource_entity;
-        let storage = self.get_storage_mut::<T>();
+        let storage = self.get_resource_storage_mut::<T>();
         storage.insert_or_update(resource_entity, resource);
+        self.subscriptions.resource_updated::<T>();
+    }
+
+    /// Inserts a global resource, failing if it already exists.
+    ///
+    /// Use this instead of [`insert_resource`](World::insert_resource) where
+    /// inserting the same resource twice indicates a bug, e.g. in setup code.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the resource was inserted
+    /// * `Err(ResourceError::AlreadyExists { .. })` if it was already present; the
+    ///   existing value is kept
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, ResourceError, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct GameTime { delta: f32 }
+    /// impl Component for GameTime {}
+    ///
+    /// let mut world = World::new();
+    /// world.try_insert_resource(GameTime { delta: 0.016 }).unwrap();
+    ///
+    /// let error = world.try_insert_resource(GameTime { delta: 0.033 }).unwrap_err();
+    /// assert!(matches!(error, ResourceError::AlreadyExists { .. }));
+    /// assert_eq!(world.get_resource::<GameTime>().unwrap().delta, 0.016);
+    /// ```
+    pub fn try_insert_resource<T: Component>(&mut self, resource: T) -> Result<(), ResourceError> {
+        if self.has_resource::<T>() {
+            return Err(ResourceError::AlreadyExists {
+                type_name: T::type_name(),
+            });
+        }
+
+        self.insert_resource(resource);
+        Ok(())
     }
 
     /// Gets an immutable reference to a global resource.
@@ -59,10 +140,53 @@ impl World {
     /// ```
     pub fn get_resource<T: Component>(&self) -> Option<&T> {
         let resource_entity = self.resource_entity;
-        let storage = self.get_storage::<T>();
+        let storage = self.get_resource_storage::<T>();
         storage?.get(resource_entity)
     }
 
+    /// Gets a mutable reference to a global resource without recording an update.
+    ///
+    /// For bookkeeping the ECS does on its own resources between ticks, such as
+    /// advancing [`Rng`](crate::Rng), which subscribers shouldn't see as a change.
+    pub(crate) fn get_resource_mut_untracked<T: Component>(&mut self) -> Option<&mut T> {
+        self.record_resource::<T>();
+        let resource_entity = self.resource_entity;
+        let storage = self
+            .resource_storages
+            .get_mut(&std::any::TypeId::of::<T>())?;
+        downcast_storage_mut::<T>(storage.as_mut())?.get_mut(resource_entity)
+    }
+
+    /// Gets a reference to a global resource, treating absence as an error.
+    ///
+    /// Like [`get_resource`](World::get_resource), but for call sites where a
+    /// missing resource is a bug that should be reported, e.g. with `?`.
+    ///
+    /// # Returns
+    /// * `Ok(&T)` if the resource exists
+    /// * `Err(ResourceError::NotFound { .. })` naming the missing resource type
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct GameTime { delta: f32 }
+    /// impl Component for GameTime {}
+    ///
+    /// let mut world = World::new();
+    /// let error = world.try_get_resource::<GameTime>().unwrap_err();
+    /// assert_eq!(error.to_string(), "resource GameTime not found");
+    ///
+    /// world.insert_resource(GameTime { delta: 0.016 });
+    /// assert_eq!(world.try_get_resource::<GameTime>().unwrap().delta, 0.016);
+    /// ```
+    pub fn try_get_resource<T: Component>(&self) -> Result<&T, ResourceError> {
+        self.get_resource::<T>().ok_or(ResourceError::NotFound {
+            type_name: T::type_name(),
+        })
+    }
+
     /// Removes a global resource and returns its value.
     ///
     /// Returns `None` if the resource doesn't exist.
@@ -89,9 +213,13 @@ impl World {
     /// assert!(world.get_resource::<GameSettings>().is_none());
     /// ```
     pub fn remove_resource<T: Component>(&mut self) -> Option<T> {
+        self.record_resource::<T>();
         let resource_entity = self.resource_entity;
-        let storage = self.get_storage_mut::<T>();
-        storage.remove(resource_entity)
+        let removed = self
+            .get_resource_storage_mut::<T>()
+            .remove(resource_entity)?;
+        self.subscriptions.resource_removed::<T>();
+        Some(removed)
     }
 
     /// Checks if a global resource exists.
@@ -125,7 +253,7 @@ impl World {
     /// ```
     pub fn has_resource<T: Component>(&self) -> bool {
         let resource_entity = self.resource_entity;
-        let storage = self.get_storage::<T>();
+        let storage = self.get_resource_storage::<T>();
         storage.is_some_and(|s| s.contains(resource_entity))
     }
 
@@ -139,7 +267,7 @@ impl World {
     ///
     /// # Returns
     /// * `Ok(T)` - The updated resource value
-    /// * `Err(ComponentError::ComponentNotFound)` - If the resource doesn't exist
+    /// * `Err(ResourceError::NotFound { .. })` - If the resource doesn't exist
     ///
     /// # Type Parameters
     /// * `T` - The resource type, must implement `Component` and `Clone`
@@ -172,22 +300,97 @@ impl World {
     /// let result = world.update_resource::<Settings, _>(|s| s);
     /// assert!(result.is_err());
     /// ```
-    pub fn update_resource<T, F>(&mut self, f: F) -> Result<T, ComponentError>
+    pub fn update_resource<T, F>(&mut self, f: F) -> Result<T, ResourceError>
     where
         T: Component + Clone,
         F: FnOnce(T) -> T,
     {
+        self.record_resource::<T>();
         let resource_entity = self.resource_entity;
-        let storage = self.get_storage_mut::<T>();
+        let storage = self.get_resource_storage_mut::<T>();
 
         match storage.get(resource_entity).cloned() {
             Some(current) => {
                 let updated = f(current);
                 storage.insert_or_update(resource_entity, updated.clone());
+                self.subscriptions.resource_updated::<T>();
                 Ok(updated)
             }
-            None => Err(ComponentError::ComponentNotFound),
+            None => Err(ResourceError::NotFound {
+                type_name: T::type_name(),
+            }),
+        }
+    }
+
+    /// Gets a global resource, inserting it with the given closure if it doesn't exist.
+    ///
+    /// The closure is only called when the resource is missing, so it can be used
+    /// to lazily initialize resources on first access instead of checking
+    /// `has_resource` before every `update_resource`.
+    ///
+    /// # Parameters
+    /// * `f` - A closure producing the initial resource value
+    ///
+    /// # Returns
+    /// A reference to the existing or newly inserted resource.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Score { value: u32 }
+    /// impl Component for Score {}
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let score = world.get_resource_or_insert_with(|| Score { value: 10 });
+    /// assert_eq!(score.value, 10);
+    ///
+    /// // Existing resources are returned unchanged
+    /// let score = world.get_resource_or_insert_with(|| Score { value: 99 });
+    /// assert_eq!(score.value, 10);
+    /// ```
+    pub fn get_resource_or_insert_with<T, F>(&mut self, f: F) -> &T
+    where
+        T: Component,
+        F: FnOnce() -> T,
+    {
+        let resource_entity = self.resource_entity;
+        if !self
+            .get_resource_storage_mut::<T>()
+            .contains(resource_entity)
+        {
+            let resource = f();
+            self.insert_resource(resource);
         }
+
+        self.get_resource_storage_mut::<T>()
+            .get(resource_entity)
+            .expect("Resource should exist after insertion")
+    }
+
+    /// Gets a global resource, inserting its `Default` value if it doesn't exist.
+    ///
+    /// This is a convenience wrapper around [`World::get_resource_or_insert_with`].
+    ///
+    /// # Returns
+    /// A reference to the existing or newly inserted resource.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, Default, PartialEq)]
+    /// struct GameTime { elapsed: u64 }
+    /// impl Component for GameTime {}
+    ///
+    /// let mut world = World::new();
+    /// assert_eq!(world.get_resource_or_default::<GameTime>().elapsed, 0);
+    /// assert!(world.has_resource::<GameTime>());
+    /// ```
+    pub fn get_resource_or_default<T: Component + Default>(&mut self) -> &T {
+        self.get_resource_or_insert_with(T::default)
     }
 }
 
@@ -403,8 +606,20 @@ mod tests {
             time
         });
 
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), ComponentError::ComponentNotFound);
+        let error = result.unwrap_err();
+        assert_eq!(
+            error,
+            ResourceError::NotFound {
+                type_name: std::any::type_name::<GameTime>()
+            }
+        );
+        assert_eq!(error.to_string(), "resource GameTime not found");
+
+        // Converts into a ComponentError for callers mixing both
+        assert!(matches!(
+            ComponentError::from(error),
+            ComponentError::ResourceNotFound { .. }
+        ));
     }
 
     #[test]
@@ -634,4 +849,246 @@ mod tests {
             vec!["CTRL".to_string(), "C".to_string()]
         );
     }
+
+    #[test]
+    fn test_get_resource_or_insert_with_inserts_missing_resource() {
+        let mut world = World::new();
+
+        let time = world.get_resource_or_insert_with(|| GameTime {
+            delta: 0.016,
+            total: 0.0,
+        });
+
+        assert_eq!(time.delta, 0.016);
+        assert!(world.has_resource::<GameTime>());
+    }
+
+    #[test]
+    fn test_get_resource_or_insert_with_runs_closure_once() {
+        let mut world = World::new();
+        let mut calls = 0;
+
+        for _ in 0..5 {
+            let score = world.get_resource_or_insert_with(|| {
+                calls += 1;
+                PlayerScore {
+                    value: 10,
+                    high_score: 20,
+                }
+            });
+            assert_eq!(score.value, 10);
+        }
+
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_get_resource_or_insert_with_keeps_existing_value() {
+        let mut world = World::new();
+        world.insert_resource(PlayerScore {
+            value: 500,
+            high_score: 900,
+        });
+
+        let score = world.get_resource_or_insert_with(|| -> PlayerScore {
+            panic!("closure must not run for existing resources")
+        });
+
+        assert_eq!(
+            score,
+            &PlayerScore {
+                value: 500,
+                high_score: 900
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_resource_or_insert_with_after_update() {
+        let mut world = World::new();
+        world.get_resource_or_insert_with(|| PlayerScore {
+            value: 0,
+            high_score: 0,
+        });
+
+        world
+            .update_resource::<PlayerScore, _>(|mut s| {
+                s.value += 7;
+                s
+            })
+            .unwrap();
+
+        let score = world.get_resource_or_insert_with(|| PlayerScore {
+            value: 0,
+            high_score: 0,
+        });
+        assert_eq!(score.value, 7);
+    }
+
+    #[test]
+    fn test_get_resource_or_default() {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        struct Counter {
+            ticks: u64,
+        }
+        impl Component for Counter {}
+
+        let mut world = World::new();
+        assert!(!world.has_resource::<Counter>());
+
+        assert_eq!(world.get_resource_or_default::<Counter>().ticks, 0);
+        assert!(world.has_resource::<Counter>());
+
+        world.insert_resource(Counter { ticks: 3 });
+        assert_eq!(world.get_resource_or_default::<Counter>().ticks, 3);
+    }
+
+    #[test]
+    fn test_resource_entity_not_in_entities() {
+        let mut world = World::new();
+        world.insert_resource(GameTime {
+            delta: 0.016,
+            total: 0.0,
+        });
+        world.insert_resource(PlayerScore {
+            value: 0,
+            high_score: 0,
+        });
+
+        assert_eq!(world.entities().count(), 0);
+        assert!(!world.entities().any(|&e| e == world.resource_entity));
+
+        let entity = world.spawn_entity();
+        assert_eq!(world.entities().count(), 1);
+        assert_eq!(world.entities().next(), Some(&entity));
+    }
+
+    #[test]
+    fn test_resource_type_also_used_as_component_is_not_queried() {
+        use crate::Query;
+
+        let mut world = World::new();
+        world.insert_resource(GameTime {
+            delta: 0.016,
+            total: 1.0,
+        });
+
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                GameTime {
+                    delta: 1.0,
+                    total: 2.0,
+                },
+            )
+            .unwrap();
+
+        let query = Query::<GameTime>::new();
+        let results: Vec<_> = query.iter(&world).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, entity);
+        assert_eq!(results[0].1.total, 2.0);
+
+        let by_type = world.entities_with_component_by_type_id(std::any::TypeId::of::<GameTime>());
+        assert_eq!(by_type.len(), 1);
+        assert!(!by_type.contains(&world.resource_entity));
+
+        // Component storage only holds the real entity
+        let storage = world.get_storage::<GameTime>().unwrap();
+        assert_eq!(storage.entities().count(), 1);
+        assert!(!storage.contains(world.resource_entity));
+
+        // Resource and component values stay independent
+        assert_eq!(world.get_resource::<GameTime>().unwrap().total, 1.0);
+        assert_eq!(world.get_component::<GameTime>(entity).unwrap().total, 2.0);
+    }
+
+    #[test]
+    fn test_component_operations_do_not_touch_resources() {
+        let mut world = World::new();
+        world.insert_resource(PlayerScore {
+            value: 10,
+            high_score: 10,
+        });
+
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                PlayerScore {
+                    value: 1,
+                    high_score: 1,
+                },
+            )
+            .unwrap();
+        world.remove_component::<PlayerScore>(entity);
+        world.delete_entity(entity);
+        world.cleanup_deleted_entities();
+
+        assert_eq!(world.get_resource::<PlayerScore>().unwrap().value, 10);
+
+        // Removing the resource doesn't affect components of the same type
+        let other = world.spawn_entity();
+        world
+            .add_component(
+                other,
+                PlayerScore {
+                    value: 2,
+                    high_score: 2,
+                },
+            )
+            .unwrap();
+        world.remove_resource::<PlayerScore>();
+        assert!(world.has_component::<PlayerScore>(other));
+        assert!(!world.has_resource::<PlayerScore>());
+    }
+
+    #[test]
+    fn test_try_get_resource() {
+        let mut world = World::new();
+
+        assert_eq!(
+            world.try_get_resource::<GameTime>(),
+            Err(ResourceError::NotFound {
+                type_name: std::any::type_name::<GameTime>()
+            })
+        );
+
+        world.insert_resource(GameTime {
+            delta: 0.016,
+            total: 1.0,
+        });
+        assert_eq!(world.try_get_resource::<GameTime>().unwrap().total, 1.0);
+    }
+
+    #[test]
+    fn test_try_insert_resource_rejects_duplicates() {
+        let mut world = World::new();
+        let score = PlayerScore {
+            value: 10,
+            high_score: 20,
+        };
+
+        assert_eq!(world.try_insert_resource(score.clone()), Ok(()));
+        let error = world
+            .try_insert_resource(PlayerScore {
+                value: 0,
+                high_score: 0,
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ResourceError::AlreadyExists {
+                type_name: std::any::type_name::<PlayerScore>()
+            }
+        );
+        assert_eq!(error.to_string(), "resource PlayerScore already exists");
+        assert_eq!(world.get_resource::<PlayerScore>(), Some(&score));
+
+        // Removing the resource makes room again
+        world.remove_resource::<PlayerScore>();
+        assert!(world.try_insert_resource(score).is_ok());
+    }
 }
@@ -0,0 +1,662 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+
+use crate::component::{downcast_storage, downcast_storage_mut};
+use crate::{AnyStorage, Component, Entity, HashMapComponentStorage, TagStorage};
+
+use super::history::ValueFns;
+use super::storage::StorageFactory;
+use super::subscriptions::Subscriptions;
+use super::{generation, World};
+
+/// Deep-copies a type-erased storage whose component type is known to be `Clone`,
+/// into a storage from the factory picked with `World::set_storage` if given.
+pub(super) type StorageCloneFn = fn(&dyn AnyStorage, Option<StorageFactory>) -> Box<dyn AnyStorage>;
+
+fn clone_storage<T: Component + Clone>(
+    storage: &dyn AnyStorage,
+    new_storage: Option<StorageFactory>,
+) -> Box<dyn AnyStorage> {
+    if new_storage.is_none() {
+        let any = storage.as_any();
+        if let Some(tags) = any.downcast_ref::<TagStorage<T>>() {
+            return tags.clone_box();
+        }
+        if let Some(data) = any.downcast_ref::<HashMapComponentStorage<T>>() {
+            return data.clone_box();
+        }
+    }
+
+    // Storages set with World::set_storage can't be cloned as they are, so
+    // their components are copied into a new storage of the same kind
+    let source = downcast_storage::<T>(storage)
+        .expect("storages are keyed by the TypeId of their component");
+    let mut copy =
+        new_storage.map_or_else(World::new_default_storage::<T>, |new_storage| new_storage());
+    let target = downcast_storage_mut::<T>(copy.as_mut()).expect("the copy stores T");
+    for (entity, component) in source.iter() {
+        target.insert_or_update(entity, component.clone());
+    }
+    copy
+}
+
+/// Errors that can occur when taking a [`WorldSnapshot`] or cloning a world
+/// with [`World::try_clone`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// A component or resource type holding data was not registered with
+    /// [`World::register_cloneable`].
+    NotCloneable { type_name: &'static str },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::NotCloneable { type_name } => {
+                write!(
+                    f,
+                    "cannot snapshot {type_name}: type is not registered as cloneable"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// A storage copied into a snapshot, along with the means to copy it again.
+struct SnapshotStorage {
+    type_id: TypeId,
+    storage: Box<dyn AnyStorage>,
+    clone: StorageCloneFn,
+}
+
+impl SnapshotStorage {
+    fn copy(&self, new_storage: Option<StorageFactory>) -> Box<dyn AnyStorage> {
+        (self.clone)(self.storage.as_ref(), new_storage)
+    }
+}
+
+/// An immutable deep copy of a world's persistent state.
+///
+/// Taken with [`World::snapshot`] and applied with [`World::restore`]. A snapshot
+/// holds entities, regular components, the reverse component index, resources,
+/// labels, tags and parent links. Ephemeral components and ephemeral resources only live for
+/// one tick and are never part of a snapshot. Observers and tag registrations
+/// are setup rather than world state and are not affected by restoring.
+///
+/// The same snapshot can be restored any number of times.
+pub struct WorldSnapshot {
+    resource_entity: Entity,
+    entities: HashSet<Entity>,
+    soft_deleted_entities: HashSet<Entity>,
+    component_storages: Vec<SnapshotStorage>,
+    reverse_component_index: HashMap<TypeId, HashSet<Entity>>,
+    resource_storages: Vec<SnapshotStorage>,
+    label_to_entity: HashMap<String, Entity>,
+    entity_to_label: HashMap<Entity, String>,
+    entity_tags: HashMap<Entity, u64>,
+    entity_parents: HashMap<Entity, Entity>,
+    entity_children: HashMap<Entity, HashSet<Entity>>,
+    next_local_entity_id: Option<u64>,
+    free_entities: Vec<Entity>,
+}
+
+impl WorldSnapshot {
+    /// Returns the number of live entities captured by the snapshot.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+}
+
+impl std::fmt::Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("entities", &self.entities.len())
+            .field("component_types", &self.component_storages.len())
+            .field("resource_types", &self.resource_storages.len())
+            .finish()
+    }
+}
+
+impl World {
+    /// Allows components and resources of type `T` to be included in snapshots
+    /// and in the history recorded by [`World::enable_history`].
+    ///
+    /// Component storages are type-erased, so the world can only copy types it
+    /// has been told are `Clone`. Registering is cheap and idempotent; do it once
+    /// for every component and resource type during setup.
+    pub fn register_cloneable<T: Component + Clone>(&mut self) {
+        self.storage_cloners
+            .insert(TypeId::of::<T>(), clone_storage::<T>);
+        self.value_cloners
+            .insert(TypeId::of::<T>(), ValueFns::of::<T>());
+    }
+
+    /// Takes a deep copy of the world's entities, components and resources.
+    ///
+    /// Ephemeral components and resources are not included. Storages of types
+    /// that hold no data are skipped, so only types actually in use need to be
+    /// registered with [`World::register_cloneable`].
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError::NotCloneable`] if a component or resource type
+    /// holding data was not registered.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_cloneable::<Position>();
+    ///
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Position { x: 0.0, y: 0.0 }).unwrap();
+    ///
+    /// let snapshot = world.snapshot().unwrap();
+    ///
+    /// // A mispredicted move...
+    /// world.replace_component(entity, Position { x: 5.0, y: 0.0 });
+    ///
+    /// // ...is rolled back
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get_component::<Position>(entity), Some(&Position { x: 0.0, y: 0.0 }));
+    /// ```
+    pub fn snapshot(&self) -> Result<WorldSnapshot, SnapshotError> {
+        Ok(WorldSnapshot {
+            resource_entity: self.resource_entity,
+            entities: self.entities.clone(),
+            soft_deleted_entities: self.soft_deleted_entities.clone(),
+            component_storages: self
+                .snapshot_storages(&self.component_storages, Some(&self.storage_factories))?,
+            reverse_component_index: self.reverse_component_index.clone(),
+            resource_storages: self.snapshot_storages(&self.resource_storages, None)?,
+            label_to_entity: self.label_to_entity.clone(),
+            entity_to_label: self.entity_to_label.clone(),
+            entity_tags: self.entity_tags.clone(),
+            entity_parents: self.entity_parents.clone(),
+            entity_children: self.entity_children.clone(),
+            next_local_entity_id: self.next_local_entity_id,
+            free_entities: self.free_entities.clone(),
+        })
+    }
+
+    /// Replaces the world's state with the contents of `snapshot`.
+    ///
+    /// Entities spawned after the snapshot was taken disappear, deleted entities
+    /// come back, and every component and resource gets its snapshot value.
+    /// Ephemeral components, resources and events of the current tick are
+    /// discarded, as are ephemeral components staged for the next tick.
+    /// Observers do not run. A world using [`EntityAllocator::Deterministic`]
+    /// also rewinds its id counter, so replaying the same operations spawns the
+    /// same entities again. The ids awaiting reuse are rewound in every world.
+    /// With history enabled, the recorded ticks are dropped, as they can't be
+    /// undone across a restore.
+    ///
+    /// [`EntityAllocator::Deterministic`]: crate::EntityAllocator::Deterministic
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.resource_entity = snapshot.resource_entity;
+        self.entities = snapshot.entities.clone();
+        self.soft_deleted_entities = snapshot.soft_deleted_entities.clone();
+        self.component_storages = snapshot
+            .component_storages
+            .iter()
+            .map(|stored| {
+                let new_storage = self.storage_factories.get(&stored.type_id).copied();
+                (stored.type_id, stored.copy(new_storage))
+            })
+            .collect();
+        for (&type_id, storage) in &self.component_storages {
+            self.component_type_names
+                .entry(type_id)
+                .or_insert_with(|| storage.component_type_name());
+        }
+        self.reverse_component_index = snapshot.reverse_component_index.clone();
+        self.resource_storages = snapshot
+            .resource_storages
+            .iter()
+            .map(|stored| (stored.type_id, stored.copy(None)))
+            .collect();
+        self.label_to_entity = snapshot.label_to_entity.clone();
+        self.entity_to_label = snapshot.entity_to_label.clone();
+        self.entity_tags = snapshot.entity_tags.clone();
+        self.entity_parents = snapshot.entity_parents.clone();
+        self.entity_children = snapshot.entity_children.clone();
+        if self.next_local_entity_id.is_some() && snapshot.next_local_entity_id.is_some() {
+            // Replays spawn the same ids again
+            self.next_local_entity_id = snapshot.next_local_entity_id;
+        }
+        self.free_entities = snapshot.free_entities.clone();
+
+        self.clear_history();
+        self.reset_transient_state();
+    }
+
+    /// Drops the ephemeral state, change tracking and indexes left over from
+    /// before the world state was put back by a restore or rollback.
+    pub(super) fn reset_transient_state(&mut self) {
+        self.ephemeral_component_storages = HashMap::new();
+        self.reverse_ephemeral_component_index = HashMap::new();
+        self.ephemeral_resource_storages = HashMap::new();
+        self.ephemeral_event_storages = HashMap::new();
+        self.next_tick_ephemerals
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.clear_change_tracking();
+        self.invalidate_all_indexes();
+
+        self.bump_generation();
+    }
+
+    /// Creates an independent deep copy of the world, for branching a
+    /// simulation or reusing a test fixture.
+    ///
+    /// The copy has the same entities, components, resources, labels, tags,
+    /// parent links, named queries and change tracking, and its id state is preserved, so every
+    /// handle of the original is valid against the copy. A world using
+    /// [`EntityAllocator::Deterministic`] spawns the same ids in both copies.
+    /// Like [`World::snapshot`], it relies on the types registered with
+    /// [`World::register_cloneable`].
+    ///
+    /// Ephemeral components, resources and events only live for the current
+    /// tick and are not copied, nor are ephemeral components staged for the
+    /// next tick. Observers, event subscriptions, component indexes and the
+    /// history recorder hold callbacks or channels bound to the original and
+    /// start out empty; register them again on the copy if needed. The copy
+    /// gets its own generation, so a [`CachedQuery`](crate::CachedQuery) never
+    /// mistakes one world for the other.
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError::NotCloneable`] if a component or resource type
+    /// holding data was not registered.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_cloneable::<Health>();
+    /// let hero = world.spawn_entity();
+    /// world.add_component(hero, Health { value: 10 }).unwrap();
+    ///
+    /// let mut branch = world.try_clone().unwrap();
+    /// branch.replace_component(hero, Health { value: 0 });
+    ///
+    /// assert_eq!(world.get_component::<Health>(hero), Some(&Health { value: 10 }));
+    /// assert_eq!(branch.get_component::<Health>(hero), Some(&Health { value: 0 }));
+    /// ```
+    ///
+    /// [`EntityAllocator::Deterministic`]: crate::EntityAllocator::Deterministic
+    pub fn try_clone(&self) -> Result<World, SnapshotError> {
+        let copy_storages = |storages, factories| -> Result<HashMap<_, _>, SnapshotError> {
+            Ok(self
+                .snapshot_storages(storages, factories)?
+                .into_iter()
+                .map(|stored| (stored.type_id, stored.storage))
+                .collect())
+        };
+
+        Ok(World {
+            resource_entity: self.resource_entity,
+            resource_storages: copy_storages(&self.resource_storages, None)?,
+            entities: self.entities.clone(),
+            soft_deleted_entities: self.soft_deleted_entities.clone(),
+            cleaned_entities: self.cleaned_entities.clone(),
+            component_storages: copy_storages(
+                &self.component_storages,
+                Some(&self.storage_factories),
+            )?,
+            storage_factories: self.storage_factories.clone(),
+            component_type_names: self.component_type_names.clone(),
+            reverse_component_index: self.reverse_component_index.clone(),
+            ephemeral_component_storages: HashMap::new(),
+            reverse_ephemeral_component_index: HashMap::new(),
+            next_tick_ephemerals: Mutex::new(Vec::new()),
+            ephemeral_resource_storages: HashMap::new(),
+            ephemeral_event_storages: HashMap::new(),
+            ephemerals_cleaned: true,
+            tag_names: self.tag_names.clone(),
+            entity_tags: self.entity_tags.clone(),
+            entity_parents: self.entity_parents.clone(),
+            entity_children: self.entity_children.clone(),
+            label_to_entity: self.label_to_entity.clone(),
+            entity_to_label: self.entity_to_label.clone(),
+            added_observers: HashMap::new(),
+            removed_observers: HashMap::new(),
+            added_this_tick: self.added_this_tick.clone(),
+            changed_this_tick: self.changed_this_tick.clone(),
+            generation: generation::next_generation(),
+            storage_cloners: self.storage_cloners.clone(),
+            value_cloners: self.value_cloners.clone(),
+            debug_formatters: self.debug_formatters.clone(),
+            component_indexes: HashMap::new(),
+            named_queries: self.named_queries.clone(),
+            next_local_entity_id: self.next_local_entity_id,
+            free_entities: self.free_entities.clone(),
+            subscriptions: Subscriptions::default(),
+            auto_shrink_threshold: self.auto_shrink_threshold,
+            history: None,
+        })
+    }
+
+    /// Copies every non-empty storage of `storages`, failing on unregistered types.
+    ///
+    /// Storages of types with an entry in `factories` are copied into a new
+    /// storage from it.
+    fn snapshot_storages(
+        &self,
+        storages: &HashMap<TypeId, Box<dyn AnyStorage>>,
+        factories: Option<&HashMap<TypeId, StorageFactory>>,
+    ) -> Result<Vec<SnapshotStorage>, SnapshotError> {
+        storages
+            .iter()
+            .filter(|(_, storage)| !storage.is_empty())
+            .map(|(&type_id, storage)| {
+                let clone = *self.storage_cloners.get(&type_id).ok_or_else(|| {
+                    SnapshotError::NotCloneable {
+                        type_name: storage.component_type_name(),
+                    }
+                })?;
+
+                let new_storage = factories.and_then(|factories| factories.get(&type_id).copied());
+                Ok(SnapshotStorage {
+                    type_id,
+                    storage: clone(storage.as_ref(), new_storage),
+                    clone,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Query;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tick(u64);
+    impl Component for Tick {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Stunned;
+    impl Component for Stunned {}
+
+    #[derive(Debug, PartialEq)]
+    struct Socket;
+    impl Component for Socket {}
+
+    fn registered_world() -> World {
+        let mut world = World::new();
+        world.register_cloneable::<Position>();
+        world.register_cloneable::<Health>();
+        world.register_cloneable::<Tick>();
+        world
+    }
+
+    fn positions(world: &World) -> Vec<(Entity, Position)> {
+        let mut positions: Vec<_> = Query::<Position>::new()
+            .iter(world)
+            .map(|(entity, position)| (entity, position.clone()))
+            .collect();
+        positions.sort_by(|a, b| a.1.x.total_cmp(&b.1.x));
+        positions
+    }
+
+    fn non_empty_index(world: &World) -> HashMap<TypeId, HashSet<Entity>> {
+        world
+            .reverse_component_index
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(type_id, entities)| (*type_id, entities.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_undoes_every_kind_of_mutation() {
+        let mut world = registered_world();
+        let hero = world.spawn_entity();
+        world
+            .add_component(hero, Position { x: 1.0, y: 1.0 })
+            .unwrap();
+        world.add_component(hero, Health { value: 100 }).unwrap();
+        world.set_entity_label(hero, "hero").unwrap();
+        let goblin = world.spawn_entity();
+        world
+            .add_component(goblin, Position { x: 2.0, y: 2.0 })
+            .unwrap();
+        world.insert_resource(Tick(7));
+
+        let snapshot = world.snapshot().unwrap();
+        let expected_positions = positions(&world);
+        let expected_index = non_empty_index(&world);
+
+        // Mutate everything
+        world
+            .update_component::<Health, _>(hero, |_| Health { value: 1 })
+            .unwrap();
+        world.remove_component::<Position>(hero);
+        world.delete_entity(goblin);
+        world.cleanup_deleted_entities();
+        let newcomer = world.spawn_entity();
+        world
+            .add_component(newcomer, Position { x: 9.0, y: 9.0 })
+            .unwrap();
+        world.insert_resource(Tick(8));
+
+        world.restore(&snapshot);
+
+        assert_eq!(positions(&world), expected_positions);
+        assert_eq!(non_empty_index(&world), expected_index);
+        assert_eq!(
+            world.get_component::<Health>(hero),
+            Some(&Health { value: 100 })
+        );
+        assert_eq!(world.get_resource::<Tick>(), Some(&Tick(7)));
+        assert_eq!(world.entity_by_label("hero"), Some(hero));
+        assert!(world.entities().any(|&e| e == goblin));
+        assert!(!world.entities().any(|&e| e == newcomer));
+        assert!(!world.has_component::<Position>(newcomer));
+        assert_eq!(world.entities().count(), snapshot.entity_count());
+    }
+
+    #[test]
+    fn test_snapshot_can_be_restored_repeatedly() {
+        let mut world = registered_world();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 10 }).unwrap();
+        let snapshot = world.snapshot().unwrap();
+
+        for damage in [3, 5] {
+            world
+                .update_component::<Health, _>(entity, |h| Health {
+                    value: h.value - damage,
+                })
+                .unwrap();
+            world.restore(&snapshot);
+            assert_eq!(
+                world.get_component::<Health>(entity),
+                Some(&Health { value: 10 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_ephemeral_state_is_excluded() {
+        let mut world = registered_world();
+        let entity = world.spawn_entity();
+        world.add_ephemeral_component(entity, Stunned).unwrap();
+        world.insert_ephemeral_resource(Stunned).unwrap();
+        world.push_ephemeral_event(entity, Stunned).unwrap();
+        world
+            .add_ephemeral_component_next_tick(entity, Stunned)
+            .unwrap();
+
+        // Stunned is not registered, but ephemeral storages are never copied
+        let snapshot = world.snapshot().unwrap();
+        world.add_component(entity, Health { value: 1 }).unwrap();
+        world.restore(&snapshot);
+
+        assert!(!world.has_ephemeral_component::<Stunned>(entity));
+        assert!(!world.has_ephemeral_resource::<Stunned>());
+        assert!(world.ephemeral_events::<Stunned>(entity).is_empty());
+        assert!(!world.has_component::<Health>(entity));
+
+        // Nothing staged before the restore shows up next tick
+        world.promote_next_tick_ephemerals();
+        assert!(!world.has_ephemeral_component::<Stunned>(entity));
+    }
+
+    #[test]
+    fn test_unregistered_type_with_data_fails() {
+        let mut world = registered_world();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Socket).unwrap();
+
+        let error = world.snapshot().unwrap_err();
+        assert_eq!(
+            error,
+            SnapshotError::NotCloneable {
+                type_name: std::any::type_name::<Socket>()
+            }
+        );
+
+        // Once the storage is empty the type no longer matters
+        world.remove_component::<Socket>(entity);
+        assert!(world.snapshot().is_ok());
+    }
+
+    #[test]
+    fn test_unregistered_resource_fails() {
+        let mut world = World::new();
+        world.insert_resource(Tick(1));
+
+        assert!(matches!(
+            world.snapshot(),
+            Err(SnapshotError::NotCloneable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_clone_answers_like_the_original() {
+        let mut world = registered_world();
+        let hero = world.spawn_entity();
+        world
+            .add_component(hero, Position { x: 1.0, y: 1.0 })
+            .unwrap();
+        world.add_component(hero, Health { value: 100 }).unwrap();
+        world.set_entity_label(hero, "hero").unwrap();
+        let goblin = world.spawn_entity();
+        world
+            .add_component(goblin, Position { x: 2.0, y: 2.0 })
+            .unwrap();
+        world.insert_resource(Tick(7));
+        let ghost = world.spawn_entity();
+        world.delete_entity(ghost);
+
+        let clone = world.try_clone().unwrap();
+
+        assert_eq!(positions(&clone), positions(&world));
+        assert_eq!(non_empty_index(&clone), non_empty_index(&world));
+        assert_eq!(
+            clone.entities().collect::<HashSet<_>>(),
+            world.entities().collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            clone.get_component::<Health>(hero),
+            Some(&Health { value: 100 })
+        );
+        assert_eq!(clone.get_resource::<Tick>(), Some(&Tick(7)));
+        assert_eq!(clone.entity_by_label("hero"), Some(hero));
+        assert!(!clone.is_entity_active(ghost));
+        assert!(clone.was_added::<Health>(hero));
+        assert_ne!(clone.generation(), world.generation());
+        assert!(clone.check_consistency().is_consistent());
+    }
+
+    #[test]
+    fn test_mutating_the_clone_leaves_the_original_alone() {
+        let mut world = registered_world();
+        let hero = world.spawn_entity();
+        world.add_component(hero, Health { value: 100 }).unwrap();
+        world.insert_resource(Tick(1));
+
+        let mut clone = world.try_clone().unwrap();
+        clone
+            .update_component::<Health, _>(hero, |_| Health { value: 1 })
+            .unwrap();
+        clone.insert_resource(Tick(2));
+        clone
+            .add_component(hero, Position { x: 3.0, y: 3.0 })
+            .unwrap();
+        let newcomer = clone.spawn_entity();
+
+        assert_eq!(
+            world.get_component::<Health>(hero),
+            Some(&Health { value: 100 })
+        );
+        assert_eq!(world.get_resource::<Tick>(), Some(&Tick(1)));
+        assert!(!world.has_component::<Position>(hero));
+        assert!(!world.entities().any(|&e| e == newcomer));
+
+        // And the other way around
+        world.delete_entity(hero);
+        assert!(clone.has_component::<Health>(hero));
+    }
+
+    #[test]
+    fn test_clone_keeps_deterministic_ids_and_drops_ephemerals() {
+        let mut world = World::new_with_allocator(crate::EntityAllocator::Deterministic);
+        world.spawn_entity();
+        let stunned = world.spawn_entity();
+        world.add_ephemeral_component(stunned, Stunned).unwrap();
+        world.add_component(stunned, Socket).unwrap();
+
+        // Socket is not cloneable
+        assert_eq!(
+            world.try_clone().err(),
+            Some(SnapshotError::NotCloneable {
+                type_name: std::any::type_name::<Socket>()
+            })
+        );
+        world.remove_component::<Socket>(stunned);
+
+        let mut clone = world.try_clone().unwrap();
+        assert!(!clone.has_ephemeral_component::<Stunned>(stunned));
+        assert_eq!(clone.spawn_entity(), world.spawn_entity());
+    }
+
+    #[test]
+    fn test_restore_changes_generation() {
+        let mut world = registered_world();
+        let snapshot = world.snapshot().unwrap();
+        let before = world.generation();
+
+        world.restore(&snapshot);
+
+        assert_ne!(world.generation(), before);
+    }
+}
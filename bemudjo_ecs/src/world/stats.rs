@@ -0,0 +1,422 @@
+use std::any::TypeId;
+
+use crate::{AnyStorage, Component};
+
+use super::World;
+
+/// Statistics about a single component storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStats {
+    /// The component's type name, see [`Component::type_name`].
+    pub type_name: &'static str,
+    /// Number of stored components, including those of soft-deleted entities.
+    pub entries: usize,
+    /// Approximate memory used by the stored components in bytes.
+    pub approx_bytes: usize,
+}
+
+/// A snapshot of the world's size, as returned by [`World::stats`].
+///
+/// Storage entry counts include data of soft-deleted entities that has not been
+/// cleaned up yet, which makes the snapshot suitable for spotting leaks: after
+/// `cleanup_deleted_entities()` every storage should only hold live entities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldStats {
+    /// Number of live entities.
+    pub entity_count: usize,
+    /// Number of deleted entities awaiting cleanup.
+    pub soft_deleted_count: usize,
+    /// Number of component types that have a storage.
+    pub component_type_count: usize,
+    /// Per component type statistics, sorted by type name.
+    pub components: Vec<ComponentStats>,
+    /// Number of ephemeral components stored for the current tick.
+    pub ephemeral_component_count: usize,
+    /// Number of global resources.
+    pub resource_count: usize,
+    /// Number of ephemeral resources stored for the current tick.
+    pub ephemeral_resource_count: usize,
+}
+
+impl WorldStats {
+    /// Returns the total number of stored components across all types.
+    pub fn total_component_entries(&self) -> usize {
+        self.components.iter().map(|c| c.entries).sum()
+    }
+
+    /// Returns the approximate memory used by all stored components in bytes.
+    pub fn total_approx_bytes(&self) -> usize {
+        self.components.iter().map(|c| c.approx_bytes).sum()
+    }
+}
+
+impl World {
+    /// Collects statistics about entities and storages.
+    ///
+    /// This walks every storage once and is meant for monitoring and debugging,
+    /// not for use in hot loops.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// for _ in 0..3 {
+    ///     let entity = world.spawn_entity();
+    ///     world.add_component(entity, Health { value: 10 }).unwrap();
+    /// }
+    ///
+    /// let stats = world.stats();
+    /// assert_eq!(stats.entity_count, 3);
+    /// assert_eq!(stats.component_type_count, 1);
+    /// assert_eq!(stats.components[0].entries, 3);
+    /// assert_eq!(stats.components[0].approx_bytes, 3 * std::mem::size_of::<Health>());
+    /// ```
+    pub fn stats(&self) -> WorldStats {
+        let mut components: Vec<ComponentStats> = self
+            .component_storages
+            .values()
+            .map(|storage| ComponentStats {
+                type_name: storage.component_type_name(),
+                entries: storage.len(),
+                approx_bytes: storage.approx_memory_bytes(),
+            })
+            .collect();
+        components.sort_by(|a, b| a.type_name.cmp(b.type_name));
+
+        let total_len = |storages: &std::collections::HashMap<_, Box<dyn AnyStorage>>| {
+            storages.values().map(|storage| storage.len()).sum()
+        };
+
+        WorldStats {
+            entity_count: self.entities.len(),
+            soft_deleted_count: self.soft_deleted_entities.len(),
+            component_type_count: self.component_storages.len(),
+            components,
+            ephemeral_component_count: total_len(&self.ephemeral_component_storages),
+            resource_count: total_len(&self.resource_storages),
+            ephemeral_resource_count: total_len(&self.ephemeral_resource_storages),
+        }
+    }
+
+    /// Returns the number of stored components of type `T`.
+    ///
+    /// Unlike queries, this counts the raw storage and includes components of
+    /// soft-deleted entities that haven't been cleaned up yet.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    ///
+    /// world.delete_entity(entity);
+    /// assert_eq!(world.storage_len::<Health>(), 1);
+    ///
+    /// world.cleanup_deleted_entities();
+    /// assert_eq!(world.storage_len::<Health>(), 0);
+    /// ```
+    pub fn storage_len<T: Component>(&self) -> usize {
+        self.get_storage::<T>().map_or(0, |storage| storage.len())
+    }
+
+    /// Returns the number of stored ephemeral components of type `T`.
+    ///
+    /// Like [`storage_len`](World::storage_len), this counts the raw storage,
+    /// which makes it useful for checking that ephemeral data doesn't pile up.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Hit { damage: u32 }
+    /// impl Component for Hit {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_ephemeral_component(entity, Hit { damage: 3 }).unwrap();
+    /// assert_eq!(world.ephemeral_storage_len::<Hit>(), 1);
+    ///
+    /// world.delete_entity(entity);
+    /// world.cleanup_deleted_entities();
+    /// assert_eq!(world.ephemeral_storage_len::<Hit>(), 0);
+    /// ```
+    pub fn ephemeral_storage_len<T: Component>(&self) -> usize {
+        self.get_ephemeral_storage::<T>()
+            .map_or(0, |storage| storage.len())
+    }
+
+    /// Returns every component type that has a storage, in no particular order.
+    ///
+    /// A storage is created the first time a component of that type is added
+    /// and is kept even after every component of the type has been removed, so
+    /// such types are still listed until
+    /// [`prune_empty_storages`](World::prune_empty_storages) drops them.
+    /// Use [`component_type_name`](World::component_type_name) for display.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    /// use std::any::TypeId;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Health { value: 10 }).unwrap();
+    ///
+    /// let types: Vec<TypeId> = world.component_types().collect();
+    /// assert_eq!(types, vec![TypeId::of::<Health>()]);
+    /// assert!(world.component_type_name(types[0]).unwrap().ends_with("Health"));
+    /// ```
+    pub fn component_types(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.component_storages.keys().copied()
+    }
+
+    /// Returns every ephemeral component type that has a storage this tick.
+    ///
+    /// Ephemeral storages are dropped by `clean_ephemeral_storage()`, so unlike
+    /// [`component_types`](World::component_types) this only lists types used
+    /// since the last end-of-tick cleanup.
+    pub fn ephemeral_component_types(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.ephemeral_component_storages.keys().copied()
+    }
+
+    /// Returns the name of a regular or ephemeral component type, see
+    /// [`Component::type_name`].
+    ///
+    /// A type is known from the first time a storage is created for it, and
+    /// stays known after its storage is dropped by
+    /// [`prune_empty_storages`](World::prune_empty_storages) or, for
+    /// ephemeral components, the end-of-tick cleanup.
+    ///
+    /// # Returns
+    /// * `Some(&str)` if a component of the type was ever added to this world
+    /// * `None` otherwise
+    pub fn component_type_name(&self, type_id: TypeId) -> Option<&'static str> {
+        self.component_type_names.get(&type_id).copied()
+    }
+
+    /// Drops the storages of component types that no entity has anymore.
+    ///
+    /// Components of soft-deleted entities still count, so run this after
+    /// `cleanup_deleted_entities()` to reclaim their storages as well. A pruned
+    /// type gets a new storage the next time it is added, of the kind picked
+    /// with [`set_storage`](World::set_storage) if there was one.
+    ///
+    /// # Returns
+    /// The number of storages that were dropped
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Burning;
+    /// impl Component for Burning {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_entity();
+    /// world.add_component(entity, Burning).unwrap();
+    /// world.remove_component::<Burning>(entity);
+    /// assert_eq!(world.component_types().count(), 1);
+    ///
+    /// assert_eq!(world.prune_empty_storages(), 1);
+    /// assert_eq!(world.component_types().count(), 0);
+    /// ```
+    pub fn prune_empty_storages(&mut self) -> usize {
+        let count_before = self.component_storages.len();
+        self.component_storages
+            .retain(|_, storage| !storage.is_empty());
+        self.reverse_component_index
+            .retain(|_, entities| !entities.is_empty());
+        count_before - self.component_storages.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Marker;
+    impl Component for Marker {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Hit;
+    impl Component for Hit {}
+
+    #[test]
+    fn test_stats_empty_world() {
+        let world = World::new();
+        let stats = world.stats();
+
+        assert_eq!(stats.entity_count, 0);
+        assert_eq!(stats.soft_deleted_count, 0);
+        assert_eq!(stats.component_type_count, 0);
+        assert!(stats.components.is_empty());
+        assert_eq!(stats.total_component_entries(), 0);
+        assert_eq!(stats.total_approx_bytes(), 0);
+    }
+
+    #[test]
+    fn test_stats_counts_every_storage_kind() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_component(entity, Marker).unwrap();
+        world.add_ephemeral_component(entity, Marker).unwrap();
+        world.insert_resource(Position { x: 1.0, y: 1.0 });
+        world.insert_ephemeral_resource(Marker).unwrap();
+
+        let stats = world.stats();
+
+        assert_eq!(stats.entity_count, 1);
+        assert_eq!(stats.component_type_count, 2);
+        assert_eq!(stats.total_component_entries(), 2);
+        assert_eq!(stats.ephemeral_component_count, 1);
+        assert_eq!(stats.resource_count, 1);
+        assert_eq!(stats.ephemeral_resource_count, 1);
+        // Zero-sized markers take no component memory
+        assert_eq!(stats.total_approx_bytes(), std::mem::size_of::<Position>());
+        // Sorted by type name
+        assert!(stats.components[0].type_name.ends_with("Marker"));
+        assert!(stats.components[1].type_name.ends_with("Position"));
+    }
+
+    #[test]
+    fn test_storage_len_includes_soft_deleted() {
+        let mut world = World::new();
+        assert_eq!(world.storage_len::<Position>(), 0);
+
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.delete_entity(entity);
+
+        assert_eq!(world.storage_len::<Position>(), 1);
+        assert_eq!(world.stats().soft_deleted_count, 1);
+
+        world.cleanup_deleted_entities();
+        assert_eq!(world.storage_len::<Position>(), 0);
+        assert_eq!(world.stats().soft_deleted_count, 0);
+    }
+
+    #[test]
+    fn test_component_types_after_add_removal_and_pruning() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        assert_eq!(world.component_types().count(), 0);
+
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_component(entity, Marker).unwrap();
+        world.add_ephemeral_component(entity, Hit).unwrap();
+
+        let types: HashSet<TypeId> = world.component_types().collect();
+        assert_eq!(
+            types,
+            HashSet::from([TypeId::of::<Position>(), TypeId::of::<Marker>()])
+        );
+        assert_eq!(
+            world.ephemeral_component_types().collect::<Vec<_>>(),
+            vec![TypeId::of::<Hit>()]
+        );
+        assert_eq!(
+            world.component_type_name(TypeId::of::<Hit>()),
+            Some(std::any::type_name::<Hit>())
+        );
+        assert_eq!(world.component_type_name(TypeId::of::<u32>()), None);
+
+        // Removing the last Marker keeps its storage listed
+        world.remove_component::<Marker>(entity);
+        assert_eq!(world.component_types().count(), 2);
+
+        assert_eq!(world.prune_empty_storages(), 1);
+        assert_eq!(
+            world.component_types().collect::<Vec<_>>(),
+            vec![TypeId::of::<Position>()]
+        );
+        assert_eq!(world.prune_empty_storages(), 0);
+
+        // Pruned types work as before
+        world.add_component(entity, Marker).unwrap();
+        assert!(world.has_component::<Marker>(entity));
+        assert_eq!(crate::Query::<Marker>::new().iter(&world).count(), 1);
+
+        world.clean_ephemeral_storage();
+        assert_eq!(world.ephemeral_component_types().count(), 0);
+    }
+
+    #[test]
+    fn test_component_type_name_matches_component_type_name() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Stamina;
+        impl Component for Stamina {
+            fn type_name() -> &'static str {
+                "stamina"
+            }
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        assert_eq!(world.component_type_name(TypeId::of::<Position>()), None);
+
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_component(entity, Stamina).unwrap();
+
+        assert_eq!(
+            world.component_type_name(TypeId::of::<Position>()),
+            Some(<Position as Component>::type_name())
+        );
+        assert_eq!(
+            <Position as Component>::type_name(),
+            std::any::type_name::<Position>()
+        );
+        assert_eq!(
+            world.component_type_name(TypeId::of::<Stamina>()),
+            Some("stamina")
+        );
+        let stats = world.stats();
+        assert!(stats
+            .components
+            .iter()
+            .any(|component| component.type_name == "stamina"));
+
+        // Still known once the storage is gone
+        world.remove_component::<Stamina>(entity);
+        world.prune_empty_storages();
+        assert_eq!(
+            world.component_type_name(TypeId::of::<Stamina>()),
+            Some("stamina")
+        );
+        assert_eq!(world.component_type_name(TypeId::of::<Marker>()), None);
+    }
+}
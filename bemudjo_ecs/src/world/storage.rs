@@ -1,60 +1,161 @@
 use std::{any::TypeId, collections::HashMap};
 
-use crate::{AnyStorage, Component, HashMapComponentStorage};
+use crate::component::{downcast_storage, downcast_storage_mut, CustomStorage, TagStorage};
+use crate::{AnyStorage, Component, ComponentError, ComponentStorage, HashMapComponentStorage};
 
+use super::ephemeral_events::EventQueue;
 use super::World;
 
+/// Creates an empty storage of the backend picked with [`World::set_storage`].
+pub(super) type StorageFactory = fn() -> Box<dyn AnyStorage>;
+
 impl World {
     /// Gets an immutable reference to a storage from the given storage map.
     fn get_storage_from_map<T: Component>(
         storage_map: &HashMap<TypeId, Box<dyn AnyStorage>>,
-    ) -> Option<&HashMapComponentStorage<T>> {
+    ) -> Option<&dyn ComponentStorage<T>> {
         let type_id = TypeId::of::<T>();
 
-        storage_map.get(&type_id).and_then(|any_storage| {
-            any_storage
-                .as_any()
-                .downcast_ref::<HashMapComponentStorage<T>>()
-        })
+        storage_map
+            .get(&type_id)
+            .and_then(|any_storage| downcast_storage::<T>(any_storage.as_ref()))
     }
 
     /// Gets a mutable reference to a storage from the given storage map, creating if needed.
+    ///
+    /// New storages are [`TagStorage`]s for data-less tag types and
+    /// [`HashMapComponentStorage`]s for everything else.
     fn get_storage_from_map_mut<T: Component>(
         storage_map: &mut HashMap<TypeId, Box<dyn AnyStorage>>,
-    ) -> &mut HashMapComponentStorage<T> {
+    ) -> &mut dyn ComponentStorage<T> {
         let type_id = TypeId::of::<T>();
 
         // Use entry API to create storage if it doesn't exist
         let any_storage = storage_map
             .entry(type_id)
-            .or_insert_with(|| Box::new(HashMapComponentStorage::<T>::new()));
+            .or_insert_with(Self::new_default_storage::<T>);
 
-        any_storage
-            .as_any_mut()
-            .downcast_mut::<HashMapComponentStorage<T>>()
+        downcast_storage_mut::<T>(any_storage.as_mut())
             .expect("Failed to downcast storage for component type")
     }
 
+    /// Creates the storage the world picks for `T` unless told otherwise, see
+    /// [`World::set_storage`].
+    pub(super) fn new_default_storage<T: Component>() -> Box<dyn AnyStorage> {
+        if TagStorage::<T>::is_supported() {
+            Box::new(TagStorage::<T>::new())
+        } else {
+            Box::new(HashMapComponentStorage::<T>::new())
+        }
+    }
+
+    /// Replaces the storage backend for components of type `T`.
+    ///
+    /// By default the world keeps data-less tag types like `Dead` in a set of
+    /// entities and everything else in a `HashMap`. Call this during setup,
+    /// before the first `T` is added, to pick another [`ComponentStorage`]
+    /// implementation for a type, e.g. a dense storage for a component nearly
+    /// every entity has. Queries and all other operations behave the same
+    /// whichever storage holds the components.
+    ///
+    /// The world remembers the choice: whenever it needs a new storage for
+    /// `T`, e.g. after [`World::prune_empty_storages`], in snapshots, after
+    /// [`World::restore`] or in a [`World::try_clone`] copy, it creates an
+    /// empty one of the same type with `Default`.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the storage is now used for `T`
+    /// * `Err(ComponentError::StorageNotEmpty)` if the current or the given
+    ///   storage already holds components
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, HashMapComponentStorage, Query, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Dead;
+    /// impl Component for Dead {}
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .set_storage::<Dead>(Box::new(HashMapComponentStorage::new()))
+    ///     .unwrap();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.add_component(goblin, Dead).unwrap();
+    /// assert_eq!(Query::<Dead>::new().iter(&world).count(), 1);
+    ///
+    /// // Too late once the type holds data
+    /// assert!(world
+    ///     .set_storage::<Dead>(Box::new(HashMapComponentStorage::new()))
+    ///     .is_err());
+    /// ```
+    pub fn set_storage<T: Component>(
+        &mut self,
+        storage: Box<impl ComponentStorage<T> + Default + 'static>,
+    ) -> Result<(), ComponentError> {
+        let in_use = self
+            .component_storages
+            .get(&TypeId::of::<T>())
+            .is_some_and(|current| !current.is_empty());
+        if in_use || !storage.is_empty() {
+            return Err(ComponentError::StorageNotEmpty {
+                type_name: T::type_name(),
+            });
+        }
+
+        self.register_type_name::<T>();
+        self.storage_factories
+            .insert(TypeId::of::<T>(), custom_storage_factory(storage.as_ref()));
+        self.component_storages
+            .insert(TypeId::of::<T>(), Box::new(CustomStorage::<T>(storage)));
+        Ok(())
+    }
+
     /// Gets an immutable reference to the storage for a specific component type.
     ///
     /// Returns `None` if no storage exists for this component type yet.
-    pub(super) fn get_storage<T: Component>(&self) -> Option<&HashMapComponentStorage<T>> {
+    pub(super) fn get_storage<T: Component>(&self) -> Option<&dyn ComponentStorage<T>> {
         Self::get_storage_from_map(&self.component_storages)
     }
 
     /// Gets a mutable reference to the storage for a specific component type.
     ///
-    /// Creates the storage if it doesn't exist yet.
-    pub(super) fn get_storage_mut<T: Component>(&mut self) -> &mut HashMapComponentStorage<T> {
+    /// Creates the storage if it doesn't exist yet, of the backend picked with
+    /// [`World::set_storage`] if there is one.
+    pub(super) fn get_storage_mut<T: Component>(&mut self) -> &mut dyn ComponentStorage<T> {
+        self.register_type_name::<T>();
+        let type_id = TypeId::of::<T>();
+        if let Some(&factory) = self.storage_factories.get(&type_id) {
+            self.component_storages
+                .entry(type_id)
+                .or_insert_with(factory);
+        }
         Self::get_storage_from_map_mut(&mut self.component_storages)
     }
 
+    /// Gets an immutable reference to the resource storage for a specific type.
+    ///
+    /// Resources live in their own storage map, separate from component storages,
+    /// so the hidden resource entity never leaks into component iteration.
+    /// Returns `None` if no resource storage exists for this type yet.
+    pub(super) fn get_resource_storage<T: Component>(&self) -> Option<&dyn ComponentStorage<T>> {
+        Self::get_storage_from_map(&self.resource_storages)
+    }
+
+    /// Gets a mutable reference to the resource storage for a specific type.
+    ///
+    /// Creates the resource storage if it doesn't exist yet.
+    pub(super) fn get_resource_storage_mut<T: Component>(
+        &mut self,
+    ) -> &mut dyn ComponentStorage<T> {
+        Self::get_storage_from_map_mut(&mut self.resource_storages)
+    }
+
     /// Gets an immutable reference to the ephemeral storage for a specific component type.
     ///
     /// Returns `None` if no ephemeral storage exists for this component type yet.
-    pub(super) fn get_ephemeral_storage<T: Component>(
-        &self,
-    ) -> Option<&HashMapComponentStorage<T>> {
+    pub(super) fn get_ephemeral_storage<T: Component>(&self) -> Option<&dyn ComponentStorage<T>> {
         Self::get_storage_from_map(&self.ephemeral_component_storages)
     }
 
@@ -63,15 +164,69 @@ impl World {
     /// Creates the ephemeral storage if it doesn't exist yet.
     pub(super) fn get_ephemeral_storage_mut<T: Component>(
         &mut self,
-    ) -> &mut HashMapComponentStorage<T> {
+    ) -> &mut dyn ComponentStorage<T> {
+        self.register_type_name::<T>();
+        self.ephemerals_cleaned = false;
         Self::get_storage_from_map_mut(&mut self.ephemeral_component_storages)
     }
+
+    /// Remembers the name of a component type for `component_type_name()`.
+    fn register_type_name<T: Component>(&mut self) {
+        self.component_type_names
+            .entry(TypeId::of::<T>())
+            .or_insert_with(T::type_name);
+    }
+
+    /// Gets an immutable reference to the ephemeral resource storage for a specific type.
+    ///
+    /// Returns `None` if no ephemeral resource storage exists for this type yet.
+    pub(super) fn get_ephemeral_resource_storage<T: Component>(
+        &self,
+    ) -> Option<&dyn ComponentStorage<T>> {
+        Self::get_storage_from_map(&self.ephemeral_resource_storages)
+    }
+
+    /// Gets a mutable reference to the ephemeral resource storage for a specific type.
+    ///
+    /// Creates the ephemeral resource storage if it doesn't exist yet.
+    pub(super) fn get_ephemeral_resource_storage_mut<T: Component>(
+        &mut self,
+    ) -> &mut dyn ComponentStorage<T> {
+        self.ephemerals_cleaned = false;
+        Self::get_storage_from_map_mut(&mut self.ephemeral_resource_storages)
+    }
+
+    /// Gets an immutable reference to the ephemeral event storage for a specific type.
+    ///
+    /// Returns `None` if no event of this type was pushed this tick.
+    pub(super) fn get_ephemeral_event_storage<T: Component>(
+        &self,
+    ) -> Option<&dyn ComponentStorage<EventQueue<T>>> {
+        Self::get_storage_from_map(&self.ephemeral_event_storages)
+    }
+
+    /// Gets a mutable reference to the ephemeral event storage for a specific type.
+    ///
+    /// Creates the ephemeral event storage if it doesn't exist yet.
+    pub(super) fn get_ephemeral_event_storage_mut<T: Component>(
+        &mut self,
+    ) -> &mut dyn ComponentStorage<EventQueue<T>> {
+        self.ephemerals_cleaned = false;
+        Self::get_storage_from_map_mut(&mut self.ephemeral_event_storages)
+    }
+}
+
+/// Returns the factory of empty storages like `storage`, see [`World::set_storage`].
+fn custom_storage_factory<T: Component, S: ComponentStorage<T> + Default + 'static>(
+    _storage: &S,
+) -> StorageFactory {
+    || Box::new(CustomStorage::<T>(Box::new(S::default())))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{AnyStorage, Component, ComponentStorage};
+    use crate::Component;
 
     #[derive(Debug, Clone, PartialEq)]
     struct Position {
@@ -561,4 +716,261 @@ mod tests {
         assert!(storage.contains(entity1)); // Still there until explicit cleanup
         assert!(storage.contains(entity2));
     }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    #[test]
+    fn test_zero_sized_components_use_tag_storage() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Dead).unwrap();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+
+        let dead = &world.component_storages[&TypeId::of::<Dead>()];
+        assert!(dead.as_any().is::<TagStorage<Dead>>());
+        let position = &world.component_storages[&TypeId::of::<Position>()];
+        assert!(position.as_any().is::<HashMapComponentStorage<Position>>());
+    }
+
+    #[test]
+    fn test_tag_storage_behaves_like_hash_map_storage() {
+        use crate::{ComponentError, Query};
+
+        let mut world = World::new();
+        let a = world.spawn_entity();
+        let b = world.spawn_entity();
+        let mut reference = HashMapComponentStorage::<Dead>::new();
+
+        // Duplicates are rejected the same way
+        assert_eq!(world.add_component(a, Dead), reference.insert(a, Dead));
+        assert!(matches!(
+            world.add_component(a, Dead),
+            Err(ComponentError::AlreadyExists { entity, .. }) if entity == a
+        ));
+        assert_eq!(reference.insert(a, Dead), world.add_component(a, Dead));
+        world.add_component(b, Dead).unwrap();
+        reference.insert(b, Dead).unwrap();
+
+        assert_eq!(world.get_component::<Dead>(a), reference.get(a));
+        assert!(world.has_component::<Dead>(b));
+        assert_eq!(Query::<Dead>::new().iter(&world).count(), 2);
+        assert_eq!(
+            Query::<Position>::new()
+                .without::<Dead>()
+                .iter(&world)
+                .count(),
+            0
+        );
+
+        // Removal reconstructs the value
+        assert_eq!(world.remove_component::<Dead>(a), reference.remove(a));
+        assert_eq!(world.remove_component::<Dead>(a), None);
+        assert_eq!(world.replace_component(b, Dead), Some(Dead));
+        assert!(!world.reverse_component_index[&TypeId::of::<Dead>()].contains(&a));
+        assert!(world.reverse_component_index[&TypeId::of::<Dead>()].contains(&b));
+    }
+
+    #[test]
+    fn test_tag_storage_memory_compared_to_data_storage() {
+        let mut world = World::new();
+        for _ in 0..1000 {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Dead).unwrap();
+            world.add_component(entity, Health { value: 1 }).unwrap();
+        }
+
+        let stats = world.stats();
+        let bytes = |name: &str| {
+            stats
+                .components
+                .iter()
+                .find(|c| c.type_name.ends_with(name))
+                .unwrap()
+                .approx_bytes
+        };
+
+        // Tags only cost their entity set, no per-entity payload
+        assert_eq!(bytes("Dead"), 0);
+        assert_eq!(bytes("Health"), 1000 * std::mem::size_of::<Health>());
+    }
+
+    #[test]
+    fn test_zero_sized_type_with_drop_uses_hash_map_storage() {
+        struct Guard;
+        impl Drop for Guard {
+            fn drop(&mut self) {}
+        }
+        impl Component for Guard {}
+
+        assert!(!TagStorage::<Guard>::is_supported());
+        assert!(TagStorage::<Dead>::is_supported());
+        assert!(!TagStorage::<Health>::is_supported());
+
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Guard).unwrap();
+        let storage = &world.component_storages[&TypeId::of::<Guard>()];
+        assert!(storage.as_any().is::<HashMapComponentStorage<Guard>>());
+    }
+
+    /// Fills `world` with positioned entities, every third one `Dead`.
+    fn populate(world: &mut World) {
+        for i in 0..30 {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    Position {
+                        x: i as f32,
+                        y: 0.0,
+                    },
+                )
+                .unwrap();
+            if i % 3 == 0 {
+                world.add_component(entity, Dead).unwrap();
+            }
+        }
+    }
+
+    fn living_positions(world: &World) -> Vec<f32> {
+        let mut xs: Vec<f32> = crate::Query::<Position>::new()
+            .without::<Dead>()
+            .iter(world)
+            .map(|(_, position)| position.x)
+            .collect();
+        xs.sort_by(f32::total_cmp);
+        xs
+    }
+
+    /// Returns `true` if `T` lives in a storage set with `set_storage` of type `S`.
+    fn has_custom_storage<T: Component, S: 'static>(world: &World) -> bool {
+        world.component_storages[&TypeId::of::<T>()]
+            .as_any()
+            .downcast_ref::<CustomStorage<T>>()
+            .is_some_and(|custom| custom.0.as_any().is::<S>())
+    }
+
+    #[test]
+    fn test_set_storage_mixes_backends_with_identical_queries() {
+        // Every type in a hash map, one storage strategy for all
+        let mut default_world = World::new();
+        default_world
+            .set_storage::<Dead>(Box::new(HashMapComponentStorage::new()))
+            .unwrap();
+        populate(&mut default_world);
+
+        // The sparse Dead in a tag storage, Position in the default hash map
+        let mut custom_world = World::new();
+        custom_world
+            .set_storage::<Dead>(Box::new(TagStorage::new()))
+            .unwrap();
+        populate(&mut custom_world);
+
+        assert!(has_custom_storage::<Dead, TagStorage<Dead>>(&custom_world));
+        let position = &custom_world.component_storages[&TypeId::of::<Position>()];
+        assert!(position.as_any().is::<HashMapComponentStorage<Position>>());
+
+        assert_eq!(
+            living_positions(&custom_world),
+            living_positions(&default_world)
+        );
+        assert_eq!(living_positions(&custom_world).len(), 20);
+        assert_eq!(
+            crate::Query::<Dead>::new().iter(&custom_world).count(),
+            crate::Query::<Dead>::new().iter(&default_world).count()
+        );
+
+        // Removals and cleanup go through the custom storage as well
+        let corpse = crate::Query::<Dead>::new()
+            .collect_entities(&custom_world)
+            .into_iter()
+            .next()
+            .unwrap();
+        assert_eq!(custom_world.remove_component::<Dead>(corpse), Some(Dead));
+        assert_eq!(living_positions(&custom_world).len(), 21);
+        custom_world.delete_entity(corpse);
+        custom_world.cleanup_deleted_entities();
+        assert!(custom_world.check_consistency().is_consistent());
+    }
+
+    #[test]
+    fn test_set_storage_fails_once_components_exist() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 3 }).unwrap();
+
+        let result = world.set_storage::<Health>(Box::new(HashMapComponentStorage::new()));
+        assert_eq!(
+            result,
+            Err(ComponentError::StorageNotEmpty {
+                type_name: std::any::type_name::<Health>()
+            })
+        );
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 3 })
+        );
+
+        let mut filled = HashMapComponentStorage::new();
+        filled.insert(entity, Position { x: 1.0, y: 1.0 }).unwrap();
+        assert!(world.set_storage::<Position>(Box::new(filled)).is_err());
+        assert!(!world.has_component::<Position>(entity));
+    }
+
+    #[test]
+    fn test_snapshot_restore_and_clone_keep_custom_storage() {
+        let mut world = World::new();
+        world.register_cloneable::<Position>();
+        world.register_cloneable::<Dead>();
+        world
+            .set_storage::<Position>(Box::new(HashMapComponentStorage::new()))
+            .unwrap();
+        populate(&mut world);
+
+        let copy = world.try_clone().unwrap();
+        assert_eq!(living_positions(&copy), living_positions(&world));
+        assert!(has_custom_storage::<
+            Position,
+            HashMapComponentStorage<Position>,
+        >(&copy));
+
+        let snapshot = world.snapshot().unwrap();
+        world.restore(&snapshot);
+        assert_eq!(living_positions(&world), living_positions(&copy));
+        assert!(has_custom_storage::<
+            Position,
+            HashMapComponentStorage<Position>,
+        >(&world));
+    }
+
+    #[test]
+    fn test_pruned_custom_storage_is_created_again() {
+        let mut world = World::new();
+        world
+            .set_storage::<Position>(Box::new(HashMapComponentStorage::new()))
+            .unwrap();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 1.0, y: 2.0 })
+            .unwrap();
+        world.remove_component::<Position>(entity);
+
+        assert_eq!(world.prune_empty_storages(), 1);
+        world
+            .add_component(entity, Position { x: 3.0, y: 4.0 })
+            .unwrap();
+
+        assert!(has_custom_storage::<
+            Position,
+            HashMapComponentStorage<Position>,
+        >(&world));
+        assert_eq!(
+            world.get_component::<Position>(entity),
+            Some(&Position { x: 3.0, y: 4.0 })
+        );
+    }
 }
@@ -0,0 +1,593 @@
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{Component, Entity};
+
+use super::World;
+
+/// The number of events a subscription buffers unless configured otherwise.
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// A change made to a [`World`], as reported to subscribers.
+///
+/// Type names are the full names reported by `std::any::type_name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldEvent {
+    /// An entity was spawned.
+    EntitySpawned(Entity),
+    /// An entity was deleted.
+    EntityDeleted(Entity),
+    /// A component was added to an entity.
+    ComponentAdded {
+        entity: Entity,
+        type_name: &'static str,
+    },
+    /// A component was removed from a live entity.
+    ComponentRemoved {
+        entity: Entity,
+        type_name: &'static str,
+    },
+    /// A resource was inserted or updated.
+    ResourceUpdated { type_name: &'static str },
+    /// A resource was removed.
+    ResourceRemoved { type_name: &'static str },
+}
+
+/// Selects the [`WorldEvent`]s a subscription receives and how many it buffers.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, EventFilter};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// // Spawns, deletions and Health changes, buffering up to 64 events
+/// let filter = EventFilter::none().entities().component::<Health>().capacity(64);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFilter {
+    entities: bool,
+    components: bool,
+    component_types: Vec<TypeId>, // empty for all component types
+    resources: bool,
+    capacity: usize,
+}
+
+impl EventFilter {
+    /// Matches every event.
+    pub fn all() -> Self {
+        Self {
+            entities: true,
+            components: true,
+            component_types: Vec::new(),
+            resources: true,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+
+    /// Matches no event; combine with the other methods to opt in.
+    pub fn none() -> Self {
+        Self {
+            entities: false,
+            components: false,
+            component_types: Vec::new(),
+            resources: false,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+
+    /// Also matches entities being spawned and deleted.
+    pub fn entities(mut self) -> Self {
+        self.entities = true;
+        self
+    }
+
+    /// Also matches components of any type being added and removed.
+    pub fn components(mut self) -> Self {
+        self.components = true;
+        self.component_types.clear();
+        self
+    }
+
+    /// Also matches components of type `T` being added and removed.
+    ///
+    /// Once a type is listed, component events of unlisted types are ignored,
+    /// unless [`components`](EventFilter::components) is called afterwards.
+    pub fn component<T: Component>(mut self) -> Self {
+        self.components = true;
+        self.component_types.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Also matches resources being inserted, updated and removed.
+    pub fn resources(mut self) -> Self {
+        self.resources = true;
+        self
+    }
+
+    /// Sets how many events the subscription buffers, 1024 by default.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "subscription capacity must be at least 1");
+        self.capacity = capacity;
+        self
+    }
+
+    fn matches(&self, event: &WorldEvent, type_id: Option<TypeId>) -> bool {
+        match event {
+            WorldEvent::EntitySpawned(_) | WorldEvent::EntityDeleted(_) => self.entities,
+            WorldEvent::ComponentAdded { .. } | WorldEvent::ComponentRemoved { .. } => {
+                self.components
+                    && (self.component_types.is_empty()
+                        || type_id.is_some_and(|type_id| self.component_types.contains(&type_id)))
+            }
+            WorldEvent::ResourceUpdated { .. } | WorldEvent::ResourceRemoved { .. } => {
+                self.resources
+            }
+        }
+    }
+}
+
+impl Default for EventFilter {
+    /// Matches every event, like [`EventFilter::all`].
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// The events of one flush, in the order the changes were made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventBatch {
+    /// Counts the flushes of the world, starting at 1 for the first one.
+    ///
+    /// Consecutive batches may skip numbers when nothing matched in between.
+    pub tick: u64,
+    /// The matching events.
+    pub events: Vec<WorldEvent>,
+}
+
+/// The buffer shared by a subscription and its receiver.
+#[derive(Debug, Default)]
+struct Channel {
+    batches: VecDeque<EventBatch>,
+    len: usize, // events across all batches
+    dropped: u64,
+}
+
+/// The receiving end of a [`World::subscribe`] subscription.
+///
+/// The receiver is `Send` and can be moved to another thread or task, while
+/// the world keeps running its ticks. Dropping it ends the subscription.
+#[derive(Debug)]
+pub struct EventReceiver {
+    channel: Arc<Mutex<Channel>>,
+}
+
+impl EventReceiver {
+    /// Takes the oldest batch, or returns `None` if no batch is waiting.
+    pub fn try_recv(&self) -> Option<EventBatch> {
+        let mut channel = self.lock();
+        let batch = channel.batches.pop_front()?;
+        channel.len -= batch.events.len();
+        Some(batch)
+    }
+
+    /// Takes every waiting batch, oldest first.
+    pub fn drain(&self) -> Vec<EventBatch> {
+        let mut channel = self.lock();
+        channel.len = 0;
+        channel.batches.drain(..).collect()
+    }
+
+    /// Returns the number of events waiting across all batches.
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    /// Returns `true` if no event is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many events were dropped so far because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.lock().dropped
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Channel> {
+        // Batches are pushed and popped whole, so a poisoned channel is consistent
+        self.channel.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct Subscriber {
+    filter: EventFilter,
+    pending: Vec<WorldEvent>,
+    channel: Arc<Mutex<Channel>>,
+}
+
+impl Subscriber {
+    /// Moves the pending events into the channel, dropping the oldest events
+    /// once the channel is over capacity.
+    fn flush(&mut self, tick: u64) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let mut channel = self.channel.lock().unwrap_or_else(|e| e.into_inner());
+        channel.len += self.pending.len();
+        channel.batches.push_back(EventBatch {
+            tick,
+            events: std::mem::take(&mut self.pending),
+        });
+
+        let mut excess = channel.len.saturating_sub(self.filter.capacity);
+        channel.len -= excess;
+        channel.dropped += excess as u64;
+        while excess > 0 {
+            let oldest = channel
+                .batches
+                .front_mut()
+                .expect("len counts queued events");
+            if oldest.events.len() <= excess {
+                excess -= oldest.events.len();
+                channel.batches.pop_front();
+            } else {
+                oldest.events.drain(..excess);
+                excess = 0;
+            }
+        }
+    }
+}
+
+/// The world's subscribers, see [`World::subscribe`].
+#[derive(Default)]
+pub(super) struct Subscriptions {
+    subscribers: Vec<Subscriber>,
+    flushes: u64,
+}
+
+impl Subscriptions {
+    /// Queues an event for every subscriber whose filter matches it.
+    ///
+    /// `type_id` is the component type of component events.
+    pub(super) fn record(&mut self, type_id: Option<TypeId>, event: WorldEvent) {
+        for subscriber in &mut self.subscribers {
+            if subscriber.filter.matches(&event, type_id) {
+                subscriber.pending.push(event.clone());
+            }
+        }
+    }
+
+    pub(super) fn entity_spawned(&mut self, entity: Entity) {
+        self.record(None, WorldEvent::EntitySpawned(entity));
+    }
+
+    pub(super) fn entity_deleted(&mut self, entity: Entity) {
+        self.record(None, WorldEvent::EntityDeleted(entity));
+    }
+
+    pub(super) fn component_added<T: Component>(&mut self, entity: Entity) {
+        let type_name = T::type_name();
+        let event = WorldEvent::ComponentAdded { entity, type_name };
+        self.record(Some(TypeId::of::<T>()), event);
+    }
+
+    pub(super) fn component_removed<T: Component>(&mut self, entity: Entity) {
+        let type_name = T::type_name();
+        self.component_removed_by_type_id(TypeId::of::<T>(), type_name, entity);
+    }
+
+    pub(super) fn component_removed_by_type_id(
+        &mut self,
+        type_id: TypeId,
+        type_name: &'static str,
+        entity: Entity,
+    ) {
+        let event = WorldEvent::ComponentRemoved { entity, type_name };
+        self.record(Some(type_id), event);
+    }
+
+    pub(super) fn resource_updated<T: Component>(&mut self) {
+        let type_name = T::type_name();
+        self.record(None, WorldEvent::ResourceUpdated { type_name });
+    }
+
+    pub(super) fn resource_removed<T: Component>(&mut self) {
+        let type_name = T::type_name();
+        self.record(None, WorldEvent::ResourceRemoved { type_name });
+    }
+}
+
+impl World {
+    /// Subscribes to the changes made to this world.
+    ///
+    /// Matching [`WorldEvent`]s are recorded while the world is mutated and
+    /// handed to the receiver as one [`EventBatch`] per call to
+    /// [`flush_world_events`](World::flush_world_events), which the scheduler
+    /// makes at the end of every tick. Subscribers thus only ever see whole
+    /// ticks and never a half-applied one.
+    ///
+    /// # Backpressure
+    /// The receiver buffers at most [`EventFilter::capacity`] events. When a
+    /// flush overflows it, the oldest events are dropped, possibly leaving a
+    /// partial batch at the front, and counted in [`EventReceiver::dropped`].
+    /// The world never blocks on a slow subscriber.
+    ///
+    /// Components of deleted entities are not reported individually, only the
+    /// [`WorldEvent::EntityDeleted`] event.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, EventFilter, World, WorldEvent};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let receiver = world.subscribe(EventFilter::all());
+    ///
+    /// let player = world.spawn_entity();
+    /// world.add_component(player, Health { value: 100 }).unwrap();
+    /// assert!(receiver.is_empty()); // Not flushed yet
+    ///
+    /// world.flush_world_events();
+    /// let batch = receiver.try_recv().unwrap();
+    /// assert_eq!(
+    ///     batch.events,
+    ///     vec![
+    ///         WorldEvent::EntitySpawned(player),
+    ///         WorldEvent::ComponentAdded {
+    ///             entity: player,
+    ///             type_name: std::any::type_name::<Health>(),
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn subscribe(&mut self, filter: EventFilter) -> EventReceiver {
+        let channel = Arc::new(Mutex::new(Channel::default()));
+        self.subscriptions.subscribers.push(Subscriber {
+            filter,
+            pending: Vec::new(),
+            channel: Arc::clone(&channel),
+        });
+        EventReceiver { channel }
+    }
+
+    /// Hands the events recorded since the last flush to the subscribers.
+    ///
+    /// Called by the scheduler at the end of every tick; call it yourself when
+    /// driving the world without a scheduler. Subscriptions whose receiver was
+    /// dropped are removed.
+    pub fn flush_world_events(&mut self) {
+        let subscriptions = &mut self.subscriptions;
+        subscriptions
+            .subscribers
+            .retain(|subscriber| Arc::strong_count(&subscriber.channel) > 1);
+        if subscriptions.subscribers.is_empty() {
+            return;
+        }
+
+        subscriptions.flushes += 1;
+        for subscriber in &mut subscriptions.subscribers {
+            subscriber.flush(subscriptions.flushes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SequentialSystemScheduler, System};
+    use std::any::type_name;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Poisoned;
+    impl Component for Poisoned {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TickCount(u32);
+    impl Component for TickCount {}
+
+    fn added<T>(entity: Entity) -> WorldEvent {
+        WorldEvent::ComponentAdded {
+            entity,
+            type_name: type_name::<T>(),
+        }
+    }
+
+    fn removed<T>(entity: Entity) -> WorldEvent {
+        WorldEvent::ComponentRemoved {
+            entity,
+            type_name: type_name::<T>(),
+        }
+    }
+
+    /// Spawns a poisoned entity every tick and kills the previous one.
+    struct SpawnSystem;
+    impl System for SpawnSystem {
+        fn run(&self, world: &mut World) {
+            let previous: Vec<Entity> = world.entities().copied().collect();
+            for entity in previous {
+                world.remove_component::<Poisoned>(entity);
+                world.delete_entity(entity);
+            }
+
+            let entity = world.spawn_entity();
+            world.add_component(entity, Health { value: 10 }).unwrap();
+            world.add_component(entity, Poisoned).unwrap();
+            world.get_resource_or_insert_with(|| TickCount(0));
+            world
+                .update_resource::<TickCount, _>(|c| TickCount(c.0 + 1))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_batches_match_each_tick() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(SpawnSystem).unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        let receiver = world.subscribe(EventFilter::all());
+
+        scheduler.run_tick(&mut world);
+        let first = world.entities().copied().next().unwrap();
+        scheduler.run_tick(&mut world);
+        let second = world.entities().copied().next().unwrap();
+
+        let resource = WorldEvent::ResourceUpdated {
+            type_name: type_name::<TickCount>(),
+        };
+        assert_eq!(
+            receiver.drain(),
+            vec![
+                EventBatch {
+                    tick: 1,
+                    events: vec![
+                        WorldEvent::EntitySpawned(first),
+                        added::<Health>(first),
+                        added::<Poisoned>(first),
+                        resource.clone(),
+                        resource.clone(),
+                    ],
+                },
+                EventBatch {
+                    tick: 2,
+                    events: vec![
+                        removed::<Poisoned>(first),
+                        WorldEvent::EntityDeleted(first),
+                        WorldEvent::EntitySpawned(second),
+                        added::<Health>(second),
+                        added::<Poisoned>(second),
+                        resource,
+                    ],
+                },
+            ]
+        );
+        assert_eq!(receiver.dropped(), 0);
+        assert!(receiver.is_empty());
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(SpawnSystem).unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        let entities = world.subscribe(EventFilter::none().entities());
+        let poison = world.subscribe(EventFilter::none().component::<Poisoned>());
+        let resources = world.subscribe(EventFilter::none().resources());
+        let nothing = world.subscribe(EventFilter::none());
+
+        scheduler.run_tick(&mut world);
+        let entity = world.entities().copied().next().unwrap();
+
+        assert_eq!(
+            entities.try_recv().unwrap().events,
+            vec![WorldEvent::EntitySpawned(entity)]
+        );
+        assert_eq!(
+            poison.try_recv().unwrap().events,
+            vec![added::<Poisoned>(entity)]
+        );
+        assert_eq!(resources.try_recv().unwrap().events.len(), 2);
+        // Nothing matched, so no batch at all
+        assert_eq!(nothing.try_recv(), None);
+    }
+
+    #[test]
+    fn test_undersized_channel_drops_oldest_events() {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(SpawnSystem).unwrap();
+        scheduler.build().unwrap();
+
+        let mut world = World::new();
+        let receiver = world.subscribe(EventFilter::none().entities().capacity(2));
+
+        // One spawn in the first tick, a delete and a spawn in each later one
+        scheduler.run_tick(&mut world);
+        scheduler.run_tick(&mut world);
+        assert_eq!(receiver.dropped(), 1);
+        assert_eq!(receiver.len(), 2);
+
+        scheduler.run_tick(&mut world);
+        let third = world.entities().copied().next().unwrap();
+        assert_eq!(receiver.dropped(), 3);
+
+        // Only the last tick is left, whole
+        let batches = receiver.drain();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].tick, 3);
+        assert_eq!(batches[0].events[1], WorldEvent::EntitySpawned(third));
+    }
+
+    #[test]
+    fn test_overflowing_batch_keeps_its_newest_events() {
+        let mut world = World::new();
+        let receiver = world.subscribe(EventFilter::all().capacity(2));
+
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: 1 }).unwrap();
+        world.add_component(entity, Poisoned).unwrap();
+        world.flush_world_events();
+
+        assert_eq!(receiver.dropped(), 1);
+        assert_eq!(
+            receiver.try_recv().unwrap().events,
+            vec![added::<Health>(entity), added::<Poisoned>(entity)]
+        );
+    }
+
+    #[test]
+    fn test_dropping_the_receiver_ends_the_subscription() {
+        let mut world = World::new();
+        let receiver = world.subscribe(EventFilter::all());
+        world.spawn_entity();
+        drop(receiver);
+
+        world.flush_world_events();
+        assert!(world.subscriptions.subscribers.is_empty());
+    }
+
+    #[test]
+    fn test_receiver_can_be_read_from_another_thread() {
+        let mut world = World::new();
+        let receiver = world.subscribe(EventFilter::all());
+        let entity = world.spawn_entity();
+        world.insert_resource(TickCount(1));
+        world.remove_resource::<TickCount>();
+        world.flush_world_events();
+
+        let batch = std::thread::spawn(move || receiver.try_recv())
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            batch.events,
+            vec![
+                WorldEvent::EntitySpawned(entity),
+                WorldEvent::ResourceUpdated {
+                    type_name: type_name::<TickCount>()
+                },
+                WorldEvent::ResourceRemoved {
+                    type_name: type_name::<TickCount>()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::Entity;
+
+use super::World;
+
+/// A tag registered with [`World::register_tag`].
+///
+/// Tags are cheap markers stored as bits: each entity carries at most one
+/// `u64` holding all of its tags, instead of one storage and one reverse index
+/// set per marker component. A world has room for [`TagId::CAPACITY`] tags.
+///
+/// Tag ids are only meaningful for the world that registered them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TagId(u8);
+
+impl TagId {
+    /// The number of tags a world can register.
+    pub const CAPACITY: usize = u64::BITS as usize;
+
+    /// Returns the position of the tag's bit, in `0..TagId::CAPACITY`.
+    pub fn index(self) -> usize {
+        usize::from(self.0)
+    }
+
+    /// Returns the tag as a bit mask.
+    pub(crate) fn bit(self) -> u64 {
+        1 << self.0
+    }
+}
+
+/// Errors that can occur when registering or setting tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagError {
+    /// All [`TagId::CAPACITY`] tags of the world are already registered.
+    TooManyTags { name: String },
+    /// The tag was not registered with this world.
+    UnknownTag { tag: TagId },
+    /// The entity does not exist or has been deleted.
+    EntityNotFound { entity: Entity },
+}
+
+impl std::fmt::Display for TagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagError::TooManyTags { name } => write!(
+                f,
+                "cannot register tag \"{name}\": all {} tags are in use",
+                TagId::CAPACITY
+            ),
+            TagError::UnknownTag { tag } => {
+                write!(f, "tag {} is not registered with this world", tag.index())
+            }
+            TagError::EntityNotFound { entity } => {
+                write!(f, "cannot tag entity {entity}: entity does not exist")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TagError {}
+
+impl World {
+    /// Registers a tag under `name` and returns its id, or the existing id if
+    /// the name is already registered.
+    ///
+    /// Tags are an opt-in alternative to marker components like `Dead` or
+    /// `Player` for hot markers: setting, clearing and testing a tag touches a
+    /// single bitset entry, and [`Query::with_tag`](crate::Query::with_tag)
+    /// filters by a bit test per candidate instead of another set operation.
+    /// Register tags once during setup and keep the ids around.
+    ///
+    /// # Returns
+    /// * `Ok(TagId)` - The tag's id
+    /// * `Err(TagError::TooManyTags)` if [`TagId::CAPACITY`] tags are already registered
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let dead = world.register_tag("dead").unwrap();
+    ///
+    /// let goblin = world.spawn_entity();
+    /// world.set_tag(goblin, dead).unwrap();
+    /// assert!(world.has_tag(goblin, dead));
+    ///
+    /// assert!(world.clear_tag(goblin, dead));
+    /// assert!(!world.has_tag(goblin, dead));
+    ///
+    /// // Registering a name again returns the same id
+    /// assert_eq!(world.register_tag("dead"), Ok(dead));
+    /// assert_eq!(world.tag_name(dead), Some("dead"));
+    /// ```
+    pub fn register_tag(&mut self, name: impl Into<String>) -> Result<TagId, TagError> {
+        let name = name.into();
+        if let Some(tag) = self.tag_id(&name) {
+            return Ok(tag);
+        }
+        if self.tag_names.len() == TagId::CAPACITY {
+            return Err(TagError::TooManyTags { name });
+        }
+
+        let tag = TagId(self.tag_names.len() as u8);
+        self.tag_names.push(name);
+        Ok(tag)
+    }
+
+    /// Returns the id of the tag registered under `name`, if any.
+    pub fn tag_id(&self, name: &str) -> Option<TagId> {
+        self.tag_names
+            .iter()
+            .position(|registered| registered == name)
+            .map(|index| TagId(index as u8))
+    }
+
+    /// Returns the name `tag` was registered under, if it belongs to this world.
+    pub fn tag_name(&self, tag: TagId) -> Option<&str> {
+        self.tag_names.get(tag.index()).map(String::as_str)
+    }
+
+    /// Sets `tag` on `entity`. Setting a tag the entity already has does nothing.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the entity has the tag
+    /// * `Err(TagError::UnknownTag)` if the tag was not registered with this world
+    /// * `Err(TagError::EntityNotFound)` if the entity doesn't exist or has been deleted
+    pub fn set_tag(&mut self, entity: Entity, tag: TagId) -> Result<(), TagError> {
+        if self.tag_name(tag).is_none() {
+            return Err(TagError::UnknownTag { tag });
+        }
+        if !self.is_entity_active(entity) {
+            return Err(TagError::EntityNotFound { entity });
+        }
+
+        self.record_tags(entity);
+        let bits = self.entity_tags.entry(entity).or_default();
+        if *bits & tag.bit() == 0 {
+            *bits |= tag.bit();
+            self.bump_generation();
+        }
+        Ok(())
+    }
+
+    /// Removes `tag` from `entity`.
+    ///
+    /// Returns `true` if the entity had the tag, `false` otherwise, including
+    /// for entities that don't exist or have been deleted.
+    pub fn clear_tag(&mut self, entity: Entity, tag: TagId) -> bool {
+        if !self.has_tag(entity, tag) {
+            return false;
+        }
+
+        self.record_tags(entity);
+        if let Some(bits) = self.entity_tags.get_mut(&entity) {
+            *bits &= !tag.bit();
+            if *bits == 0 {
+                self.entity_tags.remove(&entity);
+            }
+        }
+        self.bump_generation();
+        true
+    }
+
+    /// Returns `true` if `entity` exists and has `tag`.
+    pub fn has_tag(&self, entity: Entity, tag: TagId) -> bool {
+        self.is_entity_active(entity) && self.tag_bits(entity) & tag.bit() != 0
+    }
+
+    /// Returns the tags of `entity` as a bitset.
+    ///
+    /// Soft-deleted entities keep their bits until cleanup, so callers must
+    /// only pass active entities, like the query system's candidates.
+    pub(crate) fn tag_bits(&self, entity: Entity) -> u64 {
+        self.entity_tags.get(&entity).copied().unwrap_or(0)
+    }
+
+    /// Drops the tags of soft-deleted entities.
+    ///
+    /// Called during `cleanup_deleted_entities()`.
+    pub(super) fn cleanup_deleted_tags(&mut self) {
+        if self.entity_tags.is_empty() {
+            return;
+        }
+
+        for entity in &self.soft_deleted_entities {
+            self.entity_tags.remove(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_clear_and_has_tag() {
+        let mut world = World::new();
+        let dead = world.register_tag("dead").unwrap();
+        let player = world.register_tag("player").unwrap();
+        assert_ne!(dead, player);
+
+        let hero = world.spawn_entity();
+        world.set_tag(hero, player).unwrap();
+        world.set_tag(hero, dead).unwrap();
+        world.set_tag(hero, dead).unwrap();
+        assert!(world.has_tag(hero, player));
+        assert!(world.has_tag(hero, dead));
+
+        assert!(world.clear_tag(hero, dead));
+        assert!(!world.clear_tag(hero, dead));
+        assert!(!world.has_tag(hero, dead));
+        assert!(world.has_tag(hero, player));
+
+        assert_eq!(world.tag_id("player"), Some(player));
+        assert_eq!(world.tag_id("npc"), None);
+    }
+
+    #[test]
+    fn test_tags_change_generation_only_when_set_changes() {
+        let mut world = World::new();
+        let dead = world.register_tag("dead").unwrap();
+        let goblin = world.spawn_entity();
+
+        let before = world.generation();
+        world.set_tag(goblin, dead).unwrap();
+        let tagged = world.generation();
+        assert_ne!(tagged, before);
+
+        world.set_tag(goblin, dead).unwrap();
+        assert_eq!(world.generation(), tagged);
+        world.clear_tag(goblin, dead);
+        assert_ne!(world.generation(), tagged);
+    }
+
+    #[test]
+    fn test_deleted_entities_lose_their_tags() {
+        let mut world = World::new();
+        let dead = world.register_tag("dead").unwrap();
+        let goblin = world.spawn_entity();
+        world.set_tag(goblin, dead).unwrap();
+
+        world.delete_entity(goblin);
+        assert!(!world.has_tag(goblin, dead));
+        assert!(!world.clear_tag(goblin, dead));
+        assert_eq!(
+            world.set_tag(goblin, dead),
+            Err(TagError::EntityNotFound { entity: goblin })
+        );
+
+        world.cleanup_deleted_entities();
+        assert!(world.entity_tags.is_empty());
+    }
+
+    #[test]
+    fn test_tag_capacity_and_foreign_tags() {
+        let mut world = World::new();
+        for i in 0..TagId::CAPACITY {
+            world.register_tag(format!("tag{i}")).unwrap();
+        }
+        assert_eq!(
+            world.register_tag("one_too_many"),
+            Err(TagError::TooManyTags {
+                name: "one_too_many".to_string()
+            })
+        );
+        let last = world.tag_id("tag63").unwrap();
+        let entity = world.spawn_entity();
+        world.set_tag(entity, last).unwrap();
+        assert!(world.has_tag(entity, last));
+
+        // An id from a world with more tags is unknown here
+        let mut other = World::new();
+        let entity = other.spawn_entity();
+        assert_eq!(
+            other.set_tag(entity, last),
+            Err(TagError::UnknownTag { tag: last })
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use std::any::TypeId;
+
+use crate::{ComponentRegistry, Entity, ErasedComponent};
+
+use super::World;
+
+/// An entity's components, taken out of one world to be inserted into another.
+///
+/// Created by [`World::extract_entity`] and consumed by
+/// [`World::insert_entity_bundle`]. Every component moves: each value carries its
+/// own concrete type, so there is no registration step and no component type is
+/// ever skipped. Ephemeral components, labels, tags and parent links are not
+/// part of the bundle.
+pub struct EntityBundle {
+    components: Vec<(TypeId, Box<dyn ErasedComponent>)>,
+}
+
+impl EntityBundle {
+    /// Returns the number of components in the bundle.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns `true` if the extracted entity had no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns `true` if the bundle holds a component with the given TypeId.
+    pub fn contains_type(&self, type_id: TypeId) -> bool {
+        self.components.iter().any(|(id, _)| *id == type_id)
+    }
+
+    /// Returns the type names of the components in the bundle, sorted.
+    pub fn component_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self
+            .components
+            .iter()
+            .map(|(_, component)| component.component_type_name())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl std::fmt::Debug for EntityBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EntityBundle")
+            .field("components", &self.component_names())
+            .finish()
+    }
+}
+
+/// Errors that can occur when transferring an entity between worlds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// The entity does not exist or has been deleted.
+    EntityNotFound { entity: Entity },
+    /// The entity has components whose types are not in the registry.
+    UnregisteredComponents {
+        entity: Entity,
+        type_names: Vec<&'static str>,
+    },
+}
+
+impl std::fmt::Display for TransferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferError::EntityNotFound { entity } => {
+                write!(f, "cannot transfer entity {entity}: entity does not exist")
+            }
+            TransferError::UnregisteredComponents { entity, type_names } => write!(
+                f,
+                "cannot transfer entity {entity}: unregistered components {}",
+                type_names.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+impl World {
+    /// Removes an entity from this world and returns its components.
+    ///
+    /// The components are taken out of their storages immediately and the entity
+    /// is deleted. Callbacks registered with [`World::observe_removed`] run for
+    /// every component taken. Ephemeral components are dropped with the entity
+    /// and its label is released on the next cleanup.
+    ///
+    /// # Returns
+    /// * `Some(EntityBundle)` with every component of the entity
+    /// * `None` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player { name: String }
+    /// impl Component for Player {}
+    ///
+    /// let mut town = World::new();
+    /// let player = town.spawn_entity();
+    /// town.add_component(player, Player { name: "Alice".to_string() }).unwrap();
+    ///
+    /// let bundle = town.extract_entity(player).unwrap();
+    /// assert_eq!(bundle.len(), 1);
+    /// assert!(!town.has_component::<Player>(player));
+    /// ```
+    pub fn extract_entity(&mut self, entity: Entity) -> Option<EntityBundle> {
+        if !self.is_entity_active(entity) {
+            return None;
+        }
+
+        self.record_entity_components(entity);
+        let mut components = Vec::new();
+        for (type_id, storage) in &mut self.component_storages {
+            if let Some(component) = storage.take_boxed(entity) {
+                if let Some(entities) = self.reverse_component_index.get_mut(type_id) {
+                    entities.remove(&entity);
+                }
+                self.subscriptions.component_removed_by_type_id(
+                    *type_id,
+                    storage.component_type_name(),
+                    entity,
+                );
+                components.push((*type_id, component));
+            }
+        }
+
+        self.unindex_entity(entity);
+        self.delete_entity(entity);
+
+        for (type_id, component) in &components {
+            self.notify_removed_any(*type_id, entity, component.as_any());
+        }
+
+        Some(EntityBundle { components })
+    }
+
+    /// Spawns a new entity holding the components of `bundle`.
+    ///
+    /// Components are added one by one, so callbacks registered with
+    /// [`World::observe_added`] run for each of them.
+    ///
+    /// # Returns
+    /// The newly spawned entity
+    pub fn insert_entity_bundle(&mut self, bundle: EntityBundle) -> Entity {
+        let entity = self.spawn_entity();
+
+        for (_, component) in bundle.components {
+            component
+                .insert_into(self, entity)
+                .expect("a bundle holds at most one component per type");
+        }
+
+        entity
+    }
+
+    /// Moves an entity with all of its components into another world.
+    ///
+    /// Equivalent to [`World::extract_entity`] followed by
+    /// [`World::insert_entity_bundle`] on `destination`. The entity gets a new
+    /// handle in the destination world; the old handle is deleted here.
+    ///
+    /// # Returns
+    /// * `Some(Entity)` - The entity's handle in `destination`
+    /// * `None` if the entity doesn't exist or has been deleted
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{World, Component};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health { value: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let mut town = World::new();
+    /// let mut forest = World::new();
+    ///
+    /// let player = town.spawn_entity();
+    /// town.add_component(player, Health { value: 80 }).unwrap();
+    ///
+    /// let moved = town.move_entity_to(player, &mut forest).unwrap();
+    ///
+    /// assert!(!town.has_component::<Health>(player));
+    /// assert_eq!(forest.get_component::<Health>(moved), Some(&Health { value: 80 }));
+    /// ```
+    pub fn move_entity_to(&mut self, entity: Entity, destination: &mut World) -> Option<Entity> {
+        let bundle = self.extract_entity(entity)?;
+        Some(destination.insert_entity_bundle(bundle))
+    }
+
+    /// Moves an entity into another world, checking its components against a registry.
+    ///
+    /// Like [`World::move_entity_to`], but every component type of the entity must
+    /// be registered in `registry`. If any is not, nothing is moved and the error
+    /// lists the offending types, so a newly added component type can never be
+    /// dropped silently. Ephemeral components are not transferred and don't need
+    /// to be registered.
+    ///
+    /// # Returns
+    /// * `Ok(Entity)` - The entity's handle in `target`; the original is deleted
+    /// * `Err(TransferError::EntityNotFound)` if the entity doesn't exist or has been deleted
+    /// * `Err(TransferError::UnregisteredComponents)` if the entity has unregistered components
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_ecs::{Component, ComponentRegistry, TransferError, World};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Player { name: String }
+    /// impl Component for Player {}
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Cursed;
+    /// impl Component for Cursed {}
+    ///
+    /// let registry = ComponentRegistry::new().with::<Player>();
+    /// let mut town = World::new();
+    /// let mut dungeon = World::new();
+    ///
+    /// let player = town.spawn_entity();
+    /// town.add_component(player, Player { name: "Alice".to_string() }).unwrap();
+    /// let moved = town.transfer_entity(player, &mut dungeon, &registry).unwrap();
+    /// assert!(dungeon.has_component::<Player>(moved));
+    ///
+    /// // Unregistered components block the transfer
+    /// dungeon.add_component(moved, Cursed).unwrap();
+    /// let result = dungeon.transfer_entity(moved, &mut town, &registry);
+    /// assert!(matches!(result, Err(TransferError::UnregisteredComponents { .. })));
+    /// assert!(dungeon.has_component::<Player>(moved));
+    /// ```
+    pub fn transfer_entity(
+        &mut self,
+        entity: Entity,
+        target: &mut World,
+        registry: &ComponentRegistry,
+    ) -> Result<Entity, TransferError> {
+        if !self.is_entity_active(entity) {
+            return Err(TransferError::EntityNotFound { entity });
+        }
+
+        let mut unregistered: Vec<_> = self
+            .component_storages
+            .iter()
+            .filter(|(type_id, storage)| {
+                !registry.contains_type_id(**type_id) && storage.contains_entity(entity)
+            })
+            .map(|(_, storage)| storage.component_type_name())
+            .collect();
+        if !unregistered.is_empty() {
+            unregistered.sort_unstable();
+            return Err(TransferError::UnregisteredComponents {
+                entity,
+                type_names: unregistered,
+            });
+        }
+
+        let bundle = self
+            .extract_entity(entity)
+            .expect("entity was checked to be active");
+        Ok(target.insert_entity_bundle(bundle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Query};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    #[derive(Debug, PartialEq)]
+    struct Inventory {
+        items: Vec<String>,
+    }
+    impl Component for Inventory {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Stunned;
+    impl Component for Stunned {}
+
+    #[test]
+    fn test_move_entity_with_two_components() {
+        let mut source = World::new();
+        let mut destination = World::new();
+
+        let player = source.spawn_entity();
+        source
+            .add_component(player, Position { x: 1.0, y: 2.0 })
+            .unwrap();
+        source
+            .add_component(
+                player,
+                Inventory {
+                    items: vec!["sword".to_string()],
+                },
+            )
+            .unwrap();
+        let bystander = source.spawn_entity();
+        source
+            .add_component(bystander, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+
+        let moved = source.move_entity_to(player, &mut destination).unwrap();
+
+        // Gone from the source, including its queries
+        assert!(!source.entities().any(|&e| e == player));
+        assert!(!source.has_component::<Position>(player));
+        assert!(!source.has_component::<Inventory>(player));
+        let remaining: Vec<_> = Query::<Position>::new()
+            .iter(&source)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(remaining, vec![bystander]);
+
+        // Present in the destination
+        assert_eq!(
+            destination.get_component::<Position>(moved),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert_eq!(
+            destination.get_component::<Inventory>(moved).unwrap().items,
+            vec!["sword".to_string()]
+        );
+        assert_eq!(Query::<Inventory>::new().iter(&destination).count(), 1);
+    }
+
+    #[test]
+    fn test_extract_leaves_no_storage_data_behind() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+
+        let bundle = world.extract_entity(entity).unwrap();
+
+        assert_eq!(world.storage_len::<Position>(), 0);
+        assert_eq!(
+            bundle.component_names(),
+            vec![std::any::type_name::<Position>()]
+        );
+        assert!(bundle.contains_type(TypeId::of::<Position>()));
+    }
+
+    #[test]
+    fn test_extract_skips_ephemeral_components() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world
+            .add_component(entity, Position { x: 0.0, y: 0.0 })
+            .unwrap();
+        world.add_ephemeral_component(entity, Stunned).unwrap();
+
+        let bundle = world.extract_entity(entity).unwrap();
+
+        assert_eq!(bundle.len(), 1);
+        assert!(!bundle.contains_type(TypeId::of::<Stunned>()));
+    }
+
+    #[test]
+    fn test_extract_invalid_entity() {
+        let mut world = World::new();
+        let entity = world.spawn_entity();
+        world.delete_entity(entity);
+
+        assert!(world.extract_entity(entity).is_none());
+
+        let mut other = World::new();
+        assert!(other.move_entity_to(entity, &mut world).is_none());
+    }
+
+    #[test]
+    fn test_empty_entity_moves() {
+        let mut source = World::new();
+        let mut destination = World::new();
+        let entity = source.spawn_entity();
+
+        let moved = source.move_entity_to(entity, &mut destination).unwrap();
+
+        assert_eq!(source.entities().count(), 0);
+        assert!(destination.entities().any(|&e| e == moved));
+    }
+
+    #[test]
+    fn test_move_notifies_observers_in_both_worlds() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut source = World::new();
+        let mut destination = World::new();
+
+        let removed_log = log.clone();
+        source.observe_removed::<Position, _>(move |_, _, position| {
+            removed_log
+                .lock()
+                .unwrap()
+                .push(format!("removed {}", position.x));
+        });
+        let added_log = log.clone();
+        destination.observe_added::<Position, _>(move |_, _, position| {
+            added_log
+                .lock()
+                .unwrap()
+                .push(format!("added {}", position.x));
+        });
+
+        let entity = source.spawn_entity();
+        source
+            .add_component(entity, Position { x: 3.0, y: 0.0 })
+            .unwrap();
+        source.move_entity_to(entity, &mut destination).unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec!["removed 3", "added 3"]);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health {
+        value: u32,
+    }
+    impl Component for Health {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name(String);
+    impl Component for Name {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Player;
+    impl Component for Player {}
+
+    fn zone_registry() -> ComponentRegistry {
+        ComponentRegistry::new()
+            .with::<Position>()
+            .with::<Inventory>()
+            .with::<Health>()
+            .with::<Name>()
+            .with::<Player>()
+    }
+
+    fn spawn_player(world: &mut World) -> Entity {
+        world
+            .spawn_bundle((
+                Position { x: 4.0, y: 2.0 },
+                Inventory {
+                    items: vec!["torch".to_string()],
+                },
+                Health { value: 42 },
+                Name("Alice".to_string()),
+                Player,
+            ))
+            .unwrap()
+    }
+
+    fn assert_is_player(world: &World, entity: Entity) {
+        assert_eq!(
+            world.get_component::<Position>(entity),
+            Some(&Position { x: 4.0, y: 2.0 })
+        );
+        assert_eq!(
+            world.get_component::<Inventory>(entity).unwrap().items,
+            vec!["torch".to_string()]
+        );
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health { value: 42 })
+        );
+        assert_eq!(
+            world.get_component::<Name>(entity),
+            Some(&Name("Alice".to_string()))
+        );
+        assert!(world.has_component::<Player>(entity));
+    }
+
+    #[test]
+    fn test_transfer_round_trip_five_components() {
+        let registry = zone_registry();
+        let mut town = World::new();
+        let mut forest = World::new();
+        let player = spawn_player(&mut town);
+
+        let in_forest = town
+            .transfer_entity(player, &mut forest, &registry)
+            .unwrap();
+
+        assert_is_player(&forest, in_forest);
+        assert_eq!(Query::<Player>::new().iter(&town).count(), 0);
+        assert_eq!(Query::<Name>::new().iter(&town).count(), 0);
+        assert_eq!(Query::<Player>::new().iter(&forest).count(), 1);
+        assert_eq!(
+            Query::<Position>::new()
+                .with::<Health>()
+                .iter(&forest)
+                .count(),
+            1
+        );
+
+        let back = forest
+            .transfer_entity(in_forest, &mut town, &registry)
+            .unwrap();
+
+        assert_is_player(&town, back);
+        assert_eq!(Query::<Player>::new().iter(&forest).count(), 0);
+        assert_eq!(Query::<Player>::new().iter(&town).count(), 1);
+        assert_eq!(town.entities().count(), 1);
+        assert_eq!(forest.entities().count(), 0);
+    }
+
+    #[test]
+    fn test_transfer_rejects_unregistered_components() {
+        let registry = ComponentRegistry::new().with::<Position>();
+        let mut town = World::new();
+        let mut forest = World::new();
+        let player = spawn_player(&mut town);
+
+        let error = town
+            .transfer_entity(player, &mut forest, &registry)
+            .unwrap_err();
+
+        match error {
+            TransferError::UnregisteredComponents { entity, type_names } => {
+                assert_eq!(entity, player);
+                assert_eq!(type_names.len(), 4);
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+        // Nothing moved
+        assert_is_player(&town, player);
+        assert_eq!(forest.entities().count(), 0);
+    }
+
+    #[test]
+    fn test_transfer_ignores_ephemeral_components() {
+        let registry = zone_registry();
+        let mut town = World::new();
+        let mut forest = World::new();
+        let player = spawn_player(&mut town);
+        town.add_ephemeral_component(player, Stunned).unwrap();
+
+        let moved = town
+            .transfer_entity(player, &mut forest, &registry)
+            .unwrap();
+
+        assert!(!forest.has_ephemeral_component::<Stunned>(moved));
+    }
+
+    #[test]
+    fn test_transfer_deleted_entity() {
+        let mut town = World::new();
+        let mut forest = World::new();
+        let player = town.spawn_entity();
+        town.delete_entity(player);
+
+        let result = town.transfer_entity(player, &mut forest, &zone_registry());
+
+        assert_eq!(
+            result,
+            Err(TransferError::EntityNotFound { entity: player })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("cannot transfer entity {player}: entity does not exist")
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::ops::Deref;
+
+use crate::{Component, Query, QueryIter};
+
+use super::World;
+
+/// A read-only handle to a [`World`].
+///
+/// Systems receive a `WorldView` in their read-only phases,
+/// [`System::before_run`](crate::System::before_run) and
+/// [`System::after_run`](crate::System::after_run), and it can be handed to
+/// plugins that must only observe the world. The view dereferences to `World`,
+/// so every read method (`get_component()`, `has_component()`, `entities()`,
+/// `get_resource()`, ...) is available and [`Query::iter`] accepts `&view`
+/// directly. Views are `Copy`, and `Send` with the default
+/// `thread-safe-components` feature, so readers holding the read lock of an
+/// `RwLock<World>` can hand one to several threads at once. Methods that
+/// mutate the world are not reachable:
+///
+/// ```compile_fail
+/// use bemudjo_ecs::{Component, World};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// let mut world = World::new();
+/// let view = world.view();
+/// let entity = view.spawn_entity(); // requires &mut World
+/// ```
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, Query, World};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+/// impl Component for Health {}
+///
+/// let mut world = World::new();
+/// let hero = world.spawn_entity();
+/// world.add_component(hero, Health { value: 7 }).unwrap();
+///
+/// let view = world.view();
+/// assert_eq!(view.get_component::<Health>(hero), Some(&Health { value: 7 }));
+/// assert_eq!(view.query::<Health>().len(), 1);
+/// assert_eq!(Query::<Health>::new().iter(&view).count(), 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct WorldView<'w> {
+    world: &'w World,
+}
+
+impl<'w> WorldView<'w> {
+    /// Wraps a shared world reference.
+    pub fn new(world: &'w World) -> Self {
+        Self { world }
+    }
+
+    /// Returns the underlying world reference, for the full `'w` lifetime.
+    pub fn world(&self) -> &'w World {
+        self.world
+    }
+
+    /// Iterates over every entity with a `T` component.
+    ///
+    /// Shorthand for `Query::<T>::new().iter(view)`; build a [`Query`] for
+    /// filtered iteration.
+    pub fn query<T: Component>(&self) -> QueryIter<'w, T> {
+        Query::<T>::new().iter(self.world)
+    }
+}
+
+impl Deref for WorldView<'_> {
+    type Target = World;
+
+    fn deref(&self) -> &World {
+        self.world
+    }
+}
+
+impl std::fmt::Debug for WorldView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldView")
+            .field("entities", &self.world.entities().count())
+            .finish()
+    }
+}
+
+impl World {
+    /// Returns a read-only view of the world, see [`WorldView`].
+    pub fn view(&self) -> WorldView<'_> {
+        WorldView::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name(&'static str);
+    impl Component for Name {}
+
+    #[test]
+    fn test_view_reads_world() {
+        let mut world = World::new();
+        let goblin = world.spawn_entity();
+        world.add_component(goblin, Name("goblin")).unwrap();
+        world.insert_resource(Name("Midgard"));
+
+        let view = world.view();
+        let names: Vec<_> = view.query::<Name>().map(|(_, name)| name.0).collect();
+
+        assert_eq!(names, vec!["goblin"]);
+        assert!(view.has_component::<Name>(goblin));
+        assert_eq!(view.get_resource::<Name>(), Some(&Name("Midgard")));
+        assert_eq!(view.entities().count(), 1);
+        assert!(std::ptr::eq(view.world(), &world));
+    }
+
+    #[cfg(feature = "thread-safe-components")]
+    #[test]
+    fn test_views_answer_queries_from_several_threads() {
+        let mut world = World::new();
+        for name in ["goblin", "orc", "troll"] {
+            let entity = world.spawn_entity();
+            world.add_component(entity, Name(name)).unwrap();
+        }
+
+        let view = world.view();
+        let counts: Vec<usize> = std::thread::scope(|scope| {
+            let readers: Vec<_> = (0..4)
+                .map(|_| scope.spawn(move || Query::<Name>::new().iter(&view).count()))
+                .collect();
+            readers
+                .into_iter()
+                .map(|reader| reader.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(counts, vec![3; 4]);
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+
+use crate::{
+    Component, ComponentRegistry, Entity, SequentialSystemScheduler, TransferError, World,
+};
+
+/// Identifies a world managed by a [`WorldRegistry`].
+///
+/// Ids are never reused, so an id of a removed world stays invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldId(u32);
+
+impl std::fmt::Display for WorldId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "world#{}", self.0)
+    }
+}
+
+/// An entity together with the world it lives in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GlobalEntityRef {
+    pub world: WorldId,
+    pub entity: Entity,
+}
+
+impl GlobalEntityRef {
+    /// Creates a reference to `entity` in `world`.
+    pub fn new(world: WorldId, entity: Entity) -> Self {
+        Self { world, entity }
+    }
+}
+
+/// Errors that can occur when working with a [`WorldRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldRegistryError {
+    /// Another world already uses this name.
+    DuplicateName { name: String },
+    /// The world id doesn't belong to the registry or the world was removed.
+    WorldNotFound { world: WorldId },
+    /// Moving an entity between worlds failed.
+    Transfer(TransferError),
+}
+
+impl std::fmt::Display for WorldRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorldRegistryError::DuplicateName { name } => {
+                write!(f, "a world named {name} already exists")
+            }
+            WorldRegistryError::WorldNotFound { world } => write!(f, "{world} does not exist"),
+            WorldRegistryError::Transfer(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for WorldRegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WorldRegistryError::Transfer(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransferError> for WorldRegistryError {
+    fn from(error: TransferError) -> Self {
+        WorldRegistryError::Transfer(error)
+    }
+}
+
+struct NamedWorld {
+    name: String,
+    world: World,
+}
+
+/// Owns several named worlds, e.g. one per zone of a sharded server.
+///
+/// Entities are only meaningful in the world that spawned them, so code that
+/// deals with more than one world passes [`GlobalEntityRef`]s around and lets
+/// the registry route each access to the right world.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, ComponentRegistry, GlobalEntityRef, WorldRegistry};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Player { name: String }
+/// impl Component for Player {}
+///
+/// let mut registry = WorldRegistry::new();
+/// let town = registry.create_world("zone:town").unwrap();
+/// let dungeon = registry.create_world("zone:dungeon").unwrap();
+///
+/// let world = registry.world_mut(town).unwrap();
+/// let alice = world.spawn_entity();
+/// world.add_component(alice, Player { name: "Alice".to_string() }).unwrap();
+/// let alice = GlobalEntityRef::new(town, alice);
+///
+/// let components = ComponentRegistry::new().with::<Player>();
+/// let alice = registry.transfer(alice, dungeon, &components).unwrap();
+/// assert_eq!(alice.world, dungeon);
+/// assert_eq!(registry.get_component::<Player>(alice).unwrap().name, "Alice");
+/// ```
+#[derive(Default)]
+pub struct WorldRegistry {
+    worlds: Vec<Option<NamedWorld>>, // indexed by WorldId
+    ids_by_name: HashMap<String, WorldId>,
+}
+
+impl std::fmt::Debug for WorldRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.worlds.iter().enumerate().filter_map(|(index, named)| {
+                named
+                    .as_ref()
+                    .map(|named| (WorldId(index as u32), &named.name))
+            }))
+            .finish()
+    }
+}
+
+impl WorldRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty world called `name`.
+    ///
+    /// # Returns
+    /// * `Ok(WorldId)` - The id of the new world
+    /// * `Err(WorldRegistryError::DuplicateName)` if a world with that name exists
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` worlds are created.
+    pub fn create_world(&mut self, name: impl Into<String>) -> Result<WorldId, WorldRegistryError> {
+        self.insert_world(name, World::new())
+    }
+
+    /// Adds an existing world under `name`.
+    ///
+    /// # Returns
+    /// * `Ok(WorldId)` - The id of the added world
+    /// * `Err(WorldRegistryError::DuplicateName)` if a world with that name exists
+    pub fn insert_world(
+        &mut self,
+        name: impl Into<String>,
+        world: World,
+    ) -> Result<WorldId, WorldRegistryError> {
+        let name = name.into();
+        if self.ids_by_name.contains_key(&name) {
+            return Err(WorldRegistryError::DuplicateName { name });
+        }
+
+        let id = WorldId(u32::try_from(self.worlds.len()).expect("WorldRegistry ran out of ids"));
+        self.ids_by_name.insert(name.clone(), id);
+        self.worlds.push(Some(NamedWorld { name, world }));
+        Ok(id)
+    }
+
+    /// Removes a world from the registry and returns it.
+    pub fn remove_world(&mut self, id: WorldId) -> Option<World> {
+        let named = self.worlds.get_mut(id.0 as usize)?.take()?;
+        self.ids_by_name.remove(&named.name);
+        Some(named.world)
+    }
+
+    /// Returns the id of the world called `name`.
+    pub fn world_id(&self, name: &str) -> Option<WorldId> {
+        self.ids_by_name.get(name).copied()
+    }
+
+    /// Returns the name of a world.
+    pub fn name(&self, id: WorldId) -> Option<&str> {
+        self.named(id).map(|named| named.name.as_str())
+    }
+
+    /// Returns a world by id.
+    pub fn world(&self, id: WorldId) -> Option<&World> {
+        self.named(id).map(|named| &named.world)
+    }
+
+    /// Returns a world by id for modification.
+    pub fn world_mut(&mut self, id: WorldId) -> Option<&mut World> {
+        self.worlds
+            .get_mut(id.0 as usize)?
+            .as_mut()
+            .map(|named| &mut named.world)
+    }
+
+    /// Returns the number of worlds.
+    pub fn len(&self) -> usize {
+        self.ids_by_name.len()
+    }
+
+    /// Returns `true` if the registry has no worlds.
+    pub fn is_empty(&self) -> bool {
+        self.ids_by_name.is_empty()
+    }
+
+    /// Returns the ids of all worlds, in creation order.
+    pub fn ids(&self) -> impl Iterator<Item = WorldId> + '_ {
+        self.iter().map(|(id, _)| id)
+    }
+
+    /// Returns all worlds with their ids, in creation order.
+    pub fn iter(&self) -> impl Iterator<Item = (WorldId, &World)> {
+        self.worlds.iter().enumerate().filter_map(|(index, named)| {
+            named
+                .as_ref()
+                .map(|named| (WorldId(index as u32), &named.world))
+        })
+    }
+
+    /// Returns all worlds with their ids for modification, in creation order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (WorldId, &mut World)> {
+        self.worlds
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, named)| {
+                named
+                    .as_mut()
+                    .map(|named| (WorldId(index as u32), &mut named.world))
+            })
+    }
+
+    /// Returns `true` if the referenced entity is alive in its world.
+    pub fn contains(&self, entity: GlobalEntityRef) -> bool {
+        self.world(entity.world)
+            .is_some_and(|world| world.entities().any(|&e| e == entity.entity))
+    }
+
+    /// Gets a component of an entity in whichever world it lives in.
+    ///
+    /// Returns `None` if the world doesn't exist or the entity doesn't have the
+    /// component, exactly like [`World::get_component`].
+    pub fn get_component<T: Component>(&self, entity: GlobalEntityRef) -> Option<&T> {
+        self.world(entity.world)?.get_component::<T>(entity.entity)
+    }
+
+    /// Runs one tick of `scheduler` on a single world.
+    ///
+    /// # Returns
+    /// `false` if the world doesn't exist
+    pub fn run_tick(&mut self, id: WorldId, scheduler: &SequentialSystemScheduler) -> bool {
+        match self.world_mut(id) {
+            Some(world) => {
+                scheduler.run_tick(world);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs one tick of the same scheduler on every world, in creation order.
+    ///
+    /// Each world ticks independently: systems only ever see the world they
+    /// are running on.
+    pub fn run_tick_all(&mut self, scheduler: &SequentialSystemScheduler) {
+        for (_, world) in self.iter_mut() {
+            scheduler.run_tick(world);
+        }
+    }
+
+    /// Runs one tick on every world with the scheduler chosen by `scheduler_for`.
+    ///
+    /// Worlds for which `scheduler_for` returns `None` are skipped. Useful when
+    /// zones run different sets of systems.
+    pub fn run_tick_each<'s, F>(&mut self, mut scheduler_for: F)
+    where
+        F: FnMut(WorldId) -> Option<&'s SequentialSystemScheduler>,
+    {
+        for (id, world) in self.iter_mut() {
+            if let Some(scheduler) = scheduler_for(id) {
+                scheduler.run_tick(world);
+            }
+        }
+    }
+
+    /// Moves an entity with all of its components into another world.
+    ///
+    /// Uses [`World::transfer_entity`], so every component of the entity must be
+    /// registered in `components`. Moving an entity to the world it already
+    /// lives in leaves it untouched.
+    ///
+    /// # Returns
+    /// * `Ok(GlobalEntityRef)` - The entity's new reference in `target`
+    /// * `Err(WorldRegistryError::WorldNotFound)` if either world doesn't exist
+    /// * `Err(WorldRegistryError::Transfer)` if the transfer itself failed
+    pub fn transfer(
+        &mut self,
+        entity: GlobalEntityRef,
+        target: WorldId,
+        components: &ComponentRegistry,
+    ) -> Result<GlobalEntityRef, WorldRegistryError> {
+        for world in [entity.world, target] {
+            if self.world(world).is_none() {
+                return Err(WorldRegistryError::WorldNotFound { world });
+            }
+        }
+
+        let (source, target_world) = self.two_worlds_mut(entity.world, target);
+        let Some(target_world) = target_world else {
+            if !source.entities().any(|&e| e == entity.entity) {
+                return Err(TransferError::EntityNotFound {
+                    entity: entity.entity,
+                }
+                .into());
+            }
+            return Ok(entity);
+        };
+
+        let moved = source.transfer_entity(entity.entity, target_world, components)?;
+        Ok(GlobalEntityRef::new(target, moved))
+    }
+
+    fn named(&self, id: WorldId) -> Option<&NamedWorld> {
+        self.worlds.get(id.0 as usize)?.as_ref()
+    }
+
+    /// Borrows two existing worlds at once; the second is `None` if both ids match.
+    fn two_worlds_mut(
+        &mut self,
+        first: WorldId,
+        second: WorldId,
+    ) -> (&mut World, Option<&mut World>) {
+        let (first, second) = (first.0 as usize, second.0 as usize);
+        if first == second {
+            return (self.world_slot_mut(first), None);
+        }
+
+        let (low, high) = self.worlds.split_at_mut(first.max(second));
+        let low = &mut low[first.min(second)].as_mut().expect("world exists").world;
+        let high = &mut high[0].as_mut().expect("world exists").world;
+        if first < second {
+            (low, Some(high))
+        } else {
+            (high, Some(low))
+        }
+    }
+
+    fn world_slot_mut(&mut self, index: usize) -> &mut World {
+        &mut self.worlds[index].as_mut().expect("world exists").world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name(String);
+    impl Component for Name {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Secret;
+    impl Component for Secret {}
+
+    #[test]
+    fn test_create_and_look_up_worlds() {
+        let mut registry = WorldRegistry::new();
+        let town = registry.create_world("zone:town").unwrap();
+        let forest = registry.create_world("zone:forest").unwrap();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.world_id("zone:forest"), Some(forest));
+        assert_eq!(registry.name(town), Some("zone:town"));
+        assert_eq!(registry.ids().collect::<Vec<_>>(), vec![town, forest]);
+        assert_eq!(
+            registry.create_world("zone:town"),
+            Err(WorldRegistryError::DuplicateName {
+                name: "zone:town".to_string()
+            })
+        );
+
+        assert!(registry.remove_world(town).is_some());
+        assert!(registry.world(town).is_none());
+        assert_eq!(registry.world_id("zone:town"), None);
+        assert_eq!(registry.len(), 1);
+
+        // The name is free again but the old id is not reused
+        let rebuilt = registry.create_world("zone:town").unwrap();
+        assert_ne!(rebuilt, town);
+    }
+
+    #[test]
+    fn test_transfer_between_worlds() {
+        let mut registry = WorldRegistry::new();
+        let town = registry.create_world("zone:town").unwrap();
+        let forest = registry.create_world("zone:forest").unwrap();
+        let components = ComponentRegistry::new().with::<Name>();
+
+        let world = registry.world_mut(forest).unwrap();
+        let bob = world.spawn_entity();
+        world.add_component(bob, Name("Bob".to_string())).unwrap();
+        let bob = GlobalEntityRef::new(forest, bob);
+
+        // Moving "backwards" to a lower id works too
+        let moved = registry.transfer(bob, town, &components).unwrap();
+        assert_eq!(moved.world, town);
+        assert!(registry.contains(moved));
+        assert!(!registry.contains(bob));
+        assert_eq!(
+            registry.get_component::<Name>(moved),
+            Some(&Name("Bob".to_string()))
+        );
+
+        assert_eq!(registry.transfer(moved, town, &components), Ok(moved));
+    }
+
+    #[test]
+    fn test_transfer_errors() {
+        let mut registry = WorldRegistry::new();
+        let town = registry.create_world("zone:town").unwrap();
+        let forest = registry.create_world("zone:forest").unwrap();
+        let components = ComponentRegistry::new().with::<Name>();
+
+        let world = registry.world_mut(town).unwrap();
+        let spy = world.spawn_entity();
+        world.add_component(spy, Secret).unwrap();
+        let spy = GlobalEntityRef::new(town, spy);
+
+        let result = registry.transfer(spy, forest, &components);
+        assert!(matches!(
+            result,
+            Err(WorldRegistryError::Transfer(
+                TransferError::UnregisteredComponents { .. }
+            ))
+        ));
+        assert!(registry.contains(spy));
+
+        registry.remove_world(forest);
+        assert_eq!(
+            registry.transfer(spy, forest, &components),
+            Err(WorldRegistryError::WorldNotFound { world: forest })
+        );
+
+        let ghost = GlobalEntityRef::new(town, Entity::PLACEHOLDER);
+        assert!(matches!(
+            registry.transfer(ghost, town, &components),
+            Err(WorldRegistryError::Transfer(
+                TransferError::EntityNotFound { .. }
+            ))
+        ));
+    }
+}
@@ -228,14 +228,11 @@ fn test_component_lifecycle_error_conditions() {
         .unwrap();
 
     let result = world.add_component(entity, Position { x: 2.0, y: 2.0 });
-    assert!(matches!(
-        result,
-        Err(ComponentError::ComponentAlreadyExists)
-    ));
+    assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
 
     // Try to update non-existent component
     let result = world.update_component::<Velocity, _>(entity, |vel| vel);
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::NotFound { .. })));
 
     // Try to remove non-existent component
     let result = world.remove_component::<Health>(entity);
@@ -245,10 +242,10 @@ fn test_component_lifecycle_error_conditions() {
     world.delete_entity(entity);
 
     let result = world.add_component(entity, Velocity { x: 1.0, y: 1.0 });
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     let result = world.update_component::<Position, _>(entity, |pos| pos);
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     assert!(world.get_component::<Position>(entity).is_none());
     assert!(!world.has_component::<Position>(entity));
@@ -432,3 +429,71 @@ fn test_component_state_consistency() {
     // Velocity was toggled at i=7 (added) and i=14 (removed): 2 times, so should NOT exist
     assert!(!world.has_component::<Velocity>(entity));
 }
+
+#[test]
+fn test_ephemeral_only_component_is_rejected_by_add_component() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct FootstepEvent;
+    impl Component for FootstepEvent {
+        fn ephemeral_only() -> bool {
+            true
+        }
+    }
+
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+
+    let result = world.add_component(entity, FootstepEvent);
+    assert!(matches!(
+        result,
+        Err(ComponentError::EphemeralOnly { entity: e, .. }) if e == entity
+    ));
+    assert!(!world.has_component::<FootstepEvent>(entity));
+
+    let batch = world.add_component_batch(&[entity], FootstepEvent);
+    assert_eq!(batch.added, 0);
+    assert_eq!(batch.failures.len(), 1);
+
+    world
+        .add_ephemeral_component(entity, FootstepEvent)
+        .unwrap();
+    assert!(world.has_ephemeral_component::<FootstepEvent>(entity));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derived_components_lifecycle() {
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Mana {
+        current: u32,
+    }
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(ephemeral_only)]
+    struct SpellCast {
+        cost: u32,
+    }
+
+    let mut world = World::new();
+    let wizard = world.spawn_entity();
+    world.add_component(wizard, Mana { current: 10 }).unwrap();
+    world
+        .add_ephemeral_component(wizard, SpellCast { cost: 4 })
+        .unwrap();
+
+    let cost = world
+        .get_ephemeral_component::<SpellCast>(wizard)
+        .unwrap()
+        .cost;
+    world
+        .update_component::<Mana, _>(wizard, |mana| Mana {
+            current: mana.current - cost,
+        })
+        .unwrap();
+
+    assert_eq!(
+        world.get_component::<Mana>(wizard),
+        Some(&Mana { current: 6 })
+    );
+    assert!(world.add_component(wizard, SpellCast { cost: 1 }).is_err());
+}
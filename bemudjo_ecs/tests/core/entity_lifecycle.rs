@@ -3,7 +3,7 @@
 //! Tests focused on entity creation, deletion, and management
 //! operations across the entity lifecycle.
 
-use bemudjo_ecs::{Component, World};
+use bemudjo_ecs::{Component, Entity, EntityAllocator, Query, World};
 
 // Test Components
 #[derive(Clone, Debug, PartialEq)]
@@ -443,3 +443,121 @@ fn test_entity_iteration_consistency() {
     assert_eq!(final_entities.len(), 7);
     assert_eq!(final_entities, remaining_entities);
 }
+
+#[test]
+fn test_placeholder_entity_operations_are_no_ops() {
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+    world.add_component(entity, Health { value: 10 }).unwrap();
+    let generation = world.generation();
+    let placeholder = Entity::PLACEHOLDER;
+
+    // Reads
+    assert_eq!(world.get_component::<Health>(placeholder), None);
+    assert_eq!(
+        world.get_component_including_deleted::<Health>(placeholder),
+        None
+    );
+    assert!(!world.has_component::<Health>(placeholder));
+    assert!(!world.has_any_component(placeholder));
+    assert!(world.entity_component_types(placeholder).is_empty());
+    assert!(!world.was_deleted_this_tick(placeholder));
+    assert_eq!(world.label_of(placeholder), None);
+    assert_eq!(world.get_ephemeral_component::<Health>(placeholder), None);
+    assert!(!world.has_ephemeral_component::<Health>(placeholder));
+
+    // Writes fail or do nothing
+    assert!(world
+        .add_component(placeholder, Health { value: 1 })
+        .is_err());
+    assert!(world
+        .add_ephemeral_component(placeholder, Health { value: 1 })
+        .is_err());
+    assert!(world
+        .update_component::<Health, _>(placeholder, |health| health)
+        .is_err());
+    assert_eq!(
+        world.replace_component(placeholder, Health { value: 1 }),
+        None
+    );
+    assert!(world
+        .add_or_replace_component(placeholder, Health { value: 1 })
+        .is_err());
+    assert_eq!(world.remove_component::<Health>(placeholder), None);
+    assert!(world.set_entity_label(placeholder, "nobody").is_err());
+    let batch = world.add_component_batch(&[placeholder], Health { value: 1 });
+    assert_eq!(batch.added, 0);
+    assert_eq!(world.remove_component_batch::<Health>(&[placeholder]), 0);
+    assert!(world.extract_entity(placeholder).is_none());
+    world.delete_entity(placeholder);
+    world.cleanup_deleted_entities();
+
+    // Nothing changed
+    assert_eq!(world.generation(), generation);
+    assert_eq!(world.entities().count(), 1);
+    assert_eq!(Query::<Health>::new().iter(&world).count(), 1);
+    assert_eq!(
+        world.get_component::<Health>(entity),
+        Some(&Health { value: 10 })
+    );
+}
+
+#[test]
+fn test_placeholder_entity_is_never_spawned() {
+    let mut world = World::new();
+
+    for _ in 0..1000 {
+        let entity = world.spawn_entity();
+        assert_ne!(entity, Entity::PLACEHOLDER);
+        assert!(!entity.is_placeholder());
+        world.delete_entity(entity);
+        world.cleanup_deleted_entities();
+    }
+}
+
+#[test]
+fn test_deterministic_worlds_replay_identical_entity_ids() {
+    fn script() -> Vec<Entity> {
+        // Global allocations in between must not shift the ids
+        let mut unrelated = World::new();
+        unrelated.spawn_entity();
+
+        let mut world = World::new_with_allocator(EntityAllocator::Deterministic);
+        let mut spawned = Vec::new();
+        for round in 0..5 {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    Position {
+                        x: round as f32,
+                        y: 0.0,
+                    },
+                )
+                .unwrap();
+            spawned.push(entity);
+            if round % 2 == 0 {
+                world.delete_entity(entity);
+                world.cleanup_deleted_entities();
+            }
+        }
+        spawned
+    }
+
+    let first = script();
+    assert_eq!(first, script());
+    assert_eq!(
+        first.iter().map(Entity::to_string).collect::<Vec<_>>(),
+        // Deleted ids come back with a new generation
+        vec!["1", "1v1", "2", "2v1", "3"]
+    );
+}
+
+#[test]
+fn test_global_allocator_stays_the_default() {
+    let mut first = World::new();
+    let mut second = World::new_with_allocator(EntityAllocator::Global);
+
+    assert_eq!(first.entity_allocator(), EntityAllocator::Global);
+    assert_ne!(first.spawn_entity(), second.spawn_entity());
+}
@@ -139,7 +139,7 @@ fn test_world_component_updates() {
 
         assert!(result.is_ok());
         let updated = result.unwrap();
-        assert_eq!(updated.value, (1..=i).sum()); // Sum of 1+2+...+i
+        assert_eq!(updated.value, (1..=i).sum::<i64>()); // Sum of 1+2+...+i
     }
 
     let final_counter = world.get_component::<Counter>(entity).unwrap();
@@ -300,10 +300,7 @@ fn test_world_error_handling() {
 
     // Try to add duplicate
     let result = world.add_component(entity, Position { x: 2.0, y: 2.0 });
-    assert!(matches!(
-        result,
-        Err(ComponentError::ComponentAlreadyExists)
-    ));
+    assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
 
     // Original component should be unchanged
     let pos = world.get_component::<Position>(entity).unwrap();
@@ -314,10 +311,10 @@ fn test_world_error_handling() {
 
     // Operations on deleted entity should fail
     let result = world.add_component(entity, Velocity { x: 1.0, y: 1.0 });
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     let result = world.update_component::<Position, _>(entity, |pos| pos);
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     assert!(world.get_component::<Position>(entity).is_none());
     assert!(!world.has_component::<Position>(entity));
@@ -5,9 +5,11 @@
 //! - Component CRUD operations
 //! - World state consistency
 //! - Core API integration
+//! - World statistics and storage leak detection
 
 pub mod component_lifecycle;
 pub mod entity_lifecycle;
 pub mod ephemeral_component_integration;
 pub mod integration_test;
 pub mod world_operations;
+pub mod world_stats;
@@ -3,7 +3,7 @@
 //! These tests validate the public API and realistic usage patterns
 //! by testing the library as an external user would.
 
-use bemudjo_ecs::{Component, ComponentError, SequentialSystemScheduler, System, World};
+use bemudjo_ecs::{Component, ComponentError, SequentialSystemScheduler, System, World, WorldView};
 
 // Test Components
 #[derive(Clone, Debug, PartialEq)]
@@ -95,14 +95,14 @@ impl LoggingSystem {
 }
 
 impl System for LoggingSystem {
-    fn before_run(&self, world: &World) {
+    fn before_run(&self, world: &WorldView) {
         let entity_count = world.entities().count();
         self.log_entries
             .borrow_mut()
             .push(format!("BEFORE: {entity_count} entities"));
     }
 
-    fn after_run(&self, world: &World) {
+    fn after_run(&self, world: &WorldView) {
         let entity_count = world.entities().count();
         self.log_entries
             .borrow_mut()
@@ -212,10 +212,7 @@ fn test_component_lifecycle() {
 
     // Try to add duplicate component (should fail)
     let result = world.add_component(entity, Position { x: 3.0, y: 4.0 });
-    assert!(matches!(
-        result,
-        Err(ComponentError::ComponentAlreadyExists)
-    ));
+    assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
 
     // Replace component
     let old_pos = world.replace_component(entity, Position { x: 5.0, y: 6.0 });
@@ -390,8 +387,12 @@ fn test_system_execution_order() {
 
     // Add systems in specific order
     scheduler.add_system(OrderTrackingSystem { id: 1 }).unwrap();
-    scheduler.add_system(OrderTrackingSystem { id: 2 }).unwrap();
-    scheduler.add_system(OrderTrackingSystem { id: 3 }).unwrap();
+    scheduler
+        .add_system_allow_duplicates(OrderTrackingSystem { id: 2 })
+        .unwrap();
+    scheduler
+        .add_system_allow_duplicates(OrderTrackingSystem { id: 3 })
+        .unwrap();
 
     scheduler.build().unwrap();
     assert_eq!(scheduler.system_count(), 3);
@@ -551,7 +552,7 @@ fn test_error_handling() {
 
     // These should all fail gracefully
     let result = world.add_component(fake_entity, Position { x: 0.0, y: 0.0 });
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     assert!(world.get_component::<Position>(fake_entity).is_none());
     assert!(!world.has_component::<Position>(fake_entity));
@@ -563,7 +564,7 @@ fn test_error_handling() {
     let update_result = world.update_component::<Position, _>(fake_entity, |pos| pos);
     assert!(matches!(
         update_result,
-        Err(ComponentError::ComponentNotFound)
+        Err(ComponentError::EntityNotFound { .. })
     ));
 
     // Test operations on deleted entity
@@ -581,7 +582,7 @@ fn test_error_handling() {
             max: 100,
         },
     );
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     assert!(world.get_component::<Position>(entity).is_none());
     assert!(!world.has_component::<Position>(entity));
@@ -593,7 +594,7 @@ fn test_error_handling() {
     let update_result = world.update_component::<Position, _>(entity, |pos| pos);
     assert!(matches!(
         update_result,
-        Err(ComponentError::ComponentNotFound)
+        Err(ComponentError::EntityNotFound { .. })
     ));
 }
 
@@ -0,0 +1,111 @@
+//! World Statistics Integration Tests
+//!
+//! Tests that `World::stats()` and `World::storage_len()` reflect entity
+//! deletion and cleanup, so leaked storage entries are caught.
+
+use bemudjo_ecs::{Component, Query, SequentialSystemScheduler, System, World};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+impl Component for Position {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Health {
+    value: u32,
+}
+impl Component for Health {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Hit {
+    damage: u32,
+}
+impl Component for Hit {}
+
+fn spawn_many(world: &mut World, count: usize) -> Vec<bemudjo_ecs::Entity> {
+    (0..count)
+        .map(|i| {
+            world
+                .spawn_bundle((
+                    Position {
+                        x: i as f32,
+                        y: 0.0,
+                    },
+                    Health { value: 100 },
+                ))
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_stats_reflect_manual_cleanup() {
+    let mut world = World::new();
+    let entities = spawn_many(&mut world, 100);
+
+    for entity in entities.iter().take(40) {
+        world.delete_entity(*entity);
+    }
+
+    // Deleted data is still stored until cleanup
+    let before = world.stats();
+    assert_eq!(before.entity_count, 60);
+    assert_eq!(before.soft_deleted_count, 40);
+    assert_eq!(before.total_component_entries(), 200);
+    assert_eq!(world.storage_len::<Position>(), 100);
+    assert_eq!(Query::<Position>::new().iter(&world).count(), 60);
+
+    world.cleanup_deleted_entities();
+
+    let after = world.stats();
+    assert_eq!(after.entity_count, 60);
+    assert_eq!(after.soft_deleted_count, 0);
+    assert_eq!(after.component_type_count, 2);
+    assert_eq!(after.total_component_entries(), 120);
+    assert_eq!(world.storage_len::<Position>(), 60);
+    assert_eq!(world.storage_len::<Health>(), 60);
+    assert_eq!(
+        after.total_approx_bytes(),
+        60 * (std::mem::size_of::<Position>() + std::mem::size_of::<Health>())
+    );
+}
+
+#[test]
+fn test_no_storage_leak_over_many_ticks() {
+    /// Spawns a batch of short-lived entities each tick and kills last tick's batch.
+    struct ChurnSystem;
+    impl System for ChurnSystem {
+        fn run(&self, world: &mut World) {
+            let previous: Vec<_> = Query::<Health>::new()
+                .iter(world)
+                .map(|(entity, _)| entity)
+                .collect();
+            for entity in previous {
+                world
+                    .add_ephemeral_component(entity, Hit { damage: 1 })
+                    .unwrap();
+                world.delete_entity(entity);
+            }
+            spawn_many(world, 50);
+        }
+    }
+
+    let mut world = World::new();
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler.add_system(ChurnSystem).unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..20 {
+        scheduler.run_tick(&mut world);
+
+        let stats = world.stats();
+        assert_eq!(stats.entity_count, 50);
+        assert_eq!(stats.soft_deleted_count, 0);
+        assert_eq!(stats.ephemeral_component_count, 0);
+        assert_eq!(world.storage_len::<Position>(), 50);
+        assert_eq!(world.storage_len::<Health>(), 50);
+        assert_eq!(world.storage_len::<Hit>(), 0);
+    }
+}
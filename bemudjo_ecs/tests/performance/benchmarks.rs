@@ -523,7 +523,9 @@ fn benchmark_scheduler_operations() {
                     }
                 }
 
-                scheduler.add_system(BenchmarkSystem { id: i }).unwrap();
+                scheduler
+                    .add_system_allow_duplicates(BenchmarkSystem { id: i })
+                    .unwrap();
             }
 
             scheduler.build().unwrap();
@@ -545,7 +547,7 @@ fn benchmark_scheduler_operations() {
             }
         }
 
-        scheduler.add_system(EmptySystem).unwrap();
+        scheduler.add_system_allow_duplicates(EmptySystem).unwrap();
     }
 
     scheduler.build().unwrap();
@@ -713,6 +715,141 @@ fn benchmark_memory_efficiency() {
     assert_eq!(world.entities().count(), 10_000);
 }
 
+#[test]
+fn benchmark_bulk_spawn_with_reservation() {
+    const COUNT: usize = 20_000;
+
+    fn spawn_all(world: &mut World) {
+        for i in 0..COUNT {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    Position {
+                        x: i as f32,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                )
+                .unwrap();
+            world
+                .add_component(
+                    entity,
+                    Health {
+                        current: 100,
+                        max: 100,
+                    },
+                )
+                .unwrap();
+        }
+    }
+
+    let mut unreserved_world = World::new();
+    let unreserved = benchmark_operation(
+        "Bulk spawn 20,000 entities without reservation",
+        || spawn_all(&mut unreserved_world),
+        500, // 500ms max
+    );
+
+    let mut reserved_world = World::new();
+    let reserved = benchmark_operation(
+        "Bulk spawn 20,000 entities with reservation",
+        || {
+            reserved_world.reserve_entities(COUNT);
+            reserved_world.reserve::<Position>(COUNT);
+            reserved_world.reserve::<Health>(COUNT);
+            spawn_all(&mut reserved_world);
+        },
+        500, // 500ms max
+    );
+
+    println!(
+        "Reservation speedup: {:.2}x",
+        unreserved.as_secs_f64() / reserved.as_secs_f64()
+    );
+
+    // Reservation is a pure performance hint - both worlds end up identical in content
+    assert_eq!(unreserved_world.entities().count(), COUNT);
+    assert_eq!(reserved_world.entities().count(), COUNT);
+    assert_eq!(
+        Query::<Position>::new().iter(&reserved_world).count(),
+        COUNT
+    );
+    assert_eq!(Query::<Health>::new().iter(&reserved_world).count(), COUNT);
+}
+
+#[test]
+fn benchmark_spatial_index_scaling() {
+    use bemudjo_ecs::spatial::{SpatialComponent, SpatialGrid, SpatialIndexSystem};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct MapPosition {
+        x: f32,
+        y: f32,
+    }
+    impl Component for MapPosition {}
+    impl SpatialComponent for MapPosition {
+        fn x(&self) -> f32 {
+            self.x
+        }
+        fn y(&self) -> f32 {
+            self.y
+        }
+    }
+
+    // Constant density: the map grows with the entity count
+    fn neighbor_pass(count: usize) -> (Duration, usize) {
+        let side = (count as f32).sqrt() * 10.0;
+        let mut world = World::new();
+        for i in 0..count {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    MapPosition {
+                        x: (i * 7919 % count) as f32 / count as f32 * side,
+                        y: (i * 104_729 % count) as f32 / count as f32 * side,
+                    },
+                )
+                .unwrap();
+        }
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler
+            .add_system(SpatialIndexSystem::<MapPosition>::new(15.0))
+            .unwrap();
+        scheduler.build().unwrap();
+
+        let start = Instant::now();
+        scheduler.run_tick(&mut world);
+        let grid = world.get_resource::<SpatialGrid<MapPosition>>().unwrap();
+        let neighbors: usize = Query::<MapPosition>::new()
+            .iter(&world)
+            .map(|(_, p)| grid.entities_in_radius((p.x, p.y), 15.0).len())
+            .sum();
+        (start.elapsed(), neighbors)
+    }
+
+    let (small, small_neighbors) = neighbor_pass(2_500);
+    let (large, large_neighbors) = neighbor_pass(10_000);
+
+    println!("Spatial neighbor pass, 2,500 entities: {small:?}");
+    println!("Spatial neighbor pass, 10,000 entities: {large:?}");
+    assert!(small_neighbors >= 2_500 && large_neighbors >= 10_000);
+
+    // 4x entities: quadratic scanning would cost ~16x, the grid stays near-linear
+    let ratio = large.as_secs_f64() / small.as_secs_f64();
+    println!("Scaling ratio for 4x entities: {ratio:.2}x");
+    assert!(
+        ratio < 10.0,
+        "Spatial queries scaled super-linearly: {ratio:.2}x for 4x entities"
+    );
+    assert!(
+        large.as_millis() < 1_000,
+        "10,000 entity pass took {large:?}"
+    );
+}
+
 #[test]
 fn benchmark_regression_prevention() {
     // This test establishes performance baselines to prevent regressions
@@ -806,3 +943,456 @@ fn benchmark_regression_prevention() {
     assert!(tick_duration.as_millis() <= 30);
     assert!(query_duration.as_millis() <= 10);
 }
+
+#[test]
+fn benchmark_iter_components_vs_query() {
+    const COUNT: usize = 50_000;
+    let mut world = World::new();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+        if i % 10 == 0 {
+            world.delete_entity(entity);
+        }
+    }
+
+    // Integer sums, so iteration order can't affect the result
+    let mut query_sum = 0u64;
+    let query_time = benchmark_operation(
+        "Query::iter over 50,000 positions",
+        || {
+            query_sum = Query::<Position>::new()
+                .iter(&world)
+                .map(|(_, p)| p.x as u64)
+                .sum()
+        },
+        500, // 500ms max
+    );
+
+    let mut direct_sum = 0u64;
+    let direct_time = benchmark_operation(
+        "World::iter_components over 50,000 positions",
+        || {
+            direct_sum = world
+                .iter_components::<Position>()
+                .map(|(_, p)| p.x as u64)
+                .sum()
+        },
+        500, // 500ms max
+    );
+
+    println!(
+        "iter_components speedup: {:.2}x",
+        query_time.as_secs_f64() / direct_time.as_secs_f64()
+    );
+
+    // Both paths see exactly the same live components
+    assert_eq!(query_sum, direct_sum);
+    assert_eq!(
+        world.iter_components::<Position>().count(),
+        COUNT - COUNT / 10
+    );
+}
+
+#[test]
+fn benchmark_add_component_batch_vs_singles() {
+    const COUNT: usize = 10_000;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Wet;
+    impl Component for Wet {}
+
+    fn spawn_many(world: &mut World) -> Vec<bemudjo_ecs::Entity> {
+        (0..COUNT).map(|_| world.spawn_entity()).collect()
+    }
+
+    let mut single_world = World::new();
+    let single_entities = spawn_many(&mut single_world);
+    let single_time = benchmark_operation(
+        "add_component over 10,000 entities",
+        || {
+            for &entity in &single_entities {
+                single_world.add_component(entity, Wet).unwrap();
+            }
+        },
+        1000, // 1s max
+    );
+
+    let mut batch_world = World::new();
+    let batch_entities = spawn_many(&mut batch_world);
+    let mut result = None;
+    let batch_time = benchmark_operation(
+        "add_component_batch over 10,000 entities",
+        || result = Some(batch_world.add_component_batch(&batch_entities, Wet)),
+        1000, // 1s max
+    );
+
+    println!(
+        "add_component_batch speedup: {:.2}x",
+        single_time.as_secs_f64() / batch_time.as_secs_f64()
+    );
+
+    let result = result.unwrap();
+    assert!(result.is_complete());
+    assert_eq!(result.added, COUNT);
+    assert_eq!(Query::<Wet>::new().iter(&batch_world).count(), COUNT);
+
+    let removed = batch_world.remove_component_batch::<Wet>(&batch_entities);
+    assert_eq!(removed, COUNT);
+    assert_eq!(Query::<Wet>::new().iter(&batch_world).count(), 0);
+}
+
+#[test]
+fn benchmark_cached_query_on_static_world() {
+    const COUNT: usize = 50_000;
+    const ITERATIONS: usize = 20;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    let mut world = World::new();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+        world
+            .add_component(
+                entity,
+                Velocity {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+        if i % 10 == 0 {
+            world.add_component(entity, Dead).unwrap();
+        }
+    }
+
+    let query = Query::<Position>::new()
+        .with::<Velocity>()
+        .without::<Dead>();
+    let cached = Query::<Position>::new()
+        .with::<Velocity>()
+        .without::<Dead>()
+        .cached();
+    let expected = COUNT - COUNT / 10;
+
+    let uncached_time = benchmark_operation(
+        "Uncached Position/Velocity/!Dead query, 20 ticks",
+        || {
+            for _ in 0..ITERATIONS {
+                assert_eq!(query.iter(&world).count(), expected);
+            }
+        },
+        5000, // 5s max
+    );
+
+    let cached_time = benchmark_operation(
+        "Cached Position/Velocity/!Dead query, 20 ticks",
+        || {
+            for _ in 0..ITERATIONS {
+                assert_eq!(cached.iter(&world).count(), expected);
+            }
+        },
+        5000, // 5s max
+    );
+
+    println!(
+        "CachedQuery speedup: {:.2}x",
+        uncached_time.as_secs_f64() / cached_time.as_secs_f64()
+    );
+
+    assert_eq!(cached.recompute_count(), 1);
+}
+
+#[test]
+fn benchmark_query_entities_vs_iter_on_markers() {
+    const COUNT: usize = 100_000;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    let mut world = World::new();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world.add_component(entity, Dead).unwrap();
+        if i % 2 == 0 {
+            world
+                .add_component(
+                    entity,
+                    Health {
+                        current: 0,
+                        max: 10,
+                    },
+                )
+                .unwrap();
+        }
+    }
+    let query = Query::<Dead>::new().with::<Health>();
+
+    let iter_time = benchmark_operation(
+        "Query::iter over 50,000 of 100,000 markers",
+        || assert_eq!(query.iter(&world).count(), COUNT / 2),
+        2000, // 2s max
+    );
+
+    let entities_time = benchmark_operation(
+        "Query::entities over 50,000 of 100,000 markers",
+        || assert_eq!(query.entities(&world).count(), COUNT / 2),
+        2000, // 2s max
+    );
+
+    println!(
+        "Query::entities speedup: {:.2}x",
+        iter_time.as_secs_f64() / entities_time.as_secs_f64()
+    );
+}
+
+#[test]
+fn benchmark_selective_with_filter_vs_full_scan() {
+    const COUNT: usize = 50_000;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    let mut world = World::new();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+        if i % 5_000 == 0 {
+            world.add_component(entity, Dead).unwrap();
+        }
+    }
+    let selective = Query::<Position>::new().with::<Dead>();
+    let full = Query::<Position>::new();
+
+    let selective_time = benchmark_operation(
+        "Query with a rare filter over 50,000 entities",
+        || assert_eq!(selective.iter(&world).count(), COUNT / 5_000),
+        2000, // 2s max
+    );
+
+    let full_time = benchmark_operation(
+        "Query without filters over 50,000 entities",
+        || assert_eq!(full.iter(&world).count(), COUNT),
+        2000, // 2s max
+    );
+
+    println!(
+        "Rare filter speedup over a full scan: {:.2}x",
+        full_time.as_secs_f64() / selective_time.as_secs_f64()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn benchmark_par_iter_on_100k_entities() {
+    use rayon::iter::ParallelIterator;
+
+    const COUNT: usize = 100_000;
+
+    let mut world = World::new();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+    }
+    let query = Query::<Position>::new();
+
+    // Enough work per entity for the thread pool to pay off
+    let score = |position: &Position| (0..200).fold(position.x, |acc, _| (acc * 1.0001).sqrt());
+
+    let mut sequential = 0.0;
+    benchmark_operation(
+        "Query::iter scoring 100,000 entities",
+        || sequential = query.iter(&world).map(|(_, p)| score(p)).sum::<f32>(),
+        5000, // 5s max
+    );
+
+    let mut parallel = 0.0;
+    benchmark_operation(
+        "Query::par_iter scoring 100,000 entities",
+        || parallel = query.par_iter(&world).map(|(_, p)| score(p)).sum::<f32>(),
+        5000, // 5s max
+    );
+
+    assert_eq!(query.par_iter(&world).count(), COUNT);
+    assert!((sequential - parallel).abs() <= sequential.abs() * 1e-3);
+}
+
+#[test]
+fn benchmark_update_component_in_place_vs_clone() {
+    const COUNT: usize = 1_000;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Inventory {
+        items: Vec<u64>,
+    }
+    impl Component for Inventory {}
+
+    // Separate worlds, so one side never pays for reallocations caused by the other
+    let inventories = || {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..COUNT)
+            .map(|_| {
+                let entity = world.spawn_entity();
+                let mut items = Vec::with_capacity(1_001);
+                items.extend(0..1_000);
+                world.add_component(entity, Inventory { items }).unwrap();
+                entity
+            })
+            .collect();
+        (world, entities)
+    };
+    let (mut cloned_world, entities) = inventories();
+    let (mut in_place_world, in_place_entities) = inventories();
+
+    let clone_time = benchmark_operation(
+        "update_component pushing into 1,000 inventories",
+        || {
+            for &entity in &entities {
+                cloned_world
+                    .update_component::<Inventory, _>(entity, |mut inventory| {
+                        inventory.items.push(1);
+                        inventory
+                    })
+                    .unwrap();
+            }
+        },
+        2000, // 2s max
+    );
+
+    let in_place_time = benchmark_operation(
+        "update_component_in_place pushing into 1,000 inventories",
+        || {
+            for &entity in &in_place_entities {
+                in_place_world
+                    .update_component_in_place::<Inventory, _>(entity, |inventory| {
+                        inventory.items.push(1)
+                    })
+                    .unwrap();
+            }
+        },
+        2000, // 2s max
+    );
+
+    println!(
+        "update_component_in_place speedup: {:.2}x",
+        clone_time.as_secs_f64() / in_place_time.as_secs_f64()
+    );
+    assert_eq!(
+        in_place_world.get_component::<Inventory>(in_place_entities[0]),
+        cloned_world.get_component::<Inventory>(entities[0])
+    );
+}
+
+#[test]
+fn benchmark_tag_filtering_vs_marker_components() {
+    const COUNT: usize = 100_000;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Player;
+    impl Component for Player {}
+
+    // The same markers once as components and once as tags
+    let mut world = World::new();
+    let dead = world.register_tag("dead").unwrap();
+    let player = world.register_tag("player").unwrap();
+    for i in 0..COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .unwrap();
+        if i % 2 == 0 {
+            world.add_component(entity, Dead).unwrap();
+            world.set_tag(entity, dead).unwrap();
+        }
+        if i % 4 < 2 {
+            world.add_component(entity, Player).unwrap();
+            world.set_tag(entity, player).unwrap();
+        }
+    }
+
+    let cases = [
+        (
+            "living",
+            Query::<Position>::new().without::<Dead>(),
+            Query::<Position>::new().without_tag(dead),
+            COUNT / 2,
+        ),
+        (
+            "living players",
+            Query::<Position>::new().with::<Player>().without::<Dead>(),
+            Query::<Position>::new().with_tag(player).without_tag(dead),
+            COUNT / 4,
+        ),
+    ];
+    for (name, markers, tags, expected) in cases {
+        let marker_time = benchmark_operation(
+            &format!("{name} by marker components on 100,000 entities"),
+            || assert_eq!(markers.entities(&world).count(), expected),
+            2000, // 2s max
+        );
+
+        let tag_time = benchmark_operation(
+            &format!("{name} by tags on 100,000 entities"),
+            || assert_eq!(tags.entities(&world).count(), expected),
+            2000, // 2s max
+        );
+
+        println!(
+            "{name}: tags {tag_time:?}, markers {marker_time:?}, speedup {:.2}x",
+            marker_time.as_secs_f64() / tag_time.as_secs_f64()
+        );
+    }
+}
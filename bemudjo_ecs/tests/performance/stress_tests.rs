@@ -3,6 +3,9 @@
 //! Tests focused on high-load scenarios, memory pressure,
 //! and system behavior under extreme conditions.
 
+// `is_multiple_of` is newer than the workspace's rust-version
+#![allow(unknown_lints, clippy::manual_is_multiple_of)]
+
 use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
 use std::time::Instant;
 
@@ -477,7 +480,9 @@ fn test_system_execution_stress() {
             }
         }
 
-        scheduler.add_system(StressSystem { id: i }).unwrap();
+        scheduler
+            .add_system_allow_duplicates(StressSystem { id: i })
+            .unwrap();
     }
 
     scheduler.build().unwrap();
@@ -769,3 +774,42 @@ fn test_memory_leak_stress() {
         }
     }
 }
+
+#[test]
+fn test_shrink_to_fit_after_mass_despawn_stress() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = (0..1000)
+        .map(|i| {
+            let entity = world.spawn_entity();
+            world
+                .add_component(
+                    entity,
+                    Position {
+                        x: i as f32,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                )
+                .unwrap();
+            entity
+        })
+        .collect();
+
+    for &entity in entities.iter().step_by(2) {
+        world.delete_entity(entity);
+    }
+    world.cleanup_deleted_entities();
+    let grown = world.storage_capacity::<Position>();
+
+    world.shrink_to_fit();
+
+    // Capacity follows the 500 survivors instead of the 1000 peak
+    let shrunk = world.storage_capacity::<Position>();
+    assert!(shrunk < grown, "capacity {shrunk} not below {grown}");
+    assert!(shrunk >= 500);
+    assert_eq!(world.entities().count(), 500);
+    for &entity in entities.iter().skip(1).step_by(2) {
+        assert!(world.has_component::<Position>(entity));
+    }
+}
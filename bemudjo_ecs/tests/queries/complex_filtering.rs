@@ -704,7 +704,6 @@ fn test_query_with_optional_components() {
 }
 
 #[test]
-#[ignore] // TODO: Fix iterator invalidation bug - double borrow of world during filtering
 fn test_dynamic_filtering_with_component_values() {
     let mut world = World::new();
 
@@ -754,97 +753,50 @@ fn test_dynamic_filtering_with_component_values() {
         }
     }
 
-    let base_query = Query::<Position>::new().with::<Health>().with::<Level>();
-
-    // Test: High health entities (> 50)
-    let high_health: Vec<_> = base_query
-        .iter(&world)
-        .filter(|(entity, _)| {
-            world
-                .get_component::<Health>(*entity)
-                .map(|h| h.current > 50)
-                .unwrap_or(false)
-        })
+    // Single-component predicates only need the component they filter on
+    let high_health: Vec<_> = Query::<Health>::new()
+        .with::<Position>()
+        .with::<Level>()
+        .filter_by(&world, |health| health.current > 50)
         .collect();
     assert_eq!(high_health.len(), 4); // i = 6, 7, 8, 9
 
-    // Test: High level entities (>= 5)
-    let high_level: Vec<_> = base_query
-        .iter(&world)
-        .filter(|(entity, _)| {
-            world
-                .get_component::<Level>(*entity)
-                .map(|l| l.value >= 5)
-                .unwrap_or(false)
-        })
+    let high_level: Vec<_> = Query::<Level>::new()
+        .with::<Position>()
+        .with::<Health>()
+        .filter_by(&world, |level| level.value >= 5)
         .collect();
     assert_eq!(high_level.len(), 5); // i = 5, 6, 7, 8, 9
 
-    // Test: Entities with powerful weapons (damage >= 20)
-    let powerful_weapons: Vec<_> = base_query
-        .iter(&world)
-        .filter(|(entity, _)| {
-            world
-                .get_component::<Weapon>(*entity)
-                .map(|w| w.damage >= 20)
-                .unwrap_or(false)
-        })
+    let weapons = Query::<Weapon>::new().with::<Position>();
+    let powerful_weapons: Vec<_> = weapons
+        .filter_by(&world, |weapon| weapon.damage >= 20)
         .collect();
-    println!("powerful_weapons entities:");
-    for (entity, _) in &powerful_weapons {
-        let weapon = world.get_component::<Weapon>(*entity);
-        let health = world.get_component::<Health>(*entity);
-        let level = world.get_component::<Level>(*entity);
-        println!("entity: {entity:?}, weapon: {weapon:?}, health: {health:?}, level: {level:?}");
-    }
-    assert_eq!(powerful_weapons.len(), 3); // i = 4, 6, 8 (even numbers with damage >= 20)
+    assert_eq!(powerful_weapons.len(), 3); // i = 4, 6, 8
 
-    // Test: Low durability weapons (< 80)
-    let fragile_weapons: Vec<_> = base_query
-        .iter(&world)
-        .filter(|(entity, _)| {
-            world
-                .get_component::<Weapon>(*entity)
-                .map(|w| w.durability < 80)
-                .unwrap_or(false)
-        })
+    let fragile_weapons: Vec<_> = weapons
+        .filter_by(&world, |weapon| weapon.durability < 80)
         .collect();
-    assert_eq!(fragile_weapons.len(), 3); // i = 4, 6, 8
+    assert_eq!(fragile_weapons.len(), 2); // i = 6, 8 (durability 70, 60)
 
-    // Test: Players with high stats (level >= 2 AND health >= 20)
-    let elite_players: Vec<_> = base_query
+    // Predicates on several components fetch the others from the world
+    let elite_players: Vec<_> = Query::<Position>::new()
+        .with::<Player>()
         .iter(&world)
         .filter(|(entity, _)| {
-            let is_player = world.has_component::<Player>(*entity);
-            let high_level = world
-                .get_component::<Level>(*entity)
-                .map(|l| l.value >= 2)
-                .unwrap_or(false);
-            let good_health = world
-                .get_component::<Health>(*entity)
-                .map(|h| h.current >= 20)
-                .unwrap_or(false);
-
-            is_player && high_level && good_health
+            world
+                .get_components2::<Level, Health>(*entity)
+                .is_some_and(|(level, health)| level.value >= 2 && health.current >= 20)
         })
         .collect();
     assert_eq!(elite_players.len(), 1); // i = 2 (only player with level >= 2)
 
-    // Test: Combat-ready entities (have weapon AND good health AND not Npc)
-    let combat_ready: Vec<_> = base_query
-        .iter(&world)
-        .filter(|(entity, _)| {
-            let has_weapon = world.has_component::<Weapon>(*entity);
-            let good_health = world
-                .get_component::<Health>(*entity)
-                .map(|h| h.current >= 30)
-                .unwrap_or(false);
-            let not_npc = !world.has_component::<Npc>(*entity);
-
-            has_weapon && good_health && not_npc
-        })
+    // Presence conditions stay in the query, the predicate checks the value
+    let combat_ready: Vec<_> = Query::<Health>::new()
+        .with::<Weapon>()
+        .without::<Npc>()
+        .filter_by(&world, |health| health.current >= 30)
         .collect();
-    // Should find even numbers i where health >= 30 and not Npc
     // i = 4, 6 (i = 8 is Npc)
     assert_eq!(combat_ready.len(), 2);
 }
@@ -997,3 +949,181 @@ fn test_query_consistency_under_modification() {
         assert!(world.has_component::<Health>(entity));
     }
 }
+
+#[test]
+fn test_any_of_with_without_matches_brute_force() {
+    let mut world = World::new();
+    let mut entities = Vec::new();
+
+    // Every combination of Player / Npc / Dead / Velocity, twice over
+    for i in 0..32u32 {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+            )
+            .unwrap();
+        if i & 1 != 0 {
+            world.add_component(entity, Player).unwrap();
+        }
+        if i & 2 != 0 {
+            world.add_component(entity, Npc).unwrap();
+        }
+        if i & 4 != 0 {
+            world.add_component(entity, Dead).unwrap();
+        }
+        if i & 8 != 0 {
+            world
+                .add_component(entity, Velocity { x: 1.0, y: 0.0 })
+                .unwrap();
+        }
+        entities.push(entity);
+    }
+    // Entities without Position never match
+    let unplaced = world.spawn_entity();
+    world.add_component(unplaced, Player).unwrap();
+
+    let brute_force = |with_velocity: bool| -> std::collections::HashSet<_> {
+        entities
+            .iter()
+            .copied()
+            .filter(|&e| world.has_component::<Player>(e) || world.has_component::<Npc>(e))
+            .filter(|&e| !world.has_component::<Dead>(e))
+            .filter(|&e| !with_velocity || world.has_component::<Velocity>(e))
+            .collect()
+    };
+
+    // "Player or Npc but not Dead"
+    let living: std::collections::HashSet<_> = Query::<Position>::new()
+        .any_of::<(Player, Npc)>()
+        .without::<Dead>()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(living, brute_force(false));
+    assert_eq!(living.len(), 12);
+
+    // Composes with `with` as well
+    let moving: std::collections::HashSet<_> = Query::<Position>::new()
+        .with::<Velocity>()
+        .any_of::<(Player, Npc)>()
+        .without::<Dead>()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(moving, brute_force(true));
+    assert_eq!(moving.len(), 6);
+}
+
+#[test]
+fn test_tag_filters_match_marker_components() {
+    let mut world = World::new();
+    let player = world.register_tag("player").unwrap();
+    let npc = world.register_tag("npc").unwrap();
+    let dead = world.register_tag("dead").unwrap();
+
+    // Every combination of Player / Npc / Dead / Velocity, once as markers and once as tags
+    for i in 0..32u32 {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+            )
+            .unwrap();
+        if i & 1 != 0 {
+            world.add_component(entity, Player).unwrap();
+            world.set_tag(entity, player).unwrap();
+        }
+        if i & 2 != 0 {
+            world.add_component(entity, Npc).unwrap();
+            world.set_tag(entity, npc).unwrap();
+        }
+        if i & 4 != 0 {
+            world.add_component(entity, Dead).unwrap();
+            world.set_tag(entity, dead).unwrap();
+        }
+        if i & 8 != 0 {
+            world
+                .add_component(entity, Velocity { x: 1.0, y: 0.0 })
+                .unwrap();
+        }
+    }
+    // A deleted entity keeps neither its markers nor its tags
+    let deleted = world.spawn_entity();
+    world
+        .add_component(deleted, Position { x: -1.0, y: 0.0 })
+        .unwrap();
+    world.set_tag(deleted, player).unwrap();
+    world.delete_entity(deleted);
+
+    let matches = |query: Query<Position>| -> std::collections::HashSet<_> {
+        query.entities(&world).collect()
+    };
+
+    let pairs = [
+        (
+            "living",
+            Query::<Position>::new().without::<Dead>(),
+            Query::<Position>::new().without_tag(dead),
+        ),
+        (
+            "living players",
+            Query::<Position>::new().with::<Player>().without::<Dead>(),
+            Query::<Position>::new().with_tag(player).without_tag(dead),
+        ),
+        (
+            "dead npcs that move",
+            Query::<Position>::new()
+                .with::<Velocity>()
+                .with::<Npc>()
+                .with::<Dead>(),
+            Query::<Position>::new()
+                .with::<Velocity>()
+                .with_tag(npc)
+                .with_tag(dead),
+        ),
+        (
+            "nobodies",
+            Query::<Position>::new()
+                .without::<Player>()
+                .without::<Npc>()
+                .without::<Dead>(),
+            Query::<Position>::new()
+                .without_tag(player)
+                .without_tag(npc)
+                .without_tag(dead),
+        ),
+        (
+            "contradiction",
+            Query::<Position>::new().with::<Dead>().without::<Dead>(),
+            Query::<Position>::new().with_tag(dead).without_tag(dead),
+        ),
+    ];
+    for (name, markers, tags) in pairs {
+        assert_eq!(matches(markers), matches(tags), "{name}");
+    }
+    assert_eq!(
+        matches(Query::<Position>::new().with_tag(player).without_tag(dead)).len(),
+        8
+    );
+
+    // Clearing a tag is seen by the next query
+    let hero = Query::<Position>::new()
+        .with_tag(player)
+        .with_tag(dead)
+        .collect_entities(&world)[0];
+    world.clear_tag(hero, dead);
+    assert!(Query::<Position>::new()
+        .with_tag(player)
+        .without_tag(dead)
+        .entities(&world)
+        .any(|entity| entity == hero));
+}
@@ -7,9 +7,11 @@
 //! - Large-scale query operations
 //! - Ephemeral query system integration
 //! - Edge cases and boundary conditions
+//! - Spatial region queries
 
 pub mod complex_filtering;
 pub mod ephemeral_query_integration;
 pub mod query_edge_cases;
 pub mod query_integration;
 pub mod query_performance;
+pub mod spatial_queries;
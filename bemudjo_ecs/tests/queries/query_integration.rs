@@ -4,6 +4,7 @@
 //! entity lifecycle, and real-world usage patterns.
 
 use bemudjo_ecs::{Component, Query, World};
+use std::collections::HashSet;
 
 // Test Components
 #[derive(Debug, Clone, PartialEq)]
@@ -512,3 +513,108 @@ fn test_realistic_game_scenario_integration() {
         "Dead enemy position should not be included"
     );
 }
+
+#[test]
+fn test_parent_filters_follow_room_contents() {
+    let mut world = World::new();
+    let room = world.spawn_entity();
+    world
+        .add_component(room, Position { x: 0.0, y: 0.0 })
+        .unwrap();
+
+    let mut direct = HashSet::new();
+    for i in 0..3 {
+        let item = world.spawn_entity();
+        world
+            .add_component(
+                item,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+            )
+            .unwrap();
+        world.set_parent(item, room).unwrap();
+        direct.insert(item);
+    }
+    let chest = *direct.iter().next().unwrap();
+    let coin = world.spawn_entity();
+    world
+        .add_component(coin, Position { x: 9.0, y: 9.0 })
+        .unwrap();
+    world.set_parent(coin, chest).unwrap();
+    let elsewhere = world.spawn_entity();
+    world
+        .add_component(elsewhere, Position { x: 5.0, y: 5.0 })
+        .unwrap();
+
+    // Exactly the direct children, not the grandchild
+    let in_room = Query::<Position>::new().with_parent(room);
+    let found: HashSet<_> = in_room.iter(&world).map(|(entity, _)| entity).collect();
+    assert_eq!(found, direct);
+
+    let containers: HashSet<_> = Query::<Position>::new()
+        .with_children()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(containers, HashSet::from([room, chest]));
+
+    // Deleting the room empties the result right away and after cleanup
+    world.delete_entity(room);
+    assert_eq!(in_room.iter(&world).count(), 0);
+    world.cleanup_deleted_entities();
+    assert_eq!(in_room.iter(&world).count(), 0);
+
+    // The contents survive as orphans
+    for &item in &direct {
+        assert!(world.has_component::<Position>(item));
+        assert_eq!(world.parent_of(item), None);
+    }
+    assert_eq!(world.parent_of(coin), Some(chest));
+}
+
+#[test]
+fn test_collect_terminals_release_the_world_for_mutation() {
+    let mut world = World::new();
+    for i in 0..12u32 {
+        let entity = world.spawn_entity();
+        world.add_component(entity, Health { value: i }).unwrap();
+        if i % 3 == 0 {
+            world.add_component(entity, Dead).unwrap();
+        }
+    }
+
+    // Same entities as filtering the living by hand
+    let living = Query::<Health>::new().without::<Dead>();
+    let collected = living.collect_entities(&world);
+    let manual: HashSet<_> = Query::<Health>::new()
+        .iter(&world)
+        .filter(|(entity, _)| !world.has_component::<Dead>(*entity))
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(collected.iter().copied().collect::<HashSet<_>>(), manual);
+    assert_eq!(collected.len(), 8);
+
+    // The collected list holds no borrow, so the world can change while walking it
+    for entity in collected {
+        world
+            .update_component::<Health, _>(entity, |health| Health {
+                value: health.value + 100,
+            })
+            .unwrap();
+    }
+
+    let mut values: Vec<u32> = living
+        .collect_components(&world)
+        .iter()
+        .map(|health| health.value)
+        .collect();
+    values.sort_unstable();
+    assert_eq!(values, vec![101, 102, 104, 105, 107, 108, 110, 111]);
+    assert!(Query::<Health>::new()
+        .with::<Dead>()
+        .collect_components(&world)
+        .iter()
+        .all(|health| health.value < 100));
+}
@@ -3,7 +3,8 @@
 //! Tests focused on query system performance, optimization,
 //! and scalability under various load conditions.
 
-use bemudjo_ecs::{Component, Query, World};
+use bemudjo_ecs::{Component, Entity, Query, World};
+use std::collections::HashSet;
 use std::time::Instant;
 
 // Test Components
@@ -702,3 +703,60 @@ fn test_query_performance_regression() {
     // Large scale should not be more than 5x slower per entity (due to cache effects, etc.)
     assert!(large_per_entity / small_per_entity < 5.0);
 }
+
+#[test]
+fn test_selective_with_filter_starts_from_smallest_set() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dead;
+    impl Component for Dead {}
+
+    const ENTITY_COUNT: usize = 50_000;
+
+    let mut world = World::new();
+    for i in 0..ENTITY_COUNT {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+            )
+            .unwrap();
+        if i % 5_000 == 0 {
+            world.add_component(entity, Dead).unwrap();
+        }
+        if i % 10_000 == 0 {
+            world.delete_entity(entity);
+        }
+    }
+
+    // Reference result from per-entity lookups
+    let expected: HashSet<Entity> = world
+        .entities()
+        .copied()
+        .filter(|&entity| {
+            world.has_component::<Position>(entity) && world.has_component::<Dead>(entity)
+        })
+        .collect();
+    assert_eq!(expected.len(), 5);
+
+    let selective: HashSet<Entity> = Query::<Position>::new()
+        .with::<Dead>()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+
+    // Same entities whichever side the intersection starts from
+    let reversed: HashSet<Entity> = Query::<Dead>::new()
+        .with::<Position>()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(selective, expected);
+    assert_eq!(reversed, expected);
+
+    let all_positions = Query::<Position>::new().iter(&world).count();
+    assert_eq!(all_positions, ENTITY_COUNT - ENTITY_COUNT / 10_000);
+}
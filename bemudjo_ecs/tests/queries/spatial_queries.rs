@@ -0,0 +1,189 @@
+//! Spatial Query Integration Tests
+//!
+//! Tests for region queries through the spatial index, checked against a
+//! brute-force scan over every positioned entity.
+
+use bemudjo_ecs::spatial::{SpatialComponent, SpatialGrid, SpatialIndexSystem};
+use bemudjo_ecs::{Component, Entity, Query, SequentialSystemScheduler, System, World};
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock, Mutex};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+impl Component for Position {}
+
+impl SpatialComponent for Position {
+    fn x(&self) -> f32 {
+        self.x
+    }
+    fn y(&self) -> f32 {
+        self.y
+    }
+}
+
+/// Deterministic pseudo-random generator so failures are reproducible.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_f32(&mut self, range: f32) -> f32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32 / (1u64 << 24) as f32) * range
+    }
+}
+
+fn populated_world(count: usize, extent: f32) -> World {
+    let mut world = World::new();
+    let mut rng = Lcg(42);
+
+    for _ in 0..count {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Position {
+                    x: rng.next_f32(extent) - extent / 2.0,
+                    y: rng.next_f32(extent) - extent / 2.0,
+                },
+            )
+            .unwrap();
+    }
+
+    world
+}
+
+fn brute_force_radius(world: &World, center: (f32, f32), radius: f32) -> HashSet<Entity> {
+    Query::<Position>::new()
+        .iter(world)
+        .filter(|(_, p)| {
+            let dx = p.x - center.0;
+            let dy = p.y - center.1;
+            dx * dx + dy * dy <= radius * radius
+        })
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn brute_force_rect(world: &World, min: (f32, f32), max: (f32, f32)) -> HashSet<Entity> {
+    Query::<Position>::new()
+        .iter(world)
+        .filter(|(_, p)| p.x >= min.0 && p.x <= max.0 && p.y >= min.1 && p.y <= max.1)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn build_index(world: &mut World, cell_size: f32) {
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler
+        .add_system(SpatialIndexSystem::<Position>::new(cell_size))
+        .unwrap();
+    scheduler.build().unwrap();
+    scheduler.run_tick(world);
+}
+
+#[test]
+fn test_radius_queries_match_brute_force_on_10k_entities() {
+    let mut world = populated_world(10_000, 1_000.0);
+    build_index(&mut world, 25.0);
+
+    let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+    assert_eq!(grid.len(), 10_000);
+
+    let mut rng = Lcg(7);
+    for radius in [0.0, 5.0, 25.0, 60.0, 300.0] {
+        for _ in 0..20 {
+            let center = (rng.next_f32(1_200.0) - 600.0, rng.next_f32(1_200.0) - 600.0);
+
+            let indexed: Vec<Entity> = grid.entities_in_radius(center, radius);
+            let indexed_set: HashSet<Entity> = indexed.iter().copied().collect();
+
+            assert_eq!(indexed.len(), indexed_set.len(), "no duplicates");
+            assert_eq!(
+                indexed_set,
+                brute_force_radius(&world, center, radius),
+                "radius {radius} around {center:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_rect_queries_match_brute_force_on_10k_entities() {
+    let mut world = populated_world(10_000, 1_000.0);
+    build_index(&mut world, 25.0);
+
+    let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+
+    let mut rng = Lcg(99);
+    for _ in 0..100 {
+        let x = rng.next_f32(1_200.0) - 600.0;
+        let y = rng.next_f32(1_200.0) - 600.0;
+        let min = (x, y);
+        let max = (x + rng.next_f32(200.0), y + rng.next_f32(200.0));
+
+        let indexed: HashSet<Entity> = grid.entities_in_rect(min, max).into_iter().collect();
+        assert_eq!(indexed, brute_force_rect(&world, min, max));
+    }
+
+    // The whole world
+    assert_eq!(
+        grid.entities_in_rect((-500.0, -500.0), (500.0, 500.0))
+            .len(),
+        10_000
+    );
+}
+
+#[test]
+fn test_dependent_system_sees_current_tick_positions() {
+    static NEIGHBOR_DEPS: LazyLock<Vec<TypeId>> =
+        LazyLock::new(|| vec![TypeId::of::<SpatialIndexSystem<Position>>()]);
+
+    struct NeighborSystem {
+        found: Arc<Mutex<Vec<usize>>>,
+    }
+    impl System for NeighborSystem {
+        fn dependencies(&self) -> &[TypeId] {
+            &NEIGHBOR_DEPS
+        }
+        fn run(&self, world: &mut World) {
+            let grid = world.get_resource::<SpatialGrid<Position>>().unwrap();
+            self.found
+                .lock()
+                .unwrap()
+                .push(grid.entities_in_radius((0.0, 0.0), 1.0).len());
+        }
+    }
+
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+    world
+        .add_component(entity, Position { x: 0.0, y: 0.0 })
+        .unwrap();
+
+    let found = Arc::new(Mutex::new(Vec::new()));
+    let mut scheduler = SequentialSystemScheduler::new();
+    // Registered first, but must still run after the index
+    scheduler
+        .add_system(NeighborSystem {
+            found: found.clone(),
+        })
+        .unwrap();
+    scheduler
+        .add_system(SpatialIndexSystem::<Position>::new(4.0))
+        .unwrap();
+    scheduler.build().unwrap();
+
+    scheduler.run_tick(&mut world);
+    world.replace_component(entity, Position { x: 20.0, y: 0.0 });
+    scheduler.run_tick(&mut world);
+    world.replace_component(entity, Position { x: 0.5, y: 0.5 });
+    scheduler.run_tick(&mut world);
+
+    assert_eq!(*found.lock().unwrap(), vec![1, 0, 1]);
+}
@@ -0,0 +1,227 @@
+//! Ephemeral Resource Integration Tests
+//!
+//! Tests for per-tick global events stored as ephemeral resources and their
+//! visibility across system phases and ticks.
+
+use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+#[derive(Clone, Debug, PartialEq)]
+struct ShutdownRequested {
+    reason: String,
+}
+impl Component for ShutdownRequested {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Announcements {
+    messages: Vec<String>,
+}
+impl Component for Announcements {}
+
+/// Requests a shutdown on a specific tick only.
+struct ShutdownProducerSystem {
+    tick: Rc<Cell<u32>>,
+    trigger_tick: u32,
+}
+
+impl System for ShutdownProducerSystem {
+    fn run(&self, world: &mut World) {
+        let current = self.tick.get();
+        if current == self.trigger_tick {
+            world
+                .insert_ephemeral_resource(ShutdownRequested {
+                    reason: format!("requested on tick {current}"),
+                })
+                .unwrap();
+        }
+    }
+}
+
+static CONSUMER_DEPS: LazyLock<Vec<TypeId>> =
+    LazyLock::new(|| vec![TypeId::of::<ShutdownProducerSystem>()]);
+
+/// Records what it observes in every phase of every tick.
+struct ShutdownConsumerSystem {
+    tick: Rc<Cell<u32>>,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl System for ShutdownConsumerSystem {
+    fn dependencies(&self) -> &[TypeId] {
+        &CONSUMER_DEPS
+    }
+
+    fn before_run(&self, world: &WorldView) {
+        if let Some(event) = world.get_ephemeral_resource::<ShutdownRequested>() {
+            self.log.borrow_mut().push(format!(
+                "tick {} before_run: {}",
+                self.tick.get(),
+                event.reason
+            ));
+        }
+    }
+
+    fn run(&self, world: &mut World) {
+        if let Some(event) = world.get_ephemeral_resource::<ShutdownRequested>() {
+            self.log
+                .borrow_mut()
+                .push(format!("tick {} run: {}", self.tick.get(), event.reason));
+        }
+    }
+
+    fn after_run(&self, world: &WorldView) {
+        if let Some(event) = world.get_ephemeral_resource::<ShutdownRequested>() {
+            self.log.borrow_mut().push(format!(
+                "tick {} after_run: {}",
+                self.tick.get(),
+                event.reason
+            ));
+        }
+        self.tick.set(self.tick.get() + 1);
+    }
+}
+
+#[test]
+fn test_ephemeral_resource_visible_only_in_producing_tick() {
+    let mut world = World::new();
+    let mut scheduler = SequentialSystemScheduler::new();
+    let tick = Rc::new(Cell::new(0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    scheduler
+        .add_system(ShutdownConsumerSystem {
+            tick: tick.clone(),
+            log: log.clone(),
+        })
+        .unwrap();
+    scheduler
+        .add_system(ShutdownProducerSystem {
+            tick: tick.clone(),
+            trigger_tick: 1,
+        })
+        .unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..3 {
+        scheduler.run_tick(&mut world);
+    }
+
+    // Produced during run of tick 1: consumer sees it in run and after_run of tick 1 only
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "tick 1 run: requested on tick 1".to_string(),
+            "tick 1 after_run: requested on tick 1".to_string(),
+        ]
+    );
+    assert!(!world.has_ephemeral_resource::<ShutdownRequested>());
+}
+
+#[test]
+fn test_ephemeral_resource_inserted_before_tick_visible_in_all_phases() {
+    let mut world = World::new();
+    let mut scheduler = SequentialSystemScheduler::new();
+    let tick = Rc::new(Cell::new(0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    scheduler
+        .add_system(ShutdownConsumerSystem {
+            tick: tick.clone(),
+            log: log.clone(),
+        })
+        .unwrap();
+    scheduler.build().unwrap();
+
+    world
+        .insert_ephemeral_resource(ShutdownRequested {
+            reason: "external".to_string(),
+        })
+        .unwrap();
+
+    scheduler.run_tick(&mut world);
+    scheduler.run_tick(&mut world);
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "tick 0 before_run: external".to_string(),
+            "tick 0 run: external".to_string(),
+            "tick 0 after_run: external".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_multiple_producers_merge_into_ephemeral_resource() {
+    struct AnnounceSystem {
+        message: &'static str,
+    }
+
+    impl System for AnnounceSystem {
+        fn run(&self, world: &mut World) {
+            if world.has_ephemeral_resource::<Announcements>() {
+                world
+                    .update_ephemeral_resource::<Announcements, _>(|mut a| {
+                        a.messages.push(self.message.to_string());
+                        a
+                    })
+                    .unwrap();
+            } else {
+                world
+                    .insert_ephemeral_resource(Announcements {
+                        messages: vec![self.message.to_string()],
+                    })
+                    .unwrap();
+            }
+        }
+    }
+
+    let received = Rc::new(RefCell::new(Vec::new()));
+
+    struct AnnouncementReader {
+        received: Rc<RefCell<Vec<Vec<String>>>>,
+    }
+
+    impl System for AnnouncementReader {
+        fn after_run(&self, world: &WorldView) {
+            let messages = world
+                .get_ephemeral_resource::<Announcements>()
+                .map(|a| a.messages.clone())
+                .unwrap_or_default();
+            self.received.borrow_mut().push(messages);
+        }
+    }
+
+    let mut world = World::new();
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler
+        .add_system(AnnounceSystem { message: "first" })
+        .unwrap();
+    scheduler
+        .add_system_allow_duplicates(AnnounceSystem { message: "second" })
+        .unwrap();
+    scheduler
+        .add_system(AnnouncementReader {
+            received: received.clone(),
+        })
+        .unwrap();
+    scheduler.build().unwrap();
+
+    scheduler.run_tick(&mut world);
+    world
+        .update_ephemeral_resource::<Announcements, _>(|a| a)
+        .unwrap_err();
+    scheduler.run_tick(&mut world);
+
+    // Each tick starts with a fresh ephemeral resource
+    assert_eq!(
+        *received.borrow(),
+        vec![
+            vec!["first".to_string(), "second".to_string()],
+            vec!["first".to_string(), "second".to_string()]
+        ]
+    );
+}
@@ -5,7 +5,9 @@
 //! - Multi-system resource sharing
 //! - Resource integration with systems
 //! - Resource consistency and isolation
+//! - Ephemeral (per-tick) resources
 
+pub mod ephemeral_resource_integration;
 pub mod resource_integration;
 pub mod resource_lifecycle;
 pub mod resource_sharing;
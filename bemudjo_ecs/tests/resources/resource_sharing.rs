@@ -734,7 +734,7 @@ fn test_resource_sharing_performance() {
         }
 
         scheduler
-            .add_system(PerformanceSystem {
+            .add_system_allow_duplicates(PerformanceSystem {
                 id: i,
                 _execution_log: log.clone(),
             })
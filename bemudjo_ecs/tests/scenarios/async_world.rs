@@ -0,0 +1,181 @@
+//! Shared World Integration Tests
+//!
+//! Tests for an `AsyncWorld` ticked by one thread while other threads read
+//! and write it, as the tick task and connection tasks of a server would.
+
+use bemudjo_ecs::{AsyncWorld, Component, Query, SequentialSystemScheduler, System, World};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const TICKS: u64 = 300;
+const READERS: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+struct TickCount(u64);
+impl Component for TickCount {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Wallet {
+    gold: u32,
+}
+impl Component for Wallet {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Player {
+    name: String,
+}
+impl Component for Player {}
+
+/// Counts ticks and moves one gold coin between wallets, one write at a time,
+/// so a reader seeing the middle of a tick would find a coin missing.
+struct TradeSystem;
+impl System for TradeSystem {
+    fn run(&self, world: &mut World) {
+        let wallets = Query::<Wallet>::new().collect_entities(world);
+        if let [payer, payee, ..] = wallets[..] {
+            let (payer, payee) = if world.get_component::<Wallet>(payer).unwrap().gold > 0 {
+                (payer, payee)
+            } else {
+                (payee, payer)
+            };
+            world
+                .update_component_in_place::<Wallet, _>(payer, |wallet| wallet.gold -= 1)
+                .unwrap();
+            world
+                .update_component_in_place::<Wallet, _>(payee, |wallet| wallet.gold += 1)
+                .unwrap();
+        }
+        world
+            .update_resource::<TickCount, _>(|count| TickCount(count.0 + 1))
+            .unwrap();
+    }
+}
+
+fn shared_world() -> AsyncWorld {
+    let mut world = World::new();
+    world.insert_resource(TickCount(0));
+    for gold in [60, 40] {
+        let entity = world.spawn_entity();
+        world.add_component(entity, Wallet { gold }).unwrap();
+    }
+    AsyncWorld::new(world)
+}
+
+/// Ticks `world` from a new thread, which owns the scheduler like a server's
+/// tick task would.
+fn spawn_ticker(world: &AsyncWorld) -> thread::JoinHandle<()> {
+    let world = world.clone();
+    thread::spawn(move || {
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(TradeSystem).unwrap();
+        scheduler.build().unwrap();
+        for _ in 0..TICKS {
+            world.tick(&mut scheduler);
+        }
+    })
+}
+
+fn total_gold(world: &World) -> u32 {
+    Query::<Wallet>::new()
+        .collect_components(world)
+        .iter()
+        .map(|wallet| wallet.gold)
+        .sum()
+}
+
+#[test]
+fn test_concurrent_readers_see_whole_monotonic_ticks() {
+    let world = shared_world();
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let world = world.clone();
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut last_tick = 0;
+                let mut reads = 0;
+                loop {
+                    // Checked before reading, so the final tick is always seen
+                    let finished = done.load(Ordering::Acquire);
+                    let (tick, gold) = world.with_read(|world| {
+                        (
+                            world.get_resource::<TickCount>().unwrap().0,
+                            total_gold(world),
+                        )
+                    });
+                    assert!(
+                        tick >= last_tick,
+                        "tick went back from {last_tick} to {tick}"
+                    );
+                    assert_eq!(gold, 100, "read the middle of tick {tick}");
+                    last_tick = tick;
+                    reads += 1;
+                    if finished {
+                        return (last_tick, reads);
+                    }
+                    thread::yield_now();
+                }
+            })
+        })
+        .collect();
+
+    spawn_ticker(&world).join().unwrap();
+    done.store(true, Ordering::Release);
+    for reader in readers {
+        let (last_tick, reads) = reader.join().unwrap();
+        assert_eq!(last_tick, TICKS);
+        assert!(reads > 0);
+    }
+    assert_eq!(
+        world.with_read(|world| world.get_resource::<TickCount>().unwrap().0),
+        TICKS
+    );
+}
+
+#[test]
+fn test_writers_interleave_with_ticks() {
+    let world = shared_world();
+
+    // Connection threads log players in while the tick thread runs
+    let connections: Vec<_> = (0..READERS)
+        .map(|connection| {
+            let world = world.clone();
+            thread::spawn(move || {
+                (0..25)
+                    .map(|i| {
+                        world.with_write(|world| {
+                            let player = world.spawn_entity();
+                            world
+                                .add_component(
+                                    player,
+                                    Player {
+                                        name: format!("player-{connection}-{i}"),
+                                    },
+                                )
+                                .unwrap();
+                            player
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let ticker = spawn_ticker(&world);
+
+    let players: Vec<_> = connections
+        .into_iter()
+        .flat_map(|connection| connection.join().unwrap())
+        .collect();
+    ticker.join().unwrap();
+
+    let world = world.into_inner().unwrap();
+    assert_eq!(players.len(), READERS * 25);
+    assert!(players
+        .iter()
+        .all(|&player| world.has_component::<Player>(player)));
+    assert_eq!(world.get_resource::<TickCount>().unwrap().0, TICKS);
+    assert_eq!(total_gold(&world), 100);
+}
@@ -19,10 +19,10 @@ struct EmptyComponent;
 impl Component for EmptyComponent {}
 
 #[derive(Clone, Debug, PartialEq)]
-struct GenericComponent<T: Clone + 'static> {
+struct GenericComponent<T: Clone + Send + Sync + 'static> {
     value: T,
 }
-impl<T: Clone + 'static> Component for GenericComponent<T> {}
+impl<T: Clone + Send + Sync + 'static> Component for GenericComponent<T> {}
 
 #[derive(Clone, Debug, PartialEq)]
 struct CounterComponent {
@@ -398,10 +398,7 @@ fn test_component_error_conditions() {
         .unwrap();
 
     let result = world.add_component(entity, CounterComponent { value: 2 });
-    assert!(matches!(
-        result,
-        Err(ComponentError::ComponentAlreadyExists)
-    ));
+    assert!(matches!(result, Err(ComponentError::AlreadyExists { .. })));
 
     // Original component should be unchanged
     let counter = world.get_component::<CounterComponent>(entity).unwrap();
@@ -411,10 +408,10 @@ fn test_component_error_conditions() {
     world.delete_entity(entity);
 
     let result = world.add_component(entity, EmptyComponent);
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     let result = world.update_component::<CounterComponent, _>(entity, |c| c);
-    assert!(matches!(result, Err(ComponentError::ComponentNotFound)));
+    assert!(matches!(result, Err(ComponentError::EntityNotFound { .. })));
 
     assert!(world.get_component::<CounterComponent>(entity).is_none());
     assert!(!world.has_component::<CounterComponent>(entity));
@@ -393,9 +393,8 @@ impl System for LootSystem {
 
                             // Grant experience for collecting items
                             world
-                                .update_component::<Experience, _>(player_entity, |mut exp| {
+                                .update_component_in_place::<Experience, _>(player_entity, |exp| {
                                     exp.current += 10; // Small exp for collecting
-                                    exp
                                 })
                                 .ok();
                         }
@@ -5,6 +5,11 @@
 //! - Edge cases and boundary conditions
 //! - Real-world ECS usage patterns
 //! - Integration stress testing
+//! - Multiple worlds in one registry
+//! - One world shared between threads
 
+#[cfg(feature = "thread-safe-components")]
+pub mod async_world;
 pub mod edge_cases;
 pub mod game_simulation;
+pub mod multi_world;
@@ -0,0 +1,150 @@
+//! Multi-World Integration Tests
+//!
+//! Tests for running several zones in one `WorldRegistry`, each ticking
+//! independently, with players moving between them.
+
+use bemudjo_ecs::{
+    Component, ComponentRegistry, GlobalEntityRef, Query, SequentialSystemScheduler, System, World,
+    WorldRegistry,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Player {
+    name: String,
+}
+impl Component for Player {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Hunger {
+    value: u32,
+}
+impl Component for Hunger {}
+
+/// Marks a player who stepped on the zone exit.
+#[derive(Clone, Debug, PartialEq)]
+struct WantsToLeave;
+impl Component for WantsToLeave {}
+
+struct HungerSystem;
+
+impl System for HungerSystem {
+    fn run(&self, world: &mut World) {
+        let query = Query::<Hunger>::new();
+        world.update_all(&query, |hunger| Hunger {
+            value: hunger.value + 1,
+        });
+    }
+}
+
+fn spawn_player(registry: &mut WorldRegistry, zone: &str, name: &str) -> GlobalEntityRef {
+    let id = registry.world_id(zone).unwrap();
+    let world = registry.world_mut(id).unwrap();
+    let entity = world.spawn_entity();
+    world
+        .add_component(
+            entity,
+            Player {
+                name: name.to_string(),
+            },
+        )
+        .unwrap();
+    world.add_component(entity, Hunger { value: 0 }).unwrap();
+    GlobalEntityRef::new(id, entity)
+}
+
+#[test]
+fn test_zones_tick_independently() {
+    let mut registry = WorldRegistry::new();
+    let town = registry.create_world("zone:town").unwrap();
+    let forest = registry.create_world("zone:forest").unwrap();
+    let alice = spawn_player(&mut registry, "zone:town", "Alice");
+    let bob = spawn_player(&mut registry, "zone:forest", "Bob");
+
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler.add_system(HungerSystem).unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..3 {
+        registry.run_tick_all(&scheduler);
+    }
+    // Only the town is awake for the next two ticks
+    for _ in 0..2 {
+        registry.run_tick_each(|id| (id == town).then_some(&scheduler));
+    }
+    assert!(registry.run_tick(forest, &scheduler));
+
+    assert_eq!(registry.get_component::<Hunger>(alice).unwrap().value, 5);
+    assert_eq!(registry.get_component::<Hunger>(bob).unwrap().value, 4);
+    assert_eq!(
+        Query::<Player>::new()
+            .iter(registry.world(town).unwrap())
+            .count(),
+        1
+    );
+    assert_eq!(
+        Query::<Player>::new()
+            .iter(registry.world(forest).unwrap())
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_scripted_transfer_between_zones() {
+    let mut registry = WorldRegistry::new();
+    let town = registry.create_world("zone:town").unwrap();
+    let forest = registry.create_world("zone:forest").unwrap();
+    let alice = spawn_player(&mut registry, "zone:town", "Alice");
+    let _carol = spawn_player(&mut registry, "zone:town", "Carol");
+
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler.add_system(HungerSystem).unwrap();
+    scheduler.build().unwrap();
+    let components = ComponentRegistry::new()
+        .with::<Player>()
+        .with::<Hunger>()
+        .with::<WantsToLeave>();
+
+    registry.run_tick_all(&scheduler);
+    registry
+        .world_mut(town)
+        .unwrap()
+        .add_component(alice.entity, WantsToLeave)
+        .unwrap();
+
+    // The zone script moves everyone who wants to leave into the forest
+    let leaving: Vec<GlobalEntityRef> = Query::<WantsToLeave>::new()
+        .iter(registry.world(town).unwrap())
+        .map(|(entity, _)| GlobalEntityRef::new(town, entity))
+        .collect();
+    let mut arrived = Vec::new();
+    for player in leaving {
+        let moved = registry.transfer(player, forest, &components).unwrap();
+        registry
+            .world_mut(forest)
+            .unwrap()
+            .remove_component::<WantsToLeave>(moved.entity);
+        arrived.push(moved);
+    }
+    registry.run_tick_all(&scheduler);
+
+    let alice_now = arrived[0];
+    assert_eq!(alice_now.world, forest);
+    assert!(!registry.contains(alice));
+    assert_eq!(
+        registry.get_component::<Player>(alice_now).unwrap().name,
+        "Alice"
+    );
+    // Hunger kept ticking across the move
+    assert_eq!(
+        registry.get_component::<Hunger>(alice_now).unwrap().value,
+        2
+    );
+    assert!(registry.get_component::<WantsToLeave>(alice_now).is_none());
+
+    let town_players: Vec<String> = Query::<Player>::new()
+        .iter(registry.world(town).unwrap())
+        .map(|(_, player)| player.name.clone())
+        .collect();
+    assert_eq!(town_players, vec!["Carol".to_string()]);
+}
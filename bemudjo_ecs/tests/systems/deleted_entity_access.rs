@@ -0,0 +1,150 @@
+//! Integration tests for reading deleted entities before cleanup
+//!
+//! Tests that systems ordered after a deletion can still inspect the
+//! deleted entity's components until the end-of-tick cleanup.
+
+use bemudjo_ecs::{Component, Query, SequentialSystemScheduler, System, World};
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Health {
+    value: u32,
+}
+impl Component for Health {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+impl Component for Position {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Loot {
+    gold: u32,
+}
+impl Component for Loot {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Corpse {
+    gold: u32,
+}
+impl Component for Corpse {}
+
+/// Deletes every entity whose health dropped to zero
+struct DeathSystem;
+impl System for DeathSystem {
+    fn run(&self, world: &mut World) {
+        let dead: Vec<_> = Query::<Health>::new()
+            .iter(world)
+            .filter(|(_, health)| health.value == 0)
+            .map(|(entity, _)| entity)
+            .collect();
+
+        for entity in dead {
+            world.delete_entity(entity);
+        }
+    }
+}
+
+static CORPSE_DEPS: LazyLock<Vec<TypeId>> = LazyLock::new(|| vec![TypeId::of::<DeathSystem>()]);
+
+/// Replaces every entity deleted this tick with a corpse holding its loot
+struct CorpseSystem {
+    deleted_seen: Rc<RefCell<usize>>,
+}
+impl System for CorpseSystem {
+    fn dependencies(&self) -> &[TypeId] {
+        &CORPSE_DEPS
+    }
+
+    fn run(&self, world: &mut World) {
+        let corpses: Vec<_> = world
+            .deleted_entities()
+            .filter_map(|&entity| {
+                let position = world.get_component_including_deleted::<Position>(entity)?;
+                let loot = world.get_component_including_deleted::<Loot>(entity)?;
+                Some((position.clone(), loot.gold))
+            })
+            .collect();
+        *self.deleted_seen.borrow_mut() += world.deleted_entities().count();
+
+        for (position, gold) in corpses {
+            let corpse = world.spawn_entity();
+            world.add_component(corpse, position).unwrap();
+            world.add_component(corpse, Corpse { gold }).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_later_system_reads_deleted_entity_and_spawns_replacement() {
+    let mut world = World::new();
+    let goblin = world.spawn_entity();
+    world.add_component(goblin, Health { value: 0 }).unwrap();
+    world
+        .add_component(goblin, Position { x: 3, y: 7 })
+        .unwrap();
+    world.add_component(goblin, Loot { gold: 25 }).unwrap();
+
+    let survivor = world.spawn_entity();
+    world.add_component(survivor, Health { value: 10 }).unwrap();
+    world
+        .add_component(survivor, Position { x: 0, y: 0 })
+        .unwrap();
+
+    let deleted_seen = Rc::new(RefCell::new(0));
+    let mut scheduler = SequentialSystemScheduler::new();
+    // Added first, but ordered after DeathSystem by its dependency
+    scheduler
+        .add_system(CorpseSystem {
+            deleted_seen: deleted_seen.clone(),
+        })
+        .unwrap();
+    scheduler.add_system(DeathSystem).unwrap();
+    scheduler.build_strict().unwrap();
+
+    scheduler.run_tick(&mut world);
+
+    assert_eq!(*deleted_seen.borrow(), 1);
+    let corpse_query = Query::<Corpse>::new();
+    let corpses: Vec<_> = corpse_query.iter(&world).collect();
+    assert_eq!(corpses.len(), 1);
+    let (corpse, loot) = corpses[0];
+    assert_eq!(loot, &Corpse { gold: 25 });
+    assert_eq!(
+        world.get_component::<Position>(corpse),
+        Some(&Position { x: 3, y: 7 })
+    );
+
+    // The goblin was cleaned up at the end of the tick
+    assert!(!world.was_deleted_this_tick(goblin));
+    assert_eq!(world.deleted_entities().count(), 0);
+    assert_eq!(world.get_component_including_deleted::<Loot>(goblin), None);
+    assert_eq!(
+        world.get_component_including_deleted::<Position>(goblin),
+        None
+    );
+    assert_eq!(world.entities().count(), 2);
+
+    // Nothing dies on the next tick
+    scheduler.run_tick(&mut world);
+    assert_eq!(*deleted_seen.borrow(), 1);
+    assert_eq!(Query::<Corpse>::new().iter(&world).count(), 1);
+}
+
+#[test]
+fn test_including_deleted_reads_live_entities_too() {
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+    world.add_component(entity, Loot { gold: 3 }).unwrap();
+
+    assert_eq!(
+        world.get_component_including_deleted::<Loot>(entity),
+        Some(&Loot { gold: 3 })
+    );
+    assert!(!world.was_deleted_this_tick(entity));
+}
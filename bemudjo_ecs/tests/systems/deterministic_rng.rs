@@ -0,0 +1,148 @@
+//! Seeded randomness: identical runs for identical seeds, and per-system streams
+//! that don't shift when other systems are added.
+
+use bemudjo_ecs::{Component, Entity, Query, Rng, SequentialSystemScheduler, System, World};
+use std::any::TypeId;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+impl Component for Position {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Goblin {
+    serial: usize, // spawn order, since entity ids differ between runs
+    hit_points: u32,
+}
+impl Component for Goblin {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct NoiseLevel(u64);
+impl Component for NoiseLevel {}
+
+/// Spawns a goblin with random stats at a random spot every tick.
+struct GoblinSpawnSystem;
+impl System for GoblinSpawnSystem {
+    fn run(&self, world: &mut World) {
+        let mut rng = world
+            .get_resource::<Rng>()
+            .unwrap()
+            .fork(TypeId::of::<Self>());
+        let serial = Query::<Goblin>::new().iter(world).count();
+
+        let goblin = world.spawn_entity();
+        world
+            .add_component(
+                goblin,
+                Position {
+                    x: rng.range(-50.0..50.0),
+                    y: rng.range(-50.0..50.0),
+                },
+            )
+            .unwrap();
+        world
+            .add_component(
+                goblin,
+                Goblin {
+                    serial,
+                    hit_points: rng.range(10..30),
+                },
+            )
+            .unwrap();
+    }
+}
+
+/// Nudges every goblin by a random step.
+struct WanderSystem;
+impl System for WanderSystem {
+    fn run(&self, world: &mut World) {
+        let mut rng = world
+            .get_resource::<Rng>()
+            .unwrap()
+            .fork(TypeId::of::<Self>());
+
+        for goblin in goblins_in_spawn_order(world) {
+            world
+                .update_component::<Position, _>(goblin, |position| Position {
+                    x: position.x + rng.range(-1.0..1.0),
+                    y: position.y + rng.range(-1.0..1.0),
+                })
+                .unwrap();
+        }
+    }
+}
+
+/// Draws a varying amount of numbers, as an unrelated new feature might.
+struct AmbientNoiseSystem;
+impl System for AmbientNoiseSystem {
+    fn run(&self, world: &mut World) {
+        let mut rng = world
+            .get_resource::<Rng>()
+            .unwrap()
+            .fork(TypeId::of::<Self>());
+        let draws = rng.range(1..20);
+        let level = (0..draws).map(|_| rng.next_u64() % 100).sum();
+        world.insert_resource(NoiseLevel(level));
+    }
+}
+
+fn goblins_in_spawn_order(world: &World) -> Vec<Entity> {
+    let mut goblins: Vec<_> = Query::<Goblin>::new()
+        .iter(world)
+        .map(|(entity, goblin)| (goblin.serial, entity))
+        .collect();
+    goblins.sort_unstable_by_key(|(serial, _)| *serial);
+    goblins.into_iter().map(|(_, entity)| entity).collect()
+}
+
+/// Runs `ticks` ticks and returns every goblin, in spawn order.
+fn play(seed: u64, with_noise: bool, ticks: usize) -> Vec<(u32, (f32, f32))> {
+    let mut world = World::new();
+    world.insert_resource(Rng::seeded(seed));
+
+    let mut scheduler = SequentialSystemScheduler::new();
+    if with_noise {
+        scheduler.add_system(AmbientNoiseSystem).unwrap();
+    }
+    scheduler.add_system(GoblinSpawnSystem).unwrap();
+    scheduler.add_system(WanderSystem).unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..ticks {
+        scheduler.run_tick(&mut world);
+    }
+    assert_eq!(world.get_resource::<Rng>().unwrap().tick(), ticks as u64);
+
+    goblins_in_spawn_order(&world)
+        .into_iter()
+        .map(|goblin| {
+            let position = world.get_component::<Position>(goblin).unwrap();
+            let stats = world.get_component::<Goblin>(goblin).unwrap();
+            (stats.hit_points, (position.x, position.y))
+        })
+        .collect()
+}
+
+#[test]
+fn test_same_seed_same_outcome() {
+    let first = play(42, false, 10);
+    let second = play(42, false, 10);
+
+    assert_eq!(first.len(), 10);
+    assert_eq!(first, second);
+    assert_ne!(first, play(7, false, 10));
+}
+
+#[test]
+fn test_goblins_differ_from_tick_to_tick() {
+    let goblins = play(42, false, 10);
+    let hit_points: std::collections::HashSet<_> = goblins.iter().map(|(hp, _)| *hp).collect();
+    assert!(hit_points.len() > 1, "every tick rolled the same goblin");
+}
+
+#[test]
+fn test_new_system_does_not_perturb_existing_streams() {
+    assert_eq!(play(42, false, 10), play(42, true, 10));
+}
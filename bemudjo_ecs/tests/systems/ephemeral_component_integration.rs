@@ -3,7 +3,7 @@
 //! Tests focused on ephemeral component behavior within the system scheduler,
 //! including cross-system communication and lifecycle management.
 
-use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -180,7 +180,7 @@ impl LoggingSystem {
 }
 
 impl System for LoggingSystem {
-    fn after_run(&self, world: &World) {
+    fn after_run(&self, world: &WorldView) {
         let entities: Vec<_> = world.entities().cloned().collect();
 
         for &entity in &entities {
@@ -341,7 +341,7 @@ fn test_ephemeral_components_persist_across_system_phases() {
             }
         }
 
-        fn after_run(&self, world: &World) {
+        fn after_run(&self, world: &WorldView) {
             for entity in world.entities().cloned().collect::<Vec<_>>() {
                 if world.has_ephemeral_component::<MovementEvent>(entity) {
                     self.events
@@ -630,3 +630,163 @@ fn test_ephemeral_components_performance_with_many_entities() {
         assert!(pos.y > 0.0);
     }
 }
+
+#[test]
+fn test_ephemeral_components_of_deleted_entities_do_not_accumulate() {
+    struct SpawnWithEventSystem;
+    impl System for SpawnWithEventSystem {
+        fn run(&self, world: &mut World) {
+            let entity = world.spawn_entity();
+            world
+                .add_ephemeral_component(
+                    entity,
+                    DamageEvent {
+                        amount: 1,
+                        source: "trap".to_string(),
+                    },
+                )
+                .unwrap();
+        }
+    }
+
+    struct DespawnSystem;
+    impl System for DespawnSystem {
+        fn run(&self, world: &mut World) {
+            let doomed: Vec<_> = world.entities().cloned().collect();
+            for entity in doomed {
+                world.delete_entity(entity);
+            }
+
+            // Cleaning up mid-tick drops the ephemeral data of deleted entities too
+            world.cleanup_deleted_entities();
+            assert_eq!(world.ephemeral_storage_len::<DamageEvent>(), 0);
+        }
+    }
+
+    let mut world = World::new();
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler.add_system(SpawnWithEventSystem).unwrap();
+    scheduler.add_system(DespawnSystem).unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..1000 {
+        scheduler.run_tick(&mut world);
+
+        assert_eq!(world.ephemeral_storage_len::<DamageEvent>(), 0);
+        let stats = world.stats();
+        assert_eq!(stats.entity_count, 0);
+        assert_eq!(stats.soft_deleted_count, 0);
+        assert_eq!(stats.ephemeral_component_count, 0);
+    }
+}
+
+#[test]
+fn test_ephemeral_component_staged_in_after_run_is_visible_next_tick_only() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct WasRendered {
+        frame: u32,
+    }
+    impl Component for WasRendered {}
+
+    /// Tags every entity it renders, for the systems of the next tick
+    struct RenderSystem {
+        frame: RefCell<u32>,
+    }
+    impl System for RenderSystem {
+        fn after_run(&self, world: &WorldView) {
+            let frame = *self.frame.borrow();
+            if frame == 2 {
+                for &entity in world.entities() {
+                    world
+                        .add_ephemeral_component_next_tick(entity, WasRendered { frame })
+                        .unwrap();
+                }
+            }
+            *self.frame.borrow_mut() += 1;
+        }
+    }
+
+    type Sightings = Rc<RefCell<Vec<(u32, &'static str, Option<WasRendered>)>>>;
+
+    /// Records, per tick, what each phase sees
+    struct AuditSystem {
+        seen: Sightings,
+        tick: RefCell<u32>,
+        entity: bemudjo_ecs::Entity,
+    }
+    impl AuditSystem {
+        fn record(&self, phase: &'static str, world: &World) {
+            let tagged = world
+                .get_ephemeral_component::<WasRendered>(self.entity)
+                .cloned();
+            self.seen
+                .borrow_mut()
+                .push((*self.tick.borrow(), phase, tagged));
+        }
+    }
+    impl System for AuditSystem {
+        fn before_run(&self, world: &WorldView) {
+            self.record("before", world);
+        }
+        fn run(&self, world: &mut World) {
+            self.record("run", world);
+        }
+        fn after_run(&self, world: &WorldView) {
+            self.record("after", world);
+            *self.tick.borrow_mut() += 1;
+        }
+    }
+
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler
+        .add_system(RenderSystem {
+            frame: RefCell::new(0),
+        })
+        .unwrap();
+    scheduler
+        .add_system(AuditSystem {
+            seen: seen.clone(),
+            tick: RefCell::new(0),
+            entity,
+        })
+        .unwrap();
+    scheduler.build().unwrap();
+
+    for _ in 0..5 {
+        scheduler.run_tick(&mut world);
+    }
+
+    // Staged in tick 2's after_run, visible in every phase of tick 3 only
+    let seen = seen.borrow();
+    assert_eq!(seen.len(), 15);
+    for (tick, phase, tagged) in seen.iter() {
+        if *tick == 3 {
+            assert_eq!(tagged, &Some(WasRendered { frame: 2 }), "{phase}");
+        } else {
+            assert_eq!(tagged, &None, "tick {tick} {phase}");
+        }
+    }
+    assert!(!world.has_ephemeral_component::<WasRendered>(entity));
+}
+
+#[test]
+fn test_staged_ephemeral_component_of_deleted_entity_is_dropped() {
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+
+    world
+        .add_ephemeral_component_next_tick(entity, HealEvent { amount: 5 })
+        .unwrap();
+    world.delete_entity(entity);
+    world.cleanup_deleted_entities();
+    world.promote_next_tick_ephemerals();
+
+    assert!(world.get_ephemeral_component::<HealEvent>(entity).is_none());
+    assert!(world
+        .add_ephemeral_component_next_tick(entity, HealEvent { amount: 5 })
+        .is_err());
+}
@@ -5,7 +5,10 @@
 //! - Cross-system dependencies
 //! - Error handling and recovery
 //! - System execution patterns
+//! - Reading deleted entities before cleanup
 
+pub mod deleted_entity_access;
+pub mod deterministic_rng;
 pub mod ephemeral_component_integration;
 pub mod scheduler_integration;
 pub mod system_dependencies;
@@ -3,7 +3,7 @@
 //! Tests focused on system scheduler behavior, execution order,
 //! and system lifecycle management.
 
-use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -51,7 +51,7 @@ impl CounterSystem {
 }
 
 impl System for CounterSystem {
-    fn before_run(&self, _world: &World) {
+    fn before_run(&self, _world: &WorldView) {
         self.execution_log
             .borrow_mut()
             .push(format!("Before CounterSystem({})", self.increment));
@@ -75,7 +75,7 @@ impl System for CounterSystem {
         }
     }
 
-    fn after_run(&self, _world: &World) {
+    fn after_run(&self, _world: &WorldView) {
         self.execution_log
             .borrow_mut()
             .push(format!("After CounterSystem({})", self.increment));
@@ -185,7 +185,7 @@ fn test_basic_scheduler_execution() {
         .add_system(CounterSystem::new(1, log.clone()))
         .unwrap();
     scheduler
-        .add_system(CounterSystem::new(10, log.clone()))
+        .add_system_allow_duplicates(CounterSystem::new(10, log.clone()))
         .unwrap();
 
     scheduler.build().unwrap();
@@ -443,7 +443,7 @@ fn test_system_phase_interactions() {
     }
 
     impl System for PhaseTestSystem {
-        fn before_run(&self, world: &World) {
+        fn before_run(&self, world: &WorldView) {
             let count = world.entities().count();
             self.phase_log
                 .borrow_mut()
@@ -466,7 +466,7 @@ fn test_system_phase_interactions() {
                 .push(("run_end".to_string(), count));
         }
 
-        fn after_run(&self, world: &World) {
+        fn after_run(&self, world: &WorldView) {
             let count = world.entities().count();
             self.phase_log
                 .borrow_mut()
@@ -545,7 +545,7 @@ fn test_scheduler_performance_with_many_systems() {
     // Add many systems
     for _i in 0..100 {
         scheduler
-            .add_system(CounterSystem::new(1, log.clone()))
+            .add_system_allow_duplicates(CounterSystem::new(1, log.clone()))
             .unwrap();
     }
 
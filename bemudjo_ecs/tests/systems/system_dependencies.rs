@@ -675,13 +675,13 @@ fn test_system_dependency_ordering() {
         .add_system(OrderTrackingSystem::new("Physics", execution_order.clone()))
         .unwrap();
     scheduler
-        .add_system(OrderTrackingSystem::new("AI", execution_order.clone()))
+        .add_system_allow_duplicates(OrderTrackingSystem::new("AI", execution_order.clone()))
         .unwrap();
     scheduler
-        .add_system(OrderTrackingSystem::new("Combat", execution_order.clone()))
+        .add_system_allow_duplicates(OrderTrackingSystem::new("Combat", execution_order.clone()))
         .unwrap();
     scheduler
-        .add_system(OrderTrackingSystem::new("Death", execution_order.clone()))
+        .add_system_allow_duplicates(OrderTrackingSystem::new("Death", execution_order.clone()))
         .unwrap();
     scheduler.build().unwrap();
 
@@ -55,7 +55,7 @@ impl System for ComponentErrorSystem {
             // Try to add duplicate components (should fail)
             if world.has_component::<Counter>(entity) {
                 let result = world.add_component(entity, Counter { value: 999 });
-                if let Err(ComponentError::ComponentAlreadyExists) = result {
+                if let Err(ComponentError::AlreadyExists { .. }) = result {
                     self.error_log
                         .borrow_mut()
                         .push("Duplicate component error handled".to_string());
@@ -67,7 +67,7 @@ impl System for ComponentErrorSystem {
                 health.current += 10;
                 health
             });
-            if let Err(ComponentError::ComponentNotFound) = result {
+            if let Err(ComponentError::NotFound { .. }) = result {
                 self.error_log
                     .borrow_mut()
                     .push("Component not found error handled".to_string());
@@ -81,7 +81,7 @@ impl System for ComponentErrorSystem {
         };
 
         let result = world.add_component(fake_entity, Position { x: 0.0, y: 0.0 });
-        if let Err(ComponentError::ComponentNotFound) = result {
+        if let Err(ComponentError::EntityNotFound { .. }) = result {
             self.error_log
                 .borrow_mut()
                 .push("Fake entity error handled".to_string());
@@ -3,7 +3,7 @@
 //! Tests focus on different system implementation patterns and
 //! advanced usage scenarios of the System trait.
 
-use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World};
+use bemudjo_ecs::{Component, SequentialSystemScheduler, System, World, WorldView};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -40,7 +40,7 @@ impl StatefulSystem {
 }
 
 impl System for StatefulSystem {
-    fn before_run(&self, world: &World) {
+    fn before_run(&self, world: &WorldView) {
         let entity_count = world.entities().count();
         self.shared_state
             .borrow_mut()
@@ -57,7 +57,7 @@ impl System for StatefulSystem {
             .push("Run: Added entity".to_string());
     }
 
-    fn after_run(&self, world: &World) {
+    fn after_run(&self, world: &WorldView) {
         let entity_count = world.entities().count();
         self.shared_state
             .borrow_mut()
@@ -79,7 +79,7 @@ impl ReadOnlySystem {
 }
 
 impl System for ReadOnlySystem {
-    fn before_run(&self, world: &World) {
+    fn before_run(&self, world: &WorldView) {
         let total_counter_value: i32 = world
             .entities()
             .filter_map(|&entity| world.get_component::<Counter>(entity))
@@ -104,7 +104,7 @@ impl PostProcessSystem {
 }
 
 impl System for PostProcessSystem {
-    fn after_run(&self, world: &World) {
+    fn after_run(&self, world: &WorldView) {
         for &entity in world.entities() {
             if let Some(tag) = world.get_component::<Tag>(entity) {
                 self.results.borrow_mut().push(tag.name.clone());
@@ -478,8 +478,8 @@ fn test_multiple_systems_same_type() {
 
     // Add multiple instances of the same system type
     scheduler.add_system(QuerySystem).unwrap();
-    scheduler.add_system(QuerySystem).unwrap();
-    scheduler.add_system(QuerySystem).unwrap();
+    scheduler.add_system_allow_duplicates(QuerySystem).unwrap();
+    scheduler.add_system_allow_duplicates(QuerySystem).unwrap();
 
     scheduler.build().unwrap();
 
@@ -622,3 +622,68 @@ fn test_system_with_default_implementations() {
     let counter = world.get_component::<Counter>(entity).unwrap();
     assert_eq!(counter.value, 42);
 }
+
+#[test]
+fn test_after_run_renders_through_world_view() {
+    struct RenderSystem {
+        frames: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl System for RenderSystem {
+        fn run(&self, world: &mut World) {
+            for entity in world.entities().cloned().collect::<Vec<_>>() {
+                world
+                    .update_component::<Counter, _>(entity, |counter| Counter {
+                        value: counter.value + 1,
+                    })
+                    .ok();
+            }
+        }
+
+        fn after_run(&self, world: &WorldView) {
+            let mut lines: Vec<String> = world
+                .query::<Tag>()
+                .map(|(_, tag)| tag.name.clone())
+                .collect();
+            lines.sort();
+
+            let total: i32 = world.query::<Counter>().map(|(_, c)| c.value).sum();
+            lines.push(format!("total: {total}"));
+            self.frames.borrow_mut().push(lines.join(", "));
+        }
+    }
+
+    let mut world = World::new();
+    for (name, value) in [("goblin", 1), ("orc", 10)] {
+        let entity = world.spawn_entity();
+        world
+            .add_component(
+                entity,
+                Tag {
+                    name: name.to_string(),
+                },
+            )
+            .unwrap();
+        world.add_component(entity, Counter { value }).unwrap();
+    }
+
+    let frames = Rc::new(RefCell::new(Vec::new()));
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler
+        .add_system(RenderSystem {
+            frames: frames.clone(),
+        })
+        .unwrap();
+    scheduler.build().unwrap();
+
+    scheduler.run_tick(&mut world);
+    scheduler.run_tick(&mut world);
+
+    assert_eq!(
+        *frames.borrow(),
+        vec![
+            "goblin, orc, total: 13".to_string(),
+            "goblin, orc, total: 15".to_string()
+        ]
+    );
+}
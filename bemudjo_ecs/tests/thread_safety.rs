@@ -0,0 +1,11 @@
+//! Compile tests for the `thread-safe-components` feature.
+//!
+//! Error messages are snapshotted in `tests/ui/*.stderr`. After an intentional
+//! change, regenerate them with `TRYBUILD=overwrite cargo test -p bemudjo_ecs --test thread_safety`.
+#![cfg(feature = "thread-safe-components")]
+
+#[test]
+fn components_must_be_send_and_sync() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,11 @@
+use bemudjo_ecs::Component;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Inventory {
+    items: Rc<RefCell<Vec<String>>>,
+}
+
+impl Component for Inventory {}
+
+fn main() {}
@@ -0,0 +1,127 @@
+//! Derive macros for `bemudjo_ecs`.
+//!
+//! Use them through the `derive` feature of `bemudjo_ecs` rather than
+//! depending on this crate directly:
+//!
+//! ```toml
+//! bemudjo_ecs = { version = "0.1", features = ["derive"] }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implements `bemudjo_ecs::Component` for a struct or enum.
+///
+/// Components must be `Clone + 'static`, and `Send + Sync` with the
+/// `thread-safe-components` feature of `bemudjo_ecs`. The derive checks
+/// `Clone` at compile time so a missing `Clone` is reported on the type itself
+/// rather than deep inside a later `update_component` call.
+///
+/// # Attributes
+/// * `#[component(ephemeral_only)]` - The type is an event that may only be
+///   attached with `add_ephemeral_component`; `add_component` rejects it with
+///   `ComponentError::EphemeralOnly`.
+///
+/// # Example
+/// ```
+/// use bemudjo_ecs::{Component, World};
+///
+/// #[derive(Component, Clone, Debug, PartialEq)]
+/// struct Health { value: u32 }
+///
+/// #[derive(Component, Clone, Debug)]
+/// #[component(ephemeral_only)]
+/// struct DamageEvent { amount: u32 }
+///
+/// let mut world = World::new();
+/// let entity = world.spawn_entity();
+/// world.add_component(entity, Health { value: 100 }).unwrap();
+///
+/// assert!(world.add_component(entity, DamageEvent { amount: 5 }).is_err());
+/// world.add_ephemeral_component(entity, DamageEvent { amount: 5 }).unwrap();
+/// ```
+#[proc_macro_derive(Component, attributes(component))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_component(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_component(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ephemeral_only = parse_component_attributes(&input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let ephemeral_only_fn = ephemeral_only.then(|| {
+        quote! {
+            fn ephemeral_only() -> bool {
+                true
+            }
+        }
+    });
+
+    let must_be_clone = quote!(::bemudjo_ecs::__private::ComponentMustBeClone);
+    if input.generics.params.is_empty() {
+        // Asserted separately so the error points at the type, not the impl
+        let assertion = quote_spanned! {name.span()=>
+            const _: fn() = {
+                fn assert_component_must_be_clone<T: #must_be_clone>() {}
+                assert_component_must_be_clone::<#name>
+            };
+        };
+
+        Ok(quote! {
+            impl ::bemudjo_ecs::Component for #name {
+                #ephemeral_only_fn
+            }
+
+            #assertion
+        })
+    } else {
+        // Generic components are only components for arguments that make them
+        // Clone, and Send and Sync if the ECS requires it
+        let mut where_clause = where_clause.cloned().unwrap_or_else(|| syn::WhereClause {
+            where_token: Default::default(),
+            predicates: Default::default(),
+        });
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#name #ty_generics: #must_be_clone));
+        where_clause.predicates.push(syn::parse_quote!(
+            #name #ty_generics: ::bemudjo_ecs::component::MaybeSendSync
+        ));
+
+        Ok(quote! {
+            impl #impl_generics ::bemudjo_ecs::Component for #name #ty_generics #where_clause {
+                #ephemeral_only_fn
+            }
+        })
+    }
+}
+
+/// Parses the `#[component(...)]` attributes, returning whether the type is
+/// ephemeral-only.
+fn parse_component_attributes(input: &DeriveInput) -> syn::Result<bool> {
+    let mut ephemeral_only = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("component"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ephemeral_only") {
+                ephemeral_only = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown component attribute, expected `ephemeral_only`"))
+            }
+        })?;
+    }
+
+    Ok(ephemeral_only)
+}
@@ -0,0 +1,11 @@
+//! Compile tests for `#[derive(Component)]`.
+//!
+//! Error messages are snapshotted in `tests/ui/*.stderr`. After an intentional
+//! change, regenerate them with `TRYBUILD=overwrite cargo test -p bemudjo_ecs_derive`.
+
+#[test]
+fn derive_component() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass_*.rs");
+    cases.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,12 @@
+use bemudjo_ecs::{Component, World};
+
+#[derive(Component, Clone)]
+struct Tagged<T>(T);
+
+struct NotClone;
+
+fn main() {
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+    world.add_component(entity, Tagged(NotClone)).unwrap();
+}
@@ -0,0 +1,8 @@
+use bemudjo_ecs::Component;
+
+#[derive(Component, Debug)]
+struct Inventory {
+    items: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use bemudjo_ecs::Component;
+
+#[derive(Component, Clone)]
+#[component(persistent)]
+struct Health {
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use bemudjo_ecs::{Component, ComponentError, World};
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Clone, Debug, PartialEq)]
+enum Stance {
+    Standing,
+    Sitting,
+}
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Tagged<T: Clone + 'static>(T);
+
+#[derive(Component, Clone, Debug, PartialEq)]
+#[component(ephemeral_only)]
+struct DamageEvent {
+    amount: u32,
+}
+
+fn main() {
+    let mut world = World::new();
+    let entity = world.spawn_entity();
+
+    world.add_component(entity, Position { x: 1.0, y: 2.0 }).unwrap();
+    world.add_component(entity, Stance::Standing).unwrap();
+    world.add_component(entity, Tagged("guard")).unwrap();
+    world
+        .update_component::<Stance, _>(entity, |_| Stance::Sitting)
+        .unwrap();
+    assert_eq!(world.get_component::<Stance>(entity), Some(&Stance::Sitting));
+    assert_eq!(world.get_component::<Tagged<&str>>(entity), Some(&Tagged("guard")));
+
+    assert!(!Position::ephemeral_only());
+    assert!(DamageEvent::ephemeral_only());
+    assert!(matches!(
+        world.add_component(entity, DamageEvent { amount: 3 }),
+        Err(ComponentError::EphemeralOnly { .. })
+    ));
+    world
+        .add_ephemeral_component(entity, DamageEvent { amount: 3 })
+        .unwrap();
+    assert_eq!(
+        world.get_ephemeral_component::<DamageEvent>(entity),
+        Some(&DamageEvent { amount: 3 })
+    );
+}
@@ -0,0 +1,41 @@
+use bemudjo_ecs::{Component, Entity};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// A room players can stand in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Room {
+    pub name: String,
+    pub description: String,
+}
+impl Component for Room {}
+
+/// The room an entity is currently located in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub room: Entity,
+}
+impl Component for Location {}
+
+/// A connected player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Player {
+    pub name: String,
+}
+impl Component for Player {}
+
+/// An item lying around in a room.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item {
+    pub name: String,
+}
+impl Component for Item {}
+
+/// Outgoing message channel for a connected player.
+///
+/// Every line sent through this channel is written to the player's socket
+/// by the connection writer task, so game logic never has to await on I/O.
+#[derive(Clone, Debug)]
+pub struct Connection {
+    pub sender: UnboundedSender<String>,
+}
+impl Component for Connection {}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use bemudjo_ecs::Entity;
+use tokio::sync::broadcast;
+
+/// Message sent to every connected client when the server shuts down.
+pub const GOODBYE_MESSAGE: &str = "Server is shutting down. Goodbye!\r\n";
+
+/// Registry of the active connections and the server-wide shutdown signal.
+///
+/// The manager is `Send + Sync` and needs no lock around it, so shutdown can
+/// be triggered from any task or thread, e.g. a Ctrl-C handler, without
+/// touching the game world's mutex. Cloning it is cheap and every clone shares
+/// the same registry.
+///
+/// # Example
+/// ```no_run
+/// use bemudjo_server_telnet::Server;
+/// use tokio::net::TcpListener;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let server = Server::new();
+/// let connections = server.connections();
+/// tokio::spawn(async move {
+///     let _ = tokio::signal::ctrl_c().await;
+///     connections.shutdown();
+/// });
+///
+/// // Returns once every client has been told goodbye
+/// let listener = TcpListener::bind("127.0.0.1:2323").await?;
+/// server.run(listener).await
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionManager {
+    state: Arc<Mutex<RegistryState>>,
+    shutdown: broadcast::Sender<()>,
+}
+
+#[derive(Debug, Default)]
+struct RegistryState {
+    connections: HashMap<Entity, SocketAddr>,
+    shutting_down: bool,
+}
+
+impl ConnectionManager {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        let (shutdown, _) = broadcast::channel(1);
+        Self {
+            state: Arc::new(Mutex::new(RegistryState::default())),
+            shutdown,
+        }
+    }
+
+    /// Asks every client to leave and the server to stop accepting connections.
+    ///
+    /// Calling it more than once has no further effect.
+    pub fn shutdown(&self) {
+        let mut state = self.lock();
+        if state.shutting_down {
+            return;
+        }
+        state.shutting_down = true;
+        // Nobody may be listening yet, the flag covers late subscribers
+        let _ = self.shutdown.send(());
+    }
+
+    /// Returns `true` once [`shutdown`](ConnectionManager::shutdown) was called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.lock().shutting_down
+    }
+
+    /// Returns the number of registered connections.
+    pub fn connection_count(&self) -> usize {
+        self.lock().connections.len()
+    }
+
+    /// Returns the player entities of the registered connections.
+    pub fn players(&self) -> Vec<Entity> {
+        self.lock().connections.keys().copied().collect()
+    }
+
+    /// Returns the peer address of a player's connection.
+    pub fn address(&self, player: Entity) -> Option<SocketAddr> {
+        self.lock().connections.get(&player).copied()
+    }
+
+    /// Subscribes to the shutdown signal.
+    ///
+    /// Subscribers only see a shutdown triggered after they subscribed, so
+    /// check [`is_shutting_down`](ConnectionManager::is_shutting_down) afterwards.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<()> {
+        self.shutdown.subscribe()
+    }
+
+    /// Registers a connection, unless the server is already shutting down.
+    ///
+    /// Returns `false` if the connection was refused.
+    pub(crate) fn register(&self, player: Entity, addr: SocketAddr) -> bool {
+        let mut state = self.lock();
+        if state.shutting_down {
+            return false;
+        }
+        state.connections.insert(player, addr);
+        true
+    }
+
+    /// Removes a connection. Unregistering twice is a no-op.
+    pub(crate) fn unregister(&self, player: Entity) {
+        self.lock().connections.remove(&player);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RegistryState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ConnectionManager {
+    /// Creates an empty registry using the default constructor.
+    ///
+    /// This is equivalent to calling `ConnectionManager::new()`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bemudjo_ecs::World;
+
+    #[test]
+    fn test_register_and_unregister() {
+        let mut world = World::new();
+        let alice = world.spawn_entity();
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+
+        let manager = ConnectionManager::new();
+        assert!(manager.register(alice, addr));
+        assert_eq!(manager.connection_count(), 1);
+        assert_eq!(manager.players(), vec![alice]);
+        assert_eq!(manager.address(alice), Some(addr));
+
+        manager.unregister(alice);
+        manager.unregister(alice);
+        assert_eq!(manager.connection_count(), 0);
+    }
+
+    #[test]
+    fn test_shutdown_reaches_subscribers_and_refuses_new_connections() {
+        let mut world = World::new();
+        let late = world.spawn_entity();
+
+        let manager = ConnectionManager::new();
+        let mut early = manager.subscribe();
+
+        manager.shutdown();
+        manager.shutdown();
+        assert!(manager.is_shutting_down());
+        assert!(early.try_recv().is_ok());
+        assert!(early.try_recv().is_err());
+
+        // A connection accepted concurrently with the shutdown is turned away
+        assert!(!manager.register(late, "127.0.0.1:4001".parse().unwrap()));
+        assert_eq!(manager.connection_count(), 0);
+    }
+}
@@ -0,0 +1,371 @@
+use std::time::Duration;
+
+use crate::components::{Connection, Item, Location, Player, Room};
+use crate::protocol::{Command, CommandParser};
+use crate::session::SessionManager;
+use bemudjo_ecs::{Entity, Query, World};
+
+/// How long a disconnected player is kept around by default.
+pub const DEFAULT_RECONNECT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// What the connection handler should do after a command has been processed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// Keep reading commands from the client.
+    Continue,
+    /// Close the connection.
+    Quit,
+}
+
+/// Shared game state backed by an ECS [`World`].
+///
+/// Every connected player is an entity with a [`Player`], a [`Location`] and a
+/// [`Connection`] component. Rooms and items are plain entities as well, so
+/// commands like `look` and `say` are answered with regular queries. The
+/// world also holds the [`SessionManager`] resource the server logs clients in
+/// with.
+///
+/// # Example
+/// ```
+/// use bemudjo_server_telnet::session::{ConnectionId, SessionManager};
+/// use bemudjo_server_telnet::{CommandOutcome, GameWorld};
+/// use tokio::sync::mpsc::unbounded_channel;
+///
+/// let mut game = GameWorld::new();
+/// let (sender, mut receiver) = unbounded_channel();
+/// SessionManager::connect(game.world_mut(), ConnectionId(1));
+/// let player = SessionManager::login(game.world_mut(), ConnectionId(1), "Alice", sender)
+///     .unwrap()
+///     .player();
+///
+/// assert_eq!(game.handle_command(player, "look"), CommandOutcome::Continue);
+/// assert!(receiver.try_recv().unwrap().contains("Town Square"));
+/// ```
+pub struct GameWorld {
+    world: World,
+    starting_room: Entity,
+}
+
+impl GameWorld {
+    /// Creates a new game world with a starting room and a few items.
+    ///
+    /// Disconnected players are kept for [`DEFAULT_RECONNECT_TIMEOUT`].
+    pub fn new() -> Self {
+        Self::with_reconnect_timeout(DEFAULT_RECONNECT_TIMEOUT)
+    }
+
+    /// Creates a new game world keeping disconnected players for `reconnect_timeout`.
+    pub fn with_reconnect_timeout(reconnect_timeout: Duration) -> Self {
+        let mut world = World::new();
+
+        let starting_room = world.spawn_entity();
+        world
+            .add_component(
+                starting_room,
+                Room {
+                    name: "Town Square".to_string(),
+                    description: "A quiet square paved with worn cobblestones.".to_string(),
+                },
+            )
+            .expect("starting room was just spawned");
+
+        for name in ["a rusty sword", "a wooden shield"] {
+            let item = world.spawn_entity();
+            world
+                .add_component(
+                    item,
+                    Item {
+                        name: name.to_string(),
+                    },
+                )
+                .expect("item was just spawned");
+            world
+                .add_component(
+                    item,
+                    Location {
+                        room: starting_room,
+                    },
+                )
+                .expect("item was just spawned");
+        }
+
+        world.insert_resource(SessionManager::new(starting_room, reconnect_timeout));
+
+        Self {
+            world,
+            starting_room,
+        }
+    }
+
+    /// Returns a reference to the underlying ECS world.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Returns a mutable reference to the underlying ECS world.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Returns the room new players are placed in.
+    pub fn starting_room(&self) -> Entity {
+        self.starting_room
+    }
+
+    /// Returns the name of a connected player.
+    pub fn player_name(&self, player: Entity) -> Option<&str> {
+        self.world
+            .get_component::<Player>(player)
+            .map(|p| p.name.as_str())
+    }
+
+    /// Parses and processes a single command line sent by a player.
+    ///
+    /// Blank lines are ignored and lines that can't be parsed are answered
+    /// with the parse error. See [`GameWorld::execute`].
+    pub fn handle_command(&mut self, player: Entity, line: &str) -> CommandOutcome {
+        match CommandParser::new().parse(line) {
+            Ok(Some(command)) => self.execute(player, command),
+            Ok(None) => CommandOutcome::Continue,
+            Err(error) => {
+                self.send(player, &error.to_string());
+                CommandOutcome::Continue
+            }
+        }
+    }
+
+    /// Processes a command sent by a player.
+    ///
+    /// Commands issued for a player that is no longer connected are ignored
+    /// and [`CommandOutcome::Quit`] is returned.
+    pub fn execute(&mut self, player: Entity, command: Command) -> CommandOutcome {
+        if self.world.get_component::<Player>(player).is_none() {
+            return CommandOutcome::Quit;
+        }
+
+        match command {
+            Command::Quit => {
+                self.send(player, "Goodbye!");
+                return CommandOutcome::Quit;
+            }
+            Command::Help => {
+                self.send(player, "Available commands:");
+                self.send(player, "  help - Show this help message");
+                self.send(player, "  look - Look around");
+                self.send(player, "  say <message> - Say something");
+                self.send(player, "  quit - Exit the game");
+            }
+            Command::Look => self.look(player),
+            Command::Say { message } => self.say(player, &message),
+            Command::Unknown { .. } => self.send(
+                player,
+                "Unknown command. Type 'help' for available commands.",
+            ),
+        }
+
+        CommandOutcome::Continue
+    }
+
+    /// Describes the player's room, the other connected players and the items in it.
+    fn look(&self, player: Entity) {
+        let Some(room) = self.room_of(player) else {
+            self.send(player, "You are floating in the void.");
+            return;
+        };
+
+        if let Some(info) = self.world.get_component::<Room>(room) {
+            self.send(player, &info.name);
+            self.send(player, &info.description);
+        }
+
+        // Players parked after a disconnect are out of sight
+        let players_query = Query::<Location>::new()
+            .with::<Player>()
+            .with::<Connection>();
+        let mut others: Vec<&str> = players_query
+            .iter(&self.world)
+            .filter(|(entity, location)| *entity != player && location.room == room)
+            .filter_map(|(entity, _)| self.player_name(entity))
+            .collect();
+        others.sort_unstable();
+
+        let items_query = Query::<Location>::new().with::<Item>();
+        let mut items: Vec<&str> = items_query
+            .iter(&self.world)
+            .filter(|(_, location)| location.room == room)
+            .filter_map(|(entity, _)| self.world.get_component::<Item>(entity))
+            .map(|item| item.name.as_str())
+            .collect();
+        items.sort_unstable();
+
+        if !others.is_empty() {
+            self.send(player, &format!("Also here: {}.", others.join(", ")));
+        }
+        if !items.is_empty() {
+            self.send(player, &format!("You see: {}.", items.join(", ")));
+        }
+    }
+
+    /// Broadcasts a message to every player sharing the speaker's room.
+    fn say(&self, player: Entity, message: &str) {
+        let Some(room) = self.room_of(player) else {
+            return;
+        };
+        let speaker = self.player_name(player).unwrap_or("Someone");
+
+        self.send(player, &format!("You say: {message}"));
+
+        let query = Query::<Location>::new().with::<Connection>();
+        for (entity, location) in query.iter(&self.world) {
+            if entity != player && location.room == room {
+                self.send(entity, &format!("{speaker} says: {message}"));
+            }
+        }
+    }
+
+    fn room_of(&self, entity: Entity) -> Option<Entity> {
+        self.world
+            .get_component::<Location>(entity)
+            .map(|location| location.room)
+    }
+
+    /// Queues a line of text for a player's connection.
+    ///
+    /// Messages for players whose connection already went away are dropped.
+    fn send(&self, player: Entity, line: &str) {
+        if let Some(connection) = self.world.get_component::<Connection>(player) {
+            let _ = connection.sender.send(format!("{line}\r\n"));
+        }
+    }
+}
+
+impl Default for GameWorld {
+    /// Creates a new game world using the default constructor.
+    ///
+    /// This is equivalent to calling `GameWorld::new()`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::ConnectionId;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+    /// Connects and logs in a client through the session manager, like the server does.
+    fn login(game: &mut GameWorld, id: u64, name: &str) -> (Entity, UnboundedReceiver<String>) {
+        let (sender, receiver) = unbounded_channel();
+        SessionManager::connect(game.world_mut(), ConnectionId(id));
+        let player = SessionManager::login(game.world_mut(), ConnectionId(id), name, sender)
+            .unwrap()
+            .player();
+        (player, receiver)
+    }
+
+    fn drain(receiver: &mut UnboundedReceiver<String>) -> String {
+        let mut output = String::new();
+        while let Ok(line) = receiver.try_recv() {
+            output.push_str(&line);
+        }
+        output
+    }
+
+    #[test]
+    fn test_login_places_player_in_starting_room() {
+        let mut game = GameWorld::new();
+        let (player, _receiver) = login(&mut game, 1, "Alice");
+
+        let location = game.world().get_component::<Location>(player).unwrap();
+        assert_eq!(location.room, game.starting_room());
+        assert_eq!(game.player_name(player), Some("Alice"));
+    }
+
+    #[test]
+    fn test_look_lists_other_players_and_items() {
+        let mut game = GameWorld::new();
+        let (alice, mut receiver) = login(&mut game, 1, "Alice");
+        login(&mut game, 2, "Bob");
+
+        game.handle_command(alice, "look");
+        let output = drain(&mut receiver);
+
+        assert!(output.contains("Town Square"));
+        assert!(output.contains("Also here: Bob."));
+        assert!(output.contains("a rusty sword"));
+        assert!(!output.contains("Alice"));
+    }
+
+    #[test]
+    fn test_say_broadcasts_to_room_only() {
+        let mut game = GameWorld::new();
+        let (alice, mut receiver1) = login(&mut game, 1, "Alice");
+        let (_, mut receiver2) = login(&mut game, 2, "Bob");
+        let (carol, mut receiver3) = login(&mut game, 3, "Carol");
+
+        let elsewhere = game.world_mut().spawn_entity();
+        game.world_mut()
+            .replace_component(carol, Location { room: elsewhere });
+
+        game.handle_command(alice, "say hello");
+
+        assert_eq!(drain(&mut receiver1), "You say: hello\r\n");
+        assert_eq!(drain(&mut receiver2), "Alice says: hello\r\n");
+        assert_eq!(drain(&mut receiver3), "");
+    }
+
+    #[test]
+    fn test_disconnected_player_is_parked_out_of_sight() {
+        let mut game = GameWorld::new();
+        let (alice, mut receiver1) = login(&mut game, 1, "Alice");
+        let (bob, mut receiver2) = login(&mut game, 2, "Bob");
+
+        assert_eq!(
+            SessionManager::disconnect(game.world_mut(), ConnectionId(2)),
+            Some(bob)
+        );
+
+        // Kept in the world for a later reconnect, but no longer reachable
+        assert!(game.world().entities().any(|&e| e == bob));
+        assert_eq!(game.player_name(bob), Some("Bob"));
+
+        game.handle_command(alice, "look");
+        assert!(!drain(&mut receiver1).contains("Bob"));
+
+        game.handle_command(alice, "say hello");
+        assert_eq!(drain(&mut receiver2), "");
+    }
+
+    #[test]
+    fn test_parse_errors_are_reported_to_the_player() {
+        let mut game = GameWorld::new();
+        let (player, mut receiver) = login(&mut game, 1, "Alice");
+
+        assert_eq!(game.handle_command(player, "say"), CommandOutcome::Continue);
+        assert_eq!(
+            drain(&mut receiver),
+            "Missing argument. Usage: say <message>\r\n"
+        );
+
+        assert_eq!(
+            game.execute(
+                player,
+                Command::Unknown {
+                    raw: "dance".to_string()
+                }
+            ),
+            CommandOutcome::Continue
+        );
+        assert!(drain(&mut receiver).starts_with("Unknown command."));
+    }
+
+    #[test]
+    fn test_quit_command() {
+        let mut game = GameWorld::new();
+        let (player, mut receiver) = login(&mut game, 1, "Alice");
+
+        assert_eq!(game.handle_command(player, "quit"), CommandOutcome::Quit);
+        assert_eq!(drain(&mut receiver), "Goodbye!\r\n");
+    }
+}
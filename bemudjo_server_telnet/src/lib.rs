@@ -0,0 +1,12 @@
+pub mod components;
+pub mod connections;
+pub mod game;
+pub mod protocol;
+pub mod server;
+pub mod session;
+
+// Re-export commonly used types
+pub use connections::ConnectionManager;
+pub use game::{CommandOutcome, GameWorld};
+pub use server::Server;
+pub use session::SessionManager;
@@ -0,0 +1,205 @@
+use std::fmt;
+
+/// A command typed by a player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Describe the current room.
+    Look,
+    /// Say something to everyone in the room.
+    Say { message: String },
+    /// List the available commands.
+    Help,
+    /// Leave the game.
+    Quit,
+    /// A command the parser doesn't know, as typed.
+    Unknown { raw: String },
+}
+
+/// Why a line couldn't be parsed into a [`Command`].
+///
+/// The `Display` output is meant to be shown to the player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The command needs an argument that wasn't given.
+    MissingArgument { usage: &'static str },
+    /// The command takes no argument but got one.
+    UnexpectedArgument { usage: &'static str },
+    /// A quoted argument wasn't closed.
+    UnterminatedQuote,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingArgument { usage } => write!(f, "Missing argument. Usage: {usage}"),
+            Self::UnexpectedArgument { usage } => {
+                write!(f, "Too many arguments. Usage: {usage}")
+            }
+            Self::UnterminatedQuote => write!(f, "Unterminated quote."),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Turns lines of player input into [`Command`]s.
+///
+/// A line is split into whitespace-separated words. Double quotes group words
+/// into a single argument, keeping the spacing inside, and `\"` or `\\` stand
+/// for a literal quote or backslash. Command names are case-insensitive.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CommandParser;
+
+impl CommandParser {
+    /// Creates a parser.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parses a line, returning `Ok(None)` if it is blank.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_server_telnet::protocol::{Command, CommandParser, ParseError};
+    ///
+    /// let parser = CommandParser::new();
+    /// assert_eq!(parser.parse("  LOOK "), Ok(Some(Command::Look)));
+    /// assert_eq!(
+    ///     parser.parse("say hello   there"),
+    ///     Ok(Some(Command::Say { message: "hello there".to_string() }))
+    /// );
+    /// assert_eq!(parser.parse(""), Ok(None));
+    /// assert!(matches!(parser.parse("say"), Err(ParseError::MissingArgument { .. })));
+    /// ```
+    pub fn parse(&self, line: &str) -> Result<Option<Command>, ParseError> {
+        let words = tokenize(line)?;
+        let Some((name, args)) = words.split_first() else {
+            return Ok(None);
+        };
+
+        let command = match name.to_lowercase().as_str() {
+            "look" | "l" => {
+                no_arguments(args, "look")?;
+                Command::Look
+            }
+            "help" => {
+                no_arguments(args, "help")?;
+                Command::Help
+            }
+            "quit" | "exit" => {
+                no_arguments(args, "quit")?;
+                Command::Quit
+            }
+            "say" => {
+                if args.is_empty() {
+                    return Err(ParseError::MissingArgument {
+                        usage: "say <message>",
+                    });
+                }
+                Command::Say {
+                    message: args.join(" "),
+                }
+            }
+            _ => Command::Unknown {
+                raw: line.trim().to_string(),
+            },
+        };
+        Ok(Some(command))
+    }
+}
+
+fn no_arguments(args: &[String], usage: &'static str) -> Result<(), ParseError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(ParseError::UnexpectedArgument { usage })
+    }
+}
+
+/// Splits a line into words, honoring double quotes and backslash escapes.
+fn tokenize(line: &str) -> Result<Vec<String>, ParseError> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(words);
+        }
+
+        let mut word = String::new();
+        let mut in_quotes = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                '\\' if matches!(chars.peek(), Some('"' | '\\')) => {
+                    word.extend(chars.next());
+                }
+                c if c.is_whitespace() && !in_quotes => break,
+                c => word.push(c),
+            }
+        }
+        if in_quotes {
+            return Err(ParseError::UnterminatedQuote);
+        }
+        words.push(word);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Option<Command>, ParseError> {
+        CommandParser::new().parse(line)
+    }
+
+    fn say(message: &str) -> Result<Option<Command>, ParseError> {
+        Ok(Some(Command::Say {
+            message: message.to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_commands_and_aliases() {
+        assert_eq!(parse("look"), Ok(Some(Command::Look)));
+        assert_eq!(parse("L"), Ok(Some(Command::Look)));
+        assert_eq!(parse("\tHelp  "), Ok(Some(Command::Help)));
+        assert_eq!(parse("exit"), Ok(Some(Command::Quit)));
+        assert_eq!(parse("   "), Ok(None));
+        assert_eq!(
+            parse(" dance  wildly "),
+            Ok(Some(Command::Unknown {
+                raw: "dance  wildly".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_quoted_arguments() {
+        assert_eq!(parse("say hello   world"), say("hello world"));
+        assert_eq!(parse("say \"hello   world\""), say("hello   world"));
+        assert_eq!(parse("say it's \"fine\" now"), say("it's fine now"));
+        assert_eq!(parse(r#"say \"quoted\" \\o/"#), say(r#""quoted" \o/"#));
+        assert_eq!(parse("say \"\""), say(""));
+    }
+
+    #[test]
+    fn test_argument_validation() {
+        assert_eq!(
+            parse("say"),
+            Err(ParseError::MissingArgument {
+                usage: "say <message>"
+            })
+        );
+        assert_eq!(
+            parse("look around"),
+            Err(ParseError::UnexpectedArgument { usage: "look" })
+        );
+        assert_eq!(parse("say \"oops"), Err(ParseError::UnterminatedQuote));
+        assert_eq!(
+            parse("quit now").unwrap_err().to_string(),
+            "Too many arguments. Usage: quit"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Turning the raw bytes sent by a telnet client into game commands.
+//!
+//! Input goes through two layers:
+//! 1. [`TelnetCodec`] strips telnet negotiation, answers it, applies line
+//!    editing and splits the stream into lines.
+//! 2. [`CommandParser`] turns each line into a [`Command`].
+//!
+//! # Example
+//! ```
+//! use bemudjo_server_telnet::protocol::{Command, CommandParser, TelnetCodec};
+//!
+//! let mut codec = TelnetCodec::new();
+//! // A client offering to negotiate its window size, then typing a command
+//! let decoded = codec.decode(b"\xff\xfb\x1fsay \"hi  there\"\r\n");
+//! assert_eq!(decoded.replies, b"\xff\xfe\x1f"); // IAC DONT NAWS
+//!
+//! let parser = CommandParser::new();
+//! assert_eq!(
+//!     parser.parse(&decoded.lines[0]),
+//!     Ok(Some(Command::Say { message: "hi  there".to_string() }))
+//! );
+//! ```
+
+mod command;
+mod telnet;
+
+pub use command::{Command, CommandParser, ParseError};
+pub use telnet::{Decoded, TelnetCodec};
@@ -0,0 +1,213 @@
+/// Interpret As Command: starts every telnet command sequence.
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+/// Start of a subnegotiation, ended by `IAC SE`.
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const BACKSPACE: u8 = 0x08;
+const DELETE: u8 = 0x7f;
+
+/// Where the codec is within a telnet command sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Data,
+    /// Just saw a carriage return; a following LF or NUL belongs to it.
+    CarriageReturn,
+    Iac,
+    /// Saw `IAC` followed by `WILL`, `WONT`, `DO` or `DONT`.
+    Negotiation(u8),
+    Subnegotiation,
+    SubnegotiationIac,
+}
+
+/// Output of [`TelnetCodec::decode`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Decoded {
+    /// The lines completed by the decoded bytes, without line endings.
+    pub lines: Vec<String>,
+    /// Negotiation replies to send back to the client as they are.
+    pub replies: Vec<u8>,
+}
+
+/// Incremental decoder for the client side of a telnet connection.
+///
+/// Bytes can be fed in chunks of any size: command sequences and lines split
+/// across reads are reassembled. The codec:
+/// * refuses every option the client offers or requests (`WILL` is answered
+///   with `DONT`, `DO` with `WONT`) and skips subnegotiations,
+/// * accepts `CR LF`, `CR NUL`, a bare `CR` or a bare `LF` as line ending,
+/// * applies backspace and delete to the line being typed,
+/// * decodes lines as UTF-8, replacing invalid sequences.
+#[derive(Clone, Debug)]
+pub struct TelnetCodec {
+    state: State,
+    line: Vec<u8>,
+}
+
+impl TelnetCodec {
+    /// Creates a codec at the start of a connection.
+    pub fn new() -> Self {
+        Self {
+            state: State::Data,
+            line: Vec::new(),
+        }
+    }
+
+    /// Decodes the next chunk of bytes received from the client.
+    ///
+    /// # Example
+    /// ```
+    /// use bemudjo_server_telnet::protocol::TelnetCodec;
+    ///
+    /// let mut codec = TelnetCodec::new();
+    /// assert!(codec.decode(b"lo").lines.is_empty());
+    ///
+    /// let decoded = codec.decode(b"ok\r\nhelp\n");
+    /// assert_eq!(decoded.lines, vec!["look", "help"]);
+    /// ```
+    pub fn decode(&mut self, bytes: &[u8]) -> Decoded {
+        let mut decoded = Decoded::default();
+
+        for &byte in bytes {
+            self.state = match (self.state, byte) {
+                (State::Data | State::CarriageReturn, IAC) => State::Iac,
+                // The line already ended at the CR
+                (State::CarriageReturn, b'\n' | 0) => State::Data,
+                (State::Data | State::CarriageReturn, b'\r') => {
+                    self.finish_line(&mut decoded);
+                    State::CarriageReturn
+                }
+                (State::Data, b'\n') => {
+                    self.finish_line(&mut decoded);
+                    State::Data
+                }
+                (State::Data | State::CarriageReturn, BACKSPACE | DELETE) => {
+                    self.pop_char();
+                    State::Data
+                }
+                (State::Data | State::CarriageReturn, byte) => {
+                    if !byte.is_ascii_control() || byte == b'\t' {
+                        self.line.push(byte);
+                    }
+                    State::Data
+                }
+                // An escaped 255 data byte
+                (State::Iac, IAC) => {
+                    self.line.push(IAC);
+                    State::Data
+                }
+                (State::Iac, WILL | WONT | DO | DONT) => State::Negotiation(byte),
+                (State::Iac, SB) => State::Subnegotiation,
+                // NOP, GA, AYT and the other two-byte commands carry no data
+                (State::Iac, _) => State::Data,
+                (State::Negotiation(verb), option) => {
+                    match verb {
+                        WILL => decoded.replies.extend_from_slice(&[IAC, DONT, option]),
+                        DO => decoded.replies.extend_from_slice(&[IAC, WONT, option]),
+                        // Refusals need no answer, the option is already off
+                        _ => {}
+                    }
+                    State::Data
+                }
+                (State::Subnegotiation, IAC) => State::SubnegotiationIac,
+                (State::Subnegotiation, _) => State::Subnegotiation,
+                (State::SubnegotiationIac, SE) => State::Data,
+                (State::SubnegotiationIac, _) => State::Subnegotiation,
+            };
+        }
+
+        decoded
+    }
+
+    fn finish_line(&mut self, decoded: &mut Decoded) {
+        let line = std::mem::take(&mut self.line);
+        decoded
+            .lines
+            .push(String::from_utf8_lossy(&line).into_owned());
+    }
+
+    /// Removes the last character, including all bytes of a multi-byte one.
+    fn pop_char(&mut self) {
+        while let Some(byte) = self.line.pop() {
+            // Stop after removing the first byte of a UTF-8 sequence
+            if byte & 0xC0 != 0x80 {
+                break;
+            }
+        }
+    }
+}
+
+impl Default for TelnetCodec {
+    /// Creates a codec using the default constructor.
+    ///
+    /// This is equivalent to calling `TelnetCodec::new()`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_ending_variants() {
+        let mut codec = TelnetCodec::new();
+        let decoded = codec.decode(b"look\r\nhelp\nsay a\rsay b\r\0quit\r");
+        assert_eq!(
+            decoded.lines,
+            vec!["look", "help", "say a", "say b", "quit"]
+        );
+        assert!(decoded.replies.is_empty());
+
+        // A CRLF split across reads is a single line ending
+        let mut codec = TelnetCodec::new();
+        assert_eq!(codec.decode(b"look\r").lines, vec!["look"]);
+        assert!(codec.decode(b"\n").lines.is_empty());
+        assert_eq!(codec.decode(b"\n").lines, vec![""]);
+    }
+
+    #[test]
+    fn test_negotiation_is_stripped_and_refused() {
+        let mut codec = TelnetCodec::new();
+        // IAC WILL NAWS, IAC DO ECHO, IAC WONT LINEMODE, IAC DONT SGA
+        let decoded = codec.decode(b"\xff\xfb\x1f\xff\xfd\x01lo\xff\xfc\x22ok\xff\xfe\x03\r\n");
+
+        assert_eq!(decoded.lines, vec!["look"]);
+        assert_eq!(decoded.replies, vec![IAC, DONT, 0x1f, IAC, WONT, 0x01]);
+    }
+
+    #[test]
+    fn test_sequences_split_across_reads() {
+        let mut codec = TelnetCodec::new();
+        assert!(codec.decode(b"he\xff").replies.is_empty());
+        assert!(codec.decode(b"\xfb").replies.is_empty());
+        assert_eq!(codec.decode(b"\x18").replies, vec![IAC, DONT, 0x18]);
+
+        // NAWS subnegotiation, whose payload may contain anything
+        let decoded = codec.decode(b"l\xff\xfa\x1f\x00\x50\xff");
+        assert!(decoded.lines.is_empty());
+        let decoded = codec.decode(b"\xff\x00\x18\xff\xf0p\r\n");
+        assert_eq!(decoded.lines, vec!["help"]);
+        assert!(decoded.replies.is_empty());
+    }
+
+    #[test]
+    fn test_line_editing_and_escapes() {
+        let mut codec = TelnetCodec::new();
+        let decoded = codec.decode("lookk\x08 caf\u{e9}\x7f\x7fe\x07\r\n".as_bytes());
+        assert_eq!(decoded.lines, vec!["look cae"]);
+
+        // IAC IAC is a literal 255 byte, which isn't valid UTF-8 on its own
+        let decoded = codec.decode(b"say \xff\xff\n");
+        assert_eq!(decoded.lines, vec!["say \u{fffd}"]);
+
+        // IAC NOP carries no data
+        let decoded = codec.decode(b"lo\xff\xf1ok\n");
+        assert_eq!(decoded.lines, vec!["look"]);
+    }
+}
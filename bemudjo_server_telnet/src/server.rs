@@ -0,0 +1,346 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use bemudjo_ecs::{Entity, SequentialSystemScheduler, TickRunner};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::task::JoinSet;
+
+use crate::connections::{ConnectionManager, GOODBYE_MESSAGE};
+use crate::game::{CommandOutcome, GameWorld};
+use crate::protocol::{CommandParser, TelnetCodec};
+use crate::session::{
+    ConnectionId, LoginOutcome, SessionManager, SessionTimeoutSystem, LOGIN_PROMPT,
+};
+
+/// How many times per second the game world is ticked.
+pub const TICK_RATE_HZ: f64 = 10.0;
+
+/// Telnet front-end sharing a single [`GameWorld`] between all connections.
+///
+/// Every connection is served by its own task on the multi-threaded runtime,
+/// and all of them share the game world behind an `Arc<Mutex<_>>`. The lock is
+/// only held between awaits, which keeps each command atomic with respect to
+/// the other players.
+///
+/// Clients log in through the world's [`SessionManager`]: a dropped connection
+/// parks its player, and logging in under the same name within the reconnect
+/// timeout picks it up again. A dedicated thread ticks the world
+/// [`TICK_RATE_HZ`] times per second with [`SessionTimeoutSystem`], which
+/// deletes players whose timeout ran out.
+///
+/// Active connections are tracked by a [`ConnectionManager`], which is also
+/// how the server is shut down.
+///
+/// # Example
+/// ```no_run
+/// use bemudjo_server_telnet::Server;
+/// use tokio::net::TcpListener;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let listener = TcpListener::bind("127.0.0.1:2323").await?;
+/// Server::new().run(listener).await
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Server {
+    game: Arc<Mutex<GameWorld>>,
+    connections: ConnectionManager,
+    next_connection: Arc<AtomicU64>,
+}
+
+impl Server {
+    /// Creates a new server with a fresh game world.
+    pub fn new() -> Self {
+        Self::with_game(GameWorld::new())
+    }
+
+    /// Creates a new server keeping disconnected players for `reconnect_timeout`.
+    pub fn with_reconnect_timeout(reconnect_timeout: Duration) -> Self {
+        Self::with_game(GameWorld::with_reconnect_timeout(reconnect_timeout))
+    }
+
+    fn with_game(game: GameWorld) -> Self {
+        Self {
+            game: Arc::new(Mutex::new(game)),
+            connections: ConnectionManager::new(),
+            next_connection: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Returns a handle to the shared game world.
+    pub fn game(&self) -> Arc<Mutex<GameWorld>> {
+        Arc::clone(&self.game)
+    }
+
+    /// Returns a handle to the registry of active connections.
+    pub fn connections(&self) -> ConnectionManager {
+        self.connections.clone()
+    }
+
+    /// Accepts connections, handling each client on its own task, until
+    /// [`ConnectionManager::shutdown`] is called.
+    ///
+    /// The game world is ticked on a separate thread meanwhile. On shutdown
+    /// every client is sent a goodbye and disconnected, and this returns once
+    /// all of them are gone; the tick thread stops within one tick.
+    pub async fn run(&self, listener: TcpListener) -> io::Result<()> {
+        let mut shutdown = self.connections.subscribe();
+
+        let game = Arc::downgrade(&self.game);
+        let connections = self.connections();
+        std::thread::spawn(move || run_ticks(game, connections));
+
+        let mut clients = JoinSet::new();
+
+        while !self.connections.is_shutting_down() {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (socket, addr) = accepted?;
+                    println!("New connection from: {addr}");
+
+                    let game = self.game();
+                    let connections = self.connections();
+                    let connection =
+                        ConnectionId(self.next_connection.fetch_add(1, Ordering::Relaxed));
+                    clients.spawn(async move {
+                        if let Err(e) = handle_client(game, connections, connection, socket, addr).await {
+                            eprintln!("Error handling client {addr}: {e}");
+                        }
+                    });
+                }
+                // Reap finished clients so the set doesn't grow forever
+                Some(_) = clients.join_next() => {}
+                _ = shutdown.recv() => break,
+            }
+        }
+
+        while clients.join_next().await.is_some() {}
+        Ok(())
+    }
+}
+
+impl Default for Server {
+    /// Creates a new server using the default constructor.
+    ///
+    /// This is equivalent to calling `Server::new()`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ticks the game world until the server shuts down or is dropped.
+///
+/// The scheduler holds its systems as `Box<dyn System>` without a `Send` bound,
+/// so it isn't `Send` and is built on the tick thread itself.
+fn run_ticks(game: Weak<Mutex<GameWorld>>, connections: ConnectionManager) {
+    let mut scheduler = SequentialSystemScheduler::new();
+    scheduler
+        .add_system(SessionTimeoutSystem)
+        .expect("scheduler is empty");
+    scheduler
+        .build()
+        .expect("SessionTimeoutSystem has no dependencies");
+    let mut runner = TickRunner::new(scheduler, TICK_RATE_HZ);
+
+    let mut last = Instant::now();
+    while !connections.is_shutting_down() {
+        std::thread::sleep(runner.tick_duration());
+        let Some(game) = game.upgrade() else {
+            return;
+        };
+
+        let now = Instant::now();
+        runner.step(lock(&game).world_mut(), now - last);
+        last = now;
+    }
+}
+
+async fn handle_client(
+    game: Arc<Mutex<GameWorld>>,
+    connections: ConnectionManager,
+    connection: ConnectionId,
+    socket: TcpStream,
+    addr: SocketAddr,
+) -> io::Result<()> {
+    let (reader, mut writer) = socket.into_split();
+    let (sender, mut receiver) = unbounded_channel::<String>();
+    let (reply_sender, mut replies) = unbounded_channel::<Vec<u8>>();
+
+    // All output, including broadcasts from other players, goes through the
+    // channels; telnet negotiation replies are raw bytes
+    let writer_task = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                Some(message) = receiver.recv() => writer.write_all(message.as_bytes()).await?,
+                Some(reply) = replies.recv() => writer.write_all(&reply).await?,
+                else => break,
+            }
+        }
+        writer.shutdown().await
+    });
+
+    // Subscribe before anything else so a shutdown can't slip in between
+    let mut input = Input {
+        reader,
+        codec: TelnetCodec::new(),
+        lines: VecDeque::new(),
+        replies: reply_sender,
+        shutdown: connections.subscribe(),
+    };
+
+    let prompt = SessionManager::connect(lock(&game).world_mut(), connection);
+    let _ = sender.send("Welcome to Bemudjo MUD!\r\n".to_string());
+    let _ = sender.send(prompt.to_string());
+
+    // Accepted while the server was already shutting down: the broadcast is gone
+    let logged_in = if connections.is_shutting_down() {
+        let _ = sender.send(GOODBYE_MESSAGE.to_string());
+        Ok(None)
+    } else {
+        login(&game, connection, &mut input, &sender).await
+    };
+
+    let result = match logged_in {
+        Ok(Some(player)) if connections.register(player, addr) => {
+            let _ = sender
+                .send("Type 'help' for available commands or 'quit' to exit.\r\n".to_string());
+            let _ = sender.send("> ".to_string());
+
+            let result = command_loop(&game, player, &mut input, &sender).await;
+            connections.unregister(player);
+            result
+        }
+        Ok(Some(_)) => {
+            // Logged in while the server was already shutting down
+            let _ = sender.send(GOODBYE_MESSAGE.to_string());
+            Ok(())
+        }
+        other => other.map(|_| ()),
+    };
+
+    // Whatever happened the player is parked, so nobody talks to a dead socket
+    SessionManager::disconnect(lock(&game).world_mut(), connection);
+    drop(sender);
+    drop(input);
+
+    let writer_result = writer_task.await.unwrap_or(Ok(()));
+    if connections.is_shutting_down() {
+        // Clients that left while being told goodbye are no error
+        return result;
+    }
+    result.and(writer_result)
+}
+
+/// Asks for a name until the client logs in, and returns the player.
+///
+/// Returns `None` if the client left or the server shut down first.
+async fn login(
+    game: &Mutex<GameWorld>,
+    connection: ConnectionId,
+    input: &mut Input,
+    sender: &UnboundedSender<String>,
+) -> io::Result<Option<Entity>> {
+    while let Some(name) = input.next_line(sender).await? {
+        if name.trim().is_empty() {
+            let _ = sender.send(LOGIN_PROMPT.to_string());
+            continue;
+        }
+
+        let outcome =
+            SessionManager::login(lock(game).world_mut(), connection, &name, sender.clone());
+        let (player, greeting) = match outcome {
+            Ok(LoginOutcome::Created(player)) => (player, "Welcome"),
+            Ok(LoginOutcome::Reconnected(player)) => (player, "Welcome back"),
+            Err(error) => {
+                let _ = sender.send(format!("{error}\r\n{LOGIN_PROMPT}"));
+                continue;
+            }
+        };
+
+        let name = lock(game)
+            .player_name(player)
+            .unwrap_or_default()
+            .to_string();
+        let _ = sender.send(format!("{greeting}, {name}!\r\n"));
+        return Ok(Some(player));
+    }
+    Ok(None)
+}
+
+async fn command_loop(
+    game: &Mutex<GameWorld>,
+    player: Entity,
+    input: &mut Input,
+    sender: &UnboundedSender<String>,
+) -> io::Result<()> {
+    let parser = CommandParser::new();
+
+    while let Some(line) = input.next_line(sender).await? {
+        match parser.parse(&line) {
+            Ok(Some(command)) => {
+                if lock(game).execute(player, command) == CommandOutcome::Quit {
+                    return Ok(());
+                }
+            }
+            Ok(None) => continue,
+            Err(error) => {
+                let _ = sender.send(format!("{error}\r\n"));
+            }
+        }
+        let _ = sender.send("> ".to_string());
+    }
+    Ok(())
+}
+
+/// The reading half of a client connection, split into lines.
+struct Input {
+    reader: OwnedReadHalf,
+    codec: TelnetCodec,
+    lines: VecDeque<String>, // decoded but not yet handled
+    replies: UnboundedSender<Vec<u8>>,
+    shutdown: broadcast::Receiver<()>,
+}
+
+impl Input {
+    /// Returns the next line sent by the client.
+    ///
+    /// Telnet negotiation is answered along the way. Returns `None` once the
+    /// client disconnected, or once the server is shutting down, after saying
+    /// goodbye through `sender`.
+    async fn next_line(&mut self, sender: &UnboundedSender<String>) -> io::Result<Option<String>> {
+        let mut buffer = [0; 1024];
+
+        while self.lines.is_empty() {
+            let read = tokio::select! {
+                read = self.reader.read(&mut buffer) => read?,
+                _ = self.shutdown.recv() => {
+                    let _ = sender.send(GOODBYE_MESSAGE.to_string());
+                    return Ok(None);
+                }
+            };
+            if read == 0 {
+                return Ok(None);
+            }
+
+            let decoded = self.codec.decode(&buffer[..read]);
+            if !decoded.replies.is_empty() {
+                let _ = self.replies.send(decoded.replies);
+            }
+            self.lines.extend(decoded.lines);
+        }
+        Ok(self.lines.pop_front())
+    }
+}
+
+/// Locks the game world, carrying on if another client panicked while holding it.
+fn lock(game: &Mutex<GameWorld>) -> MutexGuard<'_, GameWorld> {
+    game.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use bemudjo_ecs::{Component, Entity, Query, System, Time, World};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::components::{Connection, Location, Player};
+
+/// Prompt sent to a fresh connection before it has logged in.
+pub const LOGIN_PROMPT: &str = "By what name do you wish to be known? ";
+
+/// Longest accepted player name, in characters.
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// Identifies a client connection, independently of the player it plays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectionId(pub u64);
+
+/// A player whose connection dropped.
+///
+/// The entity stays in the world with all its components, minus the
+/// [`Connection`], so logging in again under the same name picks up where the
+/// player left off. [`SessionTimeoutSystem`] deletes it once the reconnect
+/// timeout has passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Disconnected {
+    /// `Time::elapsed` when the connection dropped
+    pub since: Duration,
+}
+impl Component for Disconnected {}
+
+/// How a successful login was resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoginOutcome {
+    /// No player had the name, a new one was spawned.
+    Created(Entity),
+    /// A disconnected player with the name was reclaimed.
+    Reconnected(Entity),
+}
+
+impl LoginOutcome {
+    /// Returns the player entity, however it was obtained.
+    pub fn player(self) -> Entity {
+        match self {
+            LoginOutcome::Created(player) | LoginOutcome::Reconnected(player) => player,
+        }
+    }
+}
+
+/// Errors that can occur while logging a connection in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionError {
+    /// The connection was never announced with [`SessionManager::connect`]
+    /// or has disconnected since.
+    UnknownConnection(ConnectionId),
+    /// The connection is already playing.
+    AlreadyLoggedIn(ConnectionId),
+    /// The name is empty, too long or not purely alphanumeric.
+    InvalidName,
+    /// Another connection is currently playing under this name.
+    NameInUse(String),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::UnknownConnection(id) => write!(f, "unknown connection {}", id.0),
+            SessionError::AlreadyLoggedIn(id) => {
+                write!(f, "connection {} is already logged in", id.0)
+            }
+            SessionError::InvalidName => {
+                write!(f, "Names must be 1 to {MAX_NAME_LENGTH} letters or digits.")
+            }
+            SessionError::NameInUse(name) => write!(f, "{name} is already playing."),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// World resource tying connections to player entities.
+///
+/// A connection goes through three steps: [`connect`](SessionManager::connect)
+/// when the socket is accepted, [`login`](SessionManager::login) once the
+/// client answered the [`LOGIN_PROMPT`], and
+/// [`disconnect`](SessionManager::disconnect) when the socket goes away.
+/// Disconnecting does not delete the player: it is parked with a
+/// [`Disconnected`] marker, and logging in under the same name within the
+/// reconnect timeout reclaims it. Add [`SessionTimeoutSystem`] to the scheduler
+/// to delete players whose timeout ran out.
+///
+/// The operations take the whole [`World`] because they spawn and modify
+/// player entities; the manager must have been inserted as a resource first.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use bemudjo_ecs::World;
+/// use bemudjo_server_telnet::session::{ConnectionId, LoginOutcome, SessionManager};
+/// use tokio::sync::mpsc::unbounded_channel;
+///
+/// let mut world = World::new();
+/// let square = world.spawn_entity();
+/// world.insert_resource(SessionManager::new(square, Duration::from_secs(60)));
+///
+/// let (sender, _receiver) = unbounded_channel();
+/// SessionManager::connect(&mut world, ConnectionId(1));
+/// let outcome = SessionManager::login(&mut world, ConnectionId(1), "Alice", sender).unwrap();
+/// assert!(matches!(outcome, LoginOutcome::Created(_)));
+///
+/// // The socket drops, the player stays parked in the world
+/// let alice = SessionManager::disconnect(&mut world, ConnectionId(1)).unwrap();
+/// assert!(world.entities().any(|&e| e == alice));
+///
+/// // Logging in again reclaims the same entity
+/// let (sender, _receiver) = unbounded_channel();
+/// SessionManager::connect(&mut world, ConnectionId(2));
+/// let outcome = SessionManager::login(&mut world, ConnectionId(2), "alice", sender).unwrap();
+/// assert_eq!(outcome, LoginOutcome::Reconnected(alice));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SessionManager {
+    starting_room: Entity,
+    reconnect_timeout: Duration,
+    awaiting_login: HashSet<ConnectionId>,
+    sessions: HashMap<ConnectionId, Entity>,
+    players_by_name: HashMap<String, Entity>, // lowercase names, connected and parked players
+}
+impl Component for SessionManager {}
+
+impl SessionManager {
+    /// Creates a manager placing new players in `starting_room` and keeping
+    /// disconnected players for `reconnect_timeout`.
+    pub fn new(starting_room: Entity, reconnect_timeout: Duration) -> Self {
+        Self {
+            starting_room,
+            reconnect_timeout,
+            awaiting_login: HashSet::new(),
+            sessions: HashMap::new(),
+            players_by_name: HashMap::new(),
+        }
+    }
+
+    /// Returns how long a disconnected player is kept around.
+    pub fn reconnect_timeout(&self) -> Duration {
+        self.reconnect_timeout
+    }
+
+    /// Returns the player a connection is logged in as.
+    pub fn player(&self, connection: ConnectionId) -> Option<Entity> {
+        self.sessions.get(&connection).copied()
+    }
+
+    /// Returns `true` if the connection was accepted but has not logged in yet.
+    pub fn is_awaiting_login(&self, connection: ConnectionId) -> bool {
+        self.awaiting_login.contains(&connection)
+    }
+
+    /// Returns the player with the given name, connected or not.
+    pub fn player_by_name(&self, name: &str) -> Option<Entity> {
+        self.players_by_name.get(&name.to_lowercase()).copied()
+    }
+
+    /// Returns the number of logged in connections.
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Registers a freshly accepted connection and returns the prompt to send it.
+    ///
+    /// # Panics
+    /// Panics if the world has no `SessionManager` resource.
+    pub fn connect(world: &mut World, connection: ConnectionId) -> &'static str {
+        Self::with_manager(world, |manager, _| {
+            manager.awaiting_login.insert(connection);
+        });
+        LOGIN_PROMPT
+    }
+
+    /// Logs a connection in as the player called `name`.
+    ///
+    /// Names are matched case-insensitively. A disconnected player with that
+    /// name is reclaimed, otherwise a new player is spawned in the starting
+    /// room. Either way the player gets a [`Connection`] pushing to `sender`.
+    ///
+    /// # Errors
+    /// Fails if the connection is not awaiting a login, the name is invalid,
+    /// or another connection is playing under it. The world is unchanged then.
+    ///
+    /// # Panics
+    /// Panics if the world has no `SessionManager` resource.
+    pub fn login(
+        world: &mut World,
+        connection: ConnectionId,
+        name: &str,
+        sender: UnboundedSender<String>,
+    ) -> Result<LoginOutcome, SessionError> {
+        let name = name.trim();
+        Self::with_manager(world, |manager, world| {
+            if manager.sessions.contains_key(&connection) {
+                return Err(SessionError::AlreadyLoggedIn(connection));
+            }
+            if !manager.awaiting_login.contains(&connection) {
+                return Err(SessionError::UnknownConnection(connection));
+            }
+            if !is_valid_name(name) {
+                return Err(SessionError::InvalidName);
+            }
+
+            let key = name.to_lowercase();
+            // Players deleted behind the manager's back leave stale entries
+            if let Some(&player) = manager.players_by_name.get(&key) {
+                if world.get_component::<Player>(player).is_none() {
+                    manager.players_by_name.remove(&key);
+                }
+            }
+
+            let outcome = match manager.players_by_name.get(&key) {
+                Some(&player) => {
+                    if world.remove_component::<Disconnected>(player).is_none() {
+                        return Err(SessionError::NameInUse(name.to_string()));
+                    }
+                    LoginOutcome::Reconnected(player)
+                }
+                None => {
+                    let player = world.spawn_entity();
+                    world
+                        .add_component(
+                            player,
+                            Player {
+                                name: name.to_string(),
+                            },
+                        )
+                        .expect("player was just spawned");
+                    world
+                        .add_component(
+                            player,
+                            Location {
+                                room: manager.starting_room,
+                            },
+                        )
+                        .expect("player was just spawned");
+                    manager.players_by_name.insert(key, player);
+                    LoginOutcome::Created(player)
+                }
+            };
+
+            let player = outcome.player();
+            world.replace_component(player, Connection { sender });
+            manager.awaiting_login.remove(&connection);
+            manager.sessions.insert(connection, player);
+            Ok(outcome)
+        })
+    }
+
+    /// Forgets a connection whose socket went away.
+    ///
+    /// A logged in player loses its [`Connection`] and is parked with a
+    /// [`Disconnected`] marker stamped with the current `Time::elapsed`; it is
+    /// returned. Disconnecting before logging in, or twice, returns `None`.
+    ///
+    /// # Panics
+    /// Panics if the world has no `SessionManager` resource.
+    pub fn disconnect(world: &mut World, connection: ConnectionId) -> Option<Entity> {
+        let now = current_time(world);
+        Self::with_manager(world, |manager, world| {
+            manager.awaiting_login.remove(&connection);
+            let player = manager.sessions.remove(&connection)?;
+
+            world.remove_component::<Connection>(player);
+            world
+                .add_component(player, Disconnected { since: now })
+                .ok()?;
+            Some(player)
+        })
+    }
+
+    /// Runs `f` with mutable access to both the manager and the world.
+    ///
+    /// The manager is taken out of the world while `f` runs and inserted back
+    /// afterwards.
+    fn with_manager<R>(world: &mut World, f: impl FnOnce(&mut Self, &mut World) -> R) -> R {
+        let mut manager = world
+            .remove_resource::<Self>()
+            .expect("SessionManager resource must be inserted before use");
+        let result = f(&mut manager, world);
+        world.insert_resource(manager);
+        result
+    }
+}
+
+/// Deletes disconnected players whose reconnect timeout has passed.
+///
+/// Timeouts are measured against the `Time` resource kept by the tick runner,
+/// so parked players only expire while the game is ticking. The deleted
+/// players' names become free again.
+pub struct SessionTimeoutSystem;
+
+impl System for SessionTimeoutSystem {
+    fn run(&self, world: &mut World) {
+        let Some(timeout) = world
+            .get_resource::<SessionManager>()
+            .map(SessionManager::reconnect_timeout)
+        else {
+            return;
+        };
+        let now = current_time(world);
+
+        let expired: Vec<Entity> = Query::<Disconnected>::new()
+            .iter(world)
+            .filter(|(_, disconnected)| now.saturating_sub(disconnected.since) >= timeout)
+            .map(|(player, _)| player)
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+
+        SessionManager::with_manager(world, |manager, world| {
+            manager
+                .players_by_name
+                .retain(|_, player| !expired.contains(player));
+            for player in expired {
+                world.delete_entity(player);
+            }
+        });
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let length = name.chars().count();
+    (1..=MAX_NAME_LENGTH).contains(&length) && name.chars().all(char::is_alphanumeric)
+}
+
+fn current_time(world: &World) -> Duration {
+    world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_validation() {
+        assert!(is_valid_name("Alice"));
+        assert!(is_valid_name("Zoë42"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("Alice Smith"));
+        assert!(!is_valid_name("drop;table"));
+        assert!(!is_valid_name(&"a".repeat(MAX_NAME_LENGTH + 1)));
+    }
+
+    #[test]
+    fn test_login_before_connect_is_rejected() {
+        let mut world = World::new();
+        let room = world.spawn_entity();
+        world.insert_resource(SessionManager::new(room, Duration::from_secs(1)));
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        assert_eq!(
+            SessionManager::login(&mut world, ConnectionId(7), "Alice", sender),
+            Err(SessionError::UnknownConnection(ConnectionId(7)))
+        );
+        assert_eq!(world.entities().count(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! End-to-end tests running the telnet server with real TCP clients.
+
+use std::time::Duration;
+
+use bemudjo_ecs::World;
+use bemudjo_server_telnet::components::Player;
+use bemudjo_server_telnet::{Server, SessionManager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+struct Client {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Client {
+    /// Connects and logs in, returning the client and the login greeting.
+    async fn login(addr: std::net::SocketAddr, name: &str) -> (Client, String) {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (reader, writer) = stream.into_split();
+        let mut client = Client {
+            reader: BufReader::new(reader),
+            writer,
+        };
+        client.read_until("Welcome to Bemudjo MUD!").await;
+        client.send(name).await;
+        // Once the help hint arrived the player entity exists in the world
+        let greeting = client.read_until("Type 'help'").await;
+        (client, greeting)
+    }
+
+    async fn connect(addr: std::net::SocketAddr, name: &str) -> Client {
+        Client::login(addr, name).await.0
+    }
+
+    async fn send(&mut self, command: &str) {
+        self.writer
+            .write_all(format!("{command}\r\n").as_bytes())
+            .await
+            .unwrap();
+    }
+
+    async fn read_until(&mut self, needle: &str) -> String {
+        let mut received = String::new();
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let mut line = String::new();
+                if self.reader.read_line(&mut line).await.unwrap() == 0 {
+                    panic!("connection closed before receiving {needle:?}, got {received:?}");
+                }
+                received.push_str(&line);
+                if line.contains(needle) {
+                    break;
+                }
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("timed out waiting for {needle:?}, got {received:?}"));
+        received
+    }
+
+    async fn assert_closed(&mut self) {
+        let mut rest = String::new();
+        let read = timeout(Duration::from_secs(5), self.reader.read_line(&mut rest))
+            .await
+            .expect("timed out waiting for the connection to close");
+        assert_eq!(
+            read.unwrap(),
+            0,
+            "expected the connection to close, got {rest:?}"
+        );
+    }
+}
+
+/// Starts a server on an ephemeral port.
+async fn start_server() -> (Server, std::net::SocketAddr) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::new();
+    let running = server.clone();
+    tokio::spawn(async move { running.run(listener).await });
+    (server, addr)
+}
+
+/// Polls the game world until `condition` holds, panicking after five seconds.
+async fn wait_for(server: &Server, what: &str, condition: impl Fn(&World) -> bool) {
+    timeout(Duration::from_secs(5), async {
+        loop {
+            let done = {
+                let game = server.game();
+                let game = game.lock().unwrap();
+                condition(game.world())
+            };
+            if done {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting until {what}"));
+}
+
+fn sessions(world: &World) -> &SessionManager {
+    world.get_resource::<SessionManager>().unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_say_is_visible_to_other_client_in_same_room() {
+    let (_server, addr) = start_server().await;
+
+    let mut alice = Client::connect(addr, "Alice").await;
+    let mut bob = Client::connect(addr, "Bob").await;
+
+    alice.send("say hello there").await;
+
+    alice.read_until("You say: hello there").await;
+    let heard = bob.read_until("says: hello there").await;
+    assert!(heard.contains("Alice says: hello there"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_look_lists_other_connected_players() {
+    let (_server, addr) = start_server().await;
+
+    let mut alice = Client::connect(addr, "Alice").await;
+    let _bob = Client::connect(addr, "Bob").await;
+
+    alice.send("look").await;
+    let output = alice.read_until("You see:").await;
+
+    assert!(output.contains("Town Square"));
+    assert!(output.contains("Also here: Bob."));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_disconnect_parks_player_out_of_sight() {
+    let (server, addr) = start_server().await;
+
+    let mut alice = Client::connect(addr, "Alice").await;
+    let bob = Client::connect(addr, "Bob").await;
+    drop(bob);
+
+    wait_for(&server, "bob is parked", |world| {
+        sessions(world).session_count() == 1
+    })
+    .await;
+
+    alice.send("look").await;
+    let output = alice.read_until("You see:").await;
+    assert!(!output.contains("Bob"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_reconnect_reclaims_parked_player() {
+    let (server, addr) = start_server().await;
+
+    let (alice, greeting) = Client::login(addr, "Alice").await;
+    assert!(greeting.contains("Welcome, Alice!"));
+    let entities = server.game().lock().unwrap().world().entities().count();
+    drop(alice);
+
+    wait_for(&server, "alice is parked", |world| {
+        sessions(world).session_count() == 0
+    })
+    .await;
+
+    let (mut alice, greeting) = Client::login(addr, "alice").await;
+    assert!(greeting.contains("Welcome back, Alice!"));
+    assert_eq!(
+        server.game().lock().unwrap().world().entities().count(),
+        entities
+    );
+
+    alice.send("say still here").await;
+    alice.read_until("You say: still here").await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_name_in_use_is_asked_again() {
+    let (_server, addr) = start_server().await;
+    let _alice = Client::connect(addr, "Alice").await;
+
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let (reader, writer) = stream.into_split();
+    let mut intruder = Client {
+        reader: BufReader::new(reader),
+        writer,
+    };
+    intruder.read_until("Welcome to Bemudjo MUD!").await;
+    intruder.send("ALICE").await;
+    intruder.read_until("ALICE is already playing.").await;
+    intruder.send("Bob").await;
+    intruder.read_until("Welcome, Bob!").await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_parked_player_is_deleted_after_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::with_reconnect_timeout(Duration::from_millis(300));
+    let running = server.clone();
+    tokio::spawn(async move { running.run(listener).await });
+
+    let alice = Client::connect(addr, "Alice").await;
+    drop(alice);
+
+    // The tick thread runs the timeout system, and the tick cleans up after it
+    wait_for(&server, "alice is deleted", |world| {
+        world.storage_len::<Player>() == 0
+    })
+    .await;
+    wait_for(&server, "alice's name is free", |world| {
+        sessions(world).player_by_name("Alice").is_none()
+    })
+    .await;
+
+    let (_alice, greeting) = Client::login(addr, "Alice").await;
+    assert!(greeting.contains("Welcome, Alice!"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_shutdown_says_goodbye_and_closes_connections() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::new();
+    let running = server.clone();
+    let run = tokio::spawn(async move { running.run(listener).await });
+
+    let mut alice = Client::connect(addr, "Alice").await;
+    let bob = Client::connect(addr, "Bob").await;
+    assert_eq!(server.connections().connection_count(), 2);
+
+    // Bob leaving at the same time must not keep the server up
+    drop(bob);
+    server.connections().shutdown();
+
+    alice.read_until("Goodbye").await;
+    alice.assert_closed().await;
+
+    timeout(Duration::from_secs(5), run)
+        .await
+        .expect("server did not stop")
+        .unwrap()
+        .unwrap();
+    assert_eq!(server.connections().connection_count(), 0);
+}
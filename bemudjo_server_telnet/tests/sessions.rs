@@ -0,0 +1,244 @@
+//! Login, disconnect and reconnect driven against a plain World, without sockets.
+
+use std::time::Duration;
+
+use bemudjo_ecs::{Entity, SequentialSystemScheduler, TickRunner, World};
+use bemudjo_server_telnet::components::{Connection, Location, Player};
+use bemudjo_server_telnet::session::{
+    ConnectionId, Disconnected, LoginOutcome, SessionError, SessionManager, SessionTimeoutSystem,
+    LOGIN_PROMPT,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Game {
+    world: World,
+    runner: TickRunner,
+    square: Entity,
+}
+
+impl Game {
+    fn new() -> Game {
+        let mut world = World::new();
+        let square = world.spawn_entity();
+        world.insert_resource(SessionManager::new(square, RECONNECT_TIMEOUT));
+
+        let mut scheduler = SequentialSystemScheduler::new();
+        scheduler.add_system(SessionTimeoutSystem).unwrap();
+        scheduler.build().unwrap();
+
+        Game {
+            world,
+            // One tick per second keeps the arithmetic obvious
+            runner: TickRunner::new(scheduler, 1.0),
+            square,
+        }
+    }
+
+    fn login(
+        &mut self,
+        connection: u64,
+        name: &str,
+    ) -> (
+        Result<LoginOutcome, SessionError>,
+        UnboundedReceiver<String>,
+    ) {
+        let (sender, receiver) = unbounded_channel();
+        let connection = ConnectionId(connection);
+        assert_eq!(
+            SessionManager::connect(&mut self.world, connection),
+            LOGIN_PROMPT
+        );
+        let outcome = SessionManager::login(&mut self.world, connection, name, sender);
+        (outcome, receiver)
+    }
+
+    fn advance_secs(&mut self, secs: u64) {
+        for _ in 0..secs {
+            self.runner.step(&mut self.world, Duration::from_secs(1));
+        }
+    }
+
+    fn sessions(&self) -> &SessionManager {
+        self.world.get_resource::<SessionManager>().unwrap()
+    }
+
+    fn is_alive(&self, entity: Entity) -> bool {
+        self.world.entities().any(|&e| e == entity)
+    }
+}
+
+#[test]
+fn test_connect_and_login_creates_player() {
+    let mut game = Game::new();
+    SessionManager::connect(&mut game.world, ConnectionId(1));
+    assert!(game.sessions().is_awaiting_login(ConnectionId(1)));
+
+    let (sender, _receiver) = unbounded_channel();
+    let outcome =
+        SessionManager::login(&mut game.world, ConnectionId(1), "  Alice ", sender).unwrap();
+    let LoginOutcome::Created(alice) = outcome else {
+        panic!("expected a new player, got {outcome:?}");
+    };
+
+    assert_eq!(
+        game.world.get_component::<Player>(alice).unwrap().name,
+        "Alice"
+    );
+    assert_eq!(
+        game.world.get_component::<Location>(alice).unwrap().room,
+        game.square
+    );
+    assert!(game.world.has_component::<Connection>(alice));
+    assert!(!game.sessions().is_awaiting_login(ConnectionId(1)));
+    assert_eq!(game.sessions().player(ConnectionId(1)), Some(alice));
+    assert_eq!(game.sessions().player_by_name("ALICE"), Some(alice));
+}
+
+#[test]
+fn test_login_rejects_bad_names_and_duplicates() {
+    let mut game = Game::new();
+    let (alice, _receiver) = game.login(1, "Alice");
+    alice.unwrap();
+
+    let (outcome, _receiver) = game.login(2, "alice");
+    assert_eq!(outcome, Err(SessionError::NameInUse("alice".to_string())));
+
+    // The connection can retry with another name
+    let (sender, _receiver) = unbounded_channel();
+    assert_eq!(
+        SessionManager::login(
+            &mut game.world,
+            ConnectionId(2),
+            "not valid",
+            sender.clone()
+        ),
+        Err(SessionError::InvalidName)
+    );
+    SessionManager::login(&mut game.world, ConnectionId(2), "Bob", sender.clone()).unwrap();
+    assert_eq!(
+        SessionManager::login(&mut game.world, ConnectionId(2), "Bob", sender),
+        Err(SessionError::AlreadyLoggedIn(ConnectionId(2)))
+    );
+    assert_eq!(game.sessions().session_count(), 2);
+}
+
+#[test]
+fn test_disconnect_parks_player() {
+    let mut game = Game::new();
+    let (outcome, mut receiver) = game.login(1, "Alice");
+    let alice = outcome.unwrap().player();
+    game.advance_secs(2);
+
+    assert_eq!(
+        SessionManager::disconnect(&mut game.world, ConnectionId(1)),
+        Some(alice)
+    );
+    assert_eq!(
+        SessionManager::disconnect(&mut game.world, ConnectionId(1)),
+        None
+    );
+
+    assert!(game.is_alive(alice));
+    assert!(!game.world.has_component::<Connection>(alice));
+    assert_eq!(
+        game.world.get_component::<Disconnected>(alice),
+        Some(&Disconnected {
+            since: Duration::from_secs(2)
+        })
+    );
+    assert_eq!(game.sessions().session_count(), 0);
+
+    // The Connection held the only sender, so the writer side sees the channel close
+    assert!(receiver.try_recv().is_err());
+    assert!(receiver.is_closed());
+}
+
+#[test]
+fn test_disconnect_before_login_leaves_no_player() {
+    let mut game = Game::new();
+    SessionManager::connect(&mut game.world, ConnectionId(1));
+
+    assert_eq!(
+        SessionManager::disconnect(&mut game.world, ConnectionId(1)),
+        None
+    );
+    assert!(!game.sessions().is_awaiting_login(ConnectionId(1)));
+    assert_eq!(game.world.entities().count(), 1);
+}
+
+#[test]
+fn test_reconnect_within_timeout_reclaims_player() {
+    let mut game = Game::new();
+    let (outcome, _receiver) = game.login(1, "Alice");
+    let alice = outcome.unwrap().player();
+    let elsewhere = game.world.spawn_entity();
+    game.world
+        .replace_component(alice, Location { room: elsewhere });
+
+    SessionManager::disconnect(&mut game.world, ConnectionId(1));
+    game.advance_secs(4);
+    assert!(game.is_alive(alice));
+
+    let (outcome, mut receiver) = game.login(2, "ALICE");
+    assert_eq!(outcome, Ok(LoginOutcome::Reconnected(alice)));
+
+    // Same entity, state kept, new connection in use
+    assert!(!game.world.has_component::<Disconnected>(alice));
+    assert_eq!(
+        game.world.get_component::<Location>(alice).unwrap().room,
+        elsewhere
+    );
+    let connection = game.world.get_component::<Connection>(alice).unwrap();
+    connection.sender.send("hello".to_string()).unwrap();
+    assert_eq!(receiver.try_recv().unwrap(), "hello");
+    assert_eq!(game.sessions().player(ConnectionId(2)), Some(alice));
+
+    // Reclaimed players no longer expire
+    game.advance_secs(10);
+    assert!(game.is_alive(alice));
+}
+
+#[test]
+fn test_timeout_expiry_deletes_player_and_frees_name() {
+    let mut game = Game::new();
+    let (outcome, _receiver) = game.login(1, "Alice");
+    let alice = outcome.unwrap().player();
+    let (outcome, _bob_receiver) = game.login(2, "Bob");
+    let bob = outcome.unwrap().player();
+
+    SessionManager::disconnect(&mut game.world, ConnectionId(1));
+    game.advance_secs(4);
+    assert!(game.is_alive(alice));
+    game.advance_secs(1);
+
+    assert!(!game.is_alive(alice));
+    assert!(game.is_alive(bob));
+    assert_eq!(game.sessions().player_by_name("Alice"), None);
+
+    // The name is free again and gets a fresh player
+    let (outcome, _receiver) = game.login(3, "Alice");
+    let LoginOutcome::Created(new_alice) = outcome.unwrap() else {
+        panic!("expected a new player");
+    };
+    assert_ne!(new_alice, alice);
+}
+
+#[test]
+fn test_name_of_player_deleted_elsewhere_is_free_again() {
+    let mut game = Game::new();
+    let (outcome, _receiver) = game.login(1, "Alice");
+    let alice = outcome.unwrap().player();
+
+    // E.g. killed by a game system while still connected
+    game.world.delete_entity(alice);
+    game.world.cleanup_deleted_entities();
+
+    let (outcome, _receiver) = game.login(2, "alice");
+    let LoginOutcome::Created(new_alice) = outcome.unwrap() else {
+        panic!("expected a new player");
+    };
+    assert_ne!(new_alice, alice);
+    assert_eq!(game.sessions().player_by_name("Alice"), Some(new_alice));
+}
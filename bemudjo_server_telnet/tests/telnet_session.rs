@@ -0,0 +1,76 @@
+//! A full telnet session over a real socket, negotiation bytes included.
+
+use std::time::Duration;
+
+use bemudjo_server_telnet::session::LOGIN_PROMPT;
+use bemudjo_server_telnet::Server;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+/// Reads raw bytes until `needle` shows up, returning everything received.
+async fn read_until(stream: &mut TcpStream, needle: &[u8]) -> Vec<u8> {
+    let mut received = Vec::new();
+    timeout(Duration::from_secs(5), async {
+        let mut buffer = [0; 512];
+        while !received
+            .windows(needle.len())
+            .any(|window| window == needle)
+        {
+            let read = stream.read(&mut buffer).await.unwrap();
+            assert!(
+                read > 0,
+                "connection closed before receiving {:?}, got {:?}",
+                String::from_utf8_lossy(needle),
+                String::from_utf8_lossy(&received)
+            );
+            received.extend_from_slice(&buffer[..read]);
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out, got {:?}", String::from_utf8_lossy(&received)));
+    received
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_full_session_with_negotiation() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::new();
+    let running = server.clone();
+    tokio::spawn(async move { running.run(listener).await });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    read_until(&mut stream, LOGIN_PROMPT.as_bytes()).await;
+    stream.write_all(b"Tester\r\n").await.unwrap();
+    read_until(&mut stream, b"> ").await;
+
+    // IAC WILL NAWS and IAC DO ECHO are refused
+    stream.write_all(b"\xff\xfb\x1f\xff\xfd\x01").await.unwrap();
+    read_until(&mut stream, b"\xff\xfe\x1f\xff\xfc\x01").await;
+
+    // IAC WONT LINEMODE in the middle of a command, LF-only line ending
+    stream.write_all(b"lo\xff\xfc\x22ok\n").await.unwrap();
+    let output = read_until(&mut stream, b"You see:").await;
+    assert!(String::from_utf8_lossy(&output).contains("Town Square"));
+
+    // Command split across writes, quoted argument, CRLF
+    stream.write_all(b"say \"hello ").await.unwrap();
+    stream.write_all(b"  world\"\r\n").await.unwrap();
+    read_until(&mut stream, b"You say: hello   world\r\n").await;
+
+    stream.write_all(b"look around\r\n").await.unwrap();
+    read_until(&mut stream, b"Too many arguments. Usage: look\r\n").await;
+
+    stream.write_all(b"dance\r\n").await.unwrap();
+    read_until(&mut stream, b"Unknown command.").await;
+
+    stream.write_all(b"QUIT\r\n").await.unwrap();
+    read_until(&mut stream, b"Goodbye!\r\n").await;
+
+    let mut rest = Vec::new();
+    timeout(Duration::from_secs(5), stream.read_to_end(&mut rest))
+        .await
+        .expect("connection was not closed")
+        .unwrap();
+}